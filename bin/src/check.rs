@@ -0,0 +1,119 @@
+use crate::cli::CheckMetric;
+use core::alerts::AlertMetric;
+use core::model::DiskFilter;
+use core::{SystemInfoPoller, MINIMUM_CPU_UPDATE_INTERVAL};
+use std::fmt::{self, Display, Formatter};
+use std::thread;
+
+/// The states reported by the Nagios plugins, with the exit codes in their order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Critical,
+    /// The metric could not be measured
+    Unknown,
+}
+
+impl CheckStatus {
+    /// Returns the exit code of the plugin reporting the status.
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+            Self::Unknown => 3,
+        }
+    }
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Warning => write!(f, "WARNING"),
+            Self::Critical => write!(f, "CRITICAL"),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// Measures `metric` of the local system and compares it to the `warn` and `crit` thresholds.
+/// Returns the status and its line in the format of the Nagios plugins, like
+/// `DISK WARNING - 85.2% of / used | '/'=85.2%;80;95;0;100`.
+/// The disk metric is measured on `mount`, which must be selected by `disk_filter`.
+pub fn check(
+    metric: CheckMetric,
+    warn: f64,
+    crit: f64,
+    mount: &str,
+    disk_filter: DiskFilter,
+) -> (CheckStatus, String) {
+    let (name, alert_metric) = match metric {
+        CheckMetric::Cpu => ("CPU", AlertMetric::CpuUsage),
+        CheckMetric::Memory => ("MEMORY", AlertMetric::MemoryUsage),
+        CheckMetric::Swap => ("SWAP", AlertMetric::SwapUsage),
+        CheckMetric::Disk => ("DISK", AlertMetric::DiskUsage(mount.to_string())),
+        CheckMetric::Load => ("LOAD", AlertMetric::LoadAverage),
+    };
+
+    let mut poller = SystemInfoPoller::default().with_disk_filter(disk_filter);
+    poller.init();
+    // the CPU usage is calculated from the difference of two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    let snapshot = match poller.get_system_overview() {
+        Ok(snapshot) => snapshot,
+        Err(error) => {
+            let status = CheckStatus::Unknown;
+            return (status, format!("{} {} - {}", name, status, error));
+        }
+    };
+    let Some(value) = alert_metric.value(&snapshot) else {
+        let status = CheckStatus::Unknown;
+        let reason = match metric {
+            CheckMetric::Disk => format!("no disk is mounted at {}", mount),
+            CheckMetric::Swap => "there is no swap".to_string(),
+            _ => "the metric is not available".to_string(),
+        };
+        return (status, format!("{} {} - {}", name, status, reason));
+    };
+
+    let status = if value > crit {
+        CheckStatus::Critical
+    } else if value > warn {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Ok
+    };
+    // the performance data is `label=value[unit];warn;crit;min;max`, the labels with spaces or slashes are quoted
+    let (description, performance_data) = match metric {
+        CheckMetric::Cpu => (
+            format!("{:.1}% used", value),
+            format!("cpu={:.1}%;{};{};0;100", value, warn, crit),
+        ),
+        CheckMetric::Memory => (
+            format!("{:.1}% used", value),
+            format!("memory={:.1}%;{};{};0;100", value, warn, crit),
+        ),
+        CheckMetric::Swap => (
+            format!("{:.1}% used", value),
+            format!("swap={:.1}%;{};{};0;100", value, warn, crit),
+        ),
+        CheckMetric::Disk => (
+            format!("{:.1}% of {} used", value, mount),
+            format!("'{}'={:.1}%;{};{};0;100", mount, value, warn, crit),
+        ),
+        CheckMetric::Load => (
+            format!("load average {:.2}", value),
+            format!("load1={:.2};{};{};0;", value, warn, crit),
+        ),
+    };
+
+    (
+        status,
+        format!(
+            "{} {} - {} | {}",
+            name, status, description, performance_data
+        ),
+    )
+}
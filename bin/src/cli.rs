@@ -0,0 +1,423 @@
+use crate::export::serve_prometheus;
+use crate::metric_log::{log_metrics, Rotation};
+use crate::output::{render, Tabular};
+use crate::server::serve_api;
+use clap::{Parser, Subcommand, ValueEnum};
+use core::config::Tab;
+use core::model::SystemSnapshot;
+use core::{
+    AlertHooks, AlertRule, Config, ConfigError, SystemInfoPoller, SystemInfoProvider,
+    MINIMUM_CPU_UPDATE_INTERVAL,
+};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// System monitor for the terminal.
+/// Without a subcommand, the interactive user interface is started.
+#[derive(Parser)]
+#[command(name = "ocelo", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Output format of the snapshots
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    pub format: Format,
+
+    /// Re-emit the snapshot at every N seconds
+    #[arg(long, value_name = "N", global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    pub watch: Option<u64>,
+
+    /// Raise an alert when the rule holds, like "cpu.usage > 90 for 60s" or 'disk "/" > 95%'.
+    /// The growth rates, like 'disk.growth "/" > 1', are in percentage points per minute.
+    /// The file descriptors are watched in percent of their limit, with "fd.usage" for the whole system
+    /// and with "fd.process" for the process closest to its own limit.
+    /// The entropy of the kernel is watched in bits with "entropy", like "entropy < 200",
+    /// the offset of the clock in milliseconds either way with "clock.offset", like "clock.offset > 500".
+    /// Ending the rule with "notify" also sends a desktop notification, ending it with "bell" rings the bell of the terminal.
+    /// Can be given multiple times, replacing the alerts of the configuration file
+    #[arg(long = "alert", value_name = "RULE", global = true)]
+    pub alerts: Vec<AlertRule>,
+
+    /// Path of the configuration file [default: ~/.config/ocelo/config.toml]
+    #[arg(long, value_name = "PATH", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Time between two refreshes of the user interface and the exporter in seconds
+    #[arg(long, value_name = "SECONDS", global = true)]
+    pub interval: Option<u64>,
+
+    /// The tab displayed after starting the user interface:
+    /// overview, cpu, processes, disk, network, sensors, users, services, containers or logs
+    #[arg(long, value_name = "TAB")]
+    pub tab: Option<Tab>,
+
+    /// Exit with an error when an alert is raised
+    #[arg(long, global = true)]
+    pub fail_on_alert: bool,
+
+    /// Record the system information displayed by the user interface into a SQLite database,
+    /// which can be viewed later with `ocelo replay FILE`
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Display the CPU and memory usage of ocelo itself in the status bar of the user interface
+    #[arg(long)]
+    pub self_usage: bool,
+
+    /// Display the system information of a remote agent started with `ocelo serve`
+    /// instead of the local system
+    #[arg(long, value_name = "HOST:PORT")]
+    pub remote: Option<String>,
+
+    /// Bearer token required by the remote agent
+    #[arg(long, value_name = "TOKEN", requires = "remote")]
+    pub remote_token: Option<String>,
+}
+
+/// The snapshots which can be printed without the user interface.
+#[derive(Clone, Subcommand)]
+pub enum Command {
+    /// Processor information
+    Cpu,
+    /// Physical and swap memory usage
+    Mem,
+    /// Storage devices
+    Disk,
+    /// Network interfaces
+    Net,
+    /// Running processes
+    Proc,
+    /// Summary of the whole system
+    Overview,
+    /// Temperatures, fan speeds and voltages of the hardware sensors
+    Sensors,
+    /// Sessions of the logged in users
+    Users,
+    /// Service units of systemd, only listed when built with the `systemd` feature
+    Services,
+    /// Running containers of Docker or Podman
+    Containers,
+    /// Recent messages of the systemd journal, the syslog files or the kernel ring buffer
+    Logs,
+    /// Summary of the whole system with every running process, to be compared later with `ocelo diff`
+    Snapshot,
+    /// Compare two snapshots written by `ocelo snapshot --format json`:
+    /// the changes of the CPU, the memory and the disks, and the started and exited processes
+    Diff {
+        /// The snapshot taken first
+        earlier: PathBuf,
+
+        /// The snapshot taken later
+        later: PathBuf,
+    },
+    /// Serve the metrics continuously for monitoring systems
+    Export {
+        /// Serve the metrics in the Prometheus text exposition format
+        #[arg(long)]
+        prometheus: bool,
+
+        /// Address to serve the metrics on
+        #[arg(long, default_value = "0.0.0.0:9500")]
+        listen: String,
+    },
+    /// Serve the system information as JSON over HTTP at /overview, /cpu, /cpu-topology, /cpu-memory, /memory,
+    /// /disks, /network, /processes, /sensors, /users, /services, /containers and /logs, and stream the updates as they are polled on the /ws WebSocket.
+    /// The clients accepting application/msgpack, like the remote user interface, are answered in MessagePack instead
+    Serve {
+        /// Address to serve the API on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: String,
+
+        /// Require this bearer token in the Authorization header of the requests.
+        /// The environment of the processes is only served with a token
+        #[arg(long, value_name = "TOKEN")]
+        token: Option<String>,
+    },
+    /// Append the metrics to a CSV file at every --interval seconds, for analysing them later
+    Log {
+        /// The CSV file which the rows are appended to
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// The metrics to log, separated by commas
+        #[arg(long, value_enum, value_delimiter = ',', default_values_t = [LogMetric::Cpu, LogMetric::Memory, LogMetric::Disk, LogMetric::Network])]
+        metrics: Vec<LogMetric>,
+
+        /// Start a new file when the file has grown to this size, like 500K, 10M or 1G
+        #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+        max_size: Option<u64>,
+
+        /// Start a new file every day, at midnight in UTC
+        #[arg(long)]
+        daily: bool,
+    },
+    /// Check a metric against thresholds like a Nagios or Icinga plugin:
+    /// print a status line with the performance data and exit with 0 (OK), 1 (WARNING), 2 (CRITICAL) or 3 (UNKNOWN)
+    Check {
+        /// The metric to check, in percent except the one minute load average
+        #[arg(value_enum)]
+        metric: CheckMetric,
+
+        /// Warn when the metric is above this value
+        #[arg(long)]
+        warn: f64,
+
+        /// Report a critical state when the metric is above this value
+        #[arg(long)]
+        crit: f64,
+
+        /// The mount point of the disk to check
+        #[arg(long, default_value = "/")]
+        mount: String,
+    },
+    /// Display the system information recorded with --record in the user interface
+    Replay {
+        /// The database the system information was recorded into
+        file: PathBuf,
+
+        /// Replay the recording this many times faster than it was recorded
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
+}
+
+/// The groups of metrics which can be logged by `ocelo log`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum LogMetric {
+    /// CPU usage and load average
+    Cpu,
+    /// Physical memory and swap usage
+    Memory,
+    /// Used space and throughput of every disk
+    Disk,
+    /// Throughput of every network interface
+    Network,
+}
+
+/// The metrics which can be checked by `ocelo check`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CheckMetric {
+    /// Global CPU usage
+    Cpu,
+    /// Physical memory usage
+    Memory,
+    /// Swap usage
+    Swap,
+    /// Used space of the disk mounted at --mount
+    Disk,
+    /// One minute load average
+    Load,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Json,
+    Csv,
+    Table,
+}
+
+impl Cli {
+    /// Loads the configuration file and overrides its values with the given flags.
+    pub fn config(&self) -> Result<Config, ConfigError> {
+        let mut config = match self.config.clone().or_else(Config::default_path) {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+
+        if let Some(interval) = self.interval {
+            config.refresh_interval = interval;
+        }
+        if let Some(tab) = self.tab {
+            config.default_tab = tab;
+        }
+        if !self.alerts.is_empty() {
+            config.alerts = self.alerts.clone();
+        }
+        if let Some(record) = self.record.clone() {
+            config.record = Some(record);
+        }
+        if self.self_usage {
+            config.show_self_usage = true;
+        }
+
+        Ok(config)
+    }
+}
+
+/// Prints the snapshot of `command` to the standard output in the format selected by `options`.
+/// If watching, the snapshot is printed again at every N seconds until the process is stopped.
+/// The raised alerts are printed to the standard error.
+pub fn run(command: Command, options: &Cli, config: &Config) -> io::Result<()> {
+    if let Command::Export { prometheus, listen } = command {
+        if !prometheus {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the export format must be selected, like --prometheus",
+            ));
+        }
+        return serve_prometheus(&listen, config.refresh_interval(), config.disks.clone());
+    }
+    if let Command::Log {
+        output,
+        metrics,
+        max_size,
+        daily,
+    } = command
+    {
+        return log_metrics(
+            &output,
+            &metrics,
+            config.refresh_interval(),
+            Rotation { max_size, daily },
+            config.disks.clone(),
+        );
+    }
+    if let Command::Diff { earlier, later } = command {
+        let diff = SystemSnapshot::diff(&read_snapshot(&earlier)?, &read_snapshot(&later)?);
+        return print(&diff, options.format);
+    }
+    if let Command::Serve { listen, token } = command {
+        return serve_api(&listen, token, config);
+    }
+
+    let mut poller = SystemInfoPoller::default()
+        .with_alert_rules(config.alerts.clone())
+        .with_network_accounting(config.network_accounting)
+        .with_disk_filter(config.disks.clone());
+    // the offset of the clock is only known after its first check, so a single snapshot would not have it
+    if options.watch.is_some() {
+        poller = poller.with_clock_offset(config.ntp_server.clone());
+    }
+    poller.init();
+    // the CPU usage is calculated from the difference of two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+
+    let mut hooks = AlertHooks::new(config.alert_hooks.clone());
+    let result = print_snapshots(command, options, &mut poller, &mut hooks);
+    // the hooks of the last alerts are run before exiting
+    for error in hooks.finish() {
+        eprintln!("Failed to run the alert hook: {}", error);
+    }
+    result
+}
+
+/// Prints the snapshot of `provider` requested by `command`, and the raised alerts, which are also passed to the `hooks`.
+/// With `--watch`, a new snapshot is printed periodically until an error occurs.
+pub fn print_snapshots<P: SystemInfoProvider>(
+    command: Command,
+    options: &Cli,
+    provider: &mut P,
+    hooks: &mut AlertHooks,
+) -> io::Result<()> {
+    let format = options.format;
+    loop {
+        match command {
+            Command::Cpu => print(&provider.get_cpu_info().map_err(io::Error::other)?, format)?,
+            Command::Mem => print(
+                &provider.get_memory_info().map_err(io::Error::other)?,
+                format,
+            )?,
+            Command::Disk => print(&provider.get_disk_info().map_err(io::Error::other)?, format)?,
+            Command::Net => print(
+                &provider.get_network_details().map_err(io::Error::other)?,
+                format,
+            )?,
+            Command::Proc => print(
+                &provider.get_process_list().map_err(io::Error::other)?,
+                format,
+            )?,
+            Command::Overview => print(
+                &provider.get_system_overview().map_err(io::Error::other)?,
+                format,
+            )?,
+            Command::Sensors => print(
+                &provider.get_sensors_info().map_err(io::Error::other)?,
+                format,
+            )?,
+            Command::Users => print(&provider.get_sessions().map_err(io::Error::other)?, format)?,
+            Command::Services => {
+                print(&provider.get_services().map_err(io::Error::other)?, format)?
+            }
+            Command::Containers => print(
+                &provider.get_containers().map_err(io::Error::other)?,
+                format,
+            )?,
+            Command::Logs => print(&provider.get_logs().map_err(io::Error::other)?, format)?,
+            Command::Snapshot => {
+                print(&provider.get_snapshot().map_err(io::Error::other)?, format)?
+            }
+            Command::Diff { .. } => unreachable!("diffing is handled before polling"),
+            Command::Export { .. } => unreachable!("exporting is handled before polling"),
+            Command::Serve { .. } => unreachable!("serving is handled before polling"),
+            Command::Replay { .. } => unreachable!("replaying is handled by the user interface"),
+            Command::Check { .. } => unreachable!("checking is handled before polling"),
+            Command::Log { .. } => unreachable!("logging is handled before polling"),
+        }
+
+        let alerts = provider.evaluate_alerts().map_err(io::Error::other)?;
+        for alert in alerts.iter() {
+            eprintln!("ALERT: {}", alert);
+        }
+        core::notification::notify(&alerts);
+        hooks.dispatch(&alerts);
+        for error in hooks.failures() {
+            eprintln!("Failed to run the alert hook: {}", error);
+        }
+        if options.fail_on_alert && !alerts.is_empty() {
+            return Err(io::Error::other(format!(
+                "{} alert(s) raised",
+                alerts.len()
+            )));
+        }
+
+        match options.watch {
+            Some(seconds) => thread::sleep(Duration::from_secs(seconds)),
+            None => return Ok(()),
+        }
+    }
+}
+
+fn print<T: Serialize + Tabular>(snapshot: &T, format: Format) -> io::Result<()> {
+    let output = render(snapshot, format)?;
+    writeln!(io::stdout().lock(), "{}", output)
+}
+
+/// Reads a snapshot written by `ocelo snapshot`, in JSON or in MessagePack.
+fn read_snapshot(path: &Path) -> io::Result<SystemSnapshot> {
+    let bytes = fs::read(path).map_err(|error| {
+        io::Error::new(
+            error.kind(),
+            format!("Failed to read {}: {}", path.display(), error),
+        )
+    })?;
+    core::encoding::decode(&bytes).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to read {}: {}", path.display(), error),
+        )
+    })
+}
+
+/// Parses sizes like `500K`, `10M` or `1G` into bytes, the units are powers of 1024.
+fn parse_size(value: &str) -> Result<u64, String> {
+    let (number, multiplier) = match value.chars().last() {
+        Some('K' | 'k') => (&value[..value.len() - 1], 1 << 10),
+        Some('M' | 'm') => (&value[..value.len() - 1], 1 << 20),
+        Some('G' | 'g') => (&value[..value.len() - 1], 1 << 30),
+        _ => (value, 1),
+    };
+    let number = number.trim().parse::<u64>().map_err(|_| {
+        format!(
+            "invalid size '{}', expected a number like 500K, 10M or 1G",
+            value
+        )
+    })?;
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size {} is too large", value))
+}
@@ -0,0 +1,387 @@
+use core::model::{DiskFilter, ProcessList, SystemOverviewInfo};
+use core::{OceloError, SystemInfoPoller, MINIMUM_CPU_UPDATE_INTERVAL};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+/// The longest wait for a scraper to send its request or to receive the metrics.
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves the system metrics in the Prometheus text exposition format on `listen` address.
+/// The metrics are collected in the background at every `interval`,
+/// so scrapes are answered immediately with the last snapshot.
+/// Every scrape is answered on its own thread, so an idle connection does not hold up the others.
+/// Only the mounts selected by `disk_filter` are exported.
+pub fn serve_prometheus(
+    listen: &str,
+    interval: Duration,
+    disk_filter: DiskFilter,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(listen)?;
+    let metrics = Arc::new(RwLock::new(String::new()));
+
+    let mut poller = SystemInfoPoller::default()
+        .with_interval(interval)
+        .with_disk_filter(disk_filter);
+    poller.init();
+    let interval = poller.interval();
+    // the CPU usage is calculated from the difference of two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    *metrics.write().unwrap() = collect(&mut poller).map_err(io::Error::other)?;
+
+    let metrics_clone = metrics.clone();
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        let snapshot = match collect(&mut poller) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                eprintln!("Failed to collect the metrics: {}", error);
+                continue;
+            }
+        };
+        match metrics_clone.write() {
+            Ok(mut metrics) => *metrics = snapshot,
+            Err(error) => eprintln!("Error acquiring metrics lock: {}", error),
+        }
+    });
+
+    eprintln!("Serving Prometheus metrics on http://{}/metrics", listen);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let body = metrics.read().map(|m| m.clone()).unwrap_or_default();
+                thread::spawn(move || {
+                    if let Err(error) = respond(stream, &body) {
+                        eprintln!("Failed to answer scrape: {}", error);
+                    }
+                });
+            }
+            Err(error) => eprintln!("Failed to accept connection: {}", error),
+        }
+    }
+
+    Ok(())
+}
+
+/// Answers a single HTTP request. Only `GET /metrics` is served, everything else is not found.
+fn respond(mut stream: TcpStream, metrics: &str) -> io::Result<()> {
+    stream.set_read_timeout(Some(SCRAPE_TIMEOUT))?;
+    stream.set_write_timeout(Some(SCRAPE_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", "text/plain; version=0.0.4", metrics),
+        _ => ("404 Not Found", "text/plain", "Not found\n"),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Polls every metric and encodes them into the text exposition format.
+fn collect(poller: &mut SystemInfoPoller) -> Result<String, OceloError> {
+    let overview = poller.get_system_overview()?;
+    let processes = poller.get_process_list()?;
+
+    let mut encoder = Encoder::default();
+    encode_overview(&mut encoder, &overview);
+    encode_processes(&mut encoder, &processes);
+    Ok(encoder.output)
+}
+
+fn encode_overview(encoder: &mut Encoder, overview: &SystemOverviewInfo) {
+    encoder.gauge(
+        "ocelo_uptime_seconds",
+        "Time since the system booted",
+        [(vec![], overview.overview.uptime as f64)],
+    );
+    encoder.gauge(
+        "ocelo_load_average",
+        "System load average",
+        [
+            (
+                vec![("period", "1m".to_string())],
+                overview.overview.load_one_minute,
+            ),
+            (
+                vec![("period", "5m".to_string())],
+                overview.overview.load_five_minutes,
+            ),
+            (
+                vec![("period", "15m".to_string())],
+                overview.overview.load_fifteen_minutes,
+            ),
+        ],
+    );
+
+    encoder.gauge(
+        "ocelo_cpu_usage_percent",
+        "Global CPU usage",
+        [(vec![], overview.cpu.usage as f64)],
+    );
+    encoder.gauge(
+        "ocelo_cpu_frequency_mhz",
+        "Average CPU frequency",
+        [(vec![], overview.cpu.frequency as f64)],
+    );
+    if let Some(temperature) = overview.cpu.temperature {
+        encoder.gauge(
+            "ocelo_cpu_temperature_celsius",
+            "CPU package temperature",
+            [(vec![], temperature as f64)],
+        );
+    }
+
+    if let Some(descriptors) = &overview.overview.file_descriptors {
+        encoder.gauge(
+            "ocelo_file_descriptors_allocated",
+            "Allocated file handles of the system",
+            [(vec![], descriptors.allocated as f64)],
+        );
+        encoder.gauge(
+            "ocelo_file_descriptors_max",
+            "Most file handles of the system",
+            [(vec![], descriptors.maximum as f64)],
+        );
+    }
+    if let Some(entropy) = &overview.overview.entropy {
+        encoder.gauge(
+            "ocelo_entropy_available_bits",
+            "Available entropy of the random number generator of the kernel",
+            [(vec![], entropy.available as f64)],
+        );
+    }
+    if let Some(offset) = overview
+        .overview
+        .clock
+        .as_ref()
+        .and_then(|clock| clock.offset.as_ref())
+    {
+        encoder.gauge(
+            "ocelo_clock_offset_seconds",
+            "How far the clock is ahead of its reference",
+            [(vec![], offset.offset / 1000.0)],
+        );
+    }
+
+    let memory = &overview.memory;
+    encoder.gauge(
+        "ocelo_memory_total_bytes",
+        "Total physical memory",
+        [(vec![], memory.total as f64)],
+    );
+    encoder.gauge(
+        "ocelo_memory_used_bytes",
+        "Used physical memory",
+        [(vec![], memory.used as f64)],
+    );
+    encoder.gauge(
+        "ocelo_memory_available_bytes",
+        "Available physical memory",
+        [(vec![], memory.available as f64)],
+    );
+    encoder.gauge(
+        "ocelo_swap_total_bytes",
+        "Total swap space",
+        [(vec![], memory.swap_total as f64)],
+    );
+    encoder.gauge(
+        "ocelo_swap_used_bytes",
+        "Used swap space",
+        [(vec![], memory.swap_used as f64)],
+    );
+
+    let disk_labels = |disk: &core::model::Storage| {
+        vec![
+            ("device", disk.name.clone()),
+            ("mount", disk.mount.clone()),
+            ("fs", disk.file_system.clone()),
+        ]
+    };
+    let disks = &overview.disks.disks;
+    encoder.gauge(
+        "ocelo_disk_total_bytes",
+        "Total space of the storage device",
+        disks.iter().map(|d| (disk_labels(d), d.total_space as f64)),
+    );
+    encoder.gauge(
+        "ocelo_disk_used_bytes",
+        "Used space of the storage device",
+        disks.iter().map(|d| (disk_labels(d), d.used_space as f64)),
+    );
+    encoder.gauge(
+        "ocelo_disk_available_bytes",
+        "Available space of the storage device",
+        disks
+            .iter()
+            .map(|d| (disk_labels(d), d.available_space as f64)),
+    );
+    encoder.gauge(
+        "ocelo_disk_inodes_total",
+        "Inodes of the file system",
+        disks
+            .iter()
+            .filter(|d| d.inodes_total > 0)
+            .map(|d| (disk_labels(d), d.inodes_total as f64)),
+    );
+    encoder.gauge(
+        "ocelo_disk_inodes_used",
+        "Used inodes of the file system",
+        disks
+            .iter()
+            .filter(|d| d.inodes_total > 0)
+            .map(|d| (disk_labels(d), d.inodes_used as f64)),
+    );
+
+    let interfaces = &overview.network.interface_details;
+    let interface_label = |name: &str| vec![("interface", name.to_string())];
+    encoder.counter(
+        "ocelo_network_received_bytes_total",
+        "Received data of the network interface",
+        interfaces
+            .iter()
+            .map(|i| (interface_label(&i.name), i.total_received as f64)),
+    );
+    encoder.counter(
+        "ocelo_network_transmitted_bytes_total",
+        "Transmitted data of the network interface",
+        interfaces
+            .iter()
+            .map(|i| (interface_label(&i.name), i.total_transmitted as f64)),
+    );
+    encoder.counter(
+        "ocelo_network_received_packets_total",
+        "Received packets of the network interface",
+        interfaces
+            .iter()
+            .map(|i| (interface_label(&i.name), i.total_packets_received as f64)),
+    );
+    encoder.counter(
+        "ocelo_network_transmitted_packets_total",
+        "Transmitted packets of the network interface",
+        interfaces
+            .iter()
+            .map(|i| (interface_label(&i.name), i.total_packets_transmitted as f64)),
+    );
+    encoder.counter(
+        "ocelo_network_receive_errors_total",
+        "Errors on receiving data on the network interface",
+        interfaces
+            .iter()
+            .map(|i| (interface_label(&i.name), i.total_errors_on_received as f64)),
+    );
+    encoder.counter(
+        "ocelo_network_transmit_errors_total",
+        "Errors on transmitting data on the network interface",
+        interfaces.iter().map(|i| {
+            (
+                interface_label(&i.name),
+                i.total_errors_on_transmitted as f64,
+            )
+        }),
+    );
+}
+
+fn encode_processes(encoder: &mut Encoder, processes: &ProcessList) {
+    let labels = |process: &core::model::ProcessInfo| {
+        vec![
+            ("pid", process.pid.to_string()),
+            ("name", process.name.clone()),
+            ("user", process.username.clone()),
+        ]
+    };
+
+    encoder.gauge(
+        "ocelo_process_cpu_usage_percent",
+        "CPU usage of the process",
+        processes.iter().map(|p| (labels(p), p.cpu_usage as f64)),
+    );
+    encoder.gauge(
+        "ocelo_process_memory_bytes",
+        "Physical memory used by the process",
+        processes.iter().map(|p| (labels(p), p.memory as f64)),
+    );
+    encoder.gauge(
+        "ocelo_process_virtual_memory_bytes",
+        "Virtual memory used by the process",
+        processes
+            .iter()
+            .map(|p| (labels(p), p.virtual_memory as f64)),
+    );
+    encoder.gauge(
+        "ocelo_process_disk_read_bytes_per_second",
+        "Disk read rate of the process",
+        processes.iter().map(|p| (labels(p), p.read_rate)),
+    );
+    encoder.gauge(
+        "ocelo_process_disk_write_bytes_per_second",
+        "Disk write rate of the process",
+        processes.iter().map(|p| (labels(p), p.write_rate)),
+    );
+}
+
+type Labels = Vec<(&'static str, String)>;
+
+/// Writes metric families in the Prometheus text exposition format.
+#[derive(Default)]
+struct Encoder {
+    output: String,
+}
+
+impl Encoder {
+    fn gauge(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (Labels, f64)>) {
+        self.family(name, help, "gauge", samples);
+    }
+
+    fn counter(
+        &mut self,
+        name: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels, f64)>,
+    ) {
+        self.family(name, help, "counter", samples);
+    }
+
+    fn family(
+        &mut self,
+        name: &str,
+        help: &str,
+        kind: &str,
+        samples: impl IntoIterator<Item = (Labels, f64)>,
+    ) {
+        // writing into a String never fails
+        let _ = writeln!(self.output, "# HELP {} {}", name, help);
+        let _ = writeln!(self.output, "# TYPE {} {}", name, kind);
+
+        for (labels, value) in samples {
+            if labels.is_empty() {
+                let _ = writeln!(self.output, "{} {}", name, value);
+            } else {
+                let labels = labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                let _ = writeln!(self.output, "{}{{{}}} {}", name, labels, value);
+            }
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
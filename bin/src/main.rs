@@ -1,6 +1,91 @@
+use clap::Parser;
+use cli::{Cli, Command};
+use core::{Recorder, RemoteSource};
+use std::io::ErrorKind;
 use tui::View;
 
+mod check;
+mod cli;
+mod export;
+mod metric_log;
+mod output;
+mod server;
+
 fn main() {
-    let mut view = View::default();
-    view.run();
+    let cli = Cli::parse();
+    let config = match cli.config() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    match cli.command.clone() {
+        Some(Command::Replay { file, speed }) => {
+            if speed <= 0.0 {
+                eprintln!("the replay speed must be positive");
+                std::process::exit(1);
+            }
+            let snapshots = match Recorder::load(&file) {
+                Ok(snapshots) => snapshots,
+                Err(error) => {
+                    eprintln!("Failed to load {}: {}", file.display(), error);
+                    std::process::exit(1);
+                }
+            };
+            if let Err(error) =
+                View::replay(config, snapshots, speed).and_then(|mut view| view.run())
+            {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Check {
+            metric,
+            warn,
+            crit,
+            mount,
+        }) => {
+            let (status, line) = check::check(metric, warn, crit, &mount, config.disks.clone());
+            println!("{}", line);
+            std::process::exit(status.code());
+        }
+        Some(command) => {
+            match cli::run(command, &cli, &config) {
+                // the reader of the output has exited, like `head`
+                Err(error) if error.kind() == ErrorKind::BrokenPipe => {}
+                Err(error) => {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+                Ok(()) => {}
+            }
+        }
+        None => {
+            let recorder = match config.record.as_ref() {
+                Some(path) => match Recorder::open(path, config.record_retention()) {
+                    Ok(recorder) => Some(recorder.with_encoding(config.record_encoding)),
+                    Err(error) => {
+                        eprintln!("Failed to open {}: {}", path.display(), error);
+                        std::process::exit(1);
+                    }
+                },
+                None => None,
+            };
+            let view = match cli.remote.clone() {
+                Some(address) => {
+                    let source = RemoteSource::new(address.clone())
+                        .with_token(cli.remote_token.clone())
+                        .with_alert_rules(config.alerts.clone());
+                    View::with_source(config, address, Box::new(source), recorder)
+                }
+                None => View::new(config, recorder),
+            };
+            if let Err(error) = view.and_then(|mut view| view.run()) {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+    }
 }
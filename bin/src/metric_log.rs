@@ -0,0 +1,264 @@
+use crate::cli::LogMetric;
+use crate::output::escape_csv;
+use core::model::{DiskFilter, SystemOverviewInfo};
+use core::{SystemInfoPoller, MINIMUM_CPU_UPDATE_INTERVAL};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+/// When the log file is closed and a new one is started, the old one is renamed after the time of its last row.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rotation {
+    /// Starts a new file when the file has grown to this many bytes
+    pub max_size: Option<u64>,
+    /// Starts a new file at the first row of every day in UTC
+    pub daily: bool,
+}
+
+/// Appends a row of the selected `metrics` to the CSV file at `output` at every `interval`, until an error occurs.
+/// The first column is the time of the row in seconds since the Unix epoch,
+/// the disks and the network interfaces have their own columns, like `disk:/:used`.
+/// When the columns change, like when a disk is mounted, a new file is started.
+pub fn log_metrics(
+    output: &Path,
+    metrics: &[LogMetric],
+    interval: Duration,
+    rotation: Rotation,
+    disk_filter: DiskFilter,
+) -> io::Result<()> {
+    let mut poller = SystemInfoPoller::default()
+        .with_interval(interval)
+        .with_disk_filter(disk_filter);
+    poller.init();
+    let interval = poller.interval();
+    // the CPU usage is calculated from the difference of two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+
+    let mut log = MetricLog::new(output.to_path_buf(), rotation);
+    eprintln!(
+        "Logging the metrics into {} at every {} seconds",
+        output.display(),
+        interval.as_secs()
+    );
+    loop {
+        let snapshot = poller.get_system_overview().map_err(io::Error::other)?;
+        let (header, row) = columns(&snapshot, metrics);
+        log.append(&header, &row, unix_time())?;
+        thread::sleep(interval);
+    }
+}
+
+/// The names and the values of the columns of the `metrics` in `snapshot`.
+fn columns(snapshot: &SystemOverviewInfo, metrics: &[LogMetric]) -> (Vec<String>, Vec<String>) {
+    let mut header = vec!["timestamp".to_string()];
+    let mut row = Vec::new();
+    let mut push = |name: String, value: String| {
+        header.push(name);
+        row.push(value);
+    };
+
+    for metric in metrics {
+        match metric {
+            LogMetric::Cpu => {
+                push(
+                    "cpu:usage".to_string(),
+                    format!("{:.1}", snapshot.cpu.usage),
+                );
+                push(
+                    "cpu:load".to_string(),
+                    format!("{:.2}", snapshot.overview.load_one_minute),
+                );
+            }
+            LogMetric::Memory => {
+                let memory = &snapshot.memory;
+                push("memory:used".to_string(), memory.used.to_string());
+                push("memory:total".to_string(), memory.total.to_string());
+                push("swap:used".to_string(), memory.swap_used.to_string());
+                push("swap:total".to_string(), memory.swap_total.to_string());
+            }
+            LogMetric::Disk => {
+                for disk in snapshot.disks.disks.iter() {
+                    push(
+                        format!("disk:{}:used", disk.mount),
+                        disk.used_space.to_string(),
+                    );
+                    push(
+                        format!("disk:{}:total", disk.mount),
+                        disk.total_space.to_string(),
+                    );
+                    push(
+                        format!("disk:{}:read_per_second", disk.mount),
+                        format!("{:.0}", disk.read_rate),
+                    );
+                    push(
+                        format!("disk:{}:written_per_second", disk.mount),
+                        format!("{:.0}", disk.write_rate),
+                    );
+                }
+            }
+            LogMetric::Network => {
+                for interface in snapshot.network.interface_details.iter() {
+                    push(
+                        format!("net:{}:received_per_second", interface.name),
+                        format!("{:.0}", interface.received_rate),
+                    );
+                    push(
+                        format!("net:{}:transmitted_per_second", interface.name),
+                        format!("{:.0}", interface.transmitted_rate),
+                    );
+                }
+            }
+        }
+    }
+
+    (header, row)
+}
+
+/// A CSV file which the rows are appended to, rotated by `rotation`.
+struct MetricLog {
+    path: PathBuf,
+    rotation: Rotation,
+    /// The header of the file, `None` until the file is opened
+    header: Option<String>,
+    /// The day of the last row in days since the Unix epoch
+    day: Option<u64>,
+}
+
+impl MetricLog {
+    fn new(path: PathBuf, rotation: Rotation) -> Self {
+        MetricLog {
+            path,
+            rotation,
+            header: None,
+            day: None,
+        }
+    }
+
+    /// Appends a row of `values` under `header` at `timestamp`, rotating the file first if needed.
+    fn append(&mut self, header: &[String], values: &[String], timestamp: u64) -> io::Result<()> {
+        let header = join(header);
+        if self.header.is_none() {
+            // continues an existing file of an earlier run, unless its columns differ
+            self.header = read_header(&self.path)?;
+            self.day = fs::metadata(&self.path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|modified| modified.as_secs() / SECONDS_PER_DAY);
+        }
+
+        let day = timestamp / SECONDS_PER_DAY;
+        let size = fs::metadata(&self.path).map_or(0, |metadata| metadata.len());
+        let rotate = match &self.header {
+            None => false,
+            Some(current) => {
+                current != &header
+                    || self
+                        .rotation
+                        .max_size
+                        .is_some_and(|max_size| size >= max_size)
+                    || (self.rotation.daily && self.day.is_some_and(|last_day| last_day != day))
+            }
+        };
+        if rotate {
+            self.rotate()?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if self.header.as_ref() != Some(&header) {
+            writeln!(file, "{}", header)?;
+            self.header = Some(header);
+        }
+        writeln!(file, "{},{}", timestamp, join(values))?;
+        self.day = Some(day);
+        Ok(())
+    }
+
+    /// Renames the file after the time of its last row, like `metrics-20261015-093000.csv`.
+    fn rotate(&mut self) -> io::Result<()> {
+        let modified = fs::metadata(&self.path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |modified| modified.as_secs());
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = match self.path.extension() {
+            Some(extension) => format!(
+                "{}-{}.{}",
+                stem,
+                format_timestamp(modified),
+                extension.to_string_lossy()
+            ),
+            None => format!("{}-{}", stem, format_timestamp(modified)),
+        };
+        fs::rename(&self.path, self.path.with_file_name(name))?;
+        self.header = None;
+        Ok(())
+    }
+}
+
+/// Returns the first line of the file at `path`, `None` if the file does not exist or it is empty.
+fn read_header(path: &Path) -> io::Result<Option<String>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    BufReader::new(file).lines().next().transpose()
+}
+
+fn join(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| escape_csv(value))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs())
+}
+
+/// Formats a Unix timestamp as `YYYYMMDD-HHMMSS` in UTC, which sorts the rotated files by their time.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / SECONDS_PER_DAY) as i64;
+    let seconds = timestamp % SECONDS_PER_DAY;
+
+    // converts the days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
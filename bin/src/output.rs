@@ -0,0 +1,575 @@
+use crate::cli::Format;
+use core::model::{
+    ContainersInfo, DiskInfo, LogsInfo, MemoryInfo, NetworkInfo, ProcessList, SensorsInfo,
+    ServicesInfo, SessionsInfo, SnapshotDiff, SystemOverviewInfo, SystemSnapshot,
+};
+use core::CpuInfo;
+use serde::Serialize;
+
+/// Snapshots which can be printed as rows of values under a header.
+pub trait Tabular {
+    fn header(&self) -> Vec<&'static str>;
+
+    fn rows(&self) -> Vec<Vec<String>>;
+}
+
+/// Renders the `snapshot` into the given `format`.
+pub fn render<T: Serialize + Tabular>(
+    snapshot: &T,
+    format: Format,
+) -> Result<String, serde_json::Error> {
+    match format {
+        Format::Json => serde_json::to_string_pretty(snapshot),
+        Format::Csv => Ok(render_csv(snapshot)),
+        Format::Table => Ok(render_table(snapshot)),
+    }
+}
+
+fn render_csv(snapshot: &impl Tabular) -> String {
+    let header = snapshot.header().join(",");
+    let rows = snapshot.rows().into_iter().map(|row| {
+        row.iter()
+            .map(|value| escape_csv(value))
+            .collect::<Vec<String>>()
+            .join(",")
+    });
+
+    std::iter::once(header)
+        .chain(rows)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Quotes the value if it contains a separator, a quote or a line break.
+pub fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders the rows into left aligned columns, as wide as their widest value.
+fn render_table(snapshot: &impl Tabular) -> String {
+    let header = snapshot
+        .header()
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    let rows = snapshot.rows();
+
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|value| value.chars().count())
+                .chain(std::iter::once(title.chars().count()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<usize>>();
+
+    std::iter::once(&header)
+        .chain(rows.iter())
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(value, width)| format!("{:<width$}", value, width = width))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+impl Tabular for CpuInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "name",
+            "cores",
+            "frequency_mhz",
+            "usage_percent",
+            "temperature_c",
+            "governor",
+            "min_frequency_mhz",
+            "max_frequency_mhz",
+            "boost",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.name.clone(),
+            self.core_count.to_string(),
+            self.frequency.to_string(),
+            format!("{:.1}", self.usage),
+            self.temperature
+                .map_or(String::new(), |t| format!("{:.1}", t)),
+            self.governor.clone().unwrap_or_default(),
+            self.min_frequency.map_or(String::new(), |f| f.to_string()),
+            self.max_frequency.map_or(String::new(), |f| f.to_string()),
+            self.boost.map_or(String::new(), |boost| boost.to_string()),
+        ]]
+    }
+}
+
+impl Tabular for MemoryInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "total",
+            "used",
+            "available",
+            "swap_total",
+            "swap_used",
+            "swap_available",
+            "free",
+            "buffers",
+            "cached",
+            "shared",
+            "dirty",
+            "slab",
+            "swap_in_rate",
+            "swap_out_rate",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.total.to_string(),
+            self.used.to_string(),
+            self.available.to_string(),
+            self.swap_total.to_string(),
+            self.swap_used.to_string(),
+            self.swap_available.to_string(),
+            self.free.to_string(),
+            self.buffers.to_string(),
+            self.cached.to_string(),
+            self.shared.to_string(),
+            self.dirty.to_string(),
+            self.slab.to_string(),
+            format!("{:.0}", self.swap_in_rate),
+            format!("{:.0}", self.swap_out_rate),
+        ]]
+    }
+}
+
+impl Tabular for DiskInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "name",
+            "mount",
+            "file_system",
+            "total",
+            "used",
+            "available",
+            "read_bytes",
+            "written_bytes",
+            "read_rate",
+            "write_rate",
+            "inodes_total",
+            "inodes_used",
+            "iops",
+            "latency_ms",
+            "queue_depth",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.disks
+            .iter()
+            .map(|disk| {
+                vec![
+                    disk.name.clone(),
+                    disk.mount.clone(),
+                    disk.file_system.clone(),
+                    disk.total_space.to_string(),
+                    disk.used_space.to_string(),
+                    disk.available_space.to_string(),
+                    disk.bytes_read.to_string(),
+                    disk.bytes_written.to_string(),
+                    format!("{:.0}", disk.read_rate),
+                    format!("{:.0}", disk.write_rate),
+                    disk.inodes_total.to_string(),
+                    disk.inodes_used.to_string(),
+                    format!("{:.1}", disk.iops),
+                    format!("{:.2}", disk.latency),
+                    format!("{:.2}", disk.queue_depth),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for NetworkInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "interface",
+            "mac",
+            "ip",
+            "state",
+            "speed",
+            "duplex",
+            "mtu",
+            "carrier_changes",
+            "ssid",
+            "signal",
+            "received",
+            "transmitted",
+            "received_rate",
+            "transmitted_rate",
+            "packets_received",
+            "packets_transmitted",
+            "errors_received",
+            "errors_transmitted",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.interface_details
+            .iter()
+            .map(|interface| {
+                let link = interface.link.as_ref();
+                let wireless = interface.wireless.as_ref();
+                let optional = |value: Option<String>| value.unwrap_or_default();
+                vec![
+                    interface.name.clone(),
+                    interface.mac_address.clone(),
+                    interface.ip_addresses.join(" "),
+                    optional(link.map(|link| link.operational_state.clone())),
+                    optional(
+                        link.and_then(|link| link.speed)
+                            .map(|speed| speed.to_string()),
+                    ),
+                    optional(link.and_then(|link| link.duplex.clone())),
+                    optional(link.and_then(|link| link.mtu).map(|mtu| mtu.to_string())),
+                    optional(
+                        link.and_then(|link| link.carrier_changes)
+                            .map(|changes| changes.to_string()),
+                    ),
+                    optional(wireless.and_then(|wireless| wireless.ssid.clone())),
+                    optional(
+                        wireless
+                            .and_then(|wireless| wireless.signal)
+                            .map(|signal| signal.to_string()),
+                    ),
+                    interface.total_received.to_string(),
+                    interface.total_transmitted.to_string(),
+                    format!("{:.0}", interface.received_rate),
+                    format!("{:.0}", interface.transmitted_rate),
+                    interface.total_packets_received.to_string(),
+                    interface.total_packets_transmitted.to_string(),
+                    interface.total_errors_on_received.to_string(),
+                    interface.total_errors_on_transmitted.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for SensorsInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec!["label", "kind", "value", "unit", "min", "max", "critical"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let limit = |limit: Option<f64>| limit.map(|limit| limit.to_string()).unwrap_or_default();
+        self.sensors
+            .iter()
+            .map(|sensor| {
+                vec![
+                    sensor.label.clone(),
+                    sensor.kind.to_string(),
+                    sensor.value.to_string(),
+                    sensor.kind.unit().to_string(),
+                    limit(sensor.min),
+                    limit(sensor.max),
+                    limit(sensor.critical),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for SessionsInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec!["user", "tty", "host", "login_time", "idle_seconds", "pid"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.sessions
+            .iter()
+            .map(|session| {
+                vec![
+                    session.user.clone(),
+                    session.tty.clone(),
+                    session.host.clone().unwrap_or_default(),
+                    session.login_time.to_string(),
+                    session.idle.map_or(String::new(), |idle| idle.to_string()),
+                    session.pid.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for ServicesInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "name",
+            "active_state",
+            "sub_state",
+            "active_since",
+            "main_pid",
+            "memory",
+            "cpu_time_ms",
+            "description",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let optional = |value: Option<u64>| value.map_or(String::new(), |value| value.to_string());
+        self.services
+            .iter()
+            .map(|service| {
+                vec![
+                    service.name.clone(),
+                    service.active_state.clone(),
+                    service.sub_state.clone(),
+                    optional(service.active_since),
+                    optional(service.main_pid.map(u64::from)),
+                    optional(service.memory),
+                    optional(service.cpu_time),
+                    service.description.clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for ContainersInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "id",
+            "name",
+            "image",
+            "status",
+            "cpu_percent",
+            "memory",
+            "memory_limit",
+            "net_received_per_second",
+            "net_transmitted_per_second",
+            "read_per_second",
+            "written_per_second",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.containers
+            .iter()
+            .map(|container| {
+                vec![
+                    container.id.clone(),
+                    container.name.clone(),
+                    container.image.clone(),
+                    container.status.clone(),
+                    format!("{:.1}", container.cpu_usage),
+                    container.memory.to_string(),
+                    container.memory_limit.to_string(),
+                    format!("{:.0}", container.net_received_rate),
+                    format!("{:.0}", container.net_transmitted_rate),
+                    format!("{:.0}", container.read_rate),
+                    format!("{:.0}", container.write_rate),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for LogsInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec!["timestamp", "priority", "source", "message"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry
+                        .timestamp
+                        .map_or(String::new(), |timestamp| timestamp.to_string()),
+                    entry.priority.to_string(),
+                    entry.source.clone(),
+                    entry.message.clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for ProcessList {
+    fn header(&self) -> Vec<&'static str> {
+        vec![
+            "pid",
+            "name",
+            "user",
+            "cpu_percent",
+            "memory",
+            "virtual_memory",
+            "swap",
+            "oom_score",
+            "cpu_time",
+            "running_time",
+            "read_rate",
+            "write_rate",
+            "cgroup",
+            "command",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|process| {
+                vec![
+                    process.pid.to_string(),
+                    process.name.clone(),
+                    process.username.clone(),
+                    format!("{:.1}", process.cpu_usage),
+                    process.memory.to_string(),
+                    process.virtual_memory.to_string(),
+                    process.swap.map_or(String::new(), |swap| swap.to_string()),
+                    process
+                        .oom_score
+                        .map_or(String::new(), |score| score.to_string()),
+                    process.cpu_time.to_string(),
+                    process.running_time.to_string(),
+                    format!("{:.0}", process.read_rate),
+                    format!("{:.0}", process.write_rate),
+                    process.cgroup.clone().unwrap_or_default(),
+                    process.command.clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for SystemOverviewInfo {
+    fn header(&self) -> Vec<&'static str> {
+        vec!["metric", "value"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let used_space = self.disks.disks.iter().map(|d| d.used_space).sum::<u64>();
+        let total_space = self.disks.disks.iter().map(|d| d.total_space).sum::<u64>();
+
+        [
+            ("host_name", self.overview.host_name.clone()),
+            ("kernel_version", self.overview.kernel_version.clone()),
+            (
+                "distribution",
+                self.overview.distribution.clone().unwrap_or_default(),
+            ),
+            ("uptime", self.overview.uptime.to_string()),
+            ("boot_time", self.overview.boot_time.to_string()),
+            ("sessions", self.sessions.len().to_string()),
+            ("load_one_minute", self.overview.load_one_minute.to_string()),
+            (
+                "load_five_minutes",
+                self.overview.load_five_minutes.to_string(),
+            ),
+            (
+                "load_fifteen_minutes",
+                self.overview.load_fifteen_minutes.to_string(),
+            ),
+            ("cpu_name", self.cpu.name.clone()),
+            ("cpu_usage_percent", format!("{:.1}", self.cpu.usage)),
+            ("memory_total", self.memory.total.to_string()),
+            ("memory_used", self.memory.used.to_string()),
+            ("swap_total", self.memory.swap_total.to_string()),
+            ("swap_used", self.memory.swap_used.to_string()),
+            ("disk_total", total_space.to_string()),
+            ("disk_used", used_space.to_string()),
+            ("network_received", self.network.total_received.to_string()),
+            (
+                "network_transmitted",
+                self.network.total_transmitted.to_string(),
+            ),
+        ]
+        .into_iter()
+        .map(|(metric, value)| vec![metric.to_string(), value])
+        .collect()
+    }
+}
+
+impl Tabular for SystemSnapshot {
+    fn header(&self) -> Vec<&'static str> {
+        vec!["metric", "value"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let mut rows = vec![vec![
+            "timestamp".to_string(),
+            format!("{:.0}", self.timestamp),
+        ]];
+        rows.extend(self.overview.rows());
+        rows.push(vec![
+            "processes".to_string(),
+            self.processes.len().to_string(),
+        ]);
+        rows
+    }
+}
+
+impl Tabular for SnapshotDiff {
+    fn header(&self) -> Vec<&'static str> {
+        vec!["metric", "change"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let mut rows: Vec<Vec<String>> = [
+            ("elapsed_seconds", format!("{:.0}", self.elapsed)),
+            ("cpu_usage_percent", format!("{:+.1}", self.cpu_usage)),
+            (
+                "cpu_temperature",
+                self.cpu_temperature
+                    .map(|change| format!("{:+.1}", change))
+                    .unwrap_or_default(),
+            ),
+            ("load_one_minute", format!("{:+.2}", self.load_one_minute)),
+            ("memory_used", format!("{:+}", self.memory_used)),
+            ("memory_usage_percent", format!("{:+.1}", self.memory_usage)),
+            ("swap_used", format!("{:+}", self.swap_used)),
+        ]
+        .into_iter()
+        .map(|(metric, change)| vec![metric.to_string(), change])
+        .collect();
+
+        for disk in self.disks.iter() {
+            rows.push(vec![
+                format!("disk_used {}", disk.mount),
+                format!("{:+}", disk.used_space),
+            ]);
+            rows.push(vec![
+                format!("disk_usage_percent {}", disk.mount),
+                format!("{:+.1}", disk.usage),
+            ]);
+        }
+        rows.extend(self.new_processes.iter().map(|process| {
+            vec![
+                "new_process".to_string(),
+                format!("{} {}", process.pid, process.name),
+            ]
+        }));
+        rows.extend(self.exited_processes.iter().map(|process| {
+            vec![
+                "exited_process".to_string(),
+                format!("{} {}", process.pid, process.name),
+            ]
+        }));
+        rows
+    }
+}
@@ -0,0 +1,548 @@
+use core::config::Config;
+use core::model::SessionsInfo;
+use core::{Encoding, OceloError, SystemInfoPoller, SystemInfoUpdate, MINIMUM_CPU_UPDATE_INTERVAL};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+
+/// The paths served by the API, each returning one part of the system information.
+const ENDPOINTS: [&str; 13] = [
+    "/overview",
+    "/cpu",
+    "/cpu-topology",
+    "/cpu-memory",
+    "/memory",
+    "/disks",
+    "/network",
+    "/processes",
+    "/sensors",
+    "/users",
+    "/services",
+    "/containers",
+    "/logs",
+];
+
+/// The prefix of the path returning the details of a single process, like `/processes/42`.
+/// The details are fetched when requested, instead of being served from the last snapshot.
+const PROCESS_DETAILS_ENDPOINT: &str = "/processes/";
+
+/// The path returning the TCP and UDP sockets of the system, which are also read when requested.
+const CONNECTIONS_ENDPOINT: &str = "/connections";
+
+/// The path returning the kernel tunables and the resource limits, which are also read when requested.
+const LIMITS_ENDPOINT: &str = "/limits";
+
+/// The path returning the failed logins, the exposed ports and the firewall, which are also read when requested.
+const SECURITY_ENDPOINT: &str = "/security";
+
+/// The path of the WebSocket stream pushing the updates as they are polled.
+const STREAM_ENDPOINT: &str = "/ws";
+
+/// The longest wait for a client to send its request or to receive the answer,
+/// so a client which stays idle does not hold its connection open forever.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The most bytes read of a request, the endpoints only need its head.
+const MAX_REQUEST_SIZE: u64 = 8 * 1024;
+
+/// The most headers read of a request.
+const MAX_HEADERS: usize = 64;
+
+/// The answers of the endpoints by their path.
+type Endpoints = HashMap<&'static str, Payload>;
+
+/// The last polled system information, serialized for the endpoints and the stream.
+#[derive(Default)]
+struct Snapshot {
+    /// The answers of the endpoints, shared with the request handlers
+    endpoints: Arc<Endpoints>,
+    /// The `SystemInfoUpdate` messages sent to the clients of the stream
+    updates: Arc<Vec<Payload>>,
+}
+
+/// A part of the system information serialized in every format the clients can ask for.
+struct Payload {
+    json: String,
+    message_pack: Vec<u8>,
+}
+
+impl Payload {
+    fn new<T: Serialize>(value: &T) -> io::Result<Self> {
+        Ok(Payload {
+            json: serde_json::to_string(value)?,
+            message_pack: Encoding::MessagePack
+                .encode(value)
+                .map_err(io::Error::other)?,
+        })
+    }
+
+    /// Returns the serialized value in the `encoding` format.
+    fn get(&self, encoding: Encoding) -> &[u8] {
+        match encoding {
+            Encoding::Json => self.json.as_bytes(),
+            Encoding::MessagePack => &self.message_pack,
+        }
+    }
+}
+
+/// The senders of the connected stream clients, the disconnected ones are removed at the next poll.
+type Subscribers = Arc<Mutex<Vec<Sender<Arc<Vec<Payload>>>>>>;
+
+/// Serves the system information as JSON on `listen` address,
+/// or as MessagePack to the clients asking for `application/msgpack` in their `Accept` header, like the `RemoteSource`.
+/// The snapshots are collected in the background at every refresh interval of the `config`,
+/// so requests are answered immediately with the last snapshot.
+/// Every request is answered on its own thread, the clients which stay idle are dropped after `CLIENT_TIMEOUT`.
+/// Every snapshot is also pushed to the clients connected to the `/ws` WebSocket stream.
+/// If `token` is given, the requests must carry it in an `Authorization: Bearer <token>` header.
+/// Browsers can not set the headers of WebSocket requests, so the stream accepts a `?token=` query too,
+/// and a `?format=msgpack` query to receive binary MessagePack messages instead of JSON text.
+/// The network throughput of the processes is only estimated if the network accounting is enabled,
+/// the latency is only probed if there are ping targets, the public address is only looked up if it is enabled
+/// and the metadata of the cloud instance is only fetched if it is enabled by the `config`.
+/// Only the mounts selected by its disk filter are listed in the disk information.
+pub fn serve_api(listen: &str, token: Option<String>, config: &Config) -> io::Result<()> {
+    let listener = TcpListener::bind(listen)?;
+    let snapshot = Arc::new(RwLock::new(Snapshot::default()));
+    let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+
+    let mut poller = SystemInfoPoller::default()
+        .with_interval(config.refresh_interval())
+        .with_network_accounting(config.network_accounting)
+        .with_ping_targets(config.ping_targets.clone())
+        .with_public_ip(config.public_ip.clone())
+        .with_cloud_metadata(config.cloud_metadata)
+        .with_clock_offset(config.ntp_server.clone())
+        .with_packages()
+        .with_disk_filter(config.disks.clone());
+    poller.init();
+    let interval = poller.interval();
+    // the CPU usage is calculated from the difference of two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    *snapshot.write().unwrap() = collect(&mut poller)?;
+    // the details of the processes are fetched by the request handlers
+    let poller = Arc::new(Mutex::new(poller));
+
+    let snapshot_clone = snapshot.clone();
+    let subscribers_clone = subscribers.clone();
+    let poller_clone = poller.clone();
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        let result = match poller_clone.lock() {
+            Ok(mut poller) => collect(&mut poller),
+            Err(error) => Err(io::Error::other(error.to_string())),
+        };
+        let new_snapshot = match result {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                eprintln!("Failed to collect the system information: {}", error);
+                continue;
+            }
+        };
+        match subscribers_clone.lock() {
+            Ok(mut subscribers) => subscribers
+                .retain(|subscriber| subscriber.send(new_snapshot.updates.clone()).is_ok()),
+            Err(error) => eprintln!("Error acquiring subscribers lock: {}", error),
+        }
+        match snapshot_clone.write() {
+            Ok(mut snapshot) => *snapshot = new_snapshot,
+            Err(error) => eprintln!("Error acquiring snapshots lock: {}", error),
+        }
+    });
+
+    eprintln!(
+        "Serving the API on http://{} at {}, {}<pid>, {}, {}, {} and streaming the updates at {}",
+        listen,
+        ENDPOINTS.join(", "),
+        PROCESS_DETAILS_ENDPOINT,
+        CONNECTIONS_ENDPOINT,
+        LIMITS_ENDPOINT,
+        SECURITY_ENDPOINT,
+        STREAM_ENDPOINT
+    );
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("Failed to accept connection: {}", error);
+                continue;
+            }
+        };
+        let snapshot = snapshot.clone();
+        let subscribers = subscribers.clone();
+        let poller = poller.clone();
+        let token = token.clone();
+        thread::spawn(move || handle_connection(stream, &snapshot, &subscribers, &poller, token));
+    }
+
+    Ok(())
+}
+
+/// Answers the request on `stream`, or subscribes the client to the stream of the updates if it asks for it.
+/// Every connection is handled on its own thread, so a client which does not send its request
+/// holds up neither the other clients nor the polls.
+fn handle_connection(
+    stream: TcpStream,
+    snapshot: &RwLock<Snapshot>,
+    subscribers: &Subscribers,
+    poller: &Mutex<SystemInfoPoller>,
+    token: Option<String>,
+) {
+    if let Err(error) = stream
+        .set_read_timeout(Some(CLIENT_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(CLIENT_TIMEOUT)))
+    {
+        eprintln!("Failed to set the timeouts of the connection: {}", error);
+        return;
+    }
+
+    match is_stream_request(&stream) {
+        Ok(true) => {
+            let (sender, receiver) = mpsc::channel();
+            // the new client starts with the last snapshot instead of waiting for the next poll
+            if let Ok(snapshot) = snapshot.read() {
+                let _ = sender.send(snapshot.updates.clone());
+            }
+            if let Ok(mut subscribers) = subscribers.lock() {
+                subscribers.push(sender);
+            }
+            stream_updates(stream, receiver, token);
+        }
+        Ok(false) => {
+            // the lock is released before the request is read, so a slow client does not hold up the polls
+            let endpoints = match snapshot.read() {
+                Ok(snapshot) => snapshot.endpoints.clone(),
+                Err(error) => {
+                    eprintln!("Error acquiring snapshots lock: {}", error);
+                    return;
+                }
+            };
+            if let Err(error) = respond(stream, &endpoints, poller, token.as_deref()) {
+                eprintln!("Failed to answer request: {}", error);
+            }
+        }
+        Err(error) => eprintln!("Failed to read request: {}", error),
+    }
+}
+
+/// Returns true if the request on `stream` asks for the WebSocket stream.
+/// The request is only peeked, so it can still be read by the handler.
+fn is_stream_request(stream: &TcpStream) -> io::Result<bool> {
+    let mut buffer = [0; 64];
+    let length = stream.peek(&mut buffer)?;
+    let request_line = String::from_utf8_lossy(&buffer[..length]);
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    Ok(path.split('?').next() == Some(STREAM_ENDPOINT))
+}
+
+/// Upgrades the connection to a WebSocket and sends every received batch of updates
+/// as text messages, or binary ones in the requested format, until the client disconnects.
+fn stream_updates(stream: TcpStream, updates: Receiver<Arc<Vec<Payload>>>, token: Option<String>) {
+    let mut encoding = Encoding::Json;
+    // the type of the error is dictated by tungstenite
+    #[allow(clippy::result_large_err)]
+    let check_token = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        let header = request
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+        let query = |name: &str| {
+            request.uri().query().and_then(|query| {
+                query
+                    .split('&')
+                    .find_map(|parameter| parameter.strip_prefix(name)?.strip_prefix('='))
+            })
+        };
+        let query_token = query("token");
+        let accept = request
+            .headers()
+            .get("accept")
+            .and_then(|value| value.to_str().ok());
+        encoding = match query("format") {
+            Some("msgpack") => Encoding::MessagePack,
+            _ => Encoding::negotiate(accept),
+        };
+
+        if is_authorized(header, token.as_deref())
+            || (token.is_some() && query_token == token.as_deref())
+        {
+            Ok(response)
+        } else {
+            let mut error = ErrorResponse::new(Some(error_body("unauthorized")));
+            *error.status_mut() = StatusCode::UNAUTHORIZED;
+            Err(error)
+        }
+    };
+
+    let mut websocket = match tungstenite::accept_hdr(stream, check_token) {
+        Ok(websocket) => websocket,
+        Err(error) => {
+            eprintln!("Failed to open the stream: {}", error);
+            return;
+        }
+    };
+
+    for batch in updates {
+        for update in batch.iter() {
+            let message = match encoding {
+                Encoding::Json => update.json.as_str().into(),
+                Encoding::MessagePack => update.message_pack.clone().into(),
+            };
+            if websocket.send(message).is_err() {
+                // the client has disconnected, its sender is removed at the next poll
+                return;
+            }
+        }
+    }
+}
+
+/// Answers a single HTTP request. Only `GET` requests of the endpoints are served,
+/// everything else is not found.
+fn respond(
+    mut stream: TcpStream,
+    snapshots: &Endpoints,
+    poller: &Mutex<SystemInfoPoller>,
+    token: Option<&str>,
+) -> io::Result<()> {
+    let request = DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + CLIENT_TIMEOUT,
+    };
+    let mut reader = BufReader::new(request.take(MAX_REQUEST_SIZE));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut authorization = None;
+    let mut accept = None;
+    let mut header_count = 0;
+    let too_large = loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            // the head is cut short when the limit is reached before its end
+            break reader.get_ref().limit() == 0;
+        }
+        if header.trim().is_empty() {
+            break false;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADERS {
+            break true;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_string());
+            } else if name.trim().eq_ignore_ascii_case("accept") {
+                accept = Some(value.trim().to_string());
+            }
+        }
+    };
+
+    let authorized = is_authorized(authorization.as_deref(), token);
+    let encoding = Encoding::negotiate(accept.as_deref());
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        _ if too_large => (
+            "431 Request Header Fields Too Large",
+            error_body("request too large").into(),
+        ),
+        _ if !authorized => ("401 Unauthorized", error_body("unauthorized").into()),
+        (Some("GET"), Some(CONNECTIONS_ENDPOINT)) => {
+            read_on_request(poller, encoding, SystemInfoPoller::get_connections)
+        }
+        (Some("GET"), Some(LIMITS_ENDPOINT)) => {
+            read_on_request(poller, encoding, SystemInfoPoller::get_limits)
+        }
+        (Some("GET"), Some(SECURITY_ENDPOINT)) => {
+            read_on_request(poller, encoding, SystemInfoPoller::get_security)
+        }
+        (Some("GET"), Some(path)) => match path.strip_prefix(PROCESS_DETAILS_ENDPOINT) {
+            Some(pid) => process_details(pid, poller, encoding, token.is_some()),
+            None => match snapshots.get(path) {
+                Some(snapshot) => ("200 OK", snapshot.get(encoding).to_vec()),
+                None => ("404 Not Found", error_body("not found").into()),
+            },
+        },
+        _ => ("404 Not Found", error_body("not found").into()),
+    };
+    // the errors are always explained in JSON
+    let content_type = match status {
+        "200 OK" => encoding.media_type(),
+        _ => Encoding::Json.media_type(),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    if !authorized {
+        write!(stream, "WWW-Authenticate: Bearer\r\n")?;
+    }
+    write!(stream, "Connection: close\r\n\r\n")?;
+    stream.write_all(&body)
+}
+
+/// Reads the request from the client until `deadline`, so a client which sends it byte after byte
+/// can not hold its connection open for longer than `CLIENT_TIMEOUT` either.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the request was not received in time",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buffer)
+    }
+}
+
+/// Fetches the details of the process `pid` and returns the status and the body of the answer in the `encoding` format.
+/// The environment often holds secrets, like passwords and keys, so it is only served to the clients of an
+/// `authenticated` server.
+fn process_details(
+    pid: &str,
+    poller: &Mutex<SystemInfoPoller>,
+    encoding: Encoding,
+    authenticated: bool,
+) -> (&'static str, Vec<u8>) {
+    let Ok(pid) = pid.parse::<u32>() else {
+        return ("404 Not Found", error_body("not found").into());
+    };
+    let details = match poller.lock() {
+        Ok(mut poller) => poller.get_process_details(pid).map(|mut details| {
+            if !authenticated {
+                details.environment = None;
+            }
+            details
+        }),
+        Err(error) => {
+            return (
+                "500 Internal Server Error",
+                error_body(&error.to_string()).into(),
+            )
+        }
+    };
+
+    match details.map(|details| encoding.encode(&details)) {
+        Ok(Ok(body)) => ("200 OK", body),
+        Ok(Err(error)) => (
+            "500 Internal Server Error",
+            error_body(&error.to_string()).into(),
+        ),
+        Err(error) => ("404 Not Found", error_body(&error.to_string()).into()),
+    }
+}
+
+/// Reads the system information with `read`, like the sockets of the system,
+/// and returns the status and the body of the answer in the `encoding` format.
+fn read_on_request<T: Serialize>(
+    poller: &Mutex<SystemInfoPoller>,
+    encoding: Encoding,
+    read: fn(&mut SystemInfoPoller) -> Result<T, OceloError>,
+) -> (&'static str, Vec<u8>) {
+    let value = match poller.lock() {
+        Ok(mut poller) => read(&mut poller),
+        Err(error) => {
+            return (
+                "500 Internal Server Error",
+                error_body(&error.to_string()).into(),
+            )
+        }
+    };
+
+    match value.map(|value| encoding.encode(&value)) {
+        Ok(Ok(body)) => ("200 OK", body),
+        Ok(Err(error)) => (
+            "500 Internal Server Error",
+            error_body(&error.to_string()).into(),
+        ),
+        Err(error) => (
+            "500 Internal Server Error",
+            error_body(&error.to_string()).into(),
+        ),
+    }
+}
+
+/// Returns true if no `token` is required, or the `authorization` header carries it.
+fn is_authorized(authorization: Option<&str>, token: Option<&str>) -> bool {
+    match token {
+        Some(token) => authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|value| value.trim() == token),
+        None => true,
+    }
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// Polls every part of the system information and serializes them by their endpoint,
+/// and as the updates of the stream.
+fn collect(poller: &mut SystemInfoPoller) -> io::Result<Snapshot> {
+    let mut overview = poller.get_system_overview().map_err(io::Error::other)?;
+    // the clients displaying the network details read them from the snapshot
+    poller.add_network_details(&mut overview.network);
+    let cpu_memory = poller.get_cpu_amd_memory_info().map_err(io::Error::other)?;
+    let processes = poller.get_process_list().map_err(io::Error::other)?;
+    let sensors = poller.get_sensors_info().map_err(io::Error::other)?;
+    let topology = poller.get_cpu_topology().map_err(io::Error::other)?;
+    let services = poller.get_services().map_err(io::Error::other)?;
+    let containers = poller.get_containers().map_err(io::Error::other)?;
+    let logs = poller.get_logs().map_err(io::Error::other)?;
+    let sessions = SessionsInfo {
+        sessions: overview.sessions.clone(),
+    };
+
+    let mut endpoints = HashMap::new();
+    endpoints.insert("/cpu", Payload::new(&overview.cpu)?);
+    endpoints.insert("/cpu-topology", Payload::new(&topology)?);
+    endpoints.insert("/cpu-memory", Payload::new(&cpu_memory)?);
+    endpoints.insert("/memory", Payload::new(&overview.memory)?);
+    endpoints.insert("/disks", Payload::new(&overview.disks)?);
+    endpoints.insert("/network", Payload::new(&overview.network)?);
+    endpoints.insert("/processes", Payload::new(&processes)?);
+    endpoints.insert("/sensors", Payload::new(&sensors)?);
+    endpoints.insert("/users", Payload::new(&sessions)?);
+    endpoints.insert("/services", Payload::new(&services)?);
+    endpoints.insert("/containers", Payload::new(&containers)?);
+    endpoints.insert("/logs", Payload::new(&logs)?);
+    endpoints.insert("/overview", Payload::new(&overview)?);
+
+    let updates = [
+        SystemInfoUpdate::Disk(overview.disks.clone()),
+        SystemInfoUpdate::Network(Box::new(overview.network.clone())),
+        SystemInfoUpdate::CpuAndMemory(cpu_memory),
+        SystemInfoUpdate::Process(processes),
+        SystemInfoUpdate::Sensors(sensors),
+        SystemInfoUpdate::Users(sessions),
+        SystemInfoUpdate::Services(services),
+        SystemInfoUpdate::Containers(containers),
+        SystemInfoUpdate::Logs(logs),
+        SystemInfoUpdate::OverView(Box::new(overview)),
+    ]
+    .iter()
+    .map(Payload::new)
+    .collect::<io::Result<Vec<Payload>>>()?;
+
+    Ok(Snapshot {
+        endpoints: Arc::new(endpoints),
+        updates: Arc::new(updates),
+    })
+}
@@ -0,0 +1,64 @@
+//! Benchmarks of the poller, run with `cargo bench -p core`.
+//!
+//! The performance budget of a poll on a desktop with a few hundred processes:
+//! * `get_system_overview` under 10 ms, it is polled at every refresh
+//! * `get_process_list` under 50 ms, it is polled every two seconds
+//! * serializing and deserializing either of them under 5 ms, as the agents and the recorder do at every poll
+//!
+//! A change exceeding the budget makes ocelo one of the heaviest processes on the small boxes it monitors,
+//! which the status bar shows with `show_self_usage`.
+
+use core::encoding::{self, Encoding};
+use core::model::{ProcessList, SystemOverviewInfo};
+use core::{SystemInfoPoller, MINIMUM_CPU_UPDATE_INTERVAL};
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::hint::black_box;
+use std::thread;
+
+fn poller() -> SystemInfoPoller {
+    let mut poller = SystemInfoPoller::default();
+    poller.init();
+    // the CPU usage is calculated from the difference of two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    poller
+}
+
+fn polling(c: &mut Criterion) {
+    let mut poller = poller();
+    c.bench_function("get_system_overview", |b| {
+        b.iter(|| poller.get_system_overview().unwrap())
+    });
+    c.bench_function("get_process_list", |b| {
+        b.iter(|| poller.get_process_list().unwrap())
+    });
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(
+    c: &mut Criterion,
+    name: &str,
+    value: &T,
+    encoding: Encoding,
+) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            let bytes = encoding.encode(black_box(value)).unwrap();
+            encoding::decode::<T>(&bytes).unwrap()
+        })
+    });
+}
+
+fn serialization(c: &mut Criterion) {
+    let mut poller = poller();
+    let overview = poller.get_system_overview().unwrap();
+    let processes = poller.get_process_list().unwrap();
+
+    round_trip::<SystemOverviewInfo>(c, "overview_json", &overview, Encoding::Json);
+    round_trip::<SystemOverviewInfo>(c, "overview_msgpack", &overview, Encoding::MessagePack);
+    round_trip::<ProcessList>(c, "process_list_json", &processes, Encoding::Json);
+    round_trip::<ProcessList>(c, "process_list_msgpack", &processes, Encoding::MessagePack);
+}
+
+criterion_group!(benches, polling, serialization);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Measures the time of taking a snapshot of the processes.
+//!
+//! Run it with `cargo run --release -p core --example process_snapshot [iterations]`.
+
+use std::time::{Duration, Instant};
+
+use core::SystemInfoPoller;
+
+const DEFAULT_ITERATIONS: u32 = 20;
+
+fn main() {
+    let iterations = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS)
+        .max(1);
+
+    let mut poller = SystemInfoPoller::default();
+    poller.init();
+
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    let mut processes = 0;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let list = poller
+            .get_process_list()
+            .expect("failed to list the processes");
+        let elapsed = start.elapsed();
+        processes = list.len();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+
+    println!(
+        "{} snapshots of {} processes: {:.2?} on average, {:.2?} at best",
+        iterations,
+        processes,
+        total / iterations,
+        fastest
+    );
+}
@@ -0,0 +1,445 @@
+use crate::history::unix_time;
+use crate::model::{ProcessList, SnapshotDiff, SystemOverviewInfo, SystemSnapshot};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The values which alert rules can watch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlertMetric {
+    /// Global CPU usage in percent, written as `cpu.usage`
+    CpuUsage,
+    /// CPU package temperature in Celsius, written as `cpu.temperature`
+    CpuTemperature,
+    /// Physical memory usage in percent, written as `memory.usage`
+    MemoryUsage,
+    /// Available physical memory in percent of the total, written as `memory.available`,
+    /// which warns before the OOM killer steps in with a rule like `memory.available < 5`
+    MemoryAvailable,
+    /// Swap usage in percent, written as `swap.usage`
+    SwapUsage,
+    /// One minute load average, written as `load`
+    LoadAverage,
+    /// Used space in percent of the storage device mounted at the path, written as `disk "/"`
+    DiskUsage(String),
+    /// Growth of the physical memory usage in percentage points per minute, written as `memory.growth`
+    MemoryGrowth,
+    /// Growth of the used space in percentage points per minute of the storage device mounted at the path,
+    /// written as `disk.growth "/"`
+    DiskGrowth(String),
+    /// Allocated file handles of the system in percent of `fs.file-max`, written as `fd.usage`
+    DescriptorUsage,
+    /// Open file descriptors in percent of its limit of the process closest to its limit, written as `fd.process`,
+    /// which warns before a process fails to open files with a rule like `fd.process > 90`
+    ProcessDescriptorUsage,
+    /// Available entropy of the random number generator of the kernel in bits, written as `entropy`,
+    /// which warns before the reads of `/dev/random` block with a rule like `entropy < 200`
+    Entropy,
+    /// How far the clock is from its reference in milliseconds, ahead or behind, written as `clock.offset`,
+    /// which warns about a drifting clock with a rule like `clock.offset > 500`
+    ClockOffset,
+}
+
+impl AlertMetric {
+    /// Returns true if the metric is the rate of a change, computed from two snapshots by `self.change()`.
+    pub fn is_rate(&self) -> bool {
+        matches!(self, Self::MemoryGrowth | Self::DiskGrowth(_))
+    }
+
+    /// Returns the rate of the metric from the changes between two snapshots,
+    /// or `None` if it is not a rate or it is not available, like the growth of a disk mounted in only one of them.
+    pub fn change(&self, diff: &SnapshotDiff) -> Option<f64> {
+        match self {
+            Self::MemoryGrowth => diff.per_minute(diff.memory_usage),
+            Self::DiskGrowth(mount) => diff
+                .disks
+                .iter()
+                .find(|disk| &disk.mount == mount)
+                .and_then(|disk| diff.per_minute(disk.usage)),
+            _ => None,
+        }
+    }
+
+    /// Returns the current value of the metric from the `snapshot`,
+    /// or `None` if it is not available, like the temperature on some platforms, or it is a rate.
+    pub fn value(&self, snapshot: &SystemOverviewInfo) -> Option<f64> {
+        match self {
+            Self::CpuUsage => Some(snapshot.cpu.usage as f64),
+            Self::CpuTemperature => snapshot.cpu.temperature.map(|t| t as f64),
+            Self::MemoryUsage => percent(snapshot.memory.used, snapshot.memory.total),
+            Self::MemoryAvailable => percent(snapshot.memory.available, snapshot.memory.total),
+            Self::SwapUsage => percent(snapshot.memory.swap_used, snapshot.memory.swap_total),
+            Self::LoadAverage => Some(snapshot.overview.load_one_minute),
+            Self::DiskUsage(mount) => snapshot
+                .disks
+                .disks
+                .iter()
+                .find(|disk| &disk.mount == mount)
+                .and_then(|disk| percent(disk.used_space, disk.total_space)),
+            Self::DescriptorUsage => snapshot
+                .overview
+                .file_descriptors
+                .as_ref()
+                .and_then(|usage| usage.usage()),
+            Self::ProcessDescriptorUsage => snapshot
+                .overview
+                .file_descriptors
+                .as_ref()
+                .and_then(|usage| usage.busiest_process.as_ref())
+                .map(|process| process.usage()),
+            Self::Entropy => snapshot
+                .overview
+                .entropy
+                .as_ref()
+                .map(|entropy| entropy.available as f64),
+            Self::ClockOffset => snapshot
+                .overview
+                .clock
+                .as_ref()
+                .and_then(|clock| clock.offset.as_ref())
+                .map(|offset| offset.offset.abs()),
+            Self::MemoryGrowth | Self::DiskGrowth(_) => None,
+        }
+    }
+}
+
+impl Display for AlertMetric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CpuUsage => write!(f, "cpu.usage"),
+            Self::CpuTemperature => write!(f, "cpu.temperature"),
+            Self::MemoryUsage => write!(f, "memory.usage"),
+            Self::MemoryAvailable => write!(f, "memory.available"),
+            Self::SwapUsage => write!(f, "swap.usage"),
+            Self::LoadAverage => write!(f, "load"),
+            Self::DiskUsage(mount) => write!(f, "disk \"{}\"", mount),
+            Self::MemoryGrowth => write!(f, "memory.growth"),
+            Self::DiskGrowth(mount) => write!(f, "disk.growth \"{}\"", mount),
+            Self::DescriptorUsage => write!(f, "fd.usage"),
+            Self::ProcessDescriptorUsage => write!(f, "fd.process"),
+            Self::Entropy => write!(f, "entropy"),
+            Self::ClockOffset => write!(f, "clock.offset"),
+        }
+    }
+}
+
+impl FromStr for AlertMetric {
+    type Err = AlertRuleError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "cpu.usage" => Ok(Self::CpuUsage),
+            "cpu.temperature" => Ok(Self::CpuTemperature),
+            "memory.usage" => Ok(Self::MemoryUsage),
+            "memory.available" => Ok(Self::MemoryAvailable),
+            "swap.usage" => Ok(Self::SwapUsage),
+            "load" => Ok(Self::LoadAverage),
+            "memory.growth" => Ok(Self::MemoryGrowth),
+            "fd.usage" => Ok(Self::DescriptorUsage),
+            "fd.process" => Ok(Self::ProcessDescriptorUsage),
+            "entropy" => Ok(Self::Entropy),
+            "clock.offset" => Ok(Self::ClockOffset),
+            other => match (
+                mount_after(other, "disk.growth"),
+                mount_after(other, "disk"),
+            ) {
+                (Some(mount), _) => Ok(Self::DiskGrowth(mount)),
+                (None, Some(mount)) => Ok(Self::DiskUsage(mount)),
+                _ => Err(AlertRuleError::UnknownMetric(other.to_string())),
+            },
+        }
+    }
+}
+
+/// Returns the mount point written after the `keyword` of `metric`, like `/` in `disk "/"`,
+/// or `None` if `metric` is another one, like `disks "/"`, or the mount point is missing.
+fn mount_after(metric: &str, keyword: &str) -> Option<String> {
+    let mount = metric.strip_prefix(keyword)?;
+    if !mount.starts_with(|character: char| character.is_whitespace() || character == '"') {
+        return None;
+    }
+    let mount = mount.trim().trim_matches('"');
+    (!mount.is_empty()).then(|| mount.to_string())
+}
+
+/// How the value of the metric is compared to the threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+/// How the user is notified when a rule raises an alert, beside listing it in the alert log.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertNotification {
+    /// The alert is only listed
+    #[default]
+    None,
+    /// Rings the bell of the terminal, written as `bell`.
+    /// Terminal multiplexers like tmux flag the window of a pane in the background
+    Bell,
+    /// Sends a desktop notification, written as `notify`.
+    /// The bell is rung instead if the notification could not be sent
+    Desktop,
+}
+
+/// A condition which raises an alert when it holds for the given duration,
+/// like `cpu.usage > 90 for 60s` or `disk "/" > 95% notify`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct AlertRule {
+    pub metric: AlertMetric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// How long the condition must hold continuously before the alert is raised
+    pub duration: Duration,
+    /// How the user is notified of the raised alerts
+    pub notification: AlertNotification,
+}
+
+impl AlertRule {
+    fn is_breached(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+}
+
+/// Displays the condition of the rule, without the way of the notification.
+impl Display for AlertRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operator = match self.comparison {
+            Comparison::Above => ">",
+            Comparison::Below => "<",
+        };
+        write!(f, "{} {} {}", self.metric, operator, self.threshold)?;
+        if !self.duration.is_zero() {
+            write!(f, " for {}s", self.duration.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AlertRule {
+    type Err = AlertRuleError;
+
+    /// Parses a rule in the form of `<metric> <'>' or '<'> <threshold>[%] [for <duration>] [notify or bell]`,
+    /// where the duration is a number of seconds with an optional `s`, `m` or `h` unit.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (operator_index, comparison) = value
+            .char_indices()
+            .find_map(|(i, c)| match c {
+                '>' => Some((i, Comparison::Above)),
+                '<' => Some((i, Comparison::Below)),
+                _ => None,
+            })
+            .ok_or_else(|| AlertRuleError::MissingComparison(value.to_string()))?;
+
+        let metric = value[..operator_index].parse()?;
+        let condition = value[operator_index + 1..].trim();
+        let (condition, notification) = match condition.rsplit_once(' ') {
+            Some((condition, "notify")) => (condition.trim(), AlertNotification::Desktop),
+            Some((condition, "bell")) => (condition.trim(), AlertNotification::Bell),
+            _ => (condition, AlertNotification::None),
+        };
+        let (threshold, duration) = match condition.split_once(" for ") {
+            Some((threshold, duration)) => (threshold.trim(), Some(duration.trim())),
+            None => (condition, None),
+        };
+
+        let threshold = threshold
+            .trim_end_matches('%')
+            .trim()
+            .parse()
+            .map_err(|_| AlertRuleError::InvalidThreshold(threshold.to_string()))?;
+        let duration = match duration {
+            Some(duration) => parse_duration(duration)
+                .ok_or_else(|| AlertRuleError::InvalidDuration(duration.to_string()))?,
+            None => Duration::ZERO,
+        };
+
+        Ok(AlertRule {
+            metric,
+            comparison,
+            threshold,
+            duration,
+            notification,
+        })
+    }
+}
+
+impl TryFrom<String> for AlertRule {
+    type Error = AlertRuleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Reasons why an alert rule could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlertRuleError {
+    /// The rule has no `>` or `<` operator
+    MissingComparison(String),
+    UnknownMetric(String),
+    InvalidThreshold(String),
+    InvalidDuration(String),
+}
+
+impl Display for AlertRuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingComparison(rule) => write!(f, "missing '>' or '<' in rule '{}'", rule),
+            Self::UnknownMetric(metric) => write!(f, "unknown metric '{}'", metric),
+            Self::InvalidThreshold(threshold) => write!(f, "invalid threshold '{}'", threshold),
+            Self::InvalidDuration(duration) => write!(f, "invalid duration '{}'", duration),
+        }
+    }
+}
+
+impl std::error::Error for AlertRuleError {}
+
+/// Raised when the condition of a rule has held for its duration.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Alert {
+    /// The textual form of the rule which raised the alert
+    pub rule: String,
+    /// The value of the metric when the alert was raised
+    pub value: f64,
+    /// Time of raising the alert in seconds since the Unix epoch
+    pub timestamp: f64,
+    /// How the user is notified of the alert
+    #[serde(default)]
+    pub notification: AlertNotification,
+}
+
+impl Display for Alert {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (value: {:.1})", self.rule, self.value)
+    }
+}
+
+/// Evaluation state of a single rule.
+struct RuleState {
+    rule: AlertRule,
+    /// Since when the condition holds continuously
+    breached_since: Option<Instant>,
+    /// The alert was already raised for the current breach
+    raised: bool,
+}
+
+/// Evaluates the alert rules against the polled system information.
+/// An alert is raised once per breach: the condition must clear before the same rule raises again.
+/// The rates, like `memory.growth`, are computed from the changes since the previous evaluation.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::{AlertEngine, SystemInfoPoller};
+///
+/// let mut poller = SystemInfoPoller::default();
+/// poller.init();
+/// let mut engine = AlertEngine::new(vec!["cpu.usage > 90 for 60s".parse()?]);
+/// let alerts = engine.evaluate(&poller.get_system_overview()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct AlertEngine {
+    rules: Vec<RuleState>,
+    /// The snapshot of the previous evaluation, only kept for the rates
+    previous: Option<SystemSnapshot>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        AlertEngine {
+            rules: rules
+                .into_iter()
+                .map(|rule| RuleState {
+                    rule,
+                    breached_since: None,
+                    raised: false,
+                })
+                .collect(),
+            previous: None,
+        }
+    }
+
+    /// Returns true if there are no rules to evaluate.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Evaluates every rule against the `snapshot` and returns the newly raised alerts.
+    pub fn evaluate(&mut self, snapshot: &SystemOverviewInfo) -> Vec<Alert> {
+        let now = Instant::now();
+        let mut alerts = Vec::new();
+
+        let diff = if self.rules.iter().any(|state| state.rule.metric.is_rate()) {
+            let current = SystemSnapshot::new(snapshot.clone(), ProcessList::default());
+            let diff = self
+                .previous
+                .as_ref()
+                .map(|previous| SystemSnapshot::diff(previous, &current));
+            self.previous = Some(current);
+            diff
+        } else {
+            None
+        };
+
+        for state in self.rules.iter_mut() {
+            let metric = &state.rule.metric;
+            let value = if metric.is_rate() {
+                diff.as_ref().and_then(|diff| metric.change(diff))
+            } else {
+                metric.value(snapshot)
+            };
+            let breach = value.filter(|value| state.rule.is_breached(*value));
+
+            match breach {
+                Some(value) => {
+                    let since = *state.breached_since.get_or_insert(now);
+                    if !state.raised && now.duration_since(since) >= state.rule.duration {
+                        state.raised = true;
+                        alerts.push(Alert {
+                            rule: state.rule.to_string(),
+                            value,
+                            timestamp: unix_time(),
+                            notification: state.rule.notification,
+                        });
+                    }
+                }
+                None => {
+                    state.breached_since = None;
+                    state.raised = false;
+                }
+            }
+        }
+
+        alerts
+    }
+}
+
+fn percent(used: u64, total: u64) -> Option<f64> {
+    if total == 0 {
+        None
+    } else {
+        Some(used as f64 / total as f64 * 100.0)
+    }
+}
+
+/// Parses durations like `60`, `60s`, `5m` or `1h`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (number, multiplier) = match value.chars().last()? {
+        's' => (&value[..value.len() - 1], 1),
+        'm' => (&value[..value.len() - 1], 60),
+        'h' => (&value[..value.len() - 1], 60 * 60),
+        _ => (value, 1),
+    };
+    let seconds = number.trim().parse::<u64>().ok()?;
+    seconds.checked_mul(multiplier).map(Duration::from_secs)
+}
@@ -0,0 +1,61 @@
+/// The suffixes of the systemd units which hold processes, a service started by systemd
+/// or a scope of processes started elsewhere, like a login session or a container.
+const UNIT_SUFFIXES: [&str; 2] = [".service", ".scope"];
+
+/// The suffix of the systemd units which group other units.
+const SLICE_SUFFIX: &str = ".slice";
+
+/// Returns the cgroup v2 path of the process `pid`, like `/system.slice/sshd.service`.
+/// On the hybrid hierarchies the path of the systemd controller is returned, which is named the same way.
+/// The cgroups are only read on Linux, elsewhere `None` is returned.
+#[cfg(target_os = "linux")]
+pub fn cgroup(pid: u32) -> Option<String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    // every line is `hierarchy:controllers:path`, the unified hierarchy has no controllers listed
+    let paths = content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ':');
+            let hierarchy = fields.next()?;
+            let controllers = fields.next()?;
+            Some((hierarchy, controllers, fields.next()?))
+        })
+        .collect::<Vec<(&str, &str, &str)>>();
+
+    paths
+        .iter()
+        .find(|(hierarchy, controllers, _)| *hierarchy == "0" && controllers.is_empty())
+        .or_else(|| {
+            paths
+                .iter()
+                .find(|(_, controllers, _)| *controllers == "name=systemd")
+        })
+        .map(|(_, _, path)| path.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cgroup(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Returns the unit which the processes of the cgroup `path` are accounted to:
+/// the innermost service or scope, like `sshd.service` or `docker-4f2a.scope`,
+/// or the innermost slice if the path has neither, like `user.slice`.
+/// The paths outside of the systemd units are returned as they are.
+pub fn unit(path: &str) -> &str {
+    let components = path.split('/').filter(|component| !component.is_empty());
+    components
+        .clone()
+        .rev()
+        .find(|component| {
+            UNIT_SUFFIXES
+                .iter()
+                .any(|suffix| component.ends_with(suffix))
+        })
+        .or_else(|| {
+            components
+                .rev()
+                .find(|component| component.ends_with(SLICE_SUFFIX))
+        })
+        .unwrap_or(path)
+}
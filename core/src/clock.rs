@@ -0,0 +1,363 @@
+use crate::history::unix_time;
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The offset of the clock in milliseconds above which it is highlighted.
+/// The synchronized clocks stay within a few milliseconds of the time servers.
+pub const CLOCK_OFFSET_WARNING: f64 = 100.0;
+
+/// The time between two estimates of the offset of the clock.
+const CLOCK_CHECK_INTERVAL: Duration = Duration::from_secs(64);
+
+/// How long the SNTP server is waited for.
+const SNTP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Seconds from the start of the NTP era, 1900, to the Unix epoch.
+const NTP_UNIX_OFFSET: f64 = 2_208_988_800.0;
+
+/// The time settings of the system and how well its clock keeps the time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClockStatus {
+    /// The local time zone, like `Europe/Berlin`, `None` if it is not set
+    pub timezone: Option<String>,
+    /// The offset of the local time from UTC in seconds, like `3600` for UTC+01:00
+    #[serde(default)]
+    pub utc_offset: i64,
+    /// The locale of the system, like `en_US.UTF-8`, `None` if it is not set
+    pub locale: Option<String>,
+    /// Whether the kernel considers the clock synchronized by a time daemon, `None` if it is not known
+    pub synchronized: Option<bool>,
+    /// The estimated offset of the clock, `None` until it is estimated or if it can not be
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<ClockOffset>,
+}
+
+impl ClockStatus {
+    /// Formats the offset from UTC like `+01:00`.
+    pub fn format_utc_offset(&self) -> String {
+        let sign = match self.utc_offset < 0 {
+            true => '-',
+            false => '+',
+        };
+        let minutes = self.utc_offset.abs() / 60;
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+
+    /// Returns true if the clock is not synchronized, or it is farther from the reference than `CLOCK_OFFSET_WARNING`.
+    pub fn is_drifting(&self) -> bool {
+        self.synchronized == Some(false)
+            || self
+                .offset
+                .as_ref()
+                .is_some_and(|offset| offset.offset.abs() > CLOCK_OFFSET_WARNING)
+    }
+}
+
+/// The difference between the clock of the system and a reference clock.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClockOffset {
+    /// How far the clock is ahead of the reference in milliseconds, negative if it is behind
+    pub offset: f64,
+    /// Where the offset was estimated from: `chrony`, `timesyncd` or the address of the SNTP server
+    pub source: String,
+    /// Time of the estimate in seconds since the Unix epoch
+    pub checked_at: f64,
+}
+
+/// Reads the time zone, the offset from UTC, the locale and the synchronization status of the clock.
+/// The offset of the clock is left to `ClockMonitor`. The status is only read on Linux.
+#[cfg(target_os = "linux")]
+pub fn clock_status() -> Result<ClockStatus, OceloError> {
+    Ok(linux::clock_status())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn clock_status() -> Result<ClockStatus, OceloError> {
+    Err(OceloError::Unsupported(
+        "reading the status of the clock is only supported on Linux".to_string(),
+    ))
+}
+
+/// Estimates the offset of the clock at every `CLOCK_CHECK_INTERVAL` on a background thread,
+/// which stops when the monitor is dropped.
+///
+/// The offset is asked from the time daemon of the system, chrony or systemd-timesyncd, which measures it
+/// against its time servers anyway. Without either of them, the clock is compared to the `ntp_server` if it is given,
+/// with a single SNTP request, whose accuracy is bounded by the asymmetry of the network path.
+///
+/// # Example:
+/// ```no_run
+/// use core::ClockMonitor;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let monitor = ClockMonitor::start(Some("pool.ntp.org".to_string()));
+/// thread::sleep(Duration::from_secs(1));
+/// println!("{:?}", monitor.offset());
+/// ```
+pub struct ClockMonitor {
+    offset: Arc<Mutex<Option<ClockOffset>>>,
+    /// Stops the worker thread when dropped
+    _stop: Sender<()>,
+}
+
+impl ClockMonitor {
+    pub fn start(ntp_server: Option<String>) -> Self {
+        let offset = Arc::new(Mutex::new(None));
+        let (stop, stopped) = mpsc::channel();
+        let result = Arc::clone(&offset);
+        thread::spawn(move || loop {
+            let estimate = estimate_offset(ntp_server.as_deref());
+            if let Ok(mut offset) = result.lock() {
+                *offset = estimate;
+            }
+            // sleeps until the next estimate, or stops when the monitor is dropped
+            if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(CLOCK_CHECK_INTERVAL)
+            {
+                break;
+            }
+        });
+        ClockMonitor {
+            offset,
+            _stop: stop,
+        }
+    }
+
+    /// Returns the last estimate of the offset, `None` if it could not be estimated.
+    pub fn offset(&self) -> Option<ClockOffset> {
+        self.offset
+            .lock()
+            .map(|offset| offset.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Estimates the offset of the clock with the first source which knows it.
+fn estimate_offset(ntp_server: Option<&str>) -> Option<ClockOffset> {
+    let (offset, source) = match (chrony_offset(), timesyncd_offset()) {
+        (Some(offset), _) => (offset, "chrony".to_string()),
+        (None, Some(offset)) => (offset, "timesyncd".to_string()),
+        (None, None) => (sntp_offset(ntp_server?).ok()?, ntp_server?.to_string()),
+    };
+    Some(ClockOffset {
+        offset,
+        source,
+        checked_at: unix_time(),
+    })
+}
+
+/// Reads the offset measured by chrony from the CSV output of `chronyc tracking`, like
+/// ```text
+/// A29FC87B,ntp.example.com,3,1718000000.123456789,0.000012345,...,Normal
+/// ```
+/// The fifth field is the correction being applied, positive while the clock is behind.
+fn chrony_offset() -> Option<f64> {
+    let output = Command::new("chronyc")
+        .args(["-c", "tracking"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let tracking = String::from_utf8_lossy(&output.stdout);
+    let fields = tracking.trim().split(',').collect::<Vec<&str>>();
+    // the last field is the leap status, which tells whether chrony is synchronized at all
+    if fields.last() == Some(&"Not synchronised") {
+        return None;
+    }
+    let correction = fields.get(4)?.parse::<f64>().ok()?;
+    Some(-correction * 1000.0)
+}
+
+/// Reads the offset measured by systemd-timesyncd from `timedatectl timesync-status`, like `Offset: -225us`,
+/// positive while the clock is behind.
+fn timesyncd_offset() -> Option<f64> {
+    let output = Command::new("timedatectl")
+        .arg("timesync-status")
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let status = String::from_utf8_lossy(&output.stdout);
+    let offset = status
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Offset:"))?
+        .trim();
+    let split = offset.find(|c: char| c.is_ascii_alphabetic())?;
+    let (value, unit) = offset.split_at(split);
+    let milliseconds = match unit {
+        "ns" => 0.000_001,
+        "us" => 0.001,
+        "ms" => 1.0,
+        "s" => 1000.0,
+        "min" => 60_000.0,
+        _ => return None,
+    };
+    Some(-value.parse::<f64>().ok()? * milliseconds)
+}
+
+/// Compares the clock to the SNTP `server`, like `pool.ntp.org` or `192.168.1.1:123`, with a single request.
+/// Returns how far the clock is ahead of the server in milliseconds.
+fn sntp_offset(server: &str) -> io::Result<f64> {
+    let invalid = |reason: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", server, reason),
+        )
+    };
+    // the port is optional
+    let address = server
+        .to_socket_addrs()
+        .or_else(|_| (server, 123).to_socket_addrs())?
+        .next()
+        .ok_or_else(|| invalid("the server has no address"))?;
+    let socket = match address.is_ipv4() {
+        true => UdpSocket::bind("0.0.0.0:0")?,
+        false => UdpSocket::bind("[::]:0")?,
+    };
+    socket.set_read_timeout(Some(SNTP_TIMEOUT))?;
+
+    let mut request = [0u8; 48];
+    // no leap warning, version 4, client mode
+    request[0] = 0b00_100_011;
+    let sent = now();
+    // the server answers the transmit timestamp of the request as its originate timestamp
+    request[40..48].copy_from_slice(&to_ntp(sent).to_be_bytes());
+    socket.send_to(&request, address)?;
+
+    let mut response = [0u8; 48];
+    let (length, _) = socket.recv_from(&mut response)?;
+    let received = now();
+    // a stratum of 0 is a kiss-o'-death packet, telling the client to go away
+    if length < 48 || response[0] & 0b111 != 4 || response[1] == 0 {
+        return Err(invalid("invalid response"));
+    }
+    if response[24..32] != request[40..48] {
+        return Err(invalid("the response does not answer the request"));
+    }
+    let timestamp = |range: std::ops::Range<usize>| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&response[range]);
+        from_ntp(u64::from_be_bytes(bytes))
+    };
+    let server_received = timestamp(32..40);
+    let server_sent = timestamp(40..48);
+    // the offset of the server from the clock, with the network delay cancelled out on a symmetric path
+    let server_offset = ((server_received - sent) + (server_sent - received)) / 2.0;
+    Ok(-server_offset * 1000.0)
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs_f64())
+        .unwrap_or_default()
+}
+
+/// Converts the seconds since the Unix epoch to an NTP timestamp,
+/// the seconds since 1900 in the upper 32 bits and their fraction in the lower ones.
+fn to_ntp(time: f64) -> u64 {
+    let time = time + NTP_UNIX_OFFSET;
+    let seconds = time.trunc() as u64;
+    let fraction = (time.fract() * 4_294_967_296.0) as u64;
+    (seconds << 32) | fraction
+}
+
+/// Converts an NTP timestamp to the seconds since the Unix epoch.
+fn from_ntp(timestamp: u64) -> f64 {
+    let seconds = (timestamp >> 32) as f64;
+    let fraction = (timestamp & 0xffff_ffff) as f64 / 4_294_967_296.0;
+    seconds + fraction - NTP_UNIX_OFFSET
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::ClockStatus;
+    use std::env;
+    use std::fs;
+    use std::mem;
+
+    pub fn clock_status() -> ClockStatus {
+        ClockStatus {
+            timezone: timezone(),
+            utc_offset: utc_offset(),
+            locale: locale(),
+            synchronized: synchronized(),
+            offset: None,
+        }
+    }
+
+    /// Returns the time zone from the `TZ` variable, the zone file linked as `/etc/localtime`
+    /// or the name in `/etc/timezone`.
+    fn timezone() -> Option<String> {
+        if let Ok(timezone) = env::var("TZ") {
+            let timezone = timezone.trim_start_matches(':');
+            if !timezone.is_empty() {
+                return Some(timezone.to_string());
+            }
+        }
+        // the link points into the zone files, like `/usr/share/zoneinfo/Europe/Berlin`
+        if let Ok(target) = fs::read_link("/etc/localtime") {
+            let target = target.to_string_lossy();
+            if let Some((_, zone)) = target.split_once("zoneinfo/") {
+                return Some(zone.to_string());
+            }
+        }
+        fs::read_to_string("/etc/timezone")
+            .ok()
+            .map(|timezone| timezone.trim().to_string())
+            .filter(|timezone| !timezone.is_empty())
+    }
+
+    /// Returns the offset of the local time from UTC in seconds.
+    fn utc_offset() -> i64 {
+        let now = unsafe { libc::time(std::ptr::null_mut()) };
+        let mut local = unsafe { mem::zeroed::<libc::tm>() };
+        match unsafe { libc::localtime_r(&now, &mut local) }.is_null() {
+            true => 0,
+            false => local.tm_gmtoff,
+        }
+    }
+
+    /// Returns the locale of the system from `/etc/locale.conf` or `/etc/default/locale`,
+    /// or the locale of ocelo from its environment.
+    fn locale() -> Option<String> {
+        ["/etc/locale.conf", "/etc/default/locale"]
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .find_map(|content| {
+                content.lines().find_map(|line| {
+                    let locale = line.trim().strip_prefix("LANG=")?.trim_matches('"');
+                    (!locale.is_empty()).then(|| locale.to_string())
+                })
+            })
+            .or_else(|| {
+                ["LC_ALL", "LC_TIME", "LANG"]
+                    .iter()
+                    .filter_map(|name| env::var(name).ok())
+                    .find(|locale| !locale.is_empty())
+            })
+    }
+
+    /// Asks the kernel whether a time daemon keeps the clock synchronized, like `timedatectl` does.
+    /// Reading the state of the clock does not require privileges.
+    fn synchronized() -> Option<bool> {
+        let mut timex = unsafe { mem::zeroed::<libc::timex>() };
+        let state = unsafe { libc::adjtimex(&mut timex) };
+        match state {
+            -1 => None,
+            libc::TIME_ERROR => Some(false),
+            _ => Some(timex.status & libc::STA_UNSYNC == 0),
+        }
+    }
+}
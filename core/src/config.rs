@@ -0,0 +1,335 @@
+use crate::alerts::AlertRule;
+use crate::encoding::Encoding;
+use crate::hooks::AlertHook;
+use crate::http;
+use crate::model::{DiskFilter, ProcessColumn};
+use crate::public_ip::DEFAULT_PUBLIC_IP_PROVIDER;
+use crate::recorder::DEFAULT_RECORD_RETENTION;
+use crate::DEFAULT_POLLING_INTERVAL;
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs, io};
+
+/// User settings loaded from `~/.config/ocelo/config.toml`.
+/// Every setting is optional, the missing ones keep their default value.
+///
+/// # Example:
+/// ```toml
+/// refresh_interval = 2
+/// default_tab = "processes"
+/// units = "decimal"
+/// chart_style = "ascii"
+/// process_columns = ["pid", "name", "cpu_usage", "memory", "threads", "command"]
+/// alerts = ["cpu.usage > 90 for 60s", "disk \"/\" > 95% notify"]
+/// record = "/home/user/.local/share/ocelo/history.db"
+/// record_retention = 86400
+/// record_encoding = "msgpack"
+/// network_accounting = true
+/// network_bits = true
+/// ping_targets = ["gateway", "1.1.1.1", "vpn.example.com"]
+/// exact_durations = true
+/// incremental_processes = true
+/// show_self_usage = true
+/// cloud_metadata = true
+/// ntp_server = "pool.ntp.org"
+///
+/// [theme]
+/// highlight = "cyan"
+/// text = "gray"
+///
+/// [disks]
+/// exclude_file_systems = ["tmpfs", "squashfs"]
+/// exclude_mounts = ["/boot"]
+///
+/// [public_ip]
+/// enabled = true
+/// provider = "http://ip-api.com/json?fields=query,country,regionName,city"
+///
+/// [[alert_hooks]]
+/// webhook = "http://alerts.example.com:9000/ocelo"
+///
+/// [[hosts]]
+/// name = "web"
+/// address = "192.168.1.10:8080"
+/// token = "secret"
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Time between two polls of the system information in seconds.
+    /// The processes, the sensors and the disks are polled at their own intervals instead, see `PollingScheduler`
+    pub refresh_interval: u64,
+    /// The tab displayed after starting the user interface
+    pub default_tab: Tab,
+    pub theme: Theme,
+    /// The columns of the process table in the order of their appearance.
+    /// Empty displays the default columns,
+    /// which leave out `threads`, `nice`, `start_time`, `average_cpu_usage`, `swap` and `oom_score`.
+    pub process_columns: Vec<ProcessColumn>,
+    /// Prefix of the displayed data sizes
+    pub units: Units,
+    /// How the series of the charts are drawn
+    pub chart_style: ChartStyle,
+    /// Rules raising alerts, like `cpu.usage > 90 for 60s`.
+    /// A rule ending with `notify` also sends a desktop notification, one ending with `bell` rings the bell of the terminal
+    pub alerts: Vec<AlertRule>,
+    /// Actions taken when an alert is raised, like calling a webhook or running a script
+    pub alert_hooks: Vec<AlertHook>,
+    /// Path of the SQLite database which the polled system information is recorded into
+    pub record: Option<PathBuf>,
+    /// How long the recorded system information is kept for in seconds
+    pub record_retention: u64,
+    /// The format of the recorded snapshots, `json` or the more compact `msgpack`
+    pub record_encoding: Encoding,
+    /// Remote agents which can be switched to in the user interface beside the local system
+    pub hosts: Vec<Host>,
+    /// Estimates the network throughput of every process from their TCP sockets, only on Linux
+    pub network_accounting: bool,
+    /// Displays the network throughput in bits per second instead of bytes, toggled by the `b` key of the network tab
+    pub network_bits: bool,
+    /// Hosts whose latency and packet loss are probed and displayed on the network tab,
+    /// `gateway` probes the default gateway
+    pub ping_targets: Vec<String>,
+    /// Displays the running time and the CPU time of the processes in seconds, instead of like `2d 3h` or `14:32`
+    pub exact_durations: bool,
+    /// Polls only the changed processes instead of the whole list, which is lighter on the systems with many processes.
+    /// It is ignored while recording, as a replay can not start from the changes
+    pub incremental_processes: bool,
+    /// Displays the CPU and memory usage of ocelo itself in the status bar, highlighted when it is over its budget
+    pub show_self_usage: bool,
+    /// The mounts listed in the disk information, the pseudo-filesystems are excluded by default
+    pub disks: DiskFilter,
+    /// Looking up the public address of the host, disabled by default
+    pub public_ip: PublicIpSettings,
+    /// Fetches the type and the region of the cloud instance running the host from the metadata service
+    /// of its provider, disabled by default
+    pub cloud_metadata: bool,
+    /// The SNTP server which the clock is compared to when no time daemon reports its offset,
+    /// like `pool.ntp.org` or `192.168.1.1:123`, not compared by default
+    pub ntp_server: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            refresh_interval: DEFAULT_POLLING_INTERVAL.as_secs(),
+            default_tab: Tab::default(),
+            theme: Theme::default(),
+            process_columns: Vec::new(),
+            units: Units::default(),
+            chart_style: ChartStyle::default(),
+            alerts: Vec::new(),
+            alert_hooks: Vec::new(),
+            record: None,
+            record_retention: DEFAULT_RECORD_RETENTION.as_secs(),
+            record_encoding: Encoding::default(),
+            hosts: Vec::new(),
+            network_accounting: false,
+            network_bits: false,
+            ping_targets: Vec::new(),
+            exact_durations: false,
+            incremental_processes: false,
+            show_self_usage: false,
+            disks: DiskFilter::default(),
+            public_ip: PublicIpSettings::default(),
+            cloud_metadata: false,
+            ntp_server: None,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the location of the configuration file: `$XDG_CONFIG_HOME/ocelo/config.toml`,
+    /// or `$HOME/.config/ocelo/config.toml` if `XDG_CONFIG_HOME` is not set.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|config_dir| config_dir.join("ocelo").join("config.toml"))
+    }
+
+    /// Loads the configuration from `path`. A missing file results in the default configuration.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_toml(&content),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(ConfigError::Io(error)),
+        }
+    }
+
+    /// Creates `self` from a TOML representation.
+    pub fn from_toml(value: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(value).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Returns why the configuration can not be used, like a webhook URL which can not be requested.
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.public_ip.enabled {
+            http::supported_url(&self.public_ip.provider).map_err(|reason| {
+                ConfigError::Invalid(format!(
+                    "public IP provider {}: {}",
+                    self.public_ip.provider, reason
+                ))
+            })?;
+        }
+        for hook in &self.alert_hooks {
+            hook.validate()
+                .map_err(|error| ConfigError::Invalid(error.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the time between two polls of the system information.
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_interval)
+    }
+
+    /// Returns how long the recorded system information is kept for.
+    pub fn record_retention(&self) -> Duration {
+        Duration::from_secs(self.record_retention)
+    }
+}
+
+/// Reasons why the configuration could not be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file exists but could not be read
+    Io(io::Error),
+    /// The file is not a valid configuration
+    Parse(toml::de::Error),
+    /// A value of the configuration can not be used, for the given reason
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read the configuration: {}", error),
+            Self::Parse(error) => write!(f, "invalid configuration: {}", error),
+            Self::Invalid(reason) => write!(f, "invalid configuration: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The tabs of the user interface.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tab {
+    #[default]
+    Overview,
+    Cpu,
+    Processes,
+    Disk,
+    Network,
+    Sensors,
+    Users,
+    Services,
+    Containers,
+    Logs,
+}
+
+impl FromStr for Tab {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "overview" => Ok(Self::Overview),
+            "cpu" => Ok(Self::Cpu),
+            "processes" => Ok(Self::Processes),
+            "disk" => Ok(Self::Disk),
+            "network" => Ok(Self::Network),
+            "sensors" => Ok(Self::Sensors),
+            "users" => Ok(Self::Users),
+            "services" => Ok(Self::Services),
+            "containers" => Ok(Self::Containers),
+            "logs" => Ok(Self::Logs),
+            other => Err(format!(
+                "unknown tab '{}', expected overview, cpu, processes, disk, network, sensors, users, services, containers or logs",
+                other
+            )),
+        }
+    }
+}
+
+/// Colors of the user interface, given by name like `yellow` or in hexadecimal like `#ffaa00`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Color of the selected items, like the current tab
+    pub highlight: String,
+    /// Color of the regular text
+    pub text: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            highlight: "yellow".to_string(),
+            text: "white".to_string(),
+        }
+    }
+}
+
+/// A remote agent started with `ocelo serve`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Host {
+    /// The name displayed in the host list of the user interface
+    pub name: String,
+    /// Host and port of the agent, like `192.168.1.10:8080`
+    pub address: String,
+    /// Bearer token required by the agent
+    pub token: Option<String>,
+}
+
+/// Settings of looking up the public address of the host, see `PublicIpLookup`.
+/// The lookup is opt-in, as it tells the provider about the host.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PublicIpSettings {
+    pub enabled: bool,
+    /// The HTTP address answering the public address, `DEFAULT_PUBLIC_IP_PROVIDER` by default.
+    /// A provider geolocating the address, like `http://ip-api.com/json`, also tells the location of the host.
+    /// The https:// providers need ocelo to be built with the `https` feature
+    pub provider: String,
+}
+
+impl Default for PublicIpSettings {
+    fn default() -> Self {
+        PublicIpSettings {
+            enabled: false,
+            provider: DEFAULT_PUBLIC_IP_PROVIDER.to_string(),
+        }
+    }
+}
+
+/// How the series of the charts are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartStyle {
+    /// Lines of braille dots, in the highest resolution
+    #[default]
+    Braille,
+    /// A dot at every sample
+    Dots,
+    /// Lines of ASCII characters, for the terminals and the fonts without Unicode support
+    Ascii,
+}
+
+/// Prefixes of the displayed data sizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    /// Powers of 1024, like KiB and MiB
+    #[default]
+    Binary,
+    /// Powers of 1000, like kB and MB
+    Decimal,
+}
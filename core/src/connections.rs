@@ -0,0 +1,419 @@
+use crate::model::SortOrder;
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::net::SocketAddr;
+
+/// An internet socket of the system, with the process owning it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Connection {
+    /// The protocol of the socket: `tcp`, `tcp6`, `udp` or `udp6`
+    pub protocol: String,
+
+    /// The local address and port
+    pub local: String,
+
+    /// The address and port of the peer, `0.0.0.0:0` while the socket is not connected
+    pub remote: String,
+
+    /// The state of a TCP connection, like `LISTEN` or `ESTABLISHED`, empty for UDP
+    pub state: String,
+
+    /// The ID of the process owning the socket, unknown without the permission to inspect the process
+    pub pid: Option<u32>,
+
+    /// Name of the process owning the socket
+    pub process: Option<String>,
+
+    /// Name of the user owning the socket, known even if the process can not be inspected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+impl Connection {
+    /// Returns true if any field of the connection contains `query`. The comparison is case insensitive.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let pid = self.pid.map(|pid| pid.to_string()).unwrap_or_default();
+        [
+            &self.protocol,
+            &self.local,
+            &self.remote,
+            &self.state,
+            &pid,
+            self.process.as_deref().unwrap_or_default(),
+            self.user.as_deref().unwrap_or_default(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// Returns true if the socket accepts connections or datagrams from any peer:
+    /// a listening TCP socket or an unconnected UDP socket.
+    pub fn is_listening(&self) -> bool {
+        match self.protocol.starts_with("tcp") {
+            true => self.state == "LISTEN",
+            false => self.remote.ends_with(":0"),
+        }
+    }
+
+    /// Returns true if the socket is bound to an address other than the loopback one,
+    /// like `0.0.0.0:22`, `[::]:22` or `192.168.1.10:22`,
+    /// so it is reachable from the other hosts of the network unless a firewall blocks it.
+    pub fn is_exposed(&self) -> bool {
+        self.local
+            .parse::<SocketAddr>()
+            .is_ok_and(|address| !address.ip().to_canonical().is_loopback())
+    }
+}
+
+/// Counts of the TCP sockets of the system by their state, like `ss -s`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionStates {
+    pub established: u32,
+    /// Connections closed by this host, kept for a while to catch the late segments of the peer.
+    /// Many of them hold the local ports of the short outgoing connections.
+    pub time_wait: u32,
+    /// Connections closed by the peer but not yet by the process, a steady growth is a sign of a leak
+    pub close_wait: u32,
+    pub listen: u32,
+    /// Connections being opened or closed, in the remaining states
+    pub other: u32,
+    /// Count of the local ports assigned to the outgoing connections, set by `net.ipv4.ip_local_port_range`
+    pub ephemeral_ports: Option<u32>,
+    /// Count of the local ports of the ephemeral range in use by a connection
+    pub ephemeral_in_use: u32,
+}
+
+impl ConnectionStates {
+    /// Returns the count of the TCP sockets in any state.
+    pub fn total(&self) -> u32 {
+        self.established + self.time_wait + self.close_wait + self.listen + self.other
+    }
+
+    /// Returns the ephemeral ports in use in percent of the range, `None` if the range is not known.
+    /// New outgoing connections fail with `EADDRNOTAVAIL` once the range is exhausted.
+    pub fn ephemeral_usage(&self) -> Option<f64> {
+        let ports = self.ephemeral_ports.filter(|ports| *ports > 0)?;
+        Some(self.ephemeral_in_use as f64 / ports as f64 * 100.0)
+    }
+}
+
+/// The columns of the connection table, by which the connections can be sorted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionColumn {
+    #[default]
+    Protocol,
+    Local,
+    Remote,
+    State,
+    Pid,
+    Process,
+}
+
+impl ConnectionColumn {
+    /// Compares the value of this column of two connections.
+    fn compare(&self, a: &Connection, b: &Connection) -> Ordering {
+        match self {
+            Self::Protocol => a.protocol.cmp(&b.protocol),
+            Self::Local => a.local.cmp(&b.local),
+            Self::Remote => a.remote.cmp(&b.remote),
+            Self::State => a.state.cmp(&b.state),
+            Self::Pid => a.pid.cmp(&b.pid),
+            Self::Process => a.process.cmp(&b.process),
+        }
+    }
+}
+
+/// Sorts the `connections` by the values of `column` in the given `order`.
+pub fn sort_connections(
+    connections: &mut [Connection],
+    column: ConnectionColumn,
+    order: SortOrder,
+) {
+    connections.sort_by(|a, b| match order {
+        SortOrder::Ascending => column.compare(a, b),
+        SortOrder::Descending => column.compare(b, a),
+    });
+}
+
+/// Lists the TCP and UDP sockets of the system, like `netstat -tunap`.
+/// The sockets are read from `/proc/net`, so they are only listed on Linux.
+/// The owners are found by looking for the sockets among the open files of every process,
+/// so the sockets of the processes which can not be inspected have no owner.
+#[cfg(target_os = "linux")]
+pub fn connections() -> Result<Vec<Connection>, OceloError> {
+    linux::connections()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn connections() -> Result<Vec<Connection>, OceloError> {
+    Err(OceloError::Unsupported(
+        "listing the connections is only supported on Linux".to_string(),
+    ))
+}
+
+/// Counts the TCP sockets of the system by their state and the ephemeral ports they use.
+/// The sockets are read from `/proc/net` without looking for their owners, so they are counted cheaply,
+/// but only on Linux.
+#[cfg(target_os = "linux")]
+pub fn connection_states() -> Result<ConnectionStates, OceloError> {
+    linux::connection_states()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn connection_states() -> Result<ConnectionStates, OceloError> {
+    Err(OceloError::Unsupported(
+        "counting the connections is only supported on Linux".to_string(),
+    ))
+}
+
+/// Lists the listening TCP ports and the unconnected UDP ports of the system, ordered by protocol and address.
+pub fn listening_ports() -> Result<Vec<Connection>, OceloError> {
+    Ok(connections()?
+        .into_iter()
+        .filter(Connection::is_listening)
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) mod linux {
+    use super::{Connection, ConnectionStates};
+    use crate::open_files::SocketInfo;
+    use crate::OceloError;
+    use std::collections::{HashMap, HashSet};
+    use std::fs;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::path::Path;
+    use std::str::FromStr;
+    use sysinfo::{Uid, Users};
+
+    /// The names of the TCP states by their number in `/proc/net/tcp`.
+    const TCP_STATES: [&str; 12] = [
+        "",
+        "ESTABLISHED",
+        "SYN_SENT",
+        "SYN_RECV",
+        "FIN_WAIT1",
+        "FIN_WAIT2",
+        "TIME_WAIT",
+        "CLOSE",
+        "CLOSE_WAIT",
+        "LAST_ACK",
+        "LISTEN",
+        "CLOSING",
+    ];
+
+    /// The tables of the internet sockets in `/proc/net`, with their protocol names.
+    const INET_TABLES: [&str; 4] = ["tcp", "tcp6", "udp", "udp6"];
+
+    pub fn connections() -> Result<Vec<Connection>, OceloError> {
+        let sockets = inet_sockets(Path::new("/proc/net"));
+        if sockets.is_empty() && !Path::new("/proc/net/tcp").exists() {
+            return Err(OceloError::Io(
+                "failed to read the socket tables of /proc/net".to_string(),
+            ));
+        }
+        let owners = socket_owners();
+        let users = Users::new_with_refreshed_list();
+
+        let mut connections: Vec<Connection> = sockets
+            .into_iter()
+            .map(|(inode, socket, uid)| {
+                let owner = owners.get(&inode);
+                Connection {
+                    protocol: socket.protocol,
+                    local: socket.local,
+                    remote: socket.remote,
+                    state: socket.state,
+                    pid: owner.map(|(pid, _)| *pid),
+                    process: owner.map(|(_, name)| name.clone()),
+                    user: uid
+                        .and_then(|uid| users.get_user_by_id(&uid))
+                        .map(|user| user.name().to_string()),
+                }
+            })
+            .collect();
+        connections.sort_by(|a, b| (&a.protocol, &a.local).cmp(&(&b.protocol, &b.local)));
+        Ok(connections)
+    }
+
+    pub fn connection_states() -> Result<ConnectionStates, OceloError> {
+        let range = ephemeral_range();
+        let mut states = ConnectionStates {
+            ephemeral_ports: range.map(|(first, last)| last - first + 1),
+            ..ConnectionStates::default()
+        };
+        let mut ephemeral = HashSet::new();
+        let mut read = false;
+
+        for protocol in ["tcp", "tcp6"] {
+            let Ok(table) = fs::read_to_string(Path::new("/proc/net").join(protocol)) else {
+                continue;
+            };
+            read = true;
+            // the sockets are not collected by their inode, as the sockets in TIME_WAIT share the inode 0
+            for line in table.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let (Some(local), Some(state)) = (fields.get(1), fields.get(3)) else {
+                    continue;
+                };
+                let state = u8::from_str_radix(state, 16)
+                    .ok()
+                    .and_then(|state| TCP_STATES.get(state as usize))
+                    .copied()
+                    .unwrap_or_default();
+                match state {
+                    "ESTABLISHED" => states.established += 1,
+                    "TIME_WAIT" => states.time_wait += 1,
+                    "CLOSE_WAIT" => states.close_wait += 1,
+                    "LISTEN" => states.listen += 1,
+                    _ => states.other += 1,
+                }
+                let port = local
+                    .split_once(':')
+                    .and_then(|(_, port)| u16::from_str_radix(port, 16).ok());
+                if let (Some(port), Some((first, last))) = (port, range) {
+                    if state != "LISTEN" && (first..=last).contains(&(port as u32)) {
+                        ephemeral.insert(port);
+                    }
+                }
+            }
+        }
+        if !read {
+            return Err(OceloError::Io(
+                "failed to read the socket tables of /proc/net".to_string(),
+            ));
+        }
+        states.ephemeral_in_use = ephemeral.len() as u32;
+        Ok(states)
+    }
+
+    /// Reads the first and the last port of the ephemeral range, which the IPv6 connections share.
+    fn ephemeral_range() -> Option<(u32, u32)> {
+        let range = fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range").ok()?;
+        let mut ports = range
+            .split_whitespace()
+            .map(|port| port.parse::<u32>().ok());
+        let (first, last) = (ports.next()??, ports.next()??);
+        (first <= last).then_some((first, last))
+    }
+
+    /// Finds the processes owning the sockets, returning their pid and name by the inode of the sockets.
+    pub(crate) fn socket_owners() -> HashMap<u64, (u32, String)> {
+        let mut owners = HashMap::new();
+        let Ok(processes) = fs::read_dir("/proc") else {
+            return owners;
+        };
+
+        for process in processes.filter_map(|entry| entry.ok()) {
+            let Some(pid) = process
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            // the descriptors of the processes of other users can not be listed without privileges
+            let Ok(descriptors) = fs::read_dir(process.path().join("fd")) else {
+                continue;
+            };
+            let name = fs::read_to_string(process.path().join("comm"))
+                .map(|name| name.trim_end().to_string())
+                .unwrap_or_default();
+
+            for descriptor in descriptors.filter_map(|entry| entry.ok()) {
+                let inode = fs::read_link(descriptor.path())
+                    .ok()
+                    .and_then(|target| socket_inode(&target.to_string_lossy()));
+                if let Some(inode) = inode {
+                    owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+                }
+            }
+        }
+        owners
+    }
+
+    /// Returns the inode from a link target like `socket:[1234]`.
+    pub(crate) fn socket_inode(target: &str) -> Option<u64> {
+        target
+            .strip_prefix("socket:[")?
+            .strip_suffix(']')?
+            .parse()
+            .ok()
+    }
+
+    /// Reads the TCP and UDP sockets of the socket tables in the `net` directory
+    /// with their inode and the user ID of their owner.
+    /// The sockets are not collected by their inode, as the sockets in TIME_WAIT and the other ones
+    /// without a process share the inode 0.
+    pub(crate) fn inet_sockets(net: &Path) -> Vec<(u64, SocketInfo, Option<Uid>)> {
+        let mut sockets = Vec::new();
+
+        for protocol in INET_TABLES {
+            let Ok(table) = fs::read_to_string(net.join(protocol)) else {
+                continue;
+            };
+            // the first line is the header of the table
+            for line in table.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let (Some(local), Some(remote), Some(state), Some(uid), Some(inode)) = (
+                    fields.get(1),
+                    fields.get(2),
+                    fields.get(3),
+                    fields.get(7),
+                    fields.get(9),
+                ) else {
+                    continue;
+                };
+                let Ok(inode) = inode.parse::<u64>() else {
+                    continue;
+                };
+                let state = match protocol.starts_with("tcp") {
+                    true => u8::from_str_radix(state, 16)
+                        .ok()
+                        .and_then(|state| TCP_STATES.get(state as usize))
+                        .unwrap_or(&"")
+                        .to_string(),
+                    false => String::new(),
+                };
+
+                sockets.push((
+                    inode,
+                    SocketInfo {
+                        protocol: protocol.to_string(),
+                        local: parse_address(local).unwrap_or_default(),
+                        remote: parse_address(remote).unwrap_or_default(),
+                        state,
+                    },
+                    Uid::from_str(uid).ok(),
+                ));
+            }
+        }
+        sockets
+    }
+
+    /// Parses an address of the socket tables like `0100007F:0050` into `127.0.0.1:80`.
+    /// The addresses are hexadecimal numbers in the byte order of the host, 4 bytes for IPv4 and 16 bytes for IPv6.
+    fn parse_address(address: &str) -> Option<String> {
+        let (ip, port) = address.split_once(':')?;
+        let port = u16::from_str_radix(port, 16).ok()?;
+
+        let words = (0..ip.len())
+            .step_by(8)
+            .map(|start| {
+                let word = u32::from_str_radix(ip.get(start..start + 8)?, 16).ok()?;
+                Some(word.to_ne_bytes())
+            })
+            .collect::<Option<Vec<[u8; 4]>>>()?;
+        match words.as_slice() {
+            [word] => Some(format!("{}:{}", Ipv4Addr::from(*word), port)),
+            [_, _, _, _] => {
+                let bytes: [u8; 16] = words.concat().try_into().ok()?;
+                Some(format!("[{}]:{}", Ipv6Addr::from(bytes), port))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::model::ContainersInfo;
+use crate::rate::RateTracker;
+use std::fmt::{self, Display, Formatter};
+
+/// The actions which can be taken on a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerAction {
+    /// Asks the main process of the container to exit with SIGTERM, without waiting for it
+    Stop,
+    /// Kills the main process of the container with SIGKILL
+    Kill,
+}
+
+impl Display for ContainerAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stop => write!(f, "stop"),
+            Self::Kill => write!(f, "kill"),
+        }
+    }
+}
+
+/// Reasons why a container could not be controlled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerControlError {
+    /// No socket of Docker or Podman was found, or it could not be connected to
+    NoRuntime,
+    /// There is no container with the given id
+    NotFound(String),
+    /// The runtime refused or failed to carry out the action, with its message
+    Failed(String, String),
+}
+
+impl Display for ContainerControlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoRuntime => write!(f, "no Docker or Podman socket was found"),
+            Self::NotFound(id) => write!(f, "container {} not found", id),
+            Self::Failed(id, message) => write!(f, "container {}: {}", id, message),
+        }
+    }
+}
+
+impl std::error::Error for ContainerControlError {}
+
+/// Lists the running containers of Docker or Podman with their resource usage, like `docker stats`.
+/// The runtime is queried over its API socket, `DOCKER_HOST` is used if it points to a unix socket,
+/// otherwise the default sockets of Docker, then the ones of rootless and rootful Podman are tried.
+/// The rates are calculated by `rates` since the previous call.
+/// The sockets are only queried on unix, elsewhere no runtime is found.
+#[cfg(unix)]
+pub fn containers(rates: &mut RateTracker) -> ContainersInfo {
+    unix::containers(rates)
+}
+
+#[cfg(not(unix))]
+pub fn containers(_rates: &mut RateTracker) -> ContainersInfo {
+    ContainersInfo::default()
+}
+
+/// Takes `action` on the container with the given `id`.
+#[cfg(unix)]
+pub fn control_container(id: &str, action: ContainerAction) -> Result<(), ContainerControlError> {
+    unix::control_container(id, action)
+}
+
+#[cfg(not(unix))]
+pub fn control_container(_id: &str, _action: ContainerAction) -> Result<(), ContainerControlError> {
+    Err(ContainerControlError::NoRuntime)
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::{ContainerAction, ContainerControlError};
+    use crate::http;
+    use crate::model::{Container, ContainersInfo};
+    use crate::rate::RateTracker;
+    use serde_json::Value;
+    use std::env;
+    use std::io;
+    use std::os::unix::net::UnixStream;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    /// The longest time to wait for connecting to the runtime and for its answer.
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Count of the characters of the short container ids, like displayed by `docker ps`.
+    const SHORT_ID_LENGTH: usize = 12;
+
+    const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+    const PODMAN_SOCKET: &str = "/run/podman/podman.sock";
+
+    pub fn containers(rates: &mut RateTracker) -> ContainersInfo {
+        let Some(socket) = find_socket() else {
+            return ContainersInfo::default();
+        };
+        let runtime = if socket.to_string_lossy().contains("podman") {
+            "podman"
+        } else {
+            "docker"
+        };
+        let list = match request(&socket, "GET", "/containers/json") {
+            Ok((200, body)) => serde_json::from_str::<Value>(&body).unwrap_or_default(),
+            _ => Value::Null,
+        };
+
+        let containers = list
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry["Id"].as_str()?;
+                // one-shot skips waiting for a second sample, the rates are calculated from the previous poll instead
+                let path = format!("/containers/{}/stats?stream=false&one-shot=true", id);
+                let stats = match request(&socket, "GET", &path) {
+                    Ok((200, body)) => serde_json::from_str::<Value>(&body).ok()?,
+                    _ => return None,
+                };
+                Some(read_container(entry, &stats, rates))
+            })
+            .collect();
+
+        ContainersInfo {
+            runtime: Some(runtime.to_string()),
+            containers,
+        }
+    }
+
+    pub fn control_container(
+        id: &str,
+        action: ContainerAction,
+    ) -> Result<(), ContainerControlError> {
+        let socket = find_socket().ok_or(ContainerControlError::NoRuntime)?;
+        let signal = match action {
+            ContainerAction::Stop => "SIGTERM",
+            ContainerAction::Kill => "SIGKILL",
+        };
+        let path = format!("/containers/{}/kill?signal={}", id, signal);
+        match request(&socket, "POST", &path) {
+            Ok((200..=299, _)) => Ok(()),
+            Ok((404, _)) => Err(ContainerControlError::NotFound(id.to_string())),
+            // the errors are described by a JSON object like `{"message": "..."}`
+            Ok((_, body)) => Err(ContainerControlError::Failed(
+                id.to_string(),
+                serde_json::from_str::<Value>(&body)
+                    .ok()
+                    .and_then(|error| error["message"].as_str().map(str::to_string))
+                    .unwrap_or(body),
+            )),
+            Err(_) => Err(ContainerControlError::NoRuntime),
+        }
+    }
+
+    /// Creates the container from its `entry` in the container list and its `stats`.
+    fn read_container(entry: &Value, stats: &Value, rates: &mut RateTracker) -> Container {
+        let id = entry["Id"].as_str().unwrap_or_default();
+        let short_id = id.chars().take(SHORT_ID_LENGTH).collect::<String>();
+        let mut rate = |counter: &str, total: u64| {
+            rates.rate(&format!("container/{}/{}", short_id, counter), total)
+        };
+
+        // the CPU time is reported in nanoseconds
+        let cpu_time = stats["cpu_stats"]["cpu_usage"]["total_usage"]
+            .as_u64()
+            .unwrap_or_default();
+        let cpu_usage = rate("cpu", cpu_time) / 1_000_000_000.0 * 100.0;
+
+        // the page cache is left out like `docker stats` does, which is named differently by the cgroup versions
+        let memory_stats = &stats["memory_stats"];
+        let cache = ["inactive_file", "total_inactive_file", "cache"]
+            .iter()
+            .find_map(|name| memory_stats["stats"][name].as_u64())
+            .unwrap_or_default();
+        let memory = memory_stats["usage"]
+            .as_u64()
+            .unwrap_or_default()
+            .saturating_sub(cache);
+
+        let (received, transmitted) = stats["networks"]
+            .as_object()
+            .into_iter()
+            .flat_map(|networks| networks.values())
+            .fold((0, 0), |(received, transmitted), network| {
+                (
+                    received + network["rx_bytes"].as_u64().unwrap_or_default(),
+                    transmitted + network["tx_bytes"].as_u64().unwrap_or_default(),
+                )
+            });
+
+        // the operations are capitalized by Docker with cgroup v1, but not with cgroup v2
+        let block_bytes = |operation: &str| {
+            stats["blkio_stats"]["io_service_bytes_recursive"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|entry| {
+                    entry["op"]
+                        .as_str()
+                        .is_some_and(|op| op.eq_ignore_ascii_case(operation))
+                })
+                .filter_map(|entry| entry["value"].as_u64())
+                .sum::<u64>()
+        };
+
+        Container {
+            name: entry["Names"][0]
+                .as_str()
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+            image: entry["Image"].as_str().unwrap_or_default().to_string(),
+            status: entry["Status"].as_str().unwrap_or_default().to_string(),
+            cpu_usage: cpu_usage as f32,
+            memory,
+            memory_limit: memory_stats["limit"].as_u64().unwrap_or_default(),
+            net_received_rate: rate("received", received),
+            net_transmitted_rate: rate("transmitted", transmitted),
+            read_rate: rate("read", block_bytes("read")),
+            write_rate: rate("write", block_bytes("write")),
+            id: short_id,
+        }
+    }
+
+    /// Returns the first API socket of Docker or Podman which exists.
+    fn find_socket() -> Option<PathBuf> {
+        if let Some(socket) = env::var("DOCKER_HOST")
+            .ok()
+            .and_then(|host| host.strip_prefix("unix://").map(PathBuf::from))
+        {
+            return Some(socket);
+        }
+
+        let mut candidates = vec![PathBuf::from(DOCKER_SOCKET)];
+        if let Ok(runtime_directory) = env::var("XDG_RUNTIME_DIR") {
+            candidates.push(Path::new(&runtime_directory).join("podman/podman.sock"));
+        }
+        candidates.push(PathBuf::from(PODMAN_SOCKET));
+        candidates.into_iter().find(|socket| socket.exists())
+    }
+
+    /// Sends a request without a body to the API at `socket` and returns the status code and the body of the answer.
+    /// HTTP/1.0 is used, so the answer is not chunked and the connection is closed after it.
+    fn request(socket: &Path, method: &str, path: &str) -> io::Result<(u16, String)> {
+        let stream = UnixStream::connect(socket)?;
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+        let response = http::exchange(stream, method, "localhost", path, &[], &[])?;
+        Ok((response.status, response.text()))
+    }
+}
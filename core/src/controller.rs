@@ -0,0 +1,310 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+#[cfg(target_os = "linux")]
+use std::mem;
+use std::ops::RangeInclusive;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+/// Reasons why a process could not be controlled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessControlError {
+    /// There is no process with the given pid
+    NotFound(u32),
+    /// The signal is not supported on this platform
+    UnsupportedSignal,
+    /// Changing the priority of processes is not supported on this platform
+    UnsupportedPriority,
+    /// Pinning processes to cores is not supported on this platform
+    UnsupportedAffinity,
+    /// The process belongs to another user, or its priority would be raised,
+    /// which is only allowed with elevated privileges
+    PermissionDenied(u32),
+    /// The signal could not be delivered for another reason
+    Failed(u32),
+}
+
+impl Display for ProcessControlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(pid) => write!(f, "process {} not found", pid),
+            Self::UnsupportedSignal => write!(f, "signal is not supported on this platform"),
+            Self::UnsupportedPriority => {
+                write!(f, "changing the priority is not supported on this platform")
+            }
+            Self::UnsupportedAffinity => {
+                write!(f, "pinning to cores is not supported on this platform")
+            }
+            Self::PermissionDenied(pid) => write!(
+                f,
+                "permission denied to control process {}, it may require elevated privileges",
+                pid
+            ),
+            Self::Failed(pid) => write!(f, "failed to send signal to process {}", pid),
+        }
+    }
+}
+
+impl std::error::Error for ProcessControlError {}
+
+/// The nice values from the highest priority to the lowest one.
+pub const NICE_RANGE: RangeInclusive<i32> = -20..=19;
+
+/// The signals which can be sent to a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessSignal {
+    /// Usually makes a daemon reload its configuration
+    Hangup,
+    Interrupt,
+    Quit,
+    Kill,
+    User1,
+    User2,
+    Term,
+    /// Resumes a stopped process
+    Continue,
+    /// Pauses the process until it is continued
+    Stop,
+}
+
+impl ProcessSignal {
+    /// Every signal, in the order of their numbers.
+    pub const ALL: [ProcessSignal; 9] = [
+        Self::Hangup,
+        Self::Interrupt,
+        Self::Quit,
+        Self::Kill,
+        Self::User1,
+        Self::User2,
+        Self::Term,
+        Self::Continue,
+        Self::Stop,
+    ];
+
+    /// Returns the conventional name of the signal, like `SIGTERM`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hangup => "SIGHUP",
+            Self::Interrupt => "SIGINT",
+            Self::Quit => "SIGQUIT",
+            Self::Kill => "SIGKILL",
+            Self::User1 => "SIGUSR1",
+            Self::User2 => "SIGUSR2",
+            Self::Term => "SIGTERM",
+            Self::Continue => "SIGCONT",
+            Self::Stop => "SIGSTOP",
+        }
+    }
+}
+
+impl Display for ProcessSignal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl From<ProcessSignal> for Signal {
+    fn from(signal: ProcessSignal) -> Self {
+        match signal {
+            ProcessSignal::Hangup => Signal::Hangup,
+            ProcessSignal::Interrupt => Signal::Interrupt,
+            ProcessSignal::Quit => Signal::Quit,
+            ProcessSignal::Kill => Signal::Kill,
+            ProcessSignal::User1 => Signal::User1,
+            ProcessSignal::User2 => Signal::User2,
+            ProcessSignal::Term => Signal::Term,
+            ProcessSignal::Continue => Signal::Continue,
+            ProcessSignal::Stop => Signal::Stop,
+        }
+    }
+}
+
+/// Sends signals to processes on the host machine.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::ProcessController;
+///
+/// let mut controller = ProcessController::default();
+/// controller.terminate(1337)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ProcessController {
+    system: System,
+}
+
+impl Default for ProcessController {
+    fn default() -> Self {
+        ProcessController {
+            system: System::new(),
+        }
+    }
+}
+
+impl ProcessController {
+    /// Asks the process to terminate by sending SIGTERM to it.
+    pub fn terminate(&mut self, pid: u32) -> Result<(), ProcessControlError> {
+        self.send_signal(pid, ProcessSignal::Term)
+    }
+
+    /// Kills the process immediately by sending SIGKILL to it.
+    pub fn kill(&mut self, pid: u32) -> Result<(), ProcessControlError> {
+        self.send_signal(pid, ProcessSignal::Kill)
+    }
+
+    /// Changes the nice value of the process, clamped into `NICE_RANGE`.
+    /// Raising the priority of a process, even an own one, usually requires elevated privileges.
+    pub fn set_priority(&mut self, pid: u32, nice: i32) -> Result<(), ProcessControlError> {
+        renice(pid, nice)
+    }
+
+    /// Restricts the process to run only on the given cores, identified by their indexes.
+    pub fn set_affinity(&mut self, pid: u32, cores: &[usize]) -> Result<(), ProcessControlError> {
+        pin(pid, cores)
+    }
+
+    /// Sends `signal` to the process.
+    pub fn send_signal(
+        &mut self,
+        pid: u32,
+        signal: ProcessSignal,
+    ) -> Result<(), ProcessControlError> {
+        let sysinfo_pid = Pid::from_u32(pid);
+        // only the target process is refreshed, there is no need to load the whole process table
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[sysinfo_pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        let process = self
+            .system
+            .process(sysinfo_pid)
+            .ok_or(ProcessControlError::NotFound(pid))?;
+
+        match process.kill_with(signal.into()) {
+            Some(true) => Ok(()),
+            // the reason of the failure is only reported by the operating system
+            Some(false) => match io::Error::last_os_error().kind() {
+                io::ErrorKind::PermissionDenied => Err(ProcessControlError::PermissionDenied(pid)),
+                _ => Err(ProcessControlError::Failed(pid)),
+            },
+            None => Err(ProcessControlError::UnsupportedSignal),
+        }
+    }
+}
+
+/// Returns the nice value of the process, a lower value means a higher priority.
+#[cfg(unix)]
+pub fn priority(pid: u32) -> Result<i32, ProcessControlError> {
+    // -1 is a valid nice value too, so the failures are only told apart by errno
+    clear_errno();
+    let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS as _, pid as libc::id_t) };
+    match io::Error::last_os_error().raw_os_error() {
+        Some(0) | None => Ok(nice),
+        Some(errno) => Err(os_error(pid, errno)),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn priority(_pid: u32) -> Result<i32, ProcessControlError> {
+    Err(ProcessControlError::UnsupportedPriority)
+}
+
+#[cfg(unix)]
+fn renice(pid: u32, nice: i32) -> Result<(), ProcessControlError> {
+    let nice = nice.clamp(*NICE_RANGE.start(), *NICE_RANGE.end());
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS as _, pid as libc::id_t, nice) };
+    match result {
+        0 => Ok(()),
+        _ => Err(os_error(
+            pid,
+            io::Error::last_os_error()
+                .raw_os_error()
+                .unwrap_or_default(),
+        )),
+    }
+}
+
+#[cfg(not(unix))]
+fn renice(_pid: u32, _nice: i32) -> Result<(), ProcessControlError> {
+    Err(ProcessControlError::UnsupportedPriority)
+}
+
+/// Returns the indexes of the cores which the process is allowed to run on.
+#[cfg(target_os = "linux")]
+pub fn affinity(pid: u32) -> Result<Vec<usize>, ProcessControlError> {
+    let mut set = unsafe { mem::zeroed::<libc::cpu_set_t>() };
+    let result = unsafe {
+        libc::sched_getaffinity(
+            pid as libc::pid_t,
+            mem::size_of::<libc::cpu_set_t>(),
+            &mut set,
+        )
+    };
+    if result != 0 {
+        return Err(os_error(
+            pid,
+            io::Error::last_os_error()
+                .raw_os_error()
+                .unwrap_or_default(),
+        ));
+    }
+
+    Ok((0..libc::CPU_SETSIZE as usize)
+        .filter(|core| unsafe { libc::CPU_ISSET(*core, &set) })
+        .collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn affinity(_pid: u32) -> Result<Vec<usize>, ProcessControlError> {
+    Err(ProcessControlError::UnsupportedAffinity)
+}
+
+#[cfg(target_os = "linux")]
+fn pin(pid: u32, cores: &[usize]) -> Result<(), ProcessControlError> {
+    let mut set = unsafe { mem::zeroed::<libc::cpu_set_t>() };
+    for core in cores {
+        unsafe { libc::CPU_SET(*core, &mut set) };
+    }
+    let result = unsafe {
+        libc::sched_setaffinity(pid as libc::pid_t, mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    match result {
+        0 => Ok(()),
+        _ => Err(os_error(
+            pid,
+            io::Error::last_os_error()
+                .raw_os_error()
+                .unwrap_or_default(),
+        )),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin(_pid: u32, _cores: &[usize]) -> Result<(), ProcessControlError> {
+    Err(ProcessControlError::UnsupportedAffinity)
+}
+
+/// Converts the errno of a failed system call controlling the process `pid` into an error.
+#[cfg(unix)]
+fn os_error(pid: u32, errno: i32) -> ProcessControlError {
+    match errno {
+        libc::ESRCH => ProcessControlError::NotFound(pid),
+        libc::EPERM | libc::EACCES => ProcessControlError::PermissionDenied(pid),
+        _ => ProcessControlError::Failed(pid),
+    }
+}
+
+#[cfg(unix)]
+fn clear_errno() {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    unsafe {
+        *libc::__errno_location() = 0;
+    }
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+    unsafe {
+        *libc::__error() = 0;
+    }
+}
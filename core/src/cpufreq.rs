@@ -0,0 +1,78 @@
+/// The frequency scaling policy of a logical processor, with the frequencies in MHz.
+#[derive(Clone, Debug, Default)]
+pub struct CoreScaling {
+    /// The governor choosing the frequency, like `powersave` or `performance`
+    pub governor: Option<String>,
+    /// The lowest frequency the governor may choose
+    pub min_frequency: Option<u64>,
+    /// The highest frequency the governor may choose
+    pub max_frequency: Option<u64>,
+}
+
+/// Reads the scaling policy of the logical processor at `index` from the cpufreq subsystem.
+/// Returns `None` where the frequency scaling is not available, like on virtual machines and outside of Linux.
+#[cfg(target_os = "linux")]
+pub fn core_scaling(index: usize) -> Option<CoreScaling> {
+    linux::core_scaling(index)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn core_scaling(_index: usize) -> Option<CoreScaling> {
+    None
+}
+
+/// Returns whether the processor may run above its base frequency, known as Turbo Boost or Core Performance Boost.
+/// Returns `None` where it is not known.
+#[cfg(target_os = "linux")]
+pub fn boost() -> Option<bool> {
+    linux::boost()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn boost() -> Option<bool> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::CoreScaling;
+    use std::fs;
+    use std::path::Path;
+
+    const CPU_DIRECTORY: &str = "/sys/devices/system/cpu";
+
+    pub fn core_scaling(index: usize) -> Option<CoreScaling> {
+        let path = Path::new(CPU_DIRECTORY).join(format!("cpu{}/cpufreq", index));
+        if !path.is_dir() {
+            return None;
+        }
+        // the frequencies are reported in kHz
+        let frequency = |file: &str| {
+            read_string(&path.join(file))?
+                .parse::<u64>()
+                .ok()
+                .map(|frequency| frequency / 1000)
+        };
+
+        Some(CoreScaling {
+            governor: read_string(&path.join("scaling_governor")),
+            min_frequency: frequency("scaling_min_freq").or_else(|| frequency("cpuinfo_min_freq")),
+            max_frequency: frequency("scaling_max_freq").or_else(|| frequency("cpuinfo_max_freq")),
+        })
+    }
+
+    pub fn boost() -> Option<bool> {
+        let cpu = Path::new(CPU_DIRECTORY);
+        // intel_pstate turns the turbo off instead of turning the boost on
+        if let Some(no_turbo) = read_string(&cpu.join("intel_pstate/no_turbo")) {
+            return Some(no_turbo == "0");
+        }
+        read_string(&cpu.join("cpufreq/boost")).map(|boost| boost == "1")
+    }
+
+    fn read_string(path: &Path) -> Option<String> {
+        fs::read_to_string(path)
+            .ok()
+            .map(|content| content.trim().to_string())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+/// A file or a directory found by a disk usage scan, with the space allocated for it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UsageEntry {
+    pub path: PathBuf,
+
+    /// Allocated space in bytes, including everything under a directory
+    pub size: u64,
+
+    pub is_directory: bool,
+
+    /// The files and directories in a directory, the largest first
+    pub children: Vec<UsageEntry>,
+
+    /// False for the directories deeper than the depth of the scan, whose children are not listed
+    pub listed: bool,
+}
+
+impl UsageEntry {
+    /// Returns the last component of the path, or the whole path for the root.
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.to_string_lossy().into_owned())
+    }
+}
+
+/// The result of a finished disk usage scan.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// The scanned directory
+    pub root: UsageEntry,
+
+    /// Count of the directories which could not be read, their content is missing from the sizes
+    pub unreadable: u64,
+}
+
+/// A disk usage scan running in the background, started by `disk_usage_scan()`.
+/// Dropping the scan cancels it.
+///
+/// # Example:
+/// ```no_run
+/// use core::disk_usage_scan;
+///
+/// let scan = disk_usage_scan("/home", 2);
+/// while scan.try_result().is_none() {
+///     println!("{} files scanned", scan.scanned());
+/// }
+/// ```
+pub struct DiskUsageScan {
+    path: PathBuf,
+    cancelled: Arc<AtomicBool>,
+    scanned: Arc<AtomicU64>,
+    result: Receiver<Result<DiskUsage, OceloError>>,
+}
+
+impl DiskUsageScan {
+    /// The directory being scanned.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stops the scan, it will not produce a result.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the count of the files and directories scanned so far.
+    pub fn scanned(&self) -> u64 {
+        self.scanned.load(Ordering::Relaxed)
+    }
+
+    /// Returns the result of the scan if it has finished, without waiting for it.
+    /// The result is only returned once.
+    pub fn try_result(&self) -> Option<Result<DiskUsage, OceloError>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            // the result has already been returned, or the scan has been cancelled
+            Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Waits for the result of the scan.
+    pub fn wait(self) -> Result<DiskUsage, OceloError> {
+        self.result.recv().unwrap_or(Err(OceloError::Cancelled))
+    }
+}
+
+impl Drop for DiskUsageScan {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Starts scanning the space used by the files and directories under `path` in a background thread, like `du`.
+/// The directories are listed down to `depth` levels under `path`, the deeper ones only have their total size.
+///
+/// The sizes are the space allocated on the disk, the hard linked files are only counted once,
+/// the symbolic links are not followed, and the scan stays on the file system of `path`.
+pub fn disk_usage_scan(path: impl Into<PathBuf>, depth: usize) -> DiskUsageScan {
+    let path = path.into();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let scanned = Arc::new(AtomicU64::new(0));
+    let (tx, rx) = mpsc::channel();
+
+    let mut scanner = Scanner {
+        cancelled: cancelled.clone(),
+        scanned: scanned.clone(),
+        seen: HashSet::new(),
+        unreadable: 0,
+    };
+    let root = path.clone();
+    thread::spawn(move || {
+        let result = scanner.scan_root(&root, depth);
+        // the scan may have been dropped in the meantime
+        let _ = tx.send(result);
+    });
+
+    DiskUsageScan {
+        path,
+        cancelled,
+        scanned,
+        result: rx,
+    }
+}
+
+struct Scanner {
+    cancelled: Arc<AtomicBool>,
+    scanned: Arc<AtomicU64>,
+    /// The device and inode of the hard linked files already counted
+    #[cfg_attr(not(unix), allow(dead_code))]
+    seen: HashSet<(u64, u64)>,
+    unreadable: u64,
+}
+
+impl Scanner {
+    fn scan_root(&mut self, path: &Path, depth: usize) -> Result<DiskUsage, OceloError> {
+        let metadata = fs::symlink_metadata(path).map_err(|error| {
+            OceloError::Io(format!("failed to scan {}: {}", path.display(), error))
+        })?;
+        if metadata.is_dir() {
+            fs::read_dir(path).map_err(|error| {
+                OceloError::Io(format!("failed to scan {}: {}", path.display(), error))
+            })?;
+        }
+
+        let root = self
+            .scan(path.to_path_buf(), &metadata, device(&metadata), depth)
+            .ok_or(OceloError::Cancelled)?;
+        Ok(DiskUsage {
+            root,
+            unreadable: self.unreadable,
+        })
+    }
+
+    /// Returns the usage of `path` with its children listed down to `depth` levels,
+    /// `None` if the scan has been cancelled.
+    fn scan(
+        &mut self,
+        path: PathBuf,
+        metadata: &Metadata,
+        root_device: Option<u64>,
+        depth: usize,
+    ) -> Option<UsageEntry> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.scanned.fetch_add(1, Ordering::Relaxed);
+
+        let mut entry = UsageEntry {
+            size: self.allocated_size(metadata),
+            is_directory: metadata.is_dir(),
+            listed: depth > 0,
+            children: Vec::new(),
+            path,
+        };
+        if !entry.is_directory {
+            return Some(entry);
+        }
+        // the mount points of other file systems are listed without their content
+        if device(metadata) != root_device {
+            entry.listed = false;
+            return Some(entry);
+        }
+
+        let Ok(directory) = fs::read_dir(&entry.path) else {
+            self.unreadable += 1;
+            return Some(entry);
+        };
+        for child in directory.flatten() {
+            let Ok(child_metadata) = child.metadata() else {
+                continue;
+            };
+            let child = self.scan(
+                child.path(),
+                &child_metadata,
+                root_device,
+                depth.saturating_sub(1),
+            )?;
+            entry.size += child.size;
+            if depth > 0 {
+                entry.children.push(child);
+            }
+        }
+        entry.children.sort_by_key(|child| Reverse(child.size));
+        Some(entry)
+    }
+
+    /// Returns the space allocated for the file, zero for the hard links counted before.
+    #[cfg(unix)]
+    fn allocated_size(&mut self, metadata: &Metadata) -> u64 {
+        use std::os::unix::fs::MetadataExt;
+
+        if metadata.nlink() > 1
+            && !metadata.is_dir()
+            && !self.seen.insert((metadata.dev(), metadata.ino()))
+        {
+            return 0;
+        }
+        // the blocks are counted in 512 byte units regardless of the block size of the file system
+        metadata.blocks() * 512
+    }
+
+    #[cfg(not(unix))]
+    fn allocated_size(&mut self, metadata: &Metadata) -> u64 {
+        metadata.len()
+    }
+}
+
+/// Returns the device of the file system holding the file, `None` where it is not known.
+#[cfg(unix)]
+fn device(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn device(_metadata: &Metadata) -> Option<u64> {
+    None
+}
@@ -0,0 +1,120 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The first byte of the MessagePack payloads, which can not start a JSON text.
+const MESSAGE_PACK_TAG: u8 = 0x01;
+
+/// The formats the system information is serialized in between the agents and their clients, and in the recordings.
+/// JSON is kept for the HTTP API, MessagePack is more compact and quicker to parse for the per-second process lists.
+/// The MessagePack payloads start with a tag byte, so `decode()` reads both formats without being told which one it gets.
+///
+/// # Example:
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::model::{ProcessInfo, ProcessList};
+/// use core::{encoding, Encoding};
+///
+/// let processes = ProcessList::from(vec![ProcessInfo::default()]);
+/// let bytes = Encoding::MessagePack.encode(&processes)?;
+/// let decoded: ProcessList = encoding::decode(&bytes)?;
+/// assert_eq!(decoded.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Json,
+    #[serde(rename = "msgpack")]
+    MessagePack,
+}
+
+impl Encoding {
+    /// Returns the media type of the format, as sent in the `Accept` and `Content-Type` headers.
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Returns the format named by the `Accept` header of a request, JSON unless MessagePack is listed.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let accepts_message_pack = accept.is_some_and(|accept| {
+            accept.split(',').any(|media_type| {
+                media_type.split(';').next().map(str::trim) == Some(Self::MessagePack.media_type())
+            })
+        });
+        if accepts_message_pack {
+            Self::MessagePack
+        } else {
+            Self::Json
+        }
+    }
+
+    /// Serializes `value` in this format, prefixing the MessagePack payloads with their tag.
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, EncodingError> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(value)?),
+            Self::MessagePack => {
+                let mut bytes = vec![MESSAGE_PACK_TAG];
+                // the fields are written with their names, as the optional ones are skipped when missing
+                let mut serializer = rmp_serde::Serializer::new(&mut bytes).with_struct_map();
+                value.serialize(&mut serializer)?;
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+/// Deserializes `bytes` encoded by `Encoding::encode()` in either format,
+/// telling them apart by the tag of the MessagePack payloads.
+/// The payloads without a tag are JSON, like the answers of the older agents and the older recordings.
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, EncodingError> {
+    match bytes.split_first() {
+        Some((&MESSAGE_PACK_TAG, payload)) => Ok(rmp_serde::from_slice(payload)?),
+        _ => Ok(serde_json::from_slice(bytes)?),
+    }
+}
+
+/// Reasons why the system information could not be serialized or deserialized.
+#[derive(Debug)]
+pub enum EncodingError {
+    Json(serde_json::Error),
+    /// The value could not be written as MessagePack
+    MessagePackEncode(rmp_serde::encode::Error),
+    /// The payload is not valid MessagePack of the expected type
+    MessagePackDecode(rmp_serde::decode::Error),
+}
+
+impl Display for EncodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(error) => write!(f, "invalid JSON: {}", error),
+            Self::MessagePackEncode(error) => write!(f, "MessagePack encoding failed: {}", error),
+            Self::MessagePackDecode(error) => write!(f, "invalid MessagePack: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+impl From<serde_json::Error> for EncodingError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<rmp_serde::encode::Error> for EncodingError {
+    fn from(error: rmp_serde::encode::Error) -> Self {
+        Self::MessagePackEncode(error)
+    }
+}
+
+impl From<rmp_serde::decode::Error> for EncodingError {
+    fn from(error: rmp_serde::decode::Error) -> Self {
+        Self::MessagePackDecode(error)
+    }
+}
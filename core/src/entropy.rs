@@ -0,0 +1,63 @@
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+
+/// The available entropy below which the random number generator of the kernel is considered starved.
+/// The older kernels block the readers of `/dev/random` and delay the TLS handshakes of the servers
+/// without a hardware source of randomness until the pool fills up again.
+pub const LOW_ENTROPY: u64 = 200;
+
+/// The entropy gathered by the random number generator of the kernel, like `kernel.random.entropy_avail` shows it.
+/// Since Linux 5.18 the generator never runs out once it is seeded, so both counts stay at 256 bits.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Entropy {
+    /// The entropy in the input pool in bits
+    pub available: u64,
+    /// The size of the input pool in bits
+    pub pool_size: u64,
+}
+
+impl Entropy {
+    /// Returns true if the available entropy is below `LOW_ENTROPY`.
+    pub fn is_low(&self) -> bool {
+        self.available < LOW_ENTROPY
+    }
+}
+
+/// Reads the available entropy and the size of the pool from `/proc/sys/kernel/random`,
+/// so they are only read on Linux.
+#[cfg(target_os = "linux")]
+pub fn entropy() -> Result<Entropy, OceloError> {
+    linux::entropy()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn entropy() -> Result<Entropy, OceloError> {
+    Err(OceloError::Unsupported(
+        "reading the entropy is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::Entropy;
+    use crate::OceloError;
+    use std::fs;
+
+    pub fn entropy() -> Result<Entropy, OceloError> {
+        Ok(Entropy {
+            available: read("entropy_avail")?,
+            pool_size: read("poolsize")?,
+        })
+    }
+
+    /// Reads the count of `/proc/sys/kernel/random/<name>`.
+    fn read(name: &str) -> Result<u64, OceloError> {
+        let path = format!("/proc/sys/kernel/random/{}", name);
+        let content = fs::read_to_string(&path)
+            .map_err(|error| OceloError::Io(format!("failed to read {}: {}", path, error)))?;
+        content
+            .trim()
+            .parse()
+            .map_err(|_| OceloError::Io(format!("malformed {}: {}", path, content.trim())))
+    }
+}
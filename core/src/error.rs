@@ -0,0 +1,43 @@
+use std::fmt::{self, Display, Formatter};
+use std::sync::PoisonError;
+
+/// Reasons why the system information could not be polled or displayed.
+#[derive(Debug)]
+pub enum OceloError {
+    /// The system reports no processors, so their statistics can not be calculated
+    NoProcessors,
+    /// There is no process with the given pid, it may have exited
+    ProcessNotFound(u32),
+    /// The operating system could not be queried, like when a file of `/proc` could not be read
+    Io(String),
+    /// The requested information is not available on this platform
+    Unsupported(String),
+    /// A thread panicked while holding a lock, so the guarded data may be inconsistent
+    PoisonedLock(String),
+    /// The user interface could not be drawn or updated
+    Ui(String),
+    /// A background operation was cancelled before it finished
+    Cancelled,
+}
+
+impl Display for OceloError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoProcessors => write!(f, "no processors were found"),
+            Self::ProcessNotFound(pid) => write!(f, "process {} not found", pid),
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Unsupported(error) => write!(f, "{}", error),
+            Self::PoisonedLock(error) => write!(f, "lock poisoned: {}", error),
+            Self::Ui(error) => write!(f, "user interface error: {}", error),
+            Self::Cancelled => write!(f, "the operation was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for OceloError {}
+
+impl<T> From<PoisonError<T>> for OceloError {
+    fn from(error: PoisonError<T>) -> Self {
+        Self::PoisonedLock(error.to_string())
+    }
+}
@@ -0,0 +1,352 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The default length of time the history keeps the samples for, the longest period the charts can display.
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// A single measurement of a metric.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// Time of the measurement in seconds since the Unix epoch
+    pub timestamp: f64,
+    pub value: f64,
+}
+
+impl Sample {
+    /// Creates a sample of `value` measured now.
+    pub fn now(value: f64) -> Self {
+        Sample {
+            timestamp: unix_time(),
+            value,
+        }
+    }
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+pub fn unix_time() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |since_epoch| since_epoch.as_secs_f64())
+}
+
+/// A fixed size buffer which drops the oldest item when a new one is pushed into the full buffer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RingBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty buffer holding at most `capacity` items.
+    pub fn new(capacity: usize) -> Self {
+        RingBuffer {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Appends `item` to the end of the buffer, dropping the oldest item if the buffer is full.
+    pub fn push(&mut self, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    /// Changes the maximum count of items, dropping the oldest ones which do not fit anymore.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        while self.items.len() > capacity {
+            self.items.pop_front();
+        }
+        self.capacity = capacity;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the most recently pushed item.
+    pub fn last(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    /// Iterates over the items from the oldest to the newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.items.iter()
+    }
+
+    /// Iterates over the newest `count` items, from the oldest to the newest.
+    pub fn window(&self, count: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.items
+            .iter()
+            .skip(self.items.len().saturating_sub(count))
+    }
+}
+
+/// The metrics whose history is recorded.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Metric {
+    /// Global CPU usage in percent
+    CpuUsage,
+    /// Physical memory usage in percent
+    MemoryUsage,
+    /// Swap usage in percent
+    SwapUsage,
+    /// Bytes read back from the swap per second
+    SwapIn,
+    /// Bytes written out to the swap per second
+    SwapOut,
+    /// Received bytes per second of the named network interface
+    InterfaceReceived(String),
+    /// Transmitted bytes per second of the named network interface
+    InterfaceTransmitted(String),
+    /// Signal strength in dBm of the named wireless interface
+    WifiSignal(String),
+    /// Round-trip time in milliseconds of the ping target, see `LatencyProber`
+    PingRtt(String),
+    /// Retransmitted TCP segments per second
+    TcpRetransmissions,
+    /// Completed operations per second of the storage device mounted at the path
+    DiskOperations(String),
+    /// Average operation latency in milliseconds of the storage device mounted at the path
+    DiskLatency(String),
+    /// Value of the hardware sensor with the label, in the unit of the sensor
+    Sensor(String),
+    /// Frequency in MHz of the logical core with the index
+    CoreFrequency(usize),
+    /// Load average over the last minute
+    LoadOneMinute,
+    /// Load average over the last five minutes
+    LoadFiveMinutes,
+    /// Load average over the last fifteen minutes
+    LoadFifteenMinutes,
+}
+
+impl Display for Metric {
+    /// Formats the metric as the name of its column in the exported history, like `received:eth0`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CpuUsage => write!(f, "cpu_usage"),
+            Self::MemoryUsage => write!(f, "memory_usage"),
+            Self::SwapUsage => write!(f, "swap_usage"),
+            Self::SwapIn => write!(f, "swap_in"),
+            Self::SwapOut => write!(f, "swap_out"),
+            Self::InterfaceReceived(name) => write!(f, "received:{}", name),
+            Self::InterfaceTransmitted(name) => write!(f, "transmitted:{}", name),
+            Self::WifiSignal(name) => write!(f, "signal:{}", name),
+            Self::PingRtt(target) => write!(f, "rtt:{}", target),
+            Self::TcpRetransmissions => write!(f, "tcp_retransmissions"),
+            Self::DiskOperations(mount) => write!(f, "operations:{}", mount),
+            Self::DiskLatency(mount) => write!(f, "latency:{}", mount),
+            Self::Sensor(label) => write!(f, "sensor:{}", label),
+            Self::CoreFrequency(index) => write!(f, "frequency:{}", index),
+            Self::LoadOneMinute => write!(f, "load_1m"),
+            Self::LoadFiveMinutes => write!(f, "load_5m"),
+            Self::LoadFifteenMinutes => write!(f, "load_15m"),
+        }
+    }
+}
+
+/// The file formats which the recorded samples can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One `timestamp,metric,value` row per sample
+    Csv,
+    /// An object with the samples of every metric under its name
+    Json,
+}
+
+impl ExportFormat {
+    /// The extension of the files in the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Time series of metrics, each kept in its own ring buffer with the same capacity.
+///
+/// # Example:
+/// ```
+/// use core::{History, Metric, DEFAULT_POLLING_INTERVAL, DEFAULT_RETENTION};
+///
+/// let mut history = History::with_retention(DEFAULT_RETENTION, DEFAULT_POLLING_INTERVAL);
+/// history.record(Metric::CpuUsage, 12.5);
+/// let last_minute = history.window(&Metric::CpuUsage, 20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct History {
+    series: HashMap<Metric, RingBuffer<Sample>>,
+    capacity: usize,
+}
+
+impl Default for History {
+    /// Creates a history keeping `DEFAULT_RETENTION` long series polled at every `DEFAULT_POLLING_INTERVAL`.
+    fn default() -> Self {
+        Self::with_retention(DEFAULT_RETENTION, crate::DEFAULT_POLLING_INTERVAL)
+    }
+}
+
+impl History {
+    /// Creates a history keeping at most `capacity` samples per metric.
+    pub fn new(capacity: usize) -> Self {
+        History {
+            series: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Creates a history which keeps the samples for `retention` long when they are recorded at every `interval`.
+    pub fn with_retention(retention: Duration, interval: Duration) -> Self {
+        Self::new(capacity_for(retention, interval))
+    }
+
+    /// Records the `value` of `metric` measured now.
+    pub fn record(&mut self, metric: Metric, value: f64) {
+        let capacity = self.capacity;
+        self.series
+            .entry(metric)
+            .or_insert_with(|| RingBuffer::new(capacity))
+            .push(Sample::now(value));
+    }
+
+    /// Returns every recorded sample of `metric`.
+    pub fn get(&self, metric: &Metric) -> Option<&RingBuffer<Sample>> {
+        self.series.get(metric)
+    }
+
+    /// Returns the newest `count` samples of `metric`, from the oldest to the newest.
+    pub fn window(&self, metric: &Metric, count: usize) -> Vec<Sample> {
+        self.series
+            .get(metric)
+            .map(|series| series.window(count).copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the samples of `metric` measured between `start` and `end`, in seconds since the Unix epoch,
+    /// resampled into at most `count` samples by averaging the ones measured in the same `count`th of the period.
+    /// The periods without samples are left out, so a gap in the recording stays a gap.
+    pub fn resample(&self, metric: &Metric, start: f64, end: f64, count: usize) -> Vec<Sample> {
+        let Some(series) = self.series.get(metric) else {
+            return Vec::new();
+        };
+        if count == 0 || end <= start {
+            return Vec::new();
+        }
+        let period = (end - start) / count as f64;
+
+        let mut resampled: Vec<Sample> = Vec::new();
+        // the sums of the timestamps and the values in the current period, with their count
+        let mut bucket: Option<(usize, f64, f64, usize)> = None;
+        for sample in series
+            .iter()
+            .filter(|sample| sample.timestamp >= start && sample.timestamp <= end)
+        {
+            let index = (((sample.timestamp - start) / period) as usize).min(count - 1);
+            match &mut bucket {
+                Some((current, timestamps, values, samples)) if *current == index => {
+                    *timestamps += sample.timestamp;
+                    *values += sample.value;
+                    *samples += 1;
+                }
+                _ => {
+                    resampled.extend(bucket.map(average));
+                    bucket = Some((index, sample.timestamp, sample.value, 1));
+                }
+            }
+        }
+        resampled.extend(bucket.map(average));
+        resampled
+    }
+
+    /// Formats the samples of `metrics` measured between `start` and `end` in `format`,
+    /// from the oldest one of each metric. The metrics without samples are left out.
+    pub fn export(&self, metrics: &[Metric], start: f64, end: f64, format: ExportFormat) -> String {
+        let series = metrics.iter().filter_map(|metric| {
+            self.series.get(metric).map(|series| {
+                let samples = series
+                    .iter()
+                    .filter(|sample| sample.timestamp >= start && sample.timestamp <= end)
+                    .collect::<Vec<&Sample>>();
+                (metric, samples)
+            })
+        });
+        match format {
+            ExportFormat::Csv => {
+                let mut csv = String::from("timestamp,metric,value\n");
+                for (metric, series) in series {
+                    let name = metric.to_string();
+                    // the names containing the separator, like some mount points, are quoted
+                    let name = if name.contains([',', '"']) {
+                        format!("\"{}\"", name.replace('"', "\"\""))
+                    } else {
+                        name
+                    };
+                    for sample in series {
+                        csv.push_str(&format!(
+                            "{:.3},{},{}\n",
+                            sample.timestamp, name, sample.value
+                        ));
+                    }
+                }
+                csv
+            }
+            ExportFormat::Json => {
+                let object = series
+                    .map(|(metric, samples)| (metric.to_string(), serde_json::json!(samples)))
+                    .collect::<serde_json::Map<String, serde_json::Value>>();
+                serde_json::to_string_pretty(&object).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Keeps only the metrics for which `keep` returns true.
+    pub fn retain(&mut self, keep: impl Fn(&Metric) -> bool) {
+        self.series.retain(|metric, _| keep(metric));
+    }
+
+    /// The maximum count of samples kept per metric.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the maximum count of samples kept per metric.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        for series in self.series.values_mut() {
+            series.set_capacity(capacity);
+        }
+    }
+}
+
+/// Returns the sample at the average time and of the average value of the samples summed into `bucket`.
+fn average((_, timestamps, values, count): (usize, f64, f64, usize)) -> Sample {
+    Sample {
+        timestamp: timestamps / count as f64,
+        value: values / count as f64,
+    }
+}
+
+/// Returns the count of samples recorded at every `interval` during `retention`.
+pub fn capacity_for(retention: Duration, interval: Duration) -> usize {
+    if interval.is_zero() {
+        0
+    } else {
+        (retention.as_secs_f64() / interval.as_secs_f64()).ceil() as usize
+    }
+}
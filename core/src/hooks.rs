@@ -0,0 +1,252 @@
+use crate::http;
+use crate::Alert;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The longest time to wait for connecting to a webhook and for its answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The time to wait before the first retry of a failed hook, doubled before every further retry.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// An action taken when an alert is raised, like calling the webhook of an incident tool.
+///
+/// # Example:
+/// ```toml
+/// [[alert_hooks]]
+/// webhook = "http://alerts.example.com:9000/ocelo"
+/// min_interval = 600
+///
+/// [[alert_hooks]]
+/// script = "/usr/local/bin/page-oncall"
+/// retries = 5
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertHook {
+    /// URL which the alerts are posted to as JSON, `https` ones only with the `https` feature
+    pub webhook: Option<String>,
+    /// Program executed for every alert, which is described by the environment variables
+    /// `OCELO_ALERT_RULE`, `OCELO_ALERT_VALUE`, `OCELO_ALERT_TIMESTAMP` and `OCELO_ALERT_JSON`
+    pub script: Option<PathBuf>,
+    /// The shortest time between two runs of the hook for the same rule in seconds,
+    /// the alerts of the rule raised meanwhile are dropped
+    pub min_interval: u64,
+    /// How many times a failed run is repeated
+    pub retries: u32,
+}
+
+impl Default for AlertHook {
+    fn default() -> Self {
+        AlertHook {
+            webhook: None,
+            script: None,
+            min_interval: 60,
+            retries: 3,
+        }
+    }
+}
+
+impl AlertHook {
+    /// Returns why the hook can never run, like a webhook URL which cannot be requested.
+    pub fn validate(&self) -> Result<(), HookError> {
+        match &self.webhook {
+            Some(url) => http::supported_url(url)
+                .map(|_| ())
+                .map_err(|reason| HookError::InvalidUrl(format!("{}: {}", url, reason))),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the actions of the hook for `alert` once.
+    fn run(&self, alert: &Alert) -> Result<(), HookError> {
+        let json =
+            serde_json::to_string(alert).map_err(|error| HookError::Io(error.to_string()))?;
+        if let Some(url) = &self.webhook {
+            post(url, &json)?;
+        }
+        if let Some(script) = &self.script {
+            let status = Command::new(script)
+                .env("OCELO_ALERT_RULE", &alert.rule)
+                .env("OCELO_ALERT_VALUE", alert.value.to_string())
+                .env(
+                    "OCELO_ALERT_TIMESTAMP",
+                    (alert.timestamp as u64).to_string(),
+                )
+                .env("OCELO_ALERT_JSON", &json)
+                .status()
+                .map_err(|error| HookError::Io(format!("{}: {}", script.display(), error)))?;
+            if !status.success() {
+                return Err(HookError::Failed(format!(
+                    "{} exited with {}",
+                    script.display(),
+                    status
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reasons why a hook could not be run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookError {
+    /// The webhook URL cannot be requested, for the given reason
+    InvalidUrl(String),
+    /// The webhook could not be connected to or the script could not be started
+    Io(String),
+    /// The webhook answered with an error status or the script exited with an error
+    Failed(String),
+}
+
+impl Display for HookError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl(reason) => write!(f, "invalid webhook URL {}", reason),
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Failed(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for HookError {}
+
+/// Runs the alert hooks in the background, so a slow webhook or script does not delay the polling.
+/// The failed runs are retried, waiting more and more between the attempts.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::{AlertHooks, Config, SystemInfoPoller};
+///
+/// let config = Config::default();
+/// let mut poller = SystemInfoPoller::default();
+/// poller.init();
+/// let mut hooks = AlertHooks::new(config.alert_hooks.clone());
+/// hooks.dispatch(&poller.evaluate_alerts()?);
+/// for error in hooks.finish() {
+///     eprintln!("{}", error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct AlertHooks {
+    hooks: Vec<AlertHook>,
+    /// When the hooks were last run for the rules, by the index of the hook and the rule
+    last_runs: HashMap<(usize, String), Instant>,
+    /// Passes the runs to the worker thread, `None` until the first run
+    runs: Option<Sender<(AlertHook, Alert)>>,
+    /// Reports the runs which failed even after retrying
+    failures: Option<Receiver<HookError>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AlertHooks {
+    pub fn new(hooks: Vec<AlertHook>) -> Self {
+        AlertHooks {
+            hooks,
+            ..Default::default()
+        }
+    }
+
+    /// Starts running every hook for the `alerts`, unless the hook has run for the same rule too recently.
+    pub fn dispatch(&mut self, alerts: &[Alert]) {
+        let now = Instant::now();
+        let mut runs = Vec::new();
+        for alert in alerts {
+            for (index, hook) in self.hooks.iter().enumerate() {
+                let key = (index, alert.rule.clone());
+                let limited = self.last_runs.get(&key).is_some_and(|last_run| {
+                    now.duration_since(*last_run) < Duration::from_secs(hook.min_interval)
+                });
+                if !limited {
+                    self.last_runs.insert(key, now);
+                    runs.push((hook.clone(), alert.clone()));
+                }
+            }
+        }
+        if runs.is_empty() {
+            return;
+        }
+
+        if self.runs.is_none() {
+            let (sender, queue) = mpsc::channel();
+            let (failed, failures) = mpsc::channel();
+            self.runs = Some(sender);
+            self.failures = Some(failures);
+            self.worker = Some(thread::spawn(move || work(queue, failed)));
+        }
+        if let Some(sender) = &self.runs {
+            for run in runs {
+                let _ = sender.send(run);
+            }
+        }
+    }
+
+    /// Returns the runs which failed since the previous call, even after retrying.
+    pub fn failures(&self) -> Vec<HookError> {
+        self.failures
+            .as_ref()
+            .map(|failures| failures.try_iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Waits for the started runs to finish and returns the ones which failed.
+    pub fn finish(mut self) -> Vec<HookError> {
+        self.runs = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        self.failures()
+    }
+}
+
+/// Runs the hooks received on `queue` one after the other, reporting the failed ones to `failed`.
+fn work(queue: Receiver<(AlertHook, Alert)>, failed: Sender<HookError>) {
+    for (hook, alert) in queue {
+        // the invalid hooks would fail the same way at every retry
+        if let Err(error) = hook.validate() {
+            let _ = failed.send(error);
+            continue;
+        }
+        let mut delay = RETRY_DELAY;
+        let mut result = hook.run(&alert);
+        for _ in 0..hook.retries {
+            if result.is_ok() {
+                break;
+            }
+            thread::sleep(delay);
+            delay *= 2;
+            result = hook.run(&alert);
+        }
+        if let Err(error) = result {
+            let _ = failed.send(error);
+        }
+    }
+}
+
+/// Posts `body` as JSON to the webhook at `url`, like `http://example.com:9000/alerts`.
+fn post(url: &str, body: &str) -> Result<(), HookError> {
+    let response = http::request(
+        "POST",
+        url,
+        &[("Content-Type", "application/json")],
+        body.as_bytes(),
+        REQUEST_TIMEOUT,
+    )
+    .map_err(|error| HookError::Io(format!("{}: {}", url, error)))?;
+    match response.is_success() {
+        true => Ok(()),
+        false => Err(HookError::Failed(format!(
+            "{} answered '{}'",
+            url, response.status_line
+        ))),
+    }
+}
@@ -0,0 +1,231 @@
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(feature = "https")]
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// The answer to a request.
+#[derive(Clone, Debug)]
+pub struct Response {
+    /// The status code, like 200
+    pub status: u16,
+    /// The status line, like `HTTP/1.1 200 OK`
+    pub status_line: String,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Returns whether the status code is a success one, 2xx.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the body as text, the invalid UTF-8 sequences are replaced.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// The parts of an `http://host[:port]/path` or `https://host[:port]/path` URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Url<'a> {
+    /// Whether the URL is an `https` one, which is requested over TLS
+    pub https: bool,
+    /// The host with the port if the URL has one, as sent in the `Host` header
+    pub host: &'a str,
+    /// The host with the port, the default one of the scheme if the URL has none
+    pub address: String,
+    pub path: &'a str,
+}
+
+impl<'a> Url<'a> {
+    /// Parses `url`, `None` if it is neither an HTTP nor an HTTPS one.
+    pub fn parse(url: &'a str) -> Option<Self> {
+        let (https, rest) = match url.strip_prefix("https://") {
+            Some(rest) => (true, rest),
+            None => (false, url.strip_prefix("http://")?),
+        };
+        let (host, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+        if host.is_empty() {
+            return None;
+        }
+        // an IPv6 address like `[::1]` has colons without having a port
+        let address = match host.rsplit_once(':') {
+            Some((_, port)) if !port.ends_with(']') => host.to_string(),
+            _ => format!("{}:{}", host, if https { 443 } else { 80 }),
+        };
+        Some(Url {
+            https,
+            host,
+            address,
+            path,
+        })
+    }
+}
+
+/// Parses `url`, or returns why no request can be sent to it: it is not an HTTP URL,
+/// or an HTTPS one while ocelo is built without the `https` feature.
+pub fn supported_url(url: &str) -> Result<Url<'_>, String> {
+    match Url::parse(url) {
+        None => Err("only http:// and https:// URLs are supported".to_string()),
+        Some(url) if url.https && !cfg!(feature = "https") => {
+            Err("https:// URLs need ocelo to be built with the https feature".to_string())
+        }
+        Some(url) => Ok(url),
+    }
+}
+
+/// Sends a GET request with the `headers` to `url`, like `http://api.ipify.org`,
+/// waiting `timeout` at most for the connection and for every read and write.
+///
+/// Plain HTTP is spoken directly, the answer is read until the server closes the connection.
+/// The `https://` URLs are requested with curl, only with the `https` feature.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::http;
+/// use std::time::Duration;
+///
+/// let timeout = Duration::from_secs(5);
+/// let response = http::get("http://localhost:8090/overview", &[("Accept", "application/json")], timeout)?;
+/// println!("{}", response.text());
+/// # Ok(())
+/// # }
+/// ```
+pub fn get(url: &str, headers: &[(&str, &str)], timeout: Duration) -> io::Result<Response> {
+    request("GET", url, headers, &[], timeout)
+}
+
+/// Sends a request with the `headers` and the `body` to `url`, see `get()`.
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    timeout: Duration,
+) -> io::Result<Response> {
+    let parsed = supported_url(url).map_err(|reason| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("{}: {}", url, reason))
+    })?;
+    #[cfg(feature = "https")]
+    if parsed.https {
+        return curl(method, url, headers, body, timeout);
+    }
+    let stream = connect(&parsed.address, timeout)?;
+    exchange(stream, method, parsed.host, parsed.path, headers, body)
+}
+
+/// Sends the request with curl, which speaks TLS, waiting `timeout` at most for the connection and for the answer.
+/// The answer is read with its head, like by `exchange()`.
+#[cfg(feature = "https")]
+fn curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    timeout: Duration,
+) -> io::Result<Response> {
+    let seconds = timeout.as_secs_f64().to_string();
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--include", "--http1.1"])
+        .args(["--connect-timeout", &seconds, "--max-time", &seconds])
+        .args(["--request", method])
+        // the body is sent right away instead of waiting for the server to accept it
+        .args(["--header", "Expect:"]);
+    for (name, value) in headers {
+        command.arg("--header").arg(format!("{}: {}", name, value));
+    }
+    if !body.is_empty() {
+        command.args(["--data-binary", "@-"]);
+    }
+    let mut child = command
+        .args(["--url", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(body)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    parse_response(&output.stdout)
+}
+
+/// Connects to the first reachable address of `address`, like `localhost:8090`,
+/// with `timeout` set for the connection and for every read and write.
+fn connect(address: &str, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_error = io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no address found for {}", address),
+    );
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, timeout) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                return Ok(stream);
+            }
+            Err(error) => last_error = error,
+        }
+    }
+    Err(last_error)
+}
+
+/// Sends a request over the connected `stream` and reads the answer until the connection is closed.
+/// HTTP/1.0 is used, so the answer is not chunked.
+pub fn exchange(
+    mut stream: impl Read + Write,
+    method: &str,
+    host: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> io::Result<Response> {
+    let mut request = format!("{} {} HTTP/1.0\r\nHost: {}\r\n", method, path, host);
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    stream.write_all(request.as_bytes())?;
+    stream.write_all(body)?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    parse_response(&response)
+}
+
+/// Splits the `response` read from the server into its status and its body.
+fn parse_response(response: &[u8]) -> io::Result<Response> {
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "malformed response");
+    let separator = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(malformed)?;
+    let head = String::from_utf8_lossy(&response[..separator]);
+
+    // the status line looks like `HTTP/1.1 200 OK`
+    let status_line = head.lines().next().unwrap_or_default().trim().to_string();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(malformed)?;
+    Ok(Response {
+        status,
+        status_line,
+        body: response[separator + 4..].to_vec(),
+    })
+}
@@ -0,0 +1,95 @@
+use crate::model::Sensor;
+
+/// Reads the fan speeds and the voltages of the hardware monitoring chips from `/sys/class/hwmon`,
+/// like `sensors` from lm-sensors. The temperatures are read by sysinfo instead.
+/// The chips are only read on Linux, elsewhere no sensors are returned.
+#[cfg(target_os = "linux")]
+pub fn fans_and_voltages() -> Vec<Sensor> {
+    linux::fans_and_voltages()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn fans_and_voltages() -> Vec<Sensor> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use crate::model::{Sensor, SensorKind};
+    use std::fs;
+    use std::path::Path;
+
+    const HWMON_DIRECTORY: &str = "/sys/class/hwmon";
+
+    pub fn fans_and_voltages() -> Vec<Sensor> {
+        let Ok(chips) = fs::read_dir(HWMON_DIRECTORY) else {
+            return Vec::new();
+        };
+
+        let mut sensors = Vec::new();
+        for chip in chips.flatten() {
+            let path = chip.path();
+            let name = read_string(&path.join("name"))
+                .unwrap_or_else(|| chip.file_name().to_string_lossy().into_owned());
+            let Ok(files) = fs::read_dir(&path) else {
+                continue;
+            };
+
+            let mut inputs = files
+                .flatten()
+                .filter_map(|file| {
+                    let file_name = file.file_name().to_string_lossy().into_owned();
+                    let channel = file_name.strip_suffix("_input")?;
+                    let kind = match channel {
+                        fan if fan.starts_with("fan") => SensorKind::Fan,
+                        voltage if voltage.starts_with("in") => SensorKind::Voltage,
+                        _ => return None,
+                    };
+                    Some((channel.to_string(), kind))
+                })
+                .collect::<Vec<(String, SensorKind)>>();
+            inputs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            for (channel, kind) in inputs {
+                if let Some(sensor) = read_sensor(&path, &name, &channel, kind) {
+                    sensors.push(sensor);
+                }
+            }
+        }
+        sensors
+    }
+
+    /// Reads the `channel` of the chip at `path`, like `fan1` or `in0`, with its limits.
+    /// The fans report their speed in RPM, the voltages are reported in millivolts.
+    fn read_sensor(path: &Path, chip: &str, channel: &str, kind: SensorKind) -> Option<Sensor> {
+        let scale = match kind {
+            SensorKind::Voltage => 1000.0,
+            _ => 1.0,
+        };
+        let read = |attribute: &str| {
+            read_string(&path.join(format!("{}_{}", channel, attribute)))?
+                .parse::<f64>()
+                .ok()
+                .map(|value| value / scale)
+        };
+        let label = read_string(&path.join(format!("{}_label", channel)))
+            .unwrap_or_else(|| channel.to_string());
+
+        Some(Sensor {
+            label: format!("{} {}", chip, label),
+            kind,
+            value: read("input")?,
+            // the zero limits are not set, like the minimum speed of a fan without an alarm
+            min: read("min").filter(|min| *min != 0.0),
+            // the maximum speed of a fan is not a limit
+            max: read("max").filter(|max| *max != 0.0 && kind == SensorKind::Voltage),
+            critical: read("crit").filter(|critical| *critical != 0.0),
+        })
+    }
+
+    fn read_string(path: &Path) -> Option<String> {
+        fs::read_to_string(path)
+            .ok()
+            .map(|content| content.trim().to_string())
+    }
+}
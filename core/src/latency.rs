@@ -0,0 +1,286 @@
+use crate::routing::routes;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The time between two probes of a target.
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long a reply is waited for before the probe is counted as lost.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Count of the last probes of a target which the loss is calculated from.
+const LOSS_WINDOW: usize = 30;
+
+/// The target probing the default gateway, whichever it is at the time of the probe.
+pub const GATEWAY_TARGET: &str = "gateway";
+
+/// The latest results of probing a target.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyProbe {
+    /// The target as configured, like `gateway`, `1.1.1.1` or `vpn.example.com`
+    pub target: String,
+    /// The address probed, `None` while the target can not be resolved
+    pub address: Option<String>,
+    /// Round-trip time of the last probe in milliseconds, `None` if it was lost
+    pub rtt: Option<f64>,
+    /// Percentage of the lost probes among the last ones
+    pub loss: f64,
+    /// Why the target can not be probed, like a name which can not be resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Measures the round-trip time and the packet loss of a few targets with ICMP echo requests, like `ping`,
+/// at every `PROBE_INTERVAL` on a background thread, which stops when the prober is dropped.
+///
+/// The requests are sent from an ICMP datagram socket where `net.ipv4.ping_group_range` allows it,
+/// or from a raw socket, which requires root privileges or the `CAP_NET_RAW` capability.
+/// The probes are only sent on Linux.
+///
+/// # Example:
+/// ```no_run
+/// use core::latency::PROBE_INTERVAL;
+/// use core::LatencyProber;
+/// use std::thread;
+///
+/// let prober = LatencyProber::start(vec!["gateway".to_string(), "1.1.1.1".to_string()]);
+/// thread::sleep(PROBE_INTERVAL);
+/// for probe in prober.probes() {
+///     println!("{}: {:?} ms, {}% lost", probe.target, probe.rtt, probe.loss);
+/// }
+/// ```
+pub struct LatencyProber {
+    probes: Arc<Mutex<Vec<LatencyProbe>>>,
+    /// Stops the worker thread when dropped
+    _stop: Sender<()>,
+}
+
+impl LatencyProber {
+    /// Starts probing the `targets`: host names, IP addresses or `GATEWAY_TARGET`.
+    pub fn start(targets: Vec<String>) -> Self {
+        let probes = Arc::new(Mutex::new(
+            targets
+                .iter()
+                .map(|target| LatencyProbe {
+                    target: target.clone(),
+                    ..LatencyProbe::default()
+                })
+                .collect::<Vec<LatencyProbe>>(),
+        ));
+        let (stop, stopped) = mpsc::channel();
+        let results = Arc::clone(&probes);
+        thread::spawn(move || {
+            let mut replies: Vec<VecDeque<bool>> = vec![VecDeque::new(); targets.len()];
+            let mut sequence: u16 = 0;
+            loop {
+                sequence = sequence.wrapping_add(1);
+                for (index, target) in targets.iter().enumerate() {
+                    let probe = probe(target, sequence, &mut replies[index]);
+                    if let Ok(mut probes) = results.lock() {
+                        probes[index] = probe;
+                    }
+                }
+                // sleeps until the next round, or stops when the prober is dropped
+                if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(PROBE_INTERVAL) {
+                    break;
+                }
+            }
+        });
+        LatencyProber {
+            probes,
+            _stop: stop,
+        }
+    }
+
+    /// Returns the latest results of every target in the order of the targets.
+    pub fn probes(&self) -> Vec<LatencyProbe> {
+        self.probes
+            .lock()
+            .map(|probes| probes.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Probes `target` once, recording whether it replied into `replies`.
+fn probe(target: &str, sequence: u16, replies: &mut VecDeque<bool>) -> LatencyProbe {
+    let mut probe = LatencyProbe {
+        target: target.to_string(),
+        ..LatencyProbe::default()
+    };
+    let address = match resolve(target) {
+        Ok(address) => address,
+        Err(error) => {
+            probe.error = Some(error);
+            return probe;
+        }
+    };
+    probe.address = Some(address.to_string());
+
+    match ping(address, sequence, PROBE_TIMEOUT) {
+        Ok(rtt) => {
+            probe.rtt = rtt.map(|rtt| rtt.as_secs_f64() * 1000.0);
+            replies.push_back(rtt.is_some());
+            if replies.len() > LOSS_WINDOW {
+                replies.pop_front();
+            }
+            let lost = replies.iter().filter(|replied| !**replied).count();
+            probe.loss = lost as f64 / replies.len() as f64 * 100.0;
+        }
+        Err(error) => probe.error = Some(error.to_string()),
+    }
+    probe
+}
+
+/// Returns the address of `target`, looking up the default gateway for `GATEWAY_TARGET`.
+fn resolve(target: &str) -> Result<IpAddr, String> {
+    if target == GATEWAY_TARGET {
+        return routes()
+            .map_err(|error| error.to_string())?
+            .iter()
+            .filter(|route| route.is_default())
+            .find_map(|route| route.gateway.as_deref()?.parse().ok())
+            .ok_or_else(|| "there is no default gateway".to_string());
+    }
+    if let Ok(address) = target.parse() {
+        return Ok(address);
+    }
+    (target, 0)
+        .to_socket_addrs()
+        .map_err(|error| format!("failed to resolve {}: {}", target, error))?
+        .next()
+        .map(|address| address.ip())
+        .ok_or_else(|| format!("{} has no address", target))
+}
+
+/// Sends an ICMP echo request to `address` and waits for the reply for at most `timeout`.
+/// Returns the round-trip time, or `None` if no reply arrived in time.
+#[cfg(target_os = "linux")]
+fn ping(address: IpAddr, sequence: u16, timeout: Duration) -> std::io::Result<Option<Duration>> {
+    linux::ping(address, sequence, timeout)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn ping(_address: IpAddr, _sequence: u16, _timeout: Duration) -> std::io::Result<Option<Duration>> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "probing the latency is only supported on Linux",
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::io;
+    use std::net::{IpAddr, SocketAddr, UdpSocket};
+    use std::os::fd::FromRawFd;
+    use std::process;
+    use std::time::{Duration, Instant};
+
+    /// The types of the ICMP and ICMPv6 echo requests and replies
+    const ECHO_REQUEST: u8 = 8;
+    const ECHO_REPLY: u8 = 0;
+    const ECHO_REQUEST_V6: u8 = 128;
+    const ECHO_REPLY_V6: u8 = 129;
+
+    /// Size of the echo header: the type, the code, the checksum, the identifier and the sequence number
+    const HEADER_SIZE: usize = 8;
+
+    /// Size of the payload sent with the requests
+    const PAYLOAD_SIZE: usize = 16;
+
+    pub fn ping(address: IpAddr, sequence: u16, timeout: Duration) -> io::Result<Option<Duration>> {
+        let (socket, raw) = open(address)?;
+        // the kernel replaces the identifier of the requests sent from a datagram socket with the port of the socket
+        let identifier = process::id() as u16;
+        let (request, reply) = match address {
+            IpAddr::V4(_) => (ECHO_REQUEST, ECHO_REPLY),
+            IpAddr::V6(_) => (ECHO_REQUEST_V6, ECHO_REPLY_V6),
+        };
+
+        let mut packet = [0u8; HEADER_SIZE + PAYLOAD_SIZE];
+        packet[0] = request;
+        packet[4..6].copy_from_slice(&identifier.to_be_bytes());
+        packet[6..8].copy_from_slice(&sequence.to_be_bytes());
+        // the kernel calculates the checksum of the ICMPv6 packets
+        if address.is_ipv4() {
+            let checksum = checksum(&packet);
+            packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+        }
+
+        let sent = Instant::now();
+        socket.send_to(&packet, SocketAddr::new(address, 0))?;
+        let deadline = sent + timeout;
+        let mut buffer = [0u8; 1500];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            socket.set_read_timeout(Some(remaining))?;
+            let length = match socket.recv(&mut buffer) {
+                Ok(length) => length,
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    return Ok(None)
+                }
+                Err(error) => return Err(error),
+            };
+            // a raw IPv4 socket receives the packets with their IP header
+            let start = match (raw, address) {
+                (true, IpAddr::V4(_)) => (buffer[0] & 0x0f) as usize * 4,
+                _ => 0,
+            };
+            let Some(message) = buffer.get(start..length) else {
+                continue;
+            };
+            // a raw socket receives the ICMP messages of every process
+            if message.len() >= HEADER_SIZE
+                && message[0] == reply
+                && message[6..8] == sequence.to_be_bytes()
+                && (!raw || message[4..6] == identifier.to_be_bytes())
+            {
+                return Ok(Some(sent.elapsed()));
+            }
+        }
+    }
+
+    /// Opens an ICMP datagram socket, or a raw socket where the datagram sockets are not permitted.
+    /// Returns the socket and whether it is raw.
+    fn open(address: IpAddr) -> io::Result<(UdpSocket, bool)> {
+        let (domain, protocol) = match address {
+            IpAddr::V4(_) => (libc::AF_INET, libc::IPPROTO_ICMP),
+            IpAddr::V6(_) => (libc::AF_INET6, libc::IPPROTO_ICMPV6),
+        };
+        let mut error = None;
+        for kind in [libc::SOCK_DGRAM, libc::SOCK_RAW] {
+            let fd = unsafe { libc::socket(domain, kind | libc::SOCK_CLOEXEC, protocol) };
+            if fd >= 0 {
+                // the socket is only used through sendto and recv, which work the same for every datagram socket
+                let socket = unsafe { UdpSocket::from_raw_fd(fd) };
+                return Ok((socket, kind == libc::SOCK_RAW));
+            }
+            error = Some(io::Error::last_os_error());
+        }
+        Err(error.unwrap_or_else(io::Error::last_os_error))
+    }
+
+    /// Returns the internet checksum of `data`, the one's complement of the one's complement sum of its words.
+    fn checksum(data: &[u8]) -> u16 {
+        let mut sum = data
+            .chunks(2)
+            .map(|word| u16::from_be_bytes([word[0], *word.get(1).unwrap_or(&0)]) as u32)
+            .sum::<u32>();
+        while sum > 0xffff {
+            sum = (sum & 0xffff) + (sum >> 16);
+        }
+        !(sum as u16)
+    }
+}
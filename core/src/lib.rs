@@ -1,17 +1,132 @@
+pub mod alerts;
+pub mod cgroup;
+pub mod clock;
+pub mod config;
+pub mod connections;
+pub mod containers;
+pub mod controller;
+pub mod cpufreq;
+pub mod disk_usage;
+pub mod encoding;
+pub mod entropy;
+pub mod error;
+pub mod history;
+pub mod hooks;
+pub mod http;
+pub mod hwmon;
+pub mod latency;
+pub mod limits;
+pub mod link;
+pub mod log_stream;
+pub mod memory_map;
 pub mod model;
+pub mod notification;
+pub mod open_files;
+#[cfg(feature = "packages")]
+pub mod packages;
+pub mod protocols;
+pub mod provider;
+pub mod public_ip;
+pub mod rate;
+pub mod recorder;
+pub mod remote;
+pub mod routing;
+pub mod scheduler;
+pub mod security;
+pub mod self_usage;
+pub mod services;
+#[cfg(feature = "smart")]
+pub mod smart;
+pub mod source;
+#[cfg(feature = "tokio")]
+pub mod stream;
+pub mod temperature;
+pub mod throttle;
+pub mod topology;
+pub mod traffic;
+pub mod users;
+pub mod virtualization;
+#[cfg(feature = "wifi")]
+pub mod wifi;
 
+pub use self::alerts::{Alert, AlertEngine, AlertNotification, AlertRule};
+pub use self::clock::ClockMonitor;
+pub use self::config::{Config, ConfigError};
+pub use self::containers::{ContainerAction, ContainerControlError};
+pub use self::controller::{ProcessController, ProcessSignal, NICE_RANGE};
+pub use self::disk_usage::{disk_usage_scan, DiskUsage, DiskUsageScan, UsageEntry};
+pub use self::encoding::{Encoding, EncodingError};
+pub use self::error::OceloError;
+pub use self::history::{ExportFormat, History, Metric, RingBuffer, Sample, DEFAULT_RETENTION};
+pub use self::hooks::{AlertHook, AlertHooks, HookError};
+pub use self::latency::LatencyProber;
+pub use self::log_stream::{LogSource, LogStream};
 pub use self::model::{CpuInfo, SystemInfo};
+#[cfg(feature = "packages")]
+pub use self::packages::PackageMonitor;
+pub use self::provider::{MockProvider, SystemInfoProvider};
+pub use self::public_ip::PublicIpLookup;
+pub use self::rate::RateTracker;
+pub use self::recorder::{Recorder, RecorderError, DEFAULT_RECORD_RETENTION};
+pub use self::remote::RemoteSource;
+pub use self::scheduler::PollingScheduler;
+pub use self::self_usage::{SelfMonitor, SelfUsage};
+pub use self::services::{ServiceAction, ServiceControlError};
+#[cfg(feature = "smart")]
+pub use self::smart::SmartMonitor;
+pub use self::source::{ConnectionStatus, DataSource, DataSourceError, ProviderSource};
+pub use self::temperature::TemperatureProvider;
+pub use self::throttle::ThrottleDetector;
+pub use self::traffic::TrafficAccounting;
+pub use self::virtualization::CloudMetadataLookup;
+use clock::clock_status;
+use config::PublicIpSettings;
+use connections::{connection_states, connections, listening_ports, Connection};
+use entropy::entropy;
+use limits::{system_limits, SystemLimits};
+use link::link_info;
+use memory_map::memory_map;
 use model::{
-    CpuCore, CpuMemoryUpdate, DiskInfo, MemoryInfo, NetworkInfo, ProcessInfo, ProcessList, Storage,
-    SystemOverviewInfo,
+    ContainersInfo, CpuCore, CpuMemoryUpdate, CpuTopology, DiskFilter, DiskInfo, LogsInfo,
+    MemoryInfo, NetworkInfo, ProcessColumn, ProcessDelta, ProcessInfo, ProcessList,
+    ProcessListBuilder, ProcessSummary, SchemaVersion, SensorsInfo, ServicesInfo, SessionsInfo,
+    Storage, SystemOverviewInfo, ThreadInfo, TopProcesses,
 };
-use std::sync::{Arc, Mutex};
+use open_files::{descriptor_usage, open_files, ProcessDescriptors};
+use protocols::protocol_stats;
+use routing::{neighbors, routes};
+use security::{security_overview, SecurityOverview};
+use serde::{Deserialize, Serialize};
+use std::mem;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+/// The minimum time to wait between two CPU refreshes to get accurate usage values.
+pub use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
 use sysinfo::{
-    Components, CpuRefreshKind, DiskRefreshKind, Disks, MemoryRefreshKind, Networks, RefreshKind,
-    System,
+    CpuRefreshKind, DiskRefreshKind, Disks, MemoryRefreshKind, Networks, Pid, ProcessRefreshKind,
+    ProcessesToUpdate, RefreshKind, System,
 };
+use virtualization::{detect_environment, HostEnvironment};
+#[cfg(feature = "wifi")]
+use wifi::wireless_link;
 
-#[derive(Default, Clone, Copy)]
+/// The default time between two consecutive polls of the system information.
+pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Count of the top CPU and memory consumers in the system overview.
+pub const TOP_PROCESS_COUNT: usize = 5;
+
+/// The shortest allowed time between two consecutive polls.
+pub const MIN_POLLING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The longest allowed time between two consecutive polls.
+pub const MAX_POLLING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Returns the name of the local system, like `web-1`, `None` if it can not be determined.
+pub fn host_name() -> Option<String> {
+    System::host_name()
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SystemInfoPollingContext {
     #[default]
     Overview,
@@ -19,38 +134,127 @@ pub enum SystemInfoPollingContext {
     Processes,
     Disks,
     Network,
+    /// The sockets of the system, shown from the Network tab
+    Connections,
+    /// The kernel tunables and the resource limits, shown from the Overview tab
+    Limits,
+    /// The failed logins, the exposed ports and the firewall, shown from the Overview tab
+    Security,
+    Sensors,
+    Users,
+    /// The service units of systemd
+    Services,
+    /// The running containers of Docker or Podman
+    Containers,
+    /// The recent messages of the system log
+    Logs,
 }
 
-pub type SharedSystemInfoPoller = Arc<Mutex<SystemInfoPoller>>;
-
+#[derive(Serialize, Deserialize)]
 pub enum SystemInfoUpdate {
-    OverView(SystemOverviewInfo),
+    OverView(Box<SystemOverviewInfo>),
     CpuAndMemory(CpuMemoryUpdate),
     Process(ProcessList),
-    Disk,
-    Network,
+    /// The changes of the process list since the previous `Process` or `ProcessDelta` update
+    ProcessDelta(ProcessDelta),
+    Disk(DiskInfo),
+    Network(Box<NetworkInfo>),
+    Connections(Vec<Connection>),
+    Limits(SystemLimits),
+    Security(SecurityOverview),
+    Sensors(SensorsInfo),
+    Users(SessionsInfo),
+    Services(ServicesInfo),
+    Containers(ContainersInfo),
+    Logs(LogsInfo),
+    /// Alerts raised by the rules since the previous poll
+    Alerts(Vec<Alert>),
+    /// The reachability of a remote data source has changed
+    Connection(ConnectionStatus),
+    /// Polling the local system has failed for the given reason
+    Error(String),
 }
 
-impl From<(&SystemInfoPollingContext, &mut SystemInfoPoller)> for SystemInfoUpdate {
-    fn from(value: (&SystemInfoPollingContext, &mut SystemInfoPoller)) -> Self {
-        let (ctx, sysinfo) = value;
-        match ctx {
+impl SystemInfoUpdate {
+    /// Polls the system information needed by the polling context `ctx` from `provider`.
+    pub fn poll<P: SystemInfoProvider>(
+        ctx: SystemInfoPollingContext,
+        provider: &mut P,
+    ) -> Result<Self, OceloError> {
+        Ok(match ctx {
             SystemInfoPollingContext::CpuAndMemory => {
-                Self::CpuAndMemory(sysinfo.get_cpu_amd_memory_info())
+                Self::CpuAndMemory(provider.get_cpu_amd_memory_info()?)
             }
-            SystemInfoPollingContext::Disks => Self::Disk,
-            SystemInfoPollingContext::Network => Self::Network,
-            SystemInfoPollingContext::Overview => Self::OverView(sysinfo.get_system_overview()),
-            SystemInfoPollingContext::Processes => Self::Process(sysinfo.get_process_list()),
-        }
+            SystemInfoPollingContext::Disks => Self::Disk(provider.get_disk_info()?),
+            SystemInfoPollingContext::Network => {
+                Self::Network(Box::new(provider.get_network_details()?))
+            }
+            SystemInfoPollingContext::Overview => {
+                Self::OverView(Box::new(provider.get_system_overview()?))
+            }
+            SystemInfoPollingContext::Processes => Self::Process(provider.get_process_list()?),
+            SystemInfoPollingContext::Connections => Self::Connections(provider.get_connections()?),
+            SystemInfoPollingContext::Limits => Self::Limits(provider.get_limits()?),
+            SystemInfoPollingContext::Security => Self::Security(provider.get_security()?),
+            SystemInfoPollingContext::Sensors => Self::Sensors(provider.get_sensors_info()?),
+            SystemInfoPollingContext::Users => Self::Users(provider.get_sessions()?),
+            SystemInfoPollingContext::Services => Self::Services(provider.get_services()?),
+            SystemInfoPollingContext::Containers => Self::Containers(provider.get_containers()?),
+            SystemInfoPollingContext::Logs => Self::Logs(provider.get_logs()?),
+        })
     }
 }
 
 pub struct SystemInfoPoller {
     polling_context: SystemInfoPollingContext,
+    /// Time to wait between two consecutive polls
+    interval: Duration,
     inner: System,
     disks: Disks,
     networks: Networks,
+    temperatures: TemperatureProvider,
+    /// Evaluates the user-defined alert rules at every poll
+    alerts: AlertEngine,
+    /// Detects the thermal throttling of the processor at every poll
+    throttle: ThrottleDetector,
+    /// The throttling alerts raised since the last evaluation of the alerts
+    throttle_alerts: Vec<Alert>,
+    /// The layout of the processor, read once as it does not change
+    topology: Option<CpuTopology>,
+    /// The time of the last CPU refresh, the usage is only accurate between refreshes far enough apart
+    last_cpu_refresh: Option<Instant>,
+    /// Calculates the disk and network throughput from the cumulative counters
+    rates: RateTracker,
+    /// The time of the last process refresh, needed for calculating the per-process rates
+    last_process_refresh: Instant,
+    /// Converts the processes, caching the users for resolving the usernames
+    process_builder: ProcessListBuilder,
+    /// Enables `self.get_process_delta()`
+    incremental_processes: bool,
+    /// The last process list returned while the incremental updates are enabled, the next delta is taken from it
+    process_baseline: Option<ProcessList>,
+    /// Estimates the network throughput of the processes, `None` if it is not accounted
+    traffic: Option<TrafficAccounting>,
+    /// Probes the latency of the ping targets, `None` without targets
+    latency: Option<LatencyProber>,
+    /// Looks up the public address of the host, `None` unless it is enabled
+    public_ip: Option<PublicIpLookup>,
+    /// The environment of the host detected at the initialization, `None` until then or where it is not detected
+    environment: Option<HostEnvironment>,
+    /// Fetches the type and the region of the cloud instance, `None` unless it is enabled
+    cloud_metadata: Option<CloudMetadataLookup>,
+    /// Estimates the offset of the clock, if enabled
+    clock: Option<ClockMonitor>,
+    /// Selects the mounts reported by `self.get_disk_info()`
+    disk_filter: DiskFilter,
+    /// Follows the system log, opened at the first poll of the logs
+    logs: Option<LogStream>,
+    /// Reads the SMART health of the physical drives
+    #[cfg(feature = "smart")]
+    smart: SmartMonitor,
+    /// Counts the packages and their updates, if enabled
+    #[cfg(feature = "packages")]
+    packages: Option<PackageMonitor>,
 }
 
 impl Default for SystemInfoPoller {
@@ -60,24 +264,194 @@ impl Default for SystemInfoPoller {
     fn default() -> Self {
         SystemInfoPoller {
             polling_context: SystemInfoPollingContext::Overview,
+            interval: DEFAULT_POLLING_INTERVAL,
             inner: System::new(),
             disks: Disks::new(),
             networks: Networks::new(),
+            temperatures: TemperatureProvider::default(),
+            alerts: AlertEngine::default(),
+            throttle: ThrottleDetector::default(),
+            throttle_alerts: Vec::new(),
+            topology: None,
+            last_cpu_refresh: None,
+            rates: RateTracker::default(),
+            last_process_refresh: Instant::now(),
+            process_builder: ProcessListBuilder::default(),
+            incremental_processes: false,
+            process_baseline: None,
+            traffic: None,
+            latency: None,
+            public_ip: None,
+            environment: None,
+            cloud_metadata: None,
+            clock: None,
+            disk_filter: DiskFilter::default(),
+            logs: None,
+            #[cfg(feature = "smart")]
+            smart: SmartMonitor::default(),
+            #[cfg(feature = "packages")]
+            packages: None,
         }
     }
 }
 
 impl SystemInfoPoller {
+    /// Sets the time between two consecutive polls.
+    /// The interval is clamped between `MIN_POLLING_INTERVAL` and `MAX_POLLING_INTERVAL`.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.set_interval(interval);
+        self
+    }
+
     /// Initalizes the backing system info fetcher by refreshing all ystem information.
     pub fn init(&mut self) {
         self.inner.refresh_all();
+        self.last_cpu_refresh = Some(Instant::now());
+        self.last_process_refresh = Instant::now();
+        self.topology = Some(topology::cpu_topology(&self.inner));
+        self.environment = detect_environment().ok();
+        // the first observation of the disk and network counters is the base of the rates,
+        // the snapshots themselves are not needed
+        let _ = self.get_disk_info();
+        let _ = self.get_network_info();
+        let _ = self.get_memory_info();
+    }
+
+    /// Enables estimating the network throughput of the processes,
+    /// filling the network rates of the process list and the top talkers of the network information.
+    /// It is disabled by default, as the sockets of every process are looked up at every poll.
+    pub fn with_network_accounting(mut self, enabled: bool) -> Self {
+        self.traffic = enabled.then(TrafficAccounting::default);
+        self
+    }
+
+    /// Starts probing the latency and the packet loss of the `targets`, filling the latency of the network information.
+    /// The targets are host names, IP addresses or `gateway` for the default gateway, see `LatencyProber`.
+    pub fn with_ping_targets(mut self, targets: Vec<String>) -> Self {
+        self.latency = (!targets.is_empty()).then(|| LatencyProber::start(targets));
+        self
+    }
+
+    /// Starts looking up the public address of the host if it is enabled by the `settings`,
+    /// filling the public address of the network information.
+    pub fn with_public_ip(mut self, settings: PublicIpSettings) -> Self {
+        self.public_ip = settings.enabled.then(|| PublicIpLookup::start(settings));
+        self
+    }
+
+    /// Starts fetching the type and the region of the cloud instance running the host from the metadata service
+    /// of its provider if it is `enabled`, filling the cloud instance of the system information.
+    /// It is disabled by default, as the metadata service is only reachable from the cloud.
+    pub fn with_cloud_metadata(mut self, enabled: bool) -> Self {
+        self.cloud_metadata = enabled.then(CloudMetadataLookup::start);
+        self
+    }
+
+    /// Starts estimating the offset of the clock, filling the offset of the clock status of the system information.
+    /// The offset is reported by chrony or systemd-timesyncd, otherwise the clock is compared to the SNTP `server`,
+    /// like `pool.ntp.org`, if given, see `ClockMonitor`.
+    /// It is disabled by default, as the offset is only known after the first check on a background thread.
+    pub fn with_clock_offset(mut self, server: Option<String>) -> Self {
+        self.clock = Some(ClockMonitor::start(server));
+        self
+    }
+
+    /// Starts counting the installed packages and their available updates, filling the packages of the system information,
+    /// see `PackageMonitor`. It is disabled by default, as the package manager simulates the upgrade at every check.
+    #[cfg(feature = "packages")]
+    pub fn with_packages(mut self) -> Self {
+        self.packages = Some(PackageMonitor::start());
+        self
+    }
+
+    /// The packages are only counted with the `packages` feature.
+    #[cfg(not(feature = "packages"))]
+    pub fn with_packages(self) -> Self {
+        self
+    }
+
+    /// Enables `self.get_process_delta()`, returning only the changed processes instead of the whole list.
+    /// It is disabled by default, as the previous list has to be kept for comparing.
+    pub fn with_incremental_processes(mut self, enabled: bool) -> Self {
+        self.incremental_processes = enabled;
+        self
+    }
+
+    /// Sets the mounts reported by `self.get_disk_info()`.
+    /// By default the pseudo-filesystems, like `tmpfs` and the snap images, are not reported.
+    pub fn with_disk_filter(mut self, filter: DiskFilter) -> Self {
+        self.disk_filter = filter;
+        self
+    }
+
+    /// Sets the rules evaluated by `self.evaluate_alerts()`.
+    pub fn with_alert_rules(mut self, rules: Vec<AlertRule>) -> Self {
+        self.set_alert_rules(rules);
+        self
+    }
+
+    pub fn set_alert_rules(&mut self, rules: Vec<AlertRule>) {
+        self.alerts = AlertEngine::new(rules);
+    }
+
+    /// Evaluates the alert rules against a fresh snapshot of the system and returns the newly raised alerts,
+    /// including the thermal throttling of the processor.
+    /// Only the processor is refreshed when there are no rules.
+    pub fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, OceloError> {
+        self.refresh_cpu();
+        self.temperatures.refresh();
+        self.detect_throttling();
+        let mut alerts = mem::take(&mut self.throttle_alerts);
+        if self.alerts.is_empty() {
+            return Ok(alerts);
+        }
+
+        let snapshot = self.get_system_overview()?;
+        alerts.extend(self.alerts.evaluate(&snapshot));
+        Ok(alerts)
     }
 
-    pub fn get_cpu_info(&mut self) -> CpuInfo {
+    /// Feeds the throttle detector with the refreshed frequency and temperature of the processor,
+    /// keeping the alert of a new throttling event until the alerts are evaluated.
+    fn detect_throttling(&mut self) {
+        let cpus = self.inner.cpus();
+        if cpus.is_empty() {
+            return;
+        }
+        let frequency = cpus.iter().map(|cpu| cpu.frequency()).sum::<u64>() / cpus.len() as u64;
+        let max_frequency = (0..cpus.len())
+            .filter_map(|index| cpufreq::core_scaling(index)?.max_frequency)
+            .max();
+
+        if let Some(alert) = self.throttle.observe(
+            frequency,
+            max_frequency,
+            self.temperatures.package_temperature(),
+            self.temperatures.package_critical(),
+        ) {
+            self.throttle_alerts.push(alert);
+        }
+    }
+
+    /// Refreshes the CPU usage and frequency, unless they were refreshed too recently
+    /// to calculate an accurate usage, like when the alerts are evaluated right after a poll.
+    fn refresh_cpu(&mut self) {
+        let refreshed_recently = self
+            .last_cpu_refresh
+            .is_some_and(|last| last.elapsed() < MINIMUM_CPU_UPDATE_INTERVAL);
+        if refreshed_recently {
+            return;
+        }
+
         self.inner.refresh_specifics(
             RefreshKind::nothing()
                 .with_cpu(CpuRefreshKind::nothing().with_cpu_usage().with_frequency()),
         );
+        self.last_cpu_refresh = Some(Instant::now());
+    }
+
+    pub fn get_cpu_info(&mut self) -> Result<CpuInfo, OceloError> {
+        self.refresh_cpu();
 
         let cpus = self.inner.cpus();
         let name = cpus
@@ -93,70 +467,152 @@ impl SystemInfoPoller {
         };
         let usage = self.inner.global_cpu_usage();
 
-        CpuInfo {
+        self.temperatures.refresh();
+        let scaling = cpufreq::core_scaling(0).unwrap_or_default();
+
+        Ok(CpuInfo {
             name,
             frequency: average_frequency,
             core_count,
-            temperature: None,
+            temperature: self.temperatures.package_temperature(),
             usage,
+            governor: scaling.governor,
+            min_frequency: scaling.min_frequency,
+            max_frequency: scaling.max_frequency,
+            boost: cpufreq::boost(),
+        })
+    }
+
+    /// Returns the layout of the processor, like its sockets, cores and caches.
+    /// It is read at the initialization, or at the first call.
+    pub fn get_cpu_topology(&mut self) -> Result<CpuTopology, OceloError> {
+        if self.topology.is_none() {
+            self.refresh_cpu();
+            self.topology = Some(topology::cpu_topology(&self.inner));
         }
+        Ok(self.topology.clone().unwrap_or_default())
     }
 
-    fn get_cpu_amd_memory_info(&mut self) -> CpuMemoryUpdate {
-        self.inner.refresh_specifics(
-            RefreshKind::nothing()
-                .with_cpu(CpuRefreshKind::nothing().with_cpu_usage().with_frequency())
-                .with_memory(MemoryRefreshKind::everything()),
-        );
+    /// Returns the processor and memory statistics, or `OceloError::NoProcessors`
+    /// if the system reports no processors.
+    pub fn get_cpu_amd_memory_info(&mut self) -> Result<CpuMemoryUpdate, OceloError> {
+        self.refresh_cpu();
+        self.inner
+            .refresh_specifics(RefreshKind::nothing().with_memory(MemoryRefreshKind::everything()));
 
+        let core_count = self.inner.cpus().len();
+        if core_count == 0 {
+            return Err(OceloError::NoProcessors);
+        }
         let sum_freq = self
             .inner
             .cpus()
             .iter()
             .map(|core| core.frequency())
             .sum::<u64>() as usize;
-        let avg_freq = sum_freq / self.inner.cpus().len();
+        let avg_freq = sum_freq / core_count;
 
-        let _ = Components::new_with_refreshed_list()
-            .iter()
-            .map(|f| print!("{}", f.temperature().unwrap()));
+        self.temperatures.refresh();
+        self.detect_throttling();
+        let package_temperature = self.temperatures.package_temperature();
+        let core_temperatures = self.temperatures.core_temperatures();
 
-        CpuMemoryUpdate {
+        Ok(CpuMemoryUpdate {
+            version: SchemaVersion::default(),
             usage: self.inner.global_cpu_usage(),
             frequency: avg_freq,
-            temperature: 0,
+            temperature: package_temperature.unwrap_or_default().round() as usize,
             cores: self
                 .inner
                 .cpus()
                 .iter()
-                .map(|cpu| CpuCore {
-                    frequency: cpu.frequency(),
-                    temperature: 0,
-                    usage: cpu.cpu_usage() as u64,
+                .enumerate()
+                .map(|(i, cpu)| {
+                    // logical processors sharing a physical core report the temperature of that core
+                    let temperature = if core_temperatures.is_empty() {
+                        package_temperature
+                    } else {
+                        Some(core_temperatures[i % core_temperatures.len()])
+                    };
+
+                    CpuCore {
+                        frequency: cpu.frequency(),
+                        temperature: temperature.unwrap_or_default().round() as u32,
+                        usage: cpu.cpu_usage() as u64,
+                        max_frequency: cpufreq::core_scaling(i)
+                            .and_then(|scaling| scaling.max_frequency),
+                    }
                 })
                 .collect(),
-            memory_stats: self.get_memory_info(),
-        }
+            memory_stats: self.get_memory_info()?,
+            throttling: self.throttle.is_throttling(),
+            throttle_count: self.throttle.count(),
+        })
     }
 
-    fn get_disk_info(&mut self) -> DiskInfo {
+    /// Returns the storage devices of the host machine, ordered by used space in descending order.
+    pub fn get_disk_info(&mut self) -> Result<DiskInfo, OceloError> {
         self.disks.refresh_specifics(
             true,
             DiskRefreshKind::nothing().with_io_usage().with_storage(),
         );
 
+        let block_devices = read_block_device_stats();
         let mut disks = self
             .disks
             .iter()
-            .map(Storage::from)
+            .map(|disk| (disk, Storage::from(disk)))
+            .filter(|(_, storage)| self.disk_filter.matches(storage))
+            .map(|(disk, mut storage)| {
+                let usage = disk.usage();
+                storage.read_rate = self.rates.rate(
+                    &format!("disk/{}/read", storage.mount),
+                    usage.total_read_bytes,
+                );
+                storage.write_rate = self.rates.rate(
+                    &format!("disk/{}/write", storage.mount),
+                    usage.total_written_bytes,
+                );
+                if let Some((total, used)) = inode_usage(&storage.mount) {
+                    storage.inodes_total = total;
+                    storage.inodes_used = used;
+                }
+                if let Some(stats) =
+                    block_device(&storage.name).and_then(|device| block_devices.get(&device))
+                {
+                    storage.iops = self.rates.rate(
+                        &format!("disk/{}/operations", storage.mount),
+                        stats.operations,
+                    );
+                    let busy = self
+                        .rates
+                        .rate(&format!("disk/{}/busy", storage.mount), stats.busy_ms);
+                    storage.latency = if storage.iops > 0.0 {
+                        busy / storage.iops
+                    } else {
+                        0.0
+                    };
+                    storage.queue_depth = self
+                        .rates
+                        .rate(&format!("disk/{}/queue", storage.mount), stats.queue_ms)
+                        / 1000.0;
+                }
+                storage
+            })
             .collect::<Vec<Storage>>();
         disks.sort_by_key(|d| d.used_space);
         disks.reverse();
 
-        DiskInfo { disks }
+        #[cfg(feature = "smart")]
+        let drives = self.smart.drives().to_vec();
+        #[cfg(not(feature = "smart"))]
+        let drives = Vec::new();
+
+        Ok(DiskInfo { disks, drives })
     }
 
-    fn get_memory_info(&mut self) -> MemoryInfo {
+    /// Returns the physical and swap memory statistics.
+    pub fn get_memory_info(&mut self) -> Result<MemoryInfo, OceloError> {
         self.inner.refresh_memory();
 
         let total = self.inner.total_memory();
@@ -166,28 +622,262 @@ impl SystemInfoPoller {
         let swap_used = self.inner.used_swap();
         let swap_available = self.inner.free_swap();
 
-        MemoryInfo {
+        let mut memory = MemoryInfo {
             total,
             used,
             available,
             swap_total,
             swap_used,
             swap_available,
+            free: self.inner.free_memory(),
+            ..MemoryInfo::default()
+        };
+        read_memory_breakdown(&mut memory);
+        if let Some((swapped_in, swapped_out)) = swapped_bytes() {
+            memory.swap_in_rate = self.rates.rate("swap/in", swapped_in);
+            memory.swap_out_rate = self.rates.rate("swap/out", swapped_out);
         }
+        Ok(memory)
     }
 
-    fn get_network_info(&mut self) -> NetworkInfo {
+    /// Returns the aggregated and the per-interface network statistics with the state of their links,
+    /// the latency of the ping targets and the public address.
+    /// The costlier details are only collected by `get_network_details()`.
+    pub fn get_network_info(&mut self) -> Result<NetworkInfo, OceloError> {
         self.networks.refresh(true);
-        NetworkInfo::from(&self.networks)
+        let mut network_info = NetworkInfo::from(&self.networks);
+
+        for interface in network_info.interface_details.iter_mut() {
+            interface.received_rate = self.rates.rate(
+                &format!("network/{}/received", interface.name),
+                interface.total_received,
+            );
+            interface.transmitted_rate = self.rates.rate(
+                &format!("network/{}/transmitted", interface.name),
+                interface.total_transmitted,
+            );
+            interface.link = link_info(&interface.name);
+            #[cfg(feature = "wifi")]
+            {
+                interface.wireless = wireless_link(&interface.name);
+            }
+        }
+        network_info.received_rate = network_info
+            .interface_details
+            .iter()
+            .map(|interface| interface.received_rate)
+            .sum();
+        network_info.transmitted_rate = network_info
+            .interface_details
+            .iter()
+            .map(|interface| interface.transmitted_rate)
+            .sum();
+        network_info.latency = self.latency.as_ref().map(LatencyProber::probes);
+        network_info.public_ip = self.public_ip.as_ref().map(PublicIpLookup::public_ip);
+
+        Ok(network_info)
     }
 
-    /// Returns the current snapshot of processes.
-    pub fn get_process_list(&self) -> Vec<ProcessInfo> {
-        self.inner
+    /// Returns the network statistics of `get_network_info()` with their details,
+    /// which are only collected on request, as the tables of the kernel are read for them.
+    pub fn get_network_details(&mut self) -> Result<NetworkInfo, OceloError> {
+        let mut network_info = self.get_network_info()?;
+        self.add_network_details(&mut network_info);
+        Ok(network_info)
+    }
+
+    /// Adds the TCP and UDP statistics, the states of the connections, the listening ports, the routes,
+    /// the neighbors and the processes with the most traffic to the `network_info` polled last.
+    pub fn add_network_details(&mut self, network_info: &mut NetworkInfo) {
+        network_info.listening_ports = listening_ports().ok();
+        network_info.routes = routes().ok();
+        network_info.neighbors = neighbors().ok();
+        network_info.protocols = protocol_stats(&mut self.rates).ok();
+        network_info.connection_states = connection_states().ok();
+        if let Some(traffic) = &mut self.traffic {
+            network_info.top_talkers = traffic.top_talkers(TOP_PROCESS_COUNT).ok();
+        }
+    }
+
+    /// Refreshes the processes and returns their current snapshot.
+    pub fn get_process_list(&mut self) -> Result<ProcessList, OceloError> {
+        let list = self.refresh_process_list()?;
+        if self.incremental_processes {
+            self.process_baseline = Some(list.clone());
+        }
+        Ok(list)
+    }
+
+    /// Refreshes the processes and returns their changes since the list or the changes returned last.
+    /// Returns `None` if the incremental updates are disabled, or no list was returned since enabling them.
+    pub fn get_process_delta(&mut self) -> Result<Option<ProcessDelta>, OceloError> {
+        let Some(previous) = self.process_baseline.take() else {
+            return Ok(None);
+        };
+        let list = self.refresh_process_list()?;
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let delta = list.delta(&previous, time);
+        self.process_baseline = Some(list);
+        Ok(Some(delta))
+    }
+
+    /// Refreshes the processes and converts them, calculating their rates since the previous refresh.
+    fn refresh_process_list(&mut self) -> Result<ProcessList, OceloError> {
+        let memory = self.get_memory_info()?;
+        self.inner.refresh_processes(ProcessesToUpdate::All, true);
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_process_refresh).as_secs_f64();
+        self.last_process_refresh = now;
+        let traffic = self
+            .traffic
+            .as_mut()
+            .and_then(|traffic| traffic.traffic().ok());
+
+        self.process_builder.forget_exited(self.inner.processes());
+        Ok(self
+            .inner
             .processes()
+            .values()
+            .map(|process| {
+                let mut info = self
+                    .process_builder
+                    .process_info(process)
+                    .with_elapsed_time(elapsed)
+                    .with_memory_info(&memory);
+                if let Some(traffic) = traffic {
+                    let process_traffic = traffic.get(&info.pid);
+                    info.net_received_rate =
+                        Some(process_traffic.map_or(0.0, |traffic| traffic.received_rate));
+                    info.net_transmitted_rate =
+                        Some(process_traffic.map_or(0.0, |traffic| traffic.transmitted_rate));
+                }
+                info
+            })
+            .collect())
+    }
+
+    /// Returns the TCP and UDP sockets of the system with the processes owning them.
+    pub fn get_connections(&mut self) -> Result<Vec<Connection>, OceloError> {
+        connections()
+    }
+
+    /// Returns the kernel tunables and the resource limits bounding the capacity of the system,
+    /// with the current use of the resources they bound.
+    pub fn get_limits(&mut self) -> Result<SystemLimits, OceloError> {
+        system_limits()
+    }
+
+    /// Returns the failed SSH logins of the last day, the ports open to the other hosts and the state of the firewall.
+    pub fn get_security(&mut self) -> Result<SecurityOverview, OceloError> {
+        security_overview()
+    }
+
+    /// Returns the hardware sensors: the temperatures, and the fan speeds and voltages on Linux.
+    pub fn get_sensors_info(&mut self) -> Result<SensorsInfo, OceloError> {
+        self.temperatures.refresh();
+        let mut sensors = self.temperatures.sensors();
+        sensors.extend(hwmon::fans_and_voltages());
+
+        Ok(SensorsInfo { sensors })
+    }
+
+    /// Returns the sessions of the users logged in to the system.
+    pub fn get_sessions(&mut self) -> Result<SessionsInfo, OceloError> {
+        Ok(SessionsInfo {
+            sessions: users::sessions(),
+        })
+    }
+
+    /// Returns the service units of systemd with their state and resource accounting.
+    pub fn get_services(&mut self) -> Result<ServicesInfo, OceloError> {
+        Ok(ServicesInfo {
+            services: services::services(),
+        })
+    }
+
+    /// Returns the running containers of Docker or Podman with their resource usage.
+    pub fn get_containers(&mut self) -> Result<ContainersInfo, OceloError> {
+        Ok(containers::containers(&mut self.rates))
+    }
+
+    /// Returns the recent messages of the system log, read from the first log which can be read.
+    /// The log is followed from the first call, so no messages are missed between the calls.
+    pub fn get_logs(&mut self) -> Result<LogsInfo, OceloError> {
+        if self.logs.is_none() {
+            self.logs = LogStream::detect();
+        }
+        match &mut self.logs {
+            Some(logs) => {
+                logs.refresh()?;
+                Ok(logs.info())
+            }
+            None => Ok(LogsInfo::default()),
+        }
+    }
+
+    /// Refreshes only the process `pid` and returns it with its details,
+    /// like the command line, the environment, the count of the open files, its threads and its memory map.
+    pub fn get_process_details(&mut self, pid: u32) -> Result<ProcessInfo, OceloError> {
+        let memory = self.get_memory_info()?;
+        let pid = Pid::from_u32(pid);
+        self.inner.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            ProcessRefreshKind::everything(),
+        );
+        let process = self
+            .inner
+            .process(pid)
+            .ok_or(OceloError::ProcessNotFound(pid.as_u32()))?;
+        let mut details = self
+            .process_builder
+            .process_info(process)
+            .with_memory_info(&memory)
+            .with_details(process);
+        details.descriptors = open_files(pid.as_u32()).ok();
+        details.memory_map = memory_map(pid.as_u32()).ok();
+
+        // the threads are only listed on Linux, as the tasks of the process in /proc/<pid>/task
+        let Some(tids) = process
+            .tasks()
+            .map(|tasks| tasks.iter().copied().collect::<Vec<Pid>>())
+        else {
+            return Ok(details);
+        };
+        self.inner.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&tids),
+            false,
+            ProcessRefreshKind::nothing().with_cpu(),
+        );
+        let mut threads: Vec<ThreadInfo> = tids
             .iter()
-            .map(|(_pid, process)| ProcessInfo::from(process))
-            .collect()
+            .filter_map(|tid| self.inner.process(*tid))
+            .map(ThreadInfo::from)
+            .collect();
+        threads.sort_by_key(|thread| thread.tid);
+        details.thread_list = Some(threads);
+        Ok(details)
+    }
+
+    /// Returns the biggest CPU and memory consumers.
+    /// Only the CPU usage and the memory of the processes are refreshed, which is much cheaper than
+    /// `self.get_process_list()`.
+    pub fn get_top_processes(&mut self, count: usize) -> TopProcesses {
+        self.inner.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+
+        let processes = self
+            .inner
+            .processes()
+            .values()
+            .map(ProcessSummary::from)
+            .collect();
+        TopProcesses::new(processes, count)
     }
 
     fn get_system_info(&mut self) -> SystemInfo {
@@ -196,24 +886,84 @@ impl SystemInfoPoller {
         let kernel_version = System::kernel_long_version();
         let load_avg = System::load_average();
 
+        #[cfg(feature = "packages")]
+        let packages = self.packages.as_ref().and_then(PackageMonitor::summary);
+        #[cfg(not(feature = "packages"))]
+        let packages = None;
+
         SystemInfo {
             host_name,
             kernel_version,
+            distribution: System::long_os_version(),
             uptime,
+            boot_time: System::boot_time(),
             load_one_minute: load_avg.one,
             load_five_minutes: load_avg.five,
             load_fifteen_minutes: load_avg.fifteen,
+            file_descriptors: descriptor_usage().ok(),
+            entropy: entropy().ok(),
+            environment: self.environment.clone().map(|mut environment| {
+                if let Some(instance) = self
+                    .cloud_metadata
+                    .as_ref()
+                    .and_then(CloudMetadataLookup::instance)
+                {
+                    environment.cloud = Some(instance);
+                }
+                environment
+            }),
+            packages,
+            clock: clock_status().ok().map(|mut status| {
+                status.offset = self.clock.as_ref().and_then(ClockMonitor::offset);
+                status
+            }),
         }
     }
 
-    pub fn get_system_overview(&mut self) -> SystemOverviewInfo {
-        SystemOverviewInfo {
-            cpu: self.get_cpu_info(),
-            overview: self.get_system_info(),
-            memory: self.get_memory_info(),
-            disks: self.get_disk_info(),
-            network: self.get_network_info(),
+    /// Returns the process using the most of its limit of open files among the ones which can be inspected.
+    fn busiest_descriptor_process(&self) -> Option<ProcessDescriptors> {
+        let (process, open, limit) = self
+            .inner
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .filter_map(|process| {
+                let limit = process.open_files_limit().filter(|limit| *limit > 0)?;
+                Some((process, process.open_files()?, limit))
+            })
+            .max_by(|(_, a_open, a_limit), (_, b_open, b_limit)| {
+                (*a_open as f64 / *a_limit as f64).total_cmp(&(*b_open as f64 / *b_limit as f64))
+            })?;
+        Some(ProcessDescriptors {
+            pid: process.pid().as_u32(),
+            name: process.name().to_string_lossy().into_owned(),
+            open,
+            limit,
+        })
+    }
+
+    pub fn get_system_overview(&mut self) -> Result<SystemOverviewInfo, OceloError> {
+        let cpu = self.get_cpu_info()?;
+        let mut overview = self.get_system_info();
+        let memory = self.get_memory_info()?;
+        let disks = self.get_disk_info()?;
+        let network = self.get_network_info()?;
+        let top_processes = self.get_top_processes(TOP_PROCESS_COUNT);
+        // the processes were just refreshed for the top processes
+        if let Some(usage) = &mut overview.file_descriptors {
+            usage.busiest_process = self.busiest_descriptor_process();
         }
+
+        Ok(SystemOverviewInfo {
+            version: SchemaVersion::default(),
+            cpu,
+            overview,
+            memory,
+            disks,
+            network,
+            top_processes,
+            sessions: users::sessions(),
+        })
     }
 
     pub fn polling_context(&self) -> SystemInfoPollingContext {
@@ -223,4 +973,144 @@ impl SystemInfoPoller {
     pub fn set_polling_context(&mut self, new_ctx: SystemInfoPollingContext) {
         self.polling_context = new_ctx;
     }
+
+    /// Returns the time between two consecutive polls.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Changes the time between two consecutive polls.
+    /// The interval is clamped between `MIN_POLLING_INTERVAL` and `MAX_POLLING_INTERVAL`.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval.clamp(MIN_POLLING_INTERVAL, MAX_POLLING_INTERVAL);
+    }
+
+    /// Reads the values of the processes which are slow to read, like their swapped memory,
+    /// only for the displayed `columns` from now on. They are all read until it is called.
+    pub fn set_process_columns(&mut self, columns: Vec<ProcessColumn>) {
+        self.process_builder.set_columns(columns);
+    }
+}
+
+/// Fills the buffers, the page cache, the shared, the dirty and the slab memory from `/proc/meminfo`.
+/// The missing values are left unchanged.
+#[cfg(target_os = "linux")]
+fn read_memory_breakdown(memory: &mut MemoryInfo) {
+    let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") else {
+        return;
+    };
+
+    for line in meminfo.lines() {
+        // the lines look like `Buffers:          123456 kB`
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(kilobytes) = value
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let bytes = kilobytes * 1024;
+        match key {
+            "MemFree" => memory.free = bytes,
+            "Buffers" => memory.buffers = bytes,
+            "Cached" => memory.cached = bytes,
+            "Shmem" => memory.shared = bytes,
+            "Dirty" => memory.dirty = bytes,
+            "Slab" => memory.slab = bytes,
+            _ => {}
+        }
+    }
+}
+
+/// The breakdown of the memory is not known beside the free memory.
+#[cfg(not(target_os = "linux"))]
+fn read_memory_breakdown(_memory: &mut MemoryInfo) {}
+
+/// The cumulative counters of a block device since the boot.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+struct BlockDeviceStats {
+    /// Count of the completed reads and writes
+    operations: u64,
+    /// Milliseconds spent by the completed reads and writes, from their queueing to their completion
+    busy_ms: u64,
+    /// Milliseconds spent by the operations in the queue or in service, weighted by their count
+    queue_ms: u64,
+}
+
+/// Returns the counters of every block device by the kernel name of the device, like `sda1`,
+/// from `/proc/diskstats`.
+#[cfg(target_os = "linux")]
+fn read_block_device_stats() -> std::collections::HashMap<String, BlockDeviceStats> {
+    let Ok(diskstats) = std::fs::read_to_string("/proc/diskstats") else {
+        return Default::default();
+    };
+    diskstats
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let counter = |index: usize| fields.get(index)?.parse::<u64>().ok();
+            let stats = BlockDeviceStats {
+                operations: counter(3)? + counter(7)?,
+                busy_ms: counter(6)? + counter(10)?,
+                queue_ms: counter(13)?,
+            };
+            Some((fields[2].to_string(), stats))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_block_device_stats() -> std::collections::HashMap<String, BlockDeviceStats> {
+    Default::default()
+}
+
+/// Returns the kernel name of the block device at `path`, following the links like `/dev/mapper/root` to `dm-0`.
+fn block_device(path: &str) -> Option<String> {
+    let device = std::fs::canonicalize(path).ok()?;
+    Some(device.file_name()?.to_string_lossy().into_owned())
+}
+
+/// Returns the total and the used inodes of the file system mounted at `mount` with `statvfs`.
+#[cfg(unix)]
+fn inode_usage(mount: &str) -> Option<(u64, u64)> {
+    let path = std::ffi::CString::new(mount).ok()?;
+    let mut stat = unsafe { std::mem::zeroed::<libc::statvfs>() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let total = stat.f_files as u64;
+    Some((total, total.saturating_sub(stat.f_ffree as u64)))
+}
+
+#[cfg(not(unix))]
+fn inode_usage(_mount: &str) -> Option<(u64, u64)> {
+    None
+}
+
+/// Returns the total bytes swapped in and out since the boot,
+/// from the `pswpin` and `pswpout` page counters of `/proc/vmstat`.
+#[cfg(target_os = "linux")]
+fn swapped_bytes() -> Option<(u64, u64)> {
+    let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+    let counter = |name: &str| {
+        vmstat.lines().find_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            (key == name).then(|| value.trim().parse::<u64>().ok())?
+        })
+    };
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+
+    Some((
+        counter("pswpin")? * page_size,
+        counter("pswpout")? * page_size,
+    ))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn swapped_bytes() -> Option<(u64, u64)> {
+    None
 }
@@ -0,0 +1,215 @@
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+
+/// A kernel tunable bounding the capacity of the system, like `sysctl` shows it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tunable {
+    /// The name of the tunable, like `net.core.somaxconn`
+    pub name: String,
+    /// The value of the tunable, the fields of a multi-valued one separated by a space.
+    /// `None` if the kernel has no such tunable
+    pub value: Option<String>,
+    /// The current use of the resource bounded by the tunable, like the count of the allocated file handles
+    /// for `fs.file-max`, `None` if it is not measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<u64>,
+    /// What the tunable sets
+    pub description: String,
+}
+
+impl Tunable {
+    /// Returns the usage in percent of the value, `None` without a usage or a numeric value.
+    /// A range, like `net.ipv4.ip_local_port_range`, bounds the count of the values within it.
+    pub fn usage_percent(&self) -> Option<f64> {
+        let usage = self.usage?;
+        let bounds = self
+            .value
+            .as_deref()?
+            .split_whitespace()
+            .map(|bound| bound.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        let capacity = match bounds.as_slice() {
+            [value] => *value,
+            [first, last] => (last + 1).checked_sub(*first)?,
+            _ => return None,
+        };
+        (capacity > 0).then(|| usage as f64 / capacity as f64 * 100.0)
+    }
+}
+
+/// A resource limit of a process, like `ulimit -a` shows it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimit {
+    /// The name of the limit, like `Max open files`
+    pub name: String,
+    /// The enforced limit, `None` if unlimited
+    pub soft: Option<u64>,
+    /// The most the soft limit can be raised to without privileges, `None` if unlimited
+    pub hard: Option<u64>,
+    /// The unit of the limits, like `files` or `bytes`, empty for the counts without a unit
+    pub unit: String,
+    /// The current use of the resource by the process, `None` if it is not measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<u64>,
+}
+
+/// The settings bounding the capacity of the system: the kernel tunables and the resource limits.
+/// The resource limits are the ones of the monitoring process, inherited from the session or the service
+/// which started it, so they are the limits of the other processes started the same way.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemLimits {
+    pub tunables: Vec<Tunable>,
+    pub resource_limits: Vec<ResourceLimit>,
+}
+
+/// Reads the kernel tunables from `/proc/sys` and the resource limits from `/proc/self/limits`,
+/// with the current use of the resources they bound. They are only read on Linux.
+#[cfg(target_os = "linux")]
+pub fn system_limits() -> Result<SystemLimits, OceloError> {
+    linux::system_limits()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn system_limits() -> Result<SystemLimits, OceloError> {
+    Err(OceloError::Unsupported(
+        "reading the limits of the system is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{ResourceLimit, SystemLimits, Tunable};
+    use crate::connections::connection_states;
+    use crate::open_files::descriptor_usage;
+    use crate::OceloError;
+    use std::fs;
+    use std::path::Path;
+
+    /// The tunables which are read, with their descriptions.
+    const TUNABLES: [(&str, &str); 13] = [
+        ("fs.file-max", "file handles of the system"),
+        ("fs.nr_open", "highest limit of open files of a process"),
+        ("kernel.pid_max", "IDs of the processes and the threads"),
+        ("kernel.threads-max", "threads of the system"),
+        ("vm.max_map_count", "memory maps of a process"),
+        (
+            "vm.swappiness",
+            "swapping over dropping the page cache, 0-200",
+        ),
+        (
+            "vm.overcommit_memory",
+            "0: heuristic, 1: always, 2: never overcommit",
+        ),
+        ("net.core.somaxconn", "accept queue of a listening socket"),
+        (
+            "net.ipv4.tcp_max_syn_backlog",
+            "half-open connections of a socket",
+        ),
+        (
+            "net.core.netdev_max_backlog",
+            "received packets queued per core",
+        ),
+        (
+            "net.ipv4.ip_local_port_range",
+            "ports of the outgoing connections",
+        ),
+        ("fs.inotify.max_user_watches", "watched files of a user"),
+        (
+            "fs.inotify.max_user_instances",
+            "inotify instances of a user",
+        ),
+    ];
+
+    /// The resource limits which are read, by their names in `/proc/<pid>/limits`.
+    const RESOURCE_LIMITS: [&str; 6] = [
+        "Max open files",
+        "Max processes",
+        "Max locked memory",
+        "Max stack size",
+        "Max core file size",
+        "Max file size",
+    ];
+
+    pub fn system_limits() -> Result<SystemLimits, OceloError> {
+        let tunables = TUNABLES
+            .iter()
+            .map(|(name, description)| Tunable {
+                name: name.to_string(),
+                value: read_tunable(name),
+                usage: usage_of(name),
+                description: description.to_string(),
+            })
+            .collect::<Vec<Tunable>>();
+        if tunables.iter().all(|tunable| tunable.value.is_none()) {
+            return Err(OceloError::Io(
+                "failed to read the kernel tunables of /proc/sys".to_string(),
+            ));
+        }
+
+        let limits = fs::read_to_string("/proc/self/limits").unwrap_or_default();
+        let resource_limits = RESOURCE_LIMITS
+            .iter()
+            .filter_map(|name| {
+                let mut limit = parse_limit(&limits, name)?;
+                if *name == "Max open files" {
+                    limit.usage = fs::read_dir("/proc/self/fd")
+                        .ok()
+                        .map(|descriptors| descriptors.count() as u64);
+                }
+                Some(limit)
+            })
+            .collect();
+
+        Ok(SystemLimits {
+            tunables,
+            resource_limits,
+        })
+    }
+
+    /// Reads the tunable `name`, like `net.core.somaxconn` from `/proc/sys/net/core/somaxconn`.
+    fn read_tunable(name: &str) -> Option<String> {
+        let path = Path::new("/proc/sys").join(name.replace('.', "/"));
+        let value = fs::read_to_string(path).ok()?;
+        // the fields of the multi-valued tunables are separated by tabs
+        Some(value.split_whitespace().collect::<Vec<&str>>().join(" "))
+    }
+
+    /// Measures the current use of the resource bounded by the tunable `name`, if it is measured.
+    fn usage_of(name: &str) -> Option<u64> {
+        match name {
+            "fs.file-max" => descriptor_usage().ok().map(|usage| usage.allocated),
+            // every thread takes an ID, the fourth field of the load average counts the runnable
+            // and the existing threads, like `2/731`
+            "kernel.pid_max" | "kernel.threads-max" => fs::read_to_string("/proc/loadavg")
+                .ok()?
+                .split_whitespace()
+                .nth(3)?
+                .split_once('/')?
+                .1
+                .parse()
+                .ok(),
+            "net.ipv4.ip_local_port_range" => connection_states()
+                .ok()
+                .map(|states| states.ephemeral_in_use as u64),
+            _ => None,
+        }
+    }
+
+    /// Parses the limit `name` from the content of `/proc/<pid>/limits`, like
+    /// ```text
+    /// Limit                     Soft Limit           Hard Limit           Units
+    /// Max open files            1024                 524288               files
+    /// ```
+    fn parse_limit(limits: &str, name: &str) -> Option<ResourceLimit> {
+        let line = limits.lines().find(|line| line.starts_with(name))?;
+        let mut fields = line[name.len()..].split_whitespace();
+        let value = |field: Option<&str>| field?.parse::<u64>().ok();
+        Some(ResourceLimit {
+            name: name.to_string(),
+            soft: value(fields.next()),
+            hard: value(fields.next()),
+            unit: fields.next().unwrap_or_default().to_string(),
+            usage: None,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// The state of the link of a network interface, like reported by `ip link`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkInfo {
+    /// The operational state defined by RFC 2863, like `up`, `down` or `dormant`.
+    /// The loopback and the virtual interfaces often report `unknown` while they work.
+    pub operational_state: String,
+    /// The negotiated speed in Mbit/s, unknown while the link is down or for the virtual interfaces
+    pub speed: Option<u32>,
+    /// The negotiated duplex mode, `full` or `half`, unknown like the speed
+    pub duplex: Option<String>,
+    /// The largest packet the interface transmits in bytes
+    pub mtu: Option<u32>,
+    /// Count of the times the carrier was lost or found since the interface was created,
+    /// an increasing count is a sign of a flapping cable or a bad port
+    pub carrier_changes: Option<u64>,
+}
+
+impl LinkInfo {
+    /// Returns true if the interface can not pass packets, like when it is disabled or its cable is unplugged.
+    pub fn is_down(&self) -> bool {
+        matches!(
+            self.operational_state.as_str(),
+            "down" | "lowerlayerdown" | "notpresent"
+        )
+    }
+}
+
+/// Reads the link state of the network interface named `interface` from `/sys/class/net`.
+/// Returns `None` if the interface does not exist, and outside of Linux.
+#[cfg(target_os = "linux")]
+pub fn link_info(interface: &str) -> Option<LinkInfo> {
+    linux::link_info(interface)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn link_info(_interface: &str) -> Option<LinkInfo> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::LinkInfo;
+    use std::fs;
+    use std::path::Path;
+
+    const NET_DIRECTORY: &str = "/sys/class/net";
+
+    pub fn link_info(interface: &str) -> Option<LinkInfo> {
+        let path = Path::new(NET_DIRECTORY).join(interface);
+        if !path.is_dir() {
+            return None;
+        }
+        // reading the speed and the duplex fails with EINVAL while the link is down
+        let read = |file: &str| {
+            fs::read_to_string(path.join(file))
+                .ok()
+                .map(|content| content.trim().to_string())
+        };
+
+        Some(LinkInfo {
+            operational_state: read("operstate").unwrap_or_else(|| "unknown".to_string()),
+            // the drivers report -1 if the speed is unknown
+            speed: read("speed").and_then(|speed| speed.parse().ok()),
+            duplex: read("duplex").filter(|duplex| duplex != "unknown"),
+            mtu: read("mtu").and_then(|mtu| mtu.parse().ok()),
+            carrier_changes: read("carrier_changes").and_then(|changes| changes.parse().ok()),
+        })
+    }
+}
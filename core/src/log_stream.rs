@@ -0,0 +1,437 @@
+use crate::model::{LogEntry, LogPriority, LogsInfo};
+use crate::OceloError;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// The most messages kept by a `LogStream`, the older ones are dropped.
+pub const MAX_LOG_ENTRIES: usize = 1000;
+
+/// Count of the recent messages read from a log when it is opened.
+const INITIAL_ENTRIES: usize = 200;
+
+/// A log which the messages of the system are read from, like the journal of systemd.
+pub trait LogSource: Send {
+    /// The name of the log, like `journal`.
+    fn name(&self) -> &str;
+
+    /// Returns the messages logged since the previous call, from the oldest one.
+    /// The first call returns the most recent messages already in the log.
+    fn read(&mut self) -> Result<Vec<LogEntry>, OceloError>;
+}
+
+/// Returns the first log which can be read: the journal of systemd, then the syslog files,
+/// then the kernel ring buffer. The journal is only read on Linux with the `systemd` feature.
+pub fn default_source() -> Option<Box<dyn LogSource>> {
+    #[cfg(all(target_os = "linux", feature = "systemd"))]
+    if journal::JournalSource::available() {
+        return Some(Box::new(journal::JournalSource::default()));
+    }
+    if let Some(source) = FileSource::open_default() {
+        return Some(Box::new(source));
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(source) = kernel::KernelSource::open() {
+        return Some(Box::new(source));
+    }
+    None
+}
+
+/// Follows a log, keeping its most recent messages.
+pub struct LogStream {
+    source: Box<dyn LogSource>,
+    entries: VecDeque<LogEntry>,
+}
+
+impl LogStream {
+    /// Creates a new instance following `source`.
+    /// Nothing is read until the first call of `self.refresh()`.
+    pub fn new(source: Box<dyn LogSource>) -> Self {
+        LogStream {
+            source,
+            entries: VecDeque::with_capacity(MAX_LOG_ENTRIES),
+        }
+    }
+
+    /// Creates a new instance following the first log which can be read, see `default_source()`.
+    pub fn detect() -> Option<Self> {
+        default_source().map(Self::new)
+    }
+
+    /// Reads the messages logged since the previous refresh.
+    pub fn refresh(&mut self) -> Result<(), OceloError> {
+        let entries = self.source.read()?;
+        self.entries.extend(entries);
+        let excess = self.entries.len().saturating_sub(MAX_LOG_ENTRIES);
+        self.entries.drain(..excess);
+        Ok(())
+    }
+
+    /// Returns the kept messages with the name of the log.
+    pub fn info(&self) -> LogsInfo {
+        LogsInfo {
+            source: Some(self.source.name().to_string()),
+            entries: self.entries.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Reads a plain text log written by a syslog daemon, like `/var/log/syslog`.
+/// The files only record the priority of the messages if the daemon is configured so,
+/// so it is guessed from the words of the messages.
+pub struct FileSource {
+    path: PathBuf,
+    name: String,
+    /// The position in the file up to which the lines were read, `None` before the first read
+    offset: Option<u64>,
+}
+
+impl FileSource {
+    /// The syslog files of Debian and of Red Hat based distributions.
+    const DEFAULT_PATHS: [&'static str; 2] = ["/var/log/syslog", "/var/log/messages"];
+
+    /// The longest part of the end of the file which is read at the first time.
+    const INITIAL_BYTES: u64 = 256 * 1024;
+
+    /// Creates a new instance reading the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        FileSource {
+            name: path.display().to_string(),
+            path,
+            offset: None,
+        }
+    }
+
+    /// Opens the first syslog file which can be read.
+    pub fn open_default() -> Option<Self> {
+        Self::DEFAULT_PATHS
+            .iter()
+            .find(|path| File::open(path).is_ok())
+            .map(|path| Self::new(*path))
+    }
+
+    /// Creates the entry of a line like `2026-10-15T09:30:00.123456+02:00 host sshd[812]: message`.
+    /// The traditional timestamps like `Oct 15 09:30:00` have neither a year nor a time zone,
+    /// those lines are kept whole as the message.
+    fn parse_line(line: &str) -> LogEntry {
+        let priority = guess_priority(line);
+        let mut fields = line.splitn(3, ' ');
+        let parsed = match (fields.next(), fields.next(), fields.next()) {
+            (Some(timestamp), Some(_host), Some(rest)) => {
+                parse_rfc3339(timestamp).map(|timestamp| (timestamp, rest))
+            }
+            _ => None,
+        };
+        let Some((timestamp, rest)) = parsed else {
+            return LogEntry {
+                timestamp: None,
+                priority,
+                source: String::new(),
+                message: line.to_string(),
+            };
+        };
+
+        // the tag is the program name, usually followed by its pid in brackets
+        let (source, message) = match rest.split_once(": ") {
+            Some((tag, message)) if !tag.contains(' ') => {
+                (tag.split('[').next().unwrap_or(tag), message)
+            }
+            _ => ("", rest),
+        };
+        LogEntry {
+            timestamp: Some(timestamp),
+            priority,
+            source: source.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl LogSource for FileSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn read(&mut self) -> Result<Vec<LogEntry>, OceloError> {
+        let mut file = File::open(&self.path).map_err(|error| OceloError::Io(error.to_string()))?;
+        let length = file
+            .metadata()
+            .map_err(|error| OceloError::Io(error.to_string()))?
+            .len();
+        let first_read = self.offset.is_none();
+        let start = match self.offset {
+            // the file was rotated, the new one is read from its beginning
+            Some(offset) if offset > length => 0,
+            Some(offset) => offset,
+            None => length.saturating_sub(Self::INITIAL_BYTES),
+        };
+        file.seek(SeekFrom::Start(start))
+            .map_err(|error| OceloError::Io(error.to_string()))?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)
+            .map_err(|error| OceloError::Io(error.to_string()))?;
+
+        // a line still being written is read at the next time
+        let complete = content
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |index| index + 1);
+        self.offset = Some(start + complete as u64);
+        let content = String::from_utf8_lossy(&content[..complete]);
+        let mut lines = content.lines().collect::<Vec<&str>>();
+        if first_read && start > 0 && !lines.is_empty() {
+            // the first line may have been cut in half by starting inside the file
+            lines.remove(0);
+        }
+        let skipped = match first_read {
+            true => lines.len().saturating_sub(INITIAL_ENTRIES),
+            false => 0,
+        };
+
+        Ok(lines
+            .into_iter()
+            .skip(skipped)
+            .filter(|line| !line.is_empty())
+            .map(Self::parse_line)
+            .collect())
+    }
+}
+
+/// Guesses the priority of a message from its words, as the plain text logs do not record it.
+fn guess_priority(message: &str) -> LogPriority {
+    let message = message.to_lowercase();
+    if ["panic", "critical", "fatal"]
+        .iter()
+        .any(|word| message.contains(word))
+    {
+        LogPriority::Critical
+    } else if ["error", "fail"].iter().any(|word| message.contains(word)) {
+        LogPriority::Error
+    } else if message.contains("warn") {
+        LogPriority::Warning
+    } else {
+        LogPriority::Info
+    }
+}
+
+/// Returns the seconds since the Unix epoch of a timestamp like `2026-10-15T09:30:00.123456+02:00`.
+pub(crate) fn parse_rfc3339(timestamp: &str) -> Option<u64> {
+    let (date, time) = timestamp.split_once('T')?;
+    let mut date = date.split('-').map(|field| field.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    // the offset of the time zone follows the time, `Z` stands for UTC
+    let (time, offset) = match time.find(['+', '-', 'Z']) {
+        Some(index) => time.split_at(index),
+        None => return None,
+    };
+    let offset_seconds = match offset.split_once(':') {
+        Some((hours, minutes)) => {
+            let seconds =
+                hours[1..].parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+            if hours.starts_with('-') {
+                -seconds
+            } else {
+                seconds
+            }
+        }
+        None if offset == "Z" => 0,
+        None => return None,
+    };
+    let mut time = time
+        .split('.')
+        .next()?
+        .split(':')
+        .map(|field| field.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    // converts the civil date to the days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset_seconds;
+    u64::try_from(seconds).ok()
+}
+
+#[cfg(all(target_os = "linux", feature = "systemd"))]
+mod journal {
+    use super::{LogSource, INITIAL_ENTRIES};
+    use crate::model::{LogEntry, LogPriority};
+    use crate::OceloError;
+    use serde_json::Value;
+    use std::process::Command;
+
+    /// The directories of the persistent and of the volatile journal.
+    const JOURNAL_DIRECTORIES: [&str; 2] = ["/var/log/journal", "/run/log/journal"];
+
+    /// Reads the journal of systemd with `journalctl`.
+    #[derive(Default)]
+    pub struct JournalSource {
+        /// The position after the last read message, `None` before the first read
+        cursor: Option<String>,
+    }
+
+    impl JournalSource {
+        /// Returns true if the journal has any files, otherwise `journalctl` would print no messages.
+        pub fn available() -> bool {
+            JOURNAL_DIRECTORIES.iter().any(|directory| {
+                std::fs::read_dir(directory).is_ok_and(|mut entries| entries.next().is_some())
+            })
+        }
+
+        /// Creates the entry of a message printed as a JSON object by `journalctl --output=json`.
+        fn parse_entry(fields: &Value) -> LogEntry {
+            let text = |name: &str| match &fields[name] {
+                Value::String(value) => Some(value.clone()),
+                // the fields which are not valid UTF-8 are printed as arrays of bytes
+                Value::Array(bytes) => Some(
+                    String::from_utf8_lossy(
+                        &bytes
+                            .iter()
+                            .filter_map(|byte| byte.as_u64().map(|byte| byte as u8))
+                            .collect::<Vec<u8>>(),
+                    )
+                    .to_string(),
+                ),
+                _ => None,
+            };
+            let source = text("SYSLOG_IDENTIFIER")
+                .or_else(|| text("_COMM"))
+                .or_else(|| {
+                    (text("_TRANSPORT").as_deref() == Some("kernel")).then(|| "kernel".to_string())
+                })
+                .unwrap_or_default();
+
+            LogEntry {
+                // the time is printed in microseconds
+                timestamp: text("__REALTIME_TIMESTAMP")
+                    .and_then(|timestamp| timestamp.parse::<u64>().ok())
+                    .map(|microseconds| microseconds / 1_000_000),
+                priority: text("PRIORITY")
+                    .and_then(|priority| priority.parse::<u32>().ok())
+                    .map(LogPriority::from_number)
+                    .unwrap_or_default(),
+                source,
+                message: text("MESSAGE").unwrap_or_default(),
+            }
+        }
+    }
+
+    impl LogSource for JournalSource {
+        fn name(&self) -> &str {
+            "journal"
+        }
+
+        fn read(&mut self) -> Result<Vec<LogEntry>, OceloError> {
+            let mut command = Command::new("journalctl");
+            command.args(["--output=json", "--no-pager", "--quiet"]);
+            match &self.cursor {
+                Some(cursor) => command.arg(format!("--after-cursor={}", cursor)),
+                None => command.arg(format!("--lines={}", INITIAL_ENTRIES)),
+            };
+            let output = command
+                .output()
+                .map_err(|error| OceloError::Io(format!("journalctl: {}", error)))?;
+            if !output.status.success() {
+                return Err(OceloError::Io(format!(
+                    "journalctl: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+
+            // every message is printed as a JSON object on its own line
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let messages = stdout
+                .lines()
+                .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                .collect::<Vec<Value>>();
+            if let Some(cursor) = messages.last().and_then(|last| last["__CURSOR"].as_str()) {
+                self.cursor = Some(cursor.to_string());
+            }
+            Ok(messages.iter().map(Self::parse_entry).collect())
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod kernel {
+    use super::LogSource;
+    use crate::model::{LogEntry, LogPriority};
+    use crate::OceloError;
+    use std::fs::File;
+    use std::io::{ErrorKind, Read};
+    use std::os::unix::fs::OpenOptionsExt;
+
+    /// The longest record of the kernel ring buffer, reading with a smaller buffer fails.
+    const RECORD_SIZE: usize = 8192;
+
+    /// Reads the kernel ring buffer from `/dev/kmsg`, like `dmesg`.
+    pub struct KernelSource {
+        file: File,
+        /// The time of the boot in seconds since the Unix epoch, the records are timed from it
+        boot_time: u64,
+    }
+
+    impl KernelSource {
+        /// Opens the ring buffer, `None` if reading it is not permitted.
+        pub fn open() -> Option<Self> {
+            let file = File::options()
+                .read(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open("/dev/kmsg")
+                .ok()?;
+            Some(KernelSource {
+                file,
+                boot_time: sysinfo::System::boot_time(),
+            })
+        }
+
+        /// Creates the entry of a record like `6,1234,5678901,-;message`,
+        /// which starts with the syslog priority, the sequence number and the microseconds since the boot.
+        fn parse_record(&self, record: &str) -> Option<LogEntry> {
+            // the lines after the message hold its properties, like the device which logged it
+            let record = record.lines().next()?;
+            let (prefix, message) = record.split_once(';')?;
+            let mut fields = prefix.split(',');
+            let priority = fields.next()?.parse::<u32>().ok()?;
+            let microseconds = fields.nth(1)?.parse::<u64>().ok()?;
+
+            Some(LogEntry {
+                timestamp: Some(self.boot_time + microseconds / 1_000_000),
+                priority: LogPriority::from_number(priority),
+                source: "kernel".to_string(),
+                message: message.to_string(),
+            })
+        }
+    }
+
+    impl LogSource for KernelSource {
+        fn name(&self) -> &str {
+            "kernel"
+        }
+
+        fn read(&mut self) -> Result<Vec<LogEntry>, OceloError> {
+            let mut entries = Vec::new();
+            let mut buffer = vec![0; RECORD_SIZE];
+            // every read returns a single record, until no more are left
+            loop {
+                match self.file.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(length) => entries
+                        .extend(self.parse_record(&String::from_utf8_lossy(&buffer[..length]))),
+                    Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                    // the records which were overwritten before being read are skipped
+                    Err(error) if error.raw_os_error() == Some(libc::EPIPE) => continue,
+                    Err(error) => return Err(OceloError::Io(error.to_string())),
+                }
+            }
+            Ok(entries)
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+
+/// How the memory of a process or of one of its regions is made up, in bytes.
+/// The resident memory counts the pages shared with other processes in full,
+/// while the proportional memory divides them by the count of the processes sharing them,
+/// so it is the real cost of the process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryBreakdown {
+    /// Resident set size, the memory in the RAM
+    pub rss: u64,
+    /// Proportional set size, the resident memory with the shared pages divided between their users
+    pub pss: u64,
+    /// Resident pages shared with other processes, unmodified since they were read
+    pub shared_clean: u64,
+    /// Resident pages shared with other processes, modified
+    pub shared_dirty: u64,
+    /// Resident pages used only by this process, unmodified since they were read
+    pub private_clean: u64,
+    /// Resident pages used only by this process, modified
+    pub private_dirty: u64,
+    /// Memory swapped out
+    pub swap: u64,
+}
+
+impl MemoryBreakdown {
+    /// Returns the resident memory shared with other processes.
+    pub fn shared(&self) -> u64 {
+        self.shared_clean + self.shared_dirty
+    }
+
+    /// Returns the resident memory used only by this process.
+    pub fn private(&self) -> u64 {
+        self.private_clean + self.private_dirty
+    }
+}
+
+/// A mapped region of the address space of a process, like a loaded library, the heap or the stack.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryRegion {
+    /// The first address of the region
+    pub start: u64,
+    /// The address after the last one of the region
+    pub end: u64,
+    /// The access of the region, like `r-xp` for private executable code
+    pub permissions: String,
+    /// The mapped file, or the name of a special region like `[heap]`, empty for the anonymous regions
+    pub path: String,
+    pub usage: MemoryBreakdown,
+}
+
+impl MemoryRegion {
+    /// Returns the size of the address range of the region in bytes, resident or not.
+    pub fn size(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// The memory regions of a process with the totals of the whole process, like the output of `pmap -X`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryMap {
+    pub total: MemoryBreakdown,
+    /// The regions with resident or swapped memory, from the biggest resident one
+    pub regions: Vec<MemoryRegion>,
+}
+
+/// Reads the memory regions of the process `pid` from `/proc/<pid>/smaps` and their totals from `smaps_rollup`,
+/// so the map is only read on Linux. Reading the map of a process of another user requires root privileges.
+#[cfg(target_os = "linux")]
+pub fn memory_map(pid: u32) -> Result<MemoryMap, OceloError> {
+    linux::memory_map(pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn memory_map(_pid: u32) -> Result<MemoryMap, OceloError> {
+    Err(OceloError::Unsupported(
+        "reading the memory map is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{MemoryBreakdown, MemoryMap, MemoryRegion};
+    use crate::OceloError;
+    use std::cmp::Reverse;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+
+    pub fn memory_map(pid: u32) -> Result<MemoryMap, OceloError> {
+        let process = Path::new("/proc").join(pid.to_string());
+        let read = |name: &str| {
+            fs::read_to_string(process.join(name)).map_err(|error| match error.kind() {
+                io::ErrorKind::NotFound => OceloError::ProcessNotFound(pid),
+                _ => OceloError::Io(format!(
+                    "failed to read the memory map of {}: {}",
+                    pid, error
+                )),
+            })
+        };
+
+        let mut regions = parse_regions(&read("smaps")?);
+        regions.retain(|region| region.usage.rss > 0 || region.usage.swap > 0);
+        regions.sort_by_key(|region| Reverse(region.usage.rss));
+        // the rollup is missing before Linux 4.14, the regions are summed up instead
+        let total = match read("smaps_rollup") {
+            Ok(rollup) => parse_regions(&rollup)
+                .into_iter()
+                .next()
+                .map(|region| region.usage)
+                .unwrap_or_default(),
+            Err(_) => regions
+                .iter()
+                .fold(MemoryBreakdown::default(), |total, region| {
+                    add(total, &region.usage)
+                }),
+        };
+
+        Ok(MemoryMap { total, regions })
+    }
+
+    /// Parses the regions of a smaps file: a header line like
+    /// `7f3a1c000000-7f3a1c021000 r-xp 00000000 fd:01 1234 /usr/lib/libc.so.6`
+    /// followed by the sizes of the region, like `Rss:  132 kB`.
+    fn parse_regions(smaps: &str) -> Vec<MemoryRegion> {
+        let mut regions: Vec<MemoryRegion> = Vec::new();
+        for line in smaps.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(first) = fields.next() else {
+                continue;
+            };
+            match first.strip_suffix(':') {
+                Some(name) => {
+                    let (Some(region), Some(Ok(kibibytes))) =
+                        (regions.last_mut(), fields.next().map(str::parse::<u64>))
+                    else {
+                        continue;
+                    };
+                    let bytes = kibibytes * 1024;
+                    let usage = &mut region.usage;
+                    match name {
+                        "Rss" => usage.rss = bytes,
+                        "Pss" => usage.pss = bytes,
+                        "Shared_Clean" => usage.shared_clean = bytes,
+                        "Shared_Dirty" => usage.shared_dirty = bytes,
+                        "Private_Clean" => usage.private_clean = bytes,
+                        "Private_Dirty" => usage.private_dirty = bytes,
+                        "Swap" => usage.swap = bytes,
+                        _ => {}
+                    }
+                }
+                None => {
+                    let (start, end) = first.split_once('-').unwrap_or((first, first));
+                    let permissions = fields.next().unwrap_or_default().to_string();
+                    // the offset, the device and the inode precede the path, which may contain spaces
+                    let path = fields.skip(3).collect::<Vec<&str>>().join(" ");
+                    regions.push(MemoryRegion {
+                        start: u64::from_str_radix(start, 16).unwrap_or_default(),
+                        end: u64::from_str_radix(end, 16).unwrap_or_default(),
+                        permissions,
+                        path,
+                        usage: MemoryBreakdown::default(),
+                    });
+                }
+            }
+        }
+        regions
+    }
+
+    fn add(total: MemoryBreakdown, usage: &MemoryBreakdown) -> MemoryBreakdown {
+        MemoryBreakdown {
+            rss: total.rss + usage.rss,
+            pss: total.pss + usage.pss,
+            shared_clean: total.shared_clean + usage.shared_clean,
+            shared_dirty: total.shared_dirty + usage.shared_dirty,
+            private_clean: total.private_clean + usage.private_clean,
+            private_dirty: total.private_dirty + usage.private_dirty,
+            swap: total.swap + usage.swap,
+        }
+    }
+}
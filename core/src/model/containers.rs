@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// A running container with the resources used by its processes.
+/// The rates are per second since the previous poll, they are zero at the first one.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Container {
+    /// The short form of the container id, its first 12 characters
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// The state of the container as reported by the runtime, like `Up 2 hours`
+    pub status: String,
+    /// The CPU usage in percent of a single core, like the CPU usage of the processes
+    pub cpu_usage: f32,
+    /// Memory used by the container in bytes, without the page cache
+    pub memory: u64,
+    /// The memory limit of the container in bytes, the physical memory if it is not limited
+    pub memory_limit: u64,
+    /// Bytes received per second over the network interfaces of the container
+    pub net_received_rate: f64,
+    /// Bytes transmitted per second over the network interfaces of the container
+    pub net_transmitted_rate: f64,
+    /// Bytes read per second from the block devices
+    pub read_rate: f64,
+    /// Bytes written per second to the block devices
+    pub write_rate: f64,
+}
+
+/// The running containers of Docker or Podman, like listed by `docker stats`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ContainersInfo {
+    /// The container runtime answering on the socket, `None` if no runtime was found
+    pub runtime: Option<String>,
+    pub containers: Vec<Container>,
+}
+
+impl ContainersInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
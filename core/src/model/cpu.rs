@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::MemoryInfo;
+use super::{MemoryInfo, SchemaVersion};
 
 ///  Detailed information collected about the main processor.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -20,6 +20,59 @@ pub struct CpuInfo {
     /// The reported temperature of the processor.
     /// On some platforms, this information is not available
     pub temperature: Option<f32>,
+
+    /// The governor choosing the frequency of the first core, like `powersave` or `performance`.
+    /// Only available where the frequency scaling is
+    #[serde(default)]
+    pub governor: Option<String>,
+
+    /// The lowest frequency in MHz the governor of the first core may choose
+    #[serde(default)]
+    pub min_frequency: Option<u64>,
+
+    /// The highest frequency in MHz the governor of the first core may choose
+    #[serde(default)]
+    pub max_frequency: Option<u64>,
+
+    /// Whether the processor may run above its base frequency, like with Turbo Boost
+    #[serde(default)]
+    pub boost: Option<bool>,
+}
+
+/// A cache of the processor, like the L1 data cache of a core or the L3 cache shared by the cores.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CpuCache {
+    /// 1 for L1, 2 for L2 and so on
+    pub level: u8,
+
+    /// What the cache holds: `Data`, `Instruction` or `Unified`
+    pub kind: String,
+
+    /// Size of a single instance of the cache in bytes
+    pub size: u64,
+}
+
+/// The static layout of the processor, which does not change while the system is running.
+/// The caches and the flags are only known on Linux.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CpuTopology {
+    /// The manufacturer reported by the processor, like GenuineIntel or AuthenticAMD
+    pub vendor: String,
+
+    /// Count of the physical processor packages
+    pub sockets: usize,
+
+    /// Count of the physical cores of all the sockets
+    pub physical_cores: usize,
+
+    /// Count of the logical processors, more than the physical cores with simultaneous multithreading
+    pub logical_cores: usize,
+
+    /// The caches of the first core, from the lowest level
+    pub caches: Vec<CpuCache>,
+
+    /// The notable instruction set extensions and features supported by the processor, like avx2 or aes
+    pub flags: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -27,15 +80,31 @@ pub struct CpuCore {
     pub usage: u64,
     pub frequency: u64,
     pub temperature: u32,
+
+    /// The highest frequency in MHz the governor of the core may choose.
+    /// A busy core running well below it is throttled
+    #[serde(default)]
+    pub max_frequency: Option<u64>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct CpuMemoryUpdate {
+    /// The schema version of the serialized form, checked when it is read
+    #[serde(default)]
+    pub version: SchemaVersion,
     pub usage: f32,
     pub frequency: usize,
     pub temperature: usize,
     pub cores: Vec<CpuCore>,
     pub memory_stats: MemoryInfo,
+
+    /// The processor is being slowed down by its temperature
+    #[serde(default)]
+    pub throttling: bool,
+
+    /// Count of the thermal throttling events since the start of the poller
+    #[serde(default)]
+    pub throttle_count: u64,
 }
 
 impl CpuMemoryUpdate {
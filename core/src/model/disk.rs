@@ -1,9 +1,42 @@
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use sysinfo::Disk;
 
+/// File systems which do not store anything on a device, like the kernel interfaces,
+/// the memory-backed ones and the read-only images of snap packages.
+const PSEUDO_FILE_SYSTEMS: [&str; 22] = [
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "overlay",
+    "proc",
+    "pstore",
+    "ramfs",
+    "securityfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+];
+
+/// Directories whose mounts are only kernel interfaces or belong to the containers and snap packages.
+const PSEUDO_MOUNTS: [&str; 6] = ["/dev", "/proc", "/run", "/snap", "/sys", "/var/lib/docker"];
+
 /// Information collected about a storage device.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Storage {
+    /// Name of the device, like /dev/sda1
+    pub name: String,
     pub total_space: u64,
     pub used_space: u64,
     pub available_space: u64,
@@ -11,11 +44,41 @@ pub struct Storage {
     pub mount: String,
     pub bytes_read: u64,
     pub bytes_written: u64,
+    /// Read bytes per second since the previous refresh
+    pub read_rate: f64,
+    /// Written bytes per second since the previous refresh
+    pub write_rate: f64,
+    /// Count of the inodes of the file system.
+    /// The inodes are only known on Unix, and some file systems like btrfs allocate them dynamically,
+    /// it is zero for them
+    #[serde(default)]
+    pub inodes_total: u64,
+    /// Count of the inodes holding a file or a directory
+    #[serde(default)]
+    pub inodes_used: u64,
+    /// Completed read and write operations per second since the previous refresh.
+    /// The operations are only counted on Linux, it is zero elsewhere
+    #[serde(default)]
+    pub iops: f64,
+    /// Average time of the completed operations in milliseconds, including the time spent in the queue
+    #[serde(default)]
+    pub latency: f64,
+    /// Average count of the operations waiting in the queue or being served
+    #[serde(default)]
+    pub queue_depth: f64,
+}
+
+impl Storage {
+    /// Returns the percentage of the used inodes, `None` if the file system does not report them.
+    pub fn inodes_percent(&self) -> Option<f64> {
+        (self.inodes_total > 0).then(|| self.inodes_used as f64 / self.inodes_total as f64 * 100.0)
+    }
 }
 
 impl From<&Disk> for Storage {
     fn from(disk: &Disk) -> Self {
         Storage {
+            name: disk.name().to_string_lossy().into_owned(),
             total_space: disk.total_space(),
             used_space: disk.total_space() - disk.available_space(),
             available_space: disk.available_space(),
@@ -23,12 +86,133 @@ impl From<&Disk> for Storage {
             mount: disk.mount_point().to_string_lossy().to_string(),
             bytes_read: disk.usage().read_bytes,
             bytes_written: disk.usage().written_bytes,
+            read_rate: 0.0,
+            write_rate: 0.0,
+            inodes_total: 0,
+            inodes_used: 0,
+            iops: 0.0,
+            latency: 0.0,
+            queue_depth: 0.0,
+        }
+    }
+}
+
+/// Selects the mounts reported by the disk information, by their file system type and mount point.
+/// A mount is reported if it matches the include lists and none of the exclude lists.
+/// An empty include list matches every mount.
+/// The mount points are matched by whole path components, so `/snap` excludes `/snap/core/1` but not `/snapshots`.
+///
+/// By default the pseudo-filesystems, like `tmpfs`, `overlay` and `squashfs`, are excluded
+/// with the mounts under `/dev`, `/proc`, `/run`, `/snap`, `/sys` and `/var/lib/docker`.
+///
+/// # Example:
+/// ```toml
+/// [disks]
+/// include_mounts = ["/", "/home"]
+/// exclude_file_systems = ["tmpfs", "squashfs"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiskFilter {
+    /// File system types which are reported, like `ext4`
+    pub include_file_systems: Vec<String>,
+    /// File system types which are not reported, like `tmpfs`
+    pub exclude_file_systems: Vec<String>,
+    /// Mount points which are reported together with the mounts under them
+    pub include_mounts: Vec<String>,
+    /// Mount points which are not reported together with the mounts under them
+    pub exclude_mounts: Vec<String>,
+}
+
+impl Default for DiskFilter {
+    fn default() -> Self {
+        DiskFilter {
+            include_file_systems: Vec::new(),
+            exclude_file_systems: PSEUDO_FILE_SYSTEMS.map(String::from).to_vec(),
+            include_mounts: Vec::new(),
+            exclude_mounts: PSEUDO_MOUNTS.map(String::from).to_vec(),
+        }
+    }
+}
+
+impl DiskFilter {
+    /// Creates a filter reporting every mount.
+    pub fn none() -> Self {
+        DiskFilter {
+            include_file_systems: Vec::new(),
+            exclude_file_systems: Vec::new(),
+            include_mounts: Vec::new(),
+            exclude_mounts: Vec::new(),
         }
     }
+
+    /// Returns true if `storage` is reported.
+    pub fn matches(&self, storage: &Storage) -> bool {
+        let file_system = |types: &Vec<String>| {
+            types
+                .iter()
+                .any(|file_system| file_system.eq_ignore_ascii_case(&storage.file_system))
+        };
+        let mount = |prefixes: &Vec<String>| {
+            prefixes
+                .iter()
+                .any(|prefix| Path::new(&storage.mount).starts_with(prefix))
+        };
+
+        (self.include_file_systems.is_empty() || file_system(&self.include_file_systems))
+            && (self.include_mounts.is_empty() || mount(&self.include_mounts))
+            && !file_system(&self.exclude_file_systems)
+            && !mount(&self.exclude_mounts)
+    }
+}
+
+/// The drive temperature in Celsius from which the drive is considered overheating.
+pub const DRIVE_TEMPERATURE_WARNING: f64 = 60.0;
+
+/// Health of a physical drive reported by its SMART self-monitoring.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DriveHealth {
+    /// Device of the drive, like /dev/sda
+    pub device: String,
+    /// Model name reported by the drive
+    pub model: Option<String>,
+    /// Result of the overall self-assessment of the drive, `None` if it is not known
+    pub passed: Option<bool>,
+    /// Current temperature in Celsius
+    pub temperature: Option<f64>,
+    pub power_on_hours: Option<u64>,
+    /// Count of the sectors remapped to the spare area after failing, only reported by ATA drives
+    pub reallocated_sectors: Option<u64>,
+}
+
+impl DriveHealth {
+    /// Returns true if the drive failed its self-assessment, remapped sectors or is overheating.
+    pub fn is_degraded(&self) -> bool {
+        self.passed == Some(false)
+            || self.reallocated_sectors.is_some_and(|sectors| sectors > 0)
+            || self
+                .temperature
+                .is_some_and(|temperature| temperature >= DRIVE_TEMPERATURE_WARNING)
+    }
 }
 
 /// Information collected about the mass storage on the host machine.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DiskInfo {
     pub disks: Vec<Storage>,
+    /// SMART health of the physical drives, only collected with the `smart` feature
+    #[serde(default)]
+    pub drives: Vec<DriveHealth>,
+}
+
+impl DiskInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
 }
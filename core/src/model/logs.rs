@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The severity of a log message, the syslog priorities from the most severe one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogPriority {
+    /// The system is unusable
+    Emergency,
+    /// Action must be taken immediately
+    Alert,
+    Critical,
+    Error,
+    Warning,
+    /// Normal but significant condition
+    Notice,
+    #[default]
+    Info,
+    Debug,
+}
+
+impl LogPriority {
+    /// Every priority, in the order of their numbers.
+    pub const ALL: [LogPriority; 8] = [
+        Self::Emergency,
+        Self::Alert,
+        Self::Critical,
+        Self::Error,
+        Self::Warning,
+        Self::Notice,
+        Self::Info,
+        Self::Debug,
+    ];
+
+    /// Returns the priority of the syslog priority `number`, where 0 is the emergency and 7 is the debug.
+    /// The facility in the higher bits is ignored.
+    pub fn from_number(number: u32) -> Self {
+        Self::ALL[(number & 7) as usize]
+    }
+}
+
+impl Display for LogPriority {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Emergency => "emerg",
+            Self::Alert => "alert",
+            Self::Critical => "crit",
+            Self::Error => "err",
+            Self::Warning => "warning",
+            Self::Notice => "notice",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A message of the system log.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Time of the message in seconds since the Unix epoch, `None` if the log does not record it in a known format
+    pub timestamp: Option<u64>,
+    pub priority: LogPriority,
+    /// The program or the unit which logged the message, empty if it is not known
+    pub source: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// Returns true if the source or the message contains `query`.
+    /// The comparison is case insensitive.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.source.to_lowercase().contains(&query) || self.message.to_lowercase().contains(&query)
+    }
+}
+
+/// The most recent messages of the system log, from the oldest one.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LogsInfo {
+    /// The log which the messages are read from, like `journal`, `None` if no log could be read
+    pub source: Option<String>,
+    pub entries: Vec<LogEntry>,
+}
+
+impl LogsInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
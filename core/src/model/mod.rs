@@ -1,16 +1,30 @@
 use serde::{Deserialize, Serialize};
 
+mod containers;
 mod cpu;
 mod disk;
+mod logs;
 mod network;
 mod process;
+mod schema;
+mod sensors;
+mod services;
+mod snapshot;
 mod system;
+mod users;
 
+pub use containers::*;
 pub use cpu::*;
 pub use disk::*;
+pub use logs::*;
 pub use network::*;
 pub use process::*;
+pub use schema::*;
+pub use sensors::*;
+pub use services::*;
+pub use snapshot::*;
 pub use system::*;
+pub use users::*;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 /// Stores memory-related statistics.
@@ -25,16 +39,90 @@ pub struct MemoryInfo {
     pub swap_total: u64,
     pub swap_used: u64,
     pub swap_available: u64,
+    /// Memory not used for anything in bytes, unlike `self.available` it excludes the reclaimable caches
+    #[serde(default)]
+    pub free: u64,
+    /// Memory of the block device buffers in bytes.
+    /// The breakdown of the memory is only known on Linux, it is zero elsewhere
+    #[serde(default)]
+    pub buffers: u64,
+    /// Memory of the page cache in bytes, including the shared memory
+    #[serde(default)]
+    pub cached: u64,
+    /// Memory shared between processes and used by tmpfs in bytes, part of `self.cached`
+    #[serde(default)]
+    pub shared: u64,
+    /// Memory of the modified pages waiting to be written to the disk in bytes
+    #[serde(default)]
+    pub dirty: u64,
+    /// Memory of the kernel data structures in bytes
+    #[serde(default)]
+    pub slab: u64,
+    /// Bytes read back from the swap per second since the previous refresh, like the `si` column of vmstat.
+    /// Only known on Linux, it is zero elsewhere
+    #[serde(default)]
+    pub swap_in_rate: f64,
+    /// Bytes written out to the swap per second since the previous refresh, like the `so` column of vmstat
+    #[serde(default)]
+    pub swap_out_rate: f64,
+}
+
+/// Returns `part` in percent of `whole`, or zero if `whole` is zero, like the usage of a system without swap.
+/// The division is done in floating point, so a part smaller than the whole is not truncated to zero.
+///
+/// # Example:
+/// ```
+/// use core::model::percentage;
+///
+/// assert_eq!(percentage(512, 2048), 25.0);
+/// assert_eq!(percentage(512, 0), 0.0);
+/// ```
+pub fn percentage(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64 * 100.0
+    }
+}
+
+impl MemoryInfo {
+    /// Returns the used physical memory in percent of the total.
+    pub fn usage(&self) -> f64 {
+        percentage(self.used, self.total)
+    }
+
+    /// Returns the used swap in percent of the total swap, zero without swap.
+    pub fn swap_usage(&self) -> f64 {
+        percentage(self.swap_used, self.swap_total)
+    }
+
+    /// Returns the memory used by the applications in bytes:
+    /// the total memory without the free memory, the buffers, the page cache and the slab.
+    pub fn applications(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+            .saturating_sub(self.slab)
+    }
 }
 
 /// Collection of system information to be displayed in the Overview component.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SystemOverviewInfo {
+    /// The schema version of the serialized form, checked when it is read
+    #[serde(default)]
+    pub version: SchemaVersion,
     pub cpu: CpuInfo,
     pub overview: SystemInfo,
     pub memory: MemoryInfo,
     pub disks: DiskInfo,
     pub network: NetworkInfo,
+    /// The biggest CPU and memory consumers
+    pub top_processes: TopProcesses,
+    /// The users logged in to the system
+    #[serde(default)]
+    pub sessions: Vec<Session>,
 }
 
 impl SystemOverviewInfo {
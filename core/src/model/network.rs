@@ -1,5 +1,11 @@
+use crate::connections::{Connection, ConnectionStates};
+use crate::latency::LatencyProbe;
+use crate::link::LinkInfo;
+use crate::protocols::ProtocolStats;
+use crate::public_ip::PublicIp;
+use crate::routing::{Neighbor, Route};
 use serde::{Deserialize, Serialize};
-use sysinfo::Networks;
+use sysinfo::{NetworkData, Networks};
 
 /// Statistics collected about the network interfaces from the host machine.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -18,6 +24,37 @@ pub struct NetworkInfo {
     pub total_errors_on_received: u64,
     /// Sum of errors on transmitting data
     pub total_errors_on_transmitted: u64,
+    /// Sum of received bytes per second since the previous refresh
+    pub received_rate: f64,
+    /// Sum of transmitted bytes per second since the previous refresh
+    pub transmitted_rate: f64,
+    /// Statistics of the individual network interfaces, ordered by name
+    pub interface_details: Vec<NetworkInterface>,
+    /// The processes with the highest network throughput, the busiest first.
+    /// `None` while the network traffic of the processes is not accounted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_talkers: Option<Vec<ProcessTraffic>>,
+    /// The listening TCP ports and the unconnected UDP ports, `None` where they can not be listed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listening_ports: Option<Vec<Connection>>,
+    /// The routing table, the default routes first, `None` where it can not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routes: Option<Vec<Route>>,
+    /// The hosts of the directly attached networks from the ARP cache, `None` where it can not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neighbors: Option<Vec<Neighbor>>,
+    /// The latest probes of the ping targets, `None` without targets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<Vec<LatencyProbe>>,
+    /// The address of the host as seen from the internet, `None` unless its lookup is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_ip: Option<PublicIp>,
+    /// Rates of the TCP and UDP counters of the system, `None` where they can not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocols: Option<ProtocolStats>,
+    /// Counts of the TCP sockets by their state, `None` where they can not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_states: Option<ConnectionStates>,
 }
 
 impl From<&Networks> for NetworkInfo {
@@ -44,6 +81,12 @@ impl From<&Networks> for NetworkInfo {
             .map(|n| n.1.total_errors_on_transmitted())
             .sum::<u64>();
 
+        let mut interface_details = networks
+            .iter()
+            .map(NetworkInterface::from)
+            .collect::<Vec<NetworkInterface>>();
+        interface_details.sort_by(|a, b| a.name.cmp(&b.name));
+
         NetworkInfo {
             interfaces: networks.len(),
             total_received,
@@ -52,6 +95,154 @@ impl From<&Networks> for NetworkInfo {
             total_packets_transmitted,
             total_errors_on_received,
             total_errors_on_transmitted,
+            received_rate: 0.0,
+            transmitted_rate: 0.0,
+            interface_details,
+            top_talkers: None,
+            listening_ports: None,
+            routes: None,
+            neighbors: None,
+            latency: None,
+            public_ip: None,
+            protocols: None,
+            connection_states: None,
         }
     }
 }
+
+impl NetworkInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
+
+/// The estimated network throughput of a single process.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProcessTraffic {
+    pub pid: u32,
+    /// Name of the process
+    pub name: String,
+    /// Received bytes per second since the previous refresh
+    pub received_rate: f64,
+    /// Transmitted bytes per second since the previous refresh
+    pub transmitted_rate: f64,
+}
+
+/// Statistics collected about a single network interface.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NetworkInterface {
+    /// Name of the interface, like eth0
+    pub name: String,
+    /// Hardware address of the interface
+    pub mac_address: String,
+    /// IP addresses with their prefixes assigned to the interface
+    pub ip_addresses: Vec<String>,
+    /// Received data in bytes since the last refresh
+    pub received: u64,
+    /// Transmitted data in bytes since the last refresh
+    pub transmitted: u64,
+    /// Sum of received data in bytes
+    pub total_received: u64,
+    /// Sum of transmitted data in bytes
+    pub total_transmitted: u64,
+    /// Sum of received packets
+    pub total_packets_received: u64,
+    /// Sum of transmitted packets
+    pub total_packets_transmitted: u64,
+    /// Sum of errors on receiving data
+    pub total_errors_on_received: u64,
+    /// Sum of errors on transmitting data
+    pub total_errors_on_transmitted: u64,
+    /// Received bytes per second since the previous refresh
+    pub received_rate: f64,
+    /// Transmitted bytes per second since the previous refresh
+    pub transmitted_rate: f64,
+    /// State, speed and MTU of the link, `None` where they can not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkInfo>,
+    /// The connection of a wireless interface, only collected with the `wifi` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wireless: Option<WirelessLink>,
+}
+
+impl NetworkInterface {
+    /// Returns true if the link of the interface is known to be down.
+    pub fn is_down(&self) -> bool {
+        self.link.as_ref().is_some_and(LinkInfo::is_down)
+    }
+}
+
+impl From<(&String, &NetworkData)> for NetworkInterface {
+    fn from(value: (&String, &NetworkData)) -> Self {
+        let (name, data) = value;
+
+        NetworkInterface {
+            name: name.clone(),
+            mac_address: data.mac_address().to_string(),
+            ip_addresses: data
+                .ip_networks()
+                .iter()
+                .map(|network| network.to_string())
+                .collect(),
+            received: data.received(),
+            transmitted: data.transmitted(),
+            total_received: data.total_received(),
+            total_transmitted: data.total_transmitted(),
+            total_packets_received: data.total_packets_received(),
+            total_packets_transmitted: data.total_packets_transmitted(),
+            total_errors_on_received: data.total_errors_on_received(),
+            total_errors_on_transmitted: data.total_errors_on_transmitted(),
+            received_rate: 0.0,
+            transmitted_rate: 0.0,
+            link: None,
+            wireless: None,
+        }
+    }
+}
+
+/// The connection of a wireless interface to its access point.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WirelessLink {
+    /// Name of the network, `None` while the interface is not connected
+    pub ssid: Option<String>,
+    /// Strength of the received signal in dBm, from about -30 next to the access point to -90 at the edge of the range
+    pub signal: Option<i32>,
+    /// Transmit bitrate in Mbit/s
+    pub bitrate: Option<f64>,
+    /// Frequency of the channel in MHz
+    pub frequency: Option<u32>,
+}
+
+impl WirelessLink {
+    /// Returns true if the interface is connected to a network.
+    pub fn is_connected(&self) -> bool {
+        self.ssid.is_some()
+    }
+
+    /// Returns the signal strength in percent, see `signal_quality()`.
+    pub fn quality(&self) -> Option<u8> {
+        self.signal.map(signal_quality)
+    }
+
+    /// Returns the band of the channel, like `2.4 GHz`.
+    pub fn band(&self) -> Option<&'static str> {
+        match self.frequency? {
+            2400..=2500 => Some("2.4 GHz"),
+            5150..=5925 => Some("5 GHz"),
+            5926..=7125 => Some("6 GHz"),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a signal strength in dBm to percent like NetworkManager does:
+/// -100 dBm and below is 0%, -50 dBm and above is 100%.
+pub fn signal_quality(signal: i32) -> u8 {
+    ((signal + 100) * 2).clamp(0, 100) as u8
+}
@@ -1,10 +1,28 @@
-use serde::{Deserialize, Serialize};
-use sysinfo::{Process, Users};
+use serde::de::{Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, Process, ProcessStatus, Users};
+
+use super::{percentage, MemoryInfo, SchemaVersion};
+use crate::cgroup::{self, cgroup};
+use crate::controller::{affinity, priority};
+use crate::memory_map::MemoryMap;
+use crate::open_files::FileDescriptor;
 
 const NOT_FOUND: &str = "N/A";
-pub type ProcessList = Vec<ProcessInfo>;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The `oom_score_adj` exempting a process from the OOM killer.
+const OOM_SCORE_ADJ_MIN: i32 = -1000;
+
+/// How long the cached table of the users is used by `ProcessListBuilder` before it is read again
+pub const USERS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ProcessInfo {
     /// The ID of the process
     pub pid: u32,
@@ -19,6 +37,14 @@ pub struct ProcessInfo {
     /// Used virtual memory in bytes by the process
     pub virtual_memory: u64,
 
+    /// Memory of the process swapped out in bytes, only known on Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap: Option<u64>,
+
+    /// Share of the physical memory of the system used by the process in percent
+    #[serde(default)]
+    pub memory_share: f32,
+
     /// Current CPU usage percent by the process
     pub cpu_usage: f32,
 
@@ -34,12 +60,265 @@ pub struct ProcessInfo {
 
     /// The path where the process started from
     pub command: String,
+
+    /// Bytes read from the disk since the last refresh
+    pub read_bytes: u64,
+
+    /// Bytes written to the disk since the last refresh
+    pub written_bytes: u64,
+
+    /// Disk read rate in bytes per second since the last refresh
+    pub read_rate: f64,
+
+    /// Disk write rate in bytes per second since the last refresh
+    pub write_rate: f64,
+
+    /// Received network bytes per second since the last refresh, estimated from the TCP sockets of the process.
+    /// Unknown while the network traffic of the processes is not accounted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub net_received_rate: Option<f64>,
+
+    /// Transmitted network bytes per second since the last refresh, estimated like `net_received_rate`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub net_transmitted_rate: Option<f64>,
+
+    /// The nice value of the process, a lower value means a higher priority.
+    /// Unknown on the platforms without nice values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
+
+    /// The indexes of the cores which the process is allowed to run on.
+    /// Unknown on the platforms without pinning processes to cores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affinity: Option<Vec<usize>>,
+
+    /// The badness the kernel picks the process to kill by when the memory runs out, from 0 to 1000
+    /// or above, only known on Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oom_score: Option<u32>,
+
+    /// The adjustment of `oom_score` set for the process, from -1000 which exempts it from the OOM killer to 1000.
+    /// Only known on Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oom_score_adj: Option<i32>,
+
+    /// The cgroup v2 path of the process, like `/system.slice/sshd.service`.
+    /// Unknown outside of Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<String>,
+
+    /// The scheduling state of the process, like running or sleeping
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<ProcessState>,
+
+    /// The time the process started at, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<u64>,
+
+    /// Count of the threads of the process
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+
+    /// The ID of the process which started this one.
+    /// The optional fields below are only filled by fetching the details of a single process
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u32>,
+
+    /// The program and its arguments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_line: Option<Vec<String>>,
+
+    /// The environment variables in `NAME=value` form
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Vec<String>>,
+
+    /// The current working directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+
+    /// Count of the files opened by the process, unknown without the permission to inspect them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_files: Option<u32>,
+
+    /// The most files the process may open, its soft `RLIMIT_NOFILE`, unknown outside of Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_files_limit: Option<u32>,
+
+    /// The file descriptors opened by the process, including its sockets, only listed on Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descriptors: Option<Vec<FileDescriptor>>,
+
+    /// The threads of the process, only listed on Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_list: Option<Vec<ThreadInfo>>,
+
+    /// The memory regions of the process, only read on Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_map: Option<MemoryMap>,
 }
 
-impl From<&Process> for ProcessInfo {
-    fn from(proc: &Process) -> Self {
-        let users = Users::new_with_refreshed_list();
+/// The scheduling state of a process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessState {
+    Running,
+    Sleeping,
+    /// Waiting for the disk without being interruptible
+    DiskSleep,
+    Stopped,
+    /// Stopped by a debugger
+    Tracing,
+    /// Exited, but not yet waited for by its parent
+    Zombie,
+    Dead,
+    /// A kernel thread without work
+    Idle,
+    /// The states specific to a platform, like parked or waking
+    Other,
+}
+
+impl ProcessState {
+    /// Returns the letter of the state used by `ps` and `top`, like `R` for running.
+    pub fn letter(&self) -> char {
+        match self {
+            Self::Running => 'R',
+            Self::Sleeping => 'S',
+            Self::DiskSleep => 'D',
+            Self::Stopped => 'T',
+            Self::Tracing => 't',
+            Self::Zombie => 'Z',
+            Self::Dead => 'X',
+            Self::Idle => 'I',
+            Self::Other => '?',
+        }
+    }
+
+    /// Returns true if the process waits for something to happen, without being stuck on the disk.
+    pub fn is_sleeping(&self) -> bool {
+        matches!(self, Self::Sleeping | Self::Idle)
+    }
+}
+
+impl From<ProcessStatus> for ProcessState {
+    fn from(status: ProcessStatus) -> Self {
+        match status {
+            ProcessStatus::Run => Self::Running,
+            ProcessStatus::Sleep => Self::Sleeping,
+            ProcessStatus::UninterruptibleDiskSleep => Self::DiskSleep,
+            ProcessStatus::Stop => Self::Stopped,
+            ProcessStatus::Tracing => Self::Tracing,
+            ProcessStatus::Zombie => Self::Zombie,
+            ProcessStatus::Dead => Self::Dead,
+            ProcessStatus::Idle => Self::Idle,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl Display for ProcessState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running => write!(f, "running"),
+            Self::Sleeping => write!(f, "sleeping"),
+            Self::DiskSleep => write!(f, "disk sleep"),
+            Self::Stopped => write!(f, "stopped"),
+            Self::Tracing => write!(f, "tracing"),
+            Self::Zombie => write!(f, "zombie"),
+            Self::Dead => write!(f, "dead"),
+            Self::Idle => write!(f, "idle"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// A thread of a process.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ThreadInfo {
+    /// The ID of the thread
+    pub tid: u32,
+
+    /// Name of the thread, usually inherited from the process if it was not set
+    pub name: String,
+
+    /// The scheduling state of the thread, like running or sleeping
+    pub state: String,
+
+    /// Current CPU usage percent by the thread
+    pub cpu_usage: f32,
+}
+
+impl From<&Process> for ThreadInfo {
+    fn from(task: &Process) -> Self {
+        ThreadInfo {
+            tid: task.pid().as_u32(),
+            name: task.name().to_string_lossy().into_owned(),
+            state: task.status().to_string(),
+            cpu_usage: task.cpu_usage(),
+        }
+    }
+}
+
+impl ProcessInfo {
+    /// Calculates the disk rates from the bytes transferred during the `elapsed` seconds since the last refresh.
+    pub fn with_elapsed_time(mut self, elapsed: f64) -> Self {
+        if elapsed > 0.0 {
+            self.read_rate = self.read_bytes as f64 / elapsed;
+            self.write_rate = self.written_bytes as f64 / elapsed;
+        }
+        self
+    }
+
+    /// Calculates the share of the system memory used by the process from the total of `memory`.
+    pub fn with_memory_info(mut self, memory: &MemoryInfo) -> Self {
+        self.memory_share = percentage(self.memory, memory.total) as f32;
+        self
+    }
+
+    /// Fills the optional fields which are too expensive to collect for every process,
+    /// like the command line and the environment.
+    pub fn with_details(mut self, proc: &Process) -> Self {
+        self.swap = swapped_memory(self.pid);
+        self.nice = priority(self.pid).ok();
+        self.cgroup = cgroup(self.pid);
+        self.affinity = affinity(self.pid).ok();
+        self.oom_score = proc_value(self.pid, "oom_score");
+        self.oom_score_adj = proc_value(self.pid, "oom_score_adj");
+        self.parent = proc.parent().map(|pid| pid.as_u32());
+        self.command_line = Some(to_strings(proc.cmd()));
+        self.environment = Some(to_strings(proc.environ()));
+        self.cwd = proc.cwd().map(|path| path.to_string_lossy().into_owned());
+        self.open_files = proc.open_files();
+        self.open_files_limit = proc.open_files_limit();
+        self
+    }
+
+    /// Returns the average CPU usage percent over the lifetime of the process,
+    /// which is its CPU time divided by its running time.
+    pub fn average_cpu_usage(&self) -> f32 {
+        if self.running_time == 0 {
+            return 0.0;
+        }
+        // the CPU time is measured in milliseconds
+        self.cpu_time as f32 / (self.running_time as f32 * 1000.0) * 100.0
+    }
 
+    /// Returns true if the process is sleeping or idle, see `ProcessState::is_sleeping`.
+    /// The processes in an unknown state are not sleeping.
+    pub fn is_sleeping(&self) -> bool {
+        self.state.is_some_and(|state| state.is_sleeping())
+    }
+
+    /// Returns true if the name, the command or the username of the process contains `query`.
+    /// The comparison is case insensitive.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [&self.name, &self.command, &self.username]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// Creates the snapshot of `proc`, looking up the name of its owner in `users`.
+    fn new(proc: &Process, users: &Users) -> Self {
         ProcessInfo {
             pid: proc.pid().as_u32(),
             name: proc
@@ -49,6 +328,8 @@ impl From<&Process> for ProcessInfo {
                 .unwrap_or(NOT_FOUND.to_string()),
             memory: proc.memory(),
             virtual_memory: proc.virtual_memory(),
+            swap: None,
+            memory_share: 0.0,
             cpu_usage: proc.cpu_usage(),
             cpu_time: proc.accumulated_cpu_time(),
             username: proc.user_id().map_or(NOT_FOUND.to_string(), |uid| {
@@ -60,7 +341,620 @@ impl From<&Process> for ProcessInfo {
             command: proc.exe().map_or(NOT_FOUND.to_string(), |path| {
                 path.to_string_lossy().to_string()
             }),
+            read_bytes: proc.disk_usage().read_bytes,
+            written_bytes: proc.disk_usage().written_bytes,
+            read_rate: 0.0,
+            write_rate: 0.0,
+            net_received_rate: None,
+            net_transmitted_rate: None,
+            nice: None,
+            affinity: None,
+            oom_score: None,
+            oom_score_adj: None,
+            cgroup: None,
+            state: Some(ProcessState::from(proc.status())),
+            start_time: Some(proc.start_time()),
+            threads: proc.tasks().map(|tasks| tasks.len()),
+            parent: None,
+            command_line: None,
+            environment: None,
+            cwd: None,
+            open_files: None,
+            open_files_limit: None,
+            descriptors: None,
+            thread_list: None,
+            memory_map: None,
+        }
+    }
+}
+
+/// Builds the `ProcessInfo` of the processes, resolving their usernames from a cached table of the users.
+/// Reading the users for every process is expensive, so the table is only read again
+/// when it is older than `USERS_REFRESH_INTERVAL`.
+pub struct ProcessListBuilder {
+    users: Users,
+    /// The time of the last refresh of `self.users`, `None` if they were never read
+    users_refreshed: Option<Instant>,
+    /// The displayed columns, whose values are read even when they are slow to read,
+    /// `None` if every value is read
+    columns: Option<Vec<ProcessColumn>>,
+    /// The cgroups of the processes by their pid, read once per process as they rarely move to another one.
+    /// The start time of the process tells a reused pid apart
+    cgroups: HashMap<u32, (u64, Option<String>)>,
+}
+
+impl Default for ProcessListBuilder {
+    fn default() -> Self {
+        ProcessListBuilder {
+            users: Users::new(),
+            users_refreshed: None,
+            columns: None,
+            cgroups: HashMap::new(),
+        }
+    }
+}
+
+impl ProcessListBuilder {
+    /// Reads the values which are slow to read for every process, like the swapped memory,
+    /// only for the displayed `columns` from now on.
+    pub fn set_columns(&mut self, columns: Vec<ProcessColumn>) {
+        self.columns = Some(columns);
+    }
+
+    /// Forgets the cached values of the processes which are not among the running `processes` anymore.
+    pub fn forget_exited(&mut self, processes: &HashMap<Pid, Process>) {
+        self.cgroups
+            .retain(|pid, _| processes.contains_key(&Pid::from_u32(*pid)));
+    }
+
+    /// Returns whether the values of `column` are read for every process.
+    fn reads(&self, column: ProcessColumn) -> bool {
+        self.columns
+            .as_ref()
+            .is_none_or(|columns| columns.contains(&column))
+    }
+
+    /// Converts `proc` into a `ProcessInfo`, refreshing the table of the users first if it is stale.
+    pub fn process_info(&mut self, proc: &Process) -> ProcessInfo {
+        if self
+            .users_refreshed
+            .is_none_or(|refreshed| refreshed.elapsed() >= USERS_REFRESH_INTERVAL)
+        {
+            self.users.refresh();
+            self.users_refreshed = Some(Instant::now());
         }
+        let mut info = ProcessInfo::new(proc, &self.users);
+        let start_time = proc.start_time();
+        info.cgroup = match self.cgroups.get(&info.pid) {
+            Some((started, cgroup)) if *started == start_time => cgroup.clone(),
+            _ => {
+                let cgroup = cgroup(info.pid);
+                self.cgroups.insert(info.pid, (start_time, cgroup.clone()));
+                cgroup
+            }
+        };
+        if self.reads(ProcessColumn::Nice) {
+            info.nice = priority(info.pid).ok();
+        }
+        if self.reads(ProcessColumn::Swap) {
+            info.swap = swapped_memory(info.pid);
+        }
+        // the adjustment is only needed to leave the exempted processes out of the likely victims in the column
+        if self.reads(ProcessColumn::OomScore) {
+            info.oom_score = proc_value(info.pid, "oom_score");
+            info.oom_score_adj = proc_value(info.pid, "oom_score_adj");
+        }
+        info
+    }
+}
+
+/// Returns the memory of the process `pid` swapped out in bytes, from the `VmSwap` line of its status.
+/// The status is only read on Linux, elsewhere `None` is returned.
+#[cfg(target_os = "linux")]
+fn swapped_memory(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    // the size is given in kibibytes, like `VmSwap:     1234 kB`
+    let size = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmSwap:"))?;
+    let kibibytes = size.split_whitespace().next()?.parse::<u64>().ok()?;
+    Some(kibibytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn swapped_memory(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Returns the single value of the file `name` in the proc directory of the process `pid`, like its `oom_score`.
+/// The proc files are only read on Linux, elsewhere `None` is returned.
+#[cfg(target_os = "linux")]
+fn proc_value<T: std::str::FromStr>(pid: u32, name: &str) -> Option<T> {
+    std::fs::read_to_string(format!("/proc/{}/{}", pid, name))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn proc_value<T: std::str::FromStr>(_pid: u32, _name: &str) -> Option<T> {
+    None
+}
+
+/// Converts the platform strings into UTF-8, replacing the invalid sequences.
+fn to_strings(values: &[OsString]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| value.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// The most important values of a process, for displaying the top consumers.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProcessSummary {
+    pub pid: u32,
+    pub name: String,
+    /// Current CPU usage percent by the process
+    pub cpu_usage: f32,
+    /// Used physical memory in bytes by the process
+    pub memory: u64,
+}
+
+impl From<&Process> for ProcessSummary {
+    fn from(proc: &Process) -> Self {
+        ProcessSummary {
+            pid: proc.pid().as_u32(),
+            name: proc.name().to_string_lossy().into_owned(),
+            cpu_usage: proc.cpu_usage(),
+            memory: proc.memory(),
+        }
+    }
+}
+
+/// The processes using the most CPU and the most memory, in descending order.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TopProcesses {
+    pub by_cpu: Vec<ProcessSummary>,
+    pub by_memory: Vec<ProcessSummary>,
+}
+
+impl TopProcesses {
+    /// Selects the `count` biggest CPU and memory consumers from `processes`.
+    pub fn new(processes: Vec<ProcessSummary>, count: usize) -> Self {
+        let mut by_cpu = processes.clone();
+        by_cpu.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        by_cpu.truncate(count);
+
+        let mut by_memory = processes;
+        by_memory.sort_by_key(|process| Reverse(process.memory));
+        by_memory.truncate(count);
+
+        TopProcesses { by_cpu, by_memory }
+    }
+}
+
+/// The columns of a process list, by which the list can be sorted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessColumn {
+    #[default]
+    Pid,
+    Name,
+    Memory,
+    VirtualMemory,
+    CpuUsage,
+    CpuTime,
+    User,
+    RunningTime,
+    Command,
+    DiskRead,
+    DiskWrite,
+    NetReceive,
+    NetTransmit,
+    /// Count of the threads, not displayed by default
+    Threads,
+    /// The scheduling state, displayed by its letter
+    State,
+    /// The nice value, not displayed by default
+    Nice,
+    /// The time the process started at, not displayed by default
+    StartTime,
+    /// The CPU usage averaged over the lifetime of the process, not displayed by default
+    AverageCpuUsage,
+    /// The share of the system memory used by the process
+    MemoryShare,
+    /// The swapped out memory, not displayed by default
+    Swap,
+    /// The OOM score, not displayed by default
+    OomScore,
+}
+
+impl ProcessColumn {
+    /// Compares the value of this column of two processes.
+    fn compare(&self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        match self {
+            Self::Pid => a.pid.cmp(&b.pid),
+            Self::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            Self::Memory => a.memory.cmp(&b.memory),
+            Self::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+            Self::CpuUsage => a.cpu_usage.total_cmp(&b.cpu_usage),
+            Self::CpuTime => a.cpu_time.cmp(&b.cpu_time),
+            Self::User => a.username.cmp(&b.username),
+            Self::RunningTime => a.running_time.cmp(&b.running_time),
+            Self::Command => a.command.cmp(&b.command),
+            Self::DiskRead => a.read_rate.total_cmp(&b.read_rate),
+            Self::DiskWrite => a.write_rate.total_cmp(&b.write_rate),
+            Self::NetReceive => a
+                .net_received_rate
+                .unwrap_or_default()
+                .total_cmp(&b.net_received_rate.unwrap_or_default()),
+            Self::NetTransmit => a
+                .net_transmitted_rate
+                .unwrap_or_default()
+                .total_cmp(&b.net_transmitted_rate.unwrap_or_default()),
+            Self::Threads => a.threads.cmp(&b.threads),
+            Self::State => a.state.cmp(&b.state),
+            Self::Nice => a.nice.cmp(&b.nice),
+            Self::StartTime => a.start_time.cmp(&b.start_time),
+            Self::AverageCpuUsage => a.average_cpu_usage().total_cmp(&b.average_cpu_usage()),
+            Self::MemoryShare => a.memory_share.total_cmp(&b.memory_share),
+            Self::Swap => a.swap.cmp(&b.swap),
+            Self::OomScore => a.oom_score.cmp(&b.oom_score),
+        }
+    }
+
+    /// Compares the value of this column of two process groups.
+    /// The pid column compares the count of the processes, the columns without sums are equal.
+    fn compare_groups(&self, a: &ProcessGroup, b: &ProcessGroup) -> Ordering {
+        match self {
+            Self::Pid => a.count.cmp(&b.count),
+            Self::Name | Self::User => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            Self::Memory => a.memory.cmp(&b.memory),
+            Self::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+            Self::MemoryShare => a.memory_share.total_cmp(&b.memory_share),
+            Self::CpuUsage => a.cpu_usage.total_cmp(&b.cpu_usage),
+            // the groups have no lifetime, their total cost is their CPU time
+            Self::CpuTime | Self::AverageCpuUsage => a.cpu_time.cmp(&b.cpu_time),
+            Self::DiskRead => a.read_rate.total_cmp(&b.read_rate),
+            Self::DiskWrite => a.write_rate.total_cmp(&b.write_rate),
+            Self::RunningTime
+            | Self::Command
+            | Self::NetReceive
+            | Self::NetTransmit
+            | Self::Threads
+            | Self::State
+            | Self::Nice
+            | Self::StartTime
+            | Self::Swap
+            | Self::OomScore => Ordering::Equal,
+        }
+    }
+}
+
+/// The field which the processes are grouped by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessGrouping {
+    /// Groups the processes of the same user
+    User,
+    /// Groups the processes of the same executable
+    Application,
+    /// Groups the processes of the same systemd service or scope, like the processes of a container
+    Cgroup,
+}
+
+/// The summed resource usage of the processes sharing a user, an executable or a cgroup.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProcessGroup {
+    /// The username, the executable name or the cgroup unit shared by the processes
+    pub name: String,
+    /// Count of the processes in the group
+    pub count: usize,
+    pub memory: u64,
+    pub virtual_memory: u64,
+    pub memory_share: f32,
+    pub cpu_usage: f32,
+    pub cpu_time: u64,
+    pub read_rate: f64,
+    pub write_rate: f64,
+}
+
+impl ProcessGroup {
+    fn add(&mut self, process: &ProcessInfo) {
+        self.count += 1;
+        self.memory += process.memory;
+        self.virtual_memory += process.virtual_memory;
+        self.memory_share += process.memory_share;
+        self.cpu_usage += process.cpu_usage;
+        self.cpu_time += process.cpu_time;
+        self.read_rate += process.read_rate;
+        self.write_rate += process.write_rate;
+    }
+}
+
+/// Direction of sorting a process list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    /// Returns the opposite direction.
+    pub fn toggle(&self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+/// The processes which started or exited between two snapshots of the process list.
+/// A reused pid counts as an exited and a started process, as the processes are told apart by their start time.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessDiff {
+    /// The pids of the processes which are not in the previous snapshot
+    pub started: Vec<u32>,
+    /// The processes of the previous snapshot which are gone
+    pub exited: Vec<ProcessInfo>,
+}
+
+/// The changes of the process list between two snapshots, sent instead of the whole list by the incremental updates.
+/// Applying it with `ProcessList::apply` on the earlier snapshot gives the later one.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProcessDelta {
+    /// The processes which are new or have changed, besides their running time
+    pub changed: Vec<ProcessInfo>,
+    /// The pids of the processes which are gone
+    pub removed: Vec<u32>,
+    /// The time of the later snapshot in seconds since the Unix epoch,
+    /// the running time of the unchanged processes is derived from it
+    pub time: u64,
+}
+
+/// A snapshot of the running processes.
+/// It is serialized with its schema version, like `{"version": 1, "processes": [...]}`,
+/// the bare list of the unversioned forms is still read.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessList(Vec<ProcessInfo>);
+
+/// The serialized form of `ProcessList`, the version is written first so it is checked before the processes.
+#[derive(Serialize)]
+struct VersionedProcessList<'a> {
+    version: SchemaVersion,
+    processes: &'a [ProcessInfo],
+}
+
+impl Serialize for ProcessList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VersionedProcessList {
+            version: SchemaVersion::default(),
+            processes: &self.0,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProcessList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ProcessListVisitor)
+    }
+}
+
+/// Reads both the versioned and the unversioned forms of `ProcessList`.
+struct ProcessListVisitor;
+
+impl<'de> Visitor<'de> for ProcessListVisitor {
+    type Value = ProcessList;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a versioned process list or a list of processes")
+    }
+
+    /// Reads the bare list of the unversioned forms.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ProcessList, A::Error> {
+        let mut processes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(process) = seq.next_element()? {
+            processes.push(process);
+        }
+        Ok(ProcessList(processes))
+    }
+
+    /// Reads the versioned form, failing on an unsupported version before reading its processes.
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ProcessList, A::Error> {
+        let mut processes = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => {
+                    map.next_value::<SchemaVersion>()?;
+                }
+                "processes" => processes = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        processes
+            .map(ProcessList)
+            .ok_or_else(|| A::Error::missing_field("processes"))
+    }
+}
+
+impl ProcessList {
+    /// Returns the pids of the `count` processes with the highest OOM scores, from the highest,
+    /// which the kernel kills first when the memory runs out.
+    /// The processes exempted from the OOM killer and the ones with a zero or unknown score are left out.
+    pub fn likely_oom_victims(&self, count: usize) -> Vec<u32> {
+        let mut candidates: Vec<(u32, u32)> = self
+            .iter()
+            .filter(|process| process.oom_score_adj != Some(OOM_SCORE_ADJ_MIN))
+            .filter_map(|process| {
+                Some((process.pid, process.oom_score.filter(|score| *score > 0)?))
+            })
+            .collect();
+        candidates.sort_by_key(|(_, score)| Reverse(*score));
+        candidates
+            .into_iter()
+            .take(count)
+            .map(|(pid, _)| pid)
+            .collect()
+    }
+
+    /// Sorts the processes by the values of `column` in the given `order`.
+    /// Processes with equal values keep their original relative order.
+    pub fn sort(&mut self, column: ProcessColumn, order: SortOrder) {
+        self.0.sort_by(|a, b| match order {
+            SortOrder::Ascending => column.compare(a, b),
+            SortOrder::Descending => column.compare(b, a),
+        });
+    }
+
+    /// Sums the resource usage of the processes by `grouping`,
+    /// sorting the groups by the values of `column` in the given `order`.
+    pub fn group(
+        &self,
+        grouping: ProcessGrouping,
+        column: ProcessColumn,
+        order: SortOrder,
+    ) -> Vec<ProcessGroup> {
+        let mut groups: Vec<ProcessGroup> = Vec::new();
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for process in &self.0 {
+            let name = match grouping {
+                ProcessGrouping::User => process.username.as_str(),
+                ProcessGrouping::Application => process.name.as_str(),
+                ProcessGrouping::Cgroup => {
+                    process.cgroup.as_deref().map_or(NOT_FOUND, cgroup::unit)
+                }
+            };
+            let position = *positions.entry(name).or_insert_with(|| {
+                groups.push(ProcessGroup {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                groups.len() - 1
+            });
+            groups[position].add(process);
+        }
+
+        groups.sort_by(|a, b| match order {
+            SortOrder::Ascending => column.compare_groups(a, b),
+            SortOrder::Descending => column.compare_groups(b, a),
+        });
+        groups
+    }
+
+    /// Compares this snapshot to the `previous` one, returning the started and the exited processes.
+    pub fn diff(&self, previous: &ProcessList) -> ProcessDiff {
+        let identity = |process: &ProcessInfo| (process.pid, process.start_time);
+        let current: HashSet<(u32, Option<u64>)> = self.iter().map(identity).collect();
+        let earlier: HashSet<(u32, Option<u64>)> = previous.iter().map(identity).collect();
+        ProcessDiff {
+            started: self
+                .iter()
+                .filter(|process| !earlier.contains(&identity(process)))
+                .map(|process| process.pid)
+                .collect(),
+            exited: previous
+                .iter()
+                .filter(|process| !current.contains(&identity(process)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Compares this snapshot taken at `time` to the `previous` one, returning the changes between them.
+    /// The running time goes on for every process, so a process is only changed if anything else differs.
+    pub fn delta(&self, previous: &ProcessList, time: u64) -> ProcessDelta {
+        let earlier: HashMap<u32, &ProcessInfo> = previous
+            .iter()
+            .map(|process| (process.pid, process))
+            .collect();
+        let current: HashSet<u32> = self.iter().map(|process| process.pid).collect();
+        ProcessDelta {
+            changed: self
+                .iter()
+                .filter(|process| {
+                    earlier.get(&process.pid).is_none_or(|earlier| {
+                        let process = ProcessInfo {
+                            running_time: earlier.running_time,
+                            ..(*process).clone()
+                        };
+                        process != **earlier
+                    })
+                })
+                .cloned()
+                .collect(),
+            removed: previous
+                .iter()
+                .map(|process| process.pid)
+                .filter(|pid| !current.contains(pid))
+                .collect(),
+            time,
+        }
+    }
+
+    /// Patches this snapshot with the changes of `delta`, see `ProcessList::delta`.
+    pub fn apply(&mut self, delta: ProcessDelta) {
+        let removed: HashSet<u32> = delta.removed.into_iter().collect();
+        let mut changed: HashMap<u32, ProcessInfo> = delta
+            .changed
+            .into_iter()
+            .map(|process| (process.pid, process))
+            .collect();
+        self.0.retain(|process| !removed.contains(&process.pid));
+        for process in self.0.iter_mut() {
+            match changed.remove(&process.pid) {
+                Some(changed) => *process = changed,
+                None => {
+                    if let Some(start_time) = process.start_time {
+                        process.running_time = delta.time.saturating_sub(start_time);
+                    }
+                }
+            }
+        }
+        self.0.extend(changed.into_values());
+    }
+
+    /// Returns the processes whose name, command or username contains `query`, keeping their order.
+    /// An empty `query` matches every process.
+    pub fn filter(&self, query: &str) -> ProcessList {
+        self.0
+            .iter()
+            .filter(|process| process.matches(query))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Deref for ProcessList {
+    type Target = [ProcessInfo];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<ProcessInfo>> for ProcessList {
+    fn from(processes: Vec<ProcessInfo>) -> Self {
+        ProcessList(processes)
+    }
+}
+
+impl FromIterator<ProcessInfo> for ProcessList {
+    fn from_iter<I: IntoIterator<Item = ProcessInfo>>(iter: I) -> Self {
+        ProcessList(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for ProcessList {
+    type Item = ProcessInfo;
+    type IntoIter = std::vec::IntoIter<ProcessInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
     }
 }
 
@@ -0,0 +1,43 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The version of the serialized system information exchanged with the agents and kept in the recordings.
+/// It is only increased by the changes which the older versions can not read, like a renamed or retyped field.
+/// The added fields keep the version, as they have defaults and the older versions ignore them.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The schema version carried by the serialized `SystemOverviewInfo`, `CpuMemoryUpdate` and `ProcessList`.
+/// Deserializing another version than `SCHEMA_VERSION` fails with an error naming both versions.
+/// The forms without a version predate the versioning, they are read as the first version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SchemaVersion(u32);
+
+impl SchemaVersion {
+    /// Returns `version` if it can be read, or the reason why it can't.
+    pub fn check(version: u32) -> Result<Self, String> {
+        match version {
+            SCHEMA_VERSION => Ok(SchemaVersion(version)),
+            newer if newer > SCHEMA_VERSION => Err(format!(
+                "schema version {} is newer than the supported version {}, upgrade ocelo to read it",
+                newer, SCHEMA_VERSION
+            )),
+            older => Err(format!(
+                "schema version {} is older than the supported version {} and can no longer be read",
+                older, SCHEMA_VERSION
+            )),
+        }
+    }
+}
+
+impl Default for SchemaVersion {
+    fn default() -> Self {
+        SchemaVersion(SCHEMA_VERSION)
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::check(u32::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
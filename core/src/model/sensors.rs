@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The temperature below the critical threshold from which a sensor is considered running hot.
+const TEMPERATURE_WARNING_MARGIN: f64 = 10.0;
+
+/// The physical quantities measured by the hardware sensors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorKind {
+    /// Temperature in Celsius
+    #[default]
+    Temperature,
+    /// Fan speed in revolutions per minute
+    Fan,
+    /// Voltage in volts
+    Voltage,
+}
+
+impl SensorKind {
+    /// Returns the unit of the measured values.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Temperature => "°C",
+            Self::Fan => "RPM",
+            Self::Voltage => "V",
+        }
+    }
+}
+
+impl Display for SensorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Temperature => write!(f, "temperature"),
+            Self::Fan => write!(f, "fan"),
+            Self::Voltage => write!(f, "voltage"),
+        }
+    }
+}
+
+/// How far the value of a sensor is from its limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SensorStatus {
+    Normal,
+    /// The value is approaching its limit, like a temperature close to the critical one
+    Warning,
+    /// The value is beyond its limit, like a stopped fan or an out of range voltage
+    Critical,
+}
+
+/// A single hardware sensor, like the temperature of a processor package or the speed of a fan.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Sensor {
+    /// Name of the sensor, unique on the host
+    pub label: String,
+    pub kind: SensorKind,
+    /// The last measured value in the unit of `self.kind`
+    pub value: f64,
+    /// The lowest value within the limits, like the minimum speed of a fan
+    pub min: Option<f64>,
+    /// The highest value within the limits
+    pub max: Option<f64>,
+    /// The value from which the hardware is at risk, like the temperature shutting down the processor
+    pub critical: Option<f64>,
+}
+
+impl Sensor {
+    /// Compares the value of the sensor to its limits. The sensors without limits are always normal.
+    pub fn status(&self) -> SensorStatus {
+        let above = |limit: Option<f64>| limit.is_some_and(|limit| self.value >= limit);
+        let below = |limit: Option<f64>| limit.is_some_and(|limit| self.value < limit);
+
+        if above(self.critical) || below(self.min) {
+            SensorStatus::Critical
+        } else if above(self.max)
+            || (self.kind == SensorKind::Temperature
+                && above(
+                    self.critical
+                        .map(|critical| critical - TEMPERATURE_WARNING_MARGIN),
+                ))
+        {
+            SensorStatus::Warning
+        } else {
+            SensorStatus::Normal
+        }
+    }
+}
+
+/// The hardware sensors of the host machine: temperatures, fan speeds and voltages.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SensorsInfo {
+    pub sensors: Vec<Sensor>,
+}
+
+impl SensorsInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
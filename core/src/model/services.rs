@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// A service unit of systemd with its state and its resource accounting.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Service {
+    /// Name of the unit, like `sshd.service`
+    pub name: String,
+    pub description: String,
+    /// The high-level state of the unit, like `active`, `inactive` or `failed`
+    pub active_state: String,
+    /// The state specific to services, like `running` or `exited`
+    pub sub_state: String,
+    /// Time when the unit entered the active state in seconds since the Unix epoch, `None` if it is not active
+    pub active_since: Option<u64>,
+    /// The process of the service, `None` if it has no running main process
+    pub main_pid: Option<u32>,
+    /// Memory used by the processes of the service in bytes, `None` without memory accounting
+    pub memory: Option<u64>,
+    /// CPU time consumed by the processes of the service in milliseconds, `None` without CPU accounting
+    pub cpu_time: Option<u64>,
+}
+
+impl Service {
+    /// Returns true if the unit is in the failed state.
+    pub fn is_failed(&self) -> bool {
+        self.active_state == "failed"
+    }
+}
+
+/// The service units of systemd, like listed by `systemctl list-units --type=service --all`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ServicesInfo {
+    pub services: Vec<Service>,
+}
+
+impl ServicesInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
@@ -0,0 +1,138 @@
+use super::{percentage, ProcessList, ProcessSummary, SystemOverviewInfo};
+use crate::history::unix_time;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The system information at a point in time: the overview and every running process,
+/// like written by `ocelo snapshot --format json`.
+/// Two snapshots are compared by `SystemSnapshot::diff()`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    /// Time of taking the snapshot in seconds since the Unix epoch
+    pub timestamp: f64,
+    pub overview: SystemOverviewInfo,
+    /// Empty if the processes were not listed, like in the snapshots compared by the alert engine
+    #[serde(default)]
+    pub processes: ProcessList,
+}
+
+impl SystemSnapshot {
+    /// Creates a snapshot taken now.
+    pub fn new(overview: SystemOverviewInfo, processes: ProcessList) -> Self {
+        SystemSnapshot {
+            timestamp: unix_time(),
+            overview,
+            processes,
+        }
+    }
+
+    /// Returns the changes from the `earlier` snapshot to the `later` one.
+    /// The disks are matched by their mount point, only the ones in both snapshots are compared.
+    /// The processes are matched by their pid and start time, so a process reusing the pid of an exited one is new.
+    pub fn diff(earlier: &SystemSnapshot, later: &SystemSnapshot) -> SnapshotDiff {
+        let (before, after) = (&earlier.overview, &later.overview);
+        let disks = after
+            .disks
+            .disks
+            .iter()
+            .filter_map(|disk| {
+                let previous = before
+                    .disks
+                    .disks
+                    .iter()
+                    .find(|previous| previous.mount == disk.mount)?;
+                Some(DiskDiff {
+                    mount: disk.mount.clone(),
+                    used_space: change(previous.used_space, disk.used_space),
+                    usage: percentage(disk.used_space, disk.total_space)
+                        - percentage(previous.used_space, previous.total_space),
+                })
+            })
+            .collect();
+
+        let identities = |processes: &ProcessList| -> HashSet<(u32, Option<u64>)> {
+            processes
+                .iter()
+                .map(|process| (process.pid, process.start_time))
+                .collect()
+        };
+        let (running_before, running_after) =
+            (identities(&earlier.processes), identities(&later.processes));
+        let only_in = |processes: &ProcessList, others: &HashSet<(u32, Option<u64>)>| {
+            processes
+                .iter()
+                .filter(|process| !others.contains(&(process.pid, process.start_time)))
+                .map(|process| ProcessSummary {
+                    pid: process.pid,
+                    name: process.name.clone(),
+                    cpu_usage: process.cpu_usage,
+                    memory: process.memory,
+                })
+                .collect()
+        };
+
+        SnapshotDiff {
+            elapsed: later.timestamp - earlier.timestamp,
+            cpu_usage: (after.cpu.usage - before.cpu.usage) as f64,
+            cpu_temperature: after
+                .cpu
+                .temperature
+                .zip(before.cpu.temperature)
+                .map(|(after, before)| (after - before) as f64),
+            load_one_minute: after.overview.load_one_minute - before.overview.load_one_minute,
+            memory_used: change(before.memory.used, after.memory.used),
+            memory_usage: after.memory.usage() - before.memory.usage(),
+            swap_used: change(before.memory.swap_used, after.memory.swap_used),
+            disks,
+            new_processes: only_in(&later.processes, &running_before),
+            exited_processes: only_in(&earlier.processes, &running_after),
+        }
+    }
+}
+
+/// The changes between two snapshots of the system, see `SystemSnapshot::diff()`.
+/// The changes are negative when a value decreased, like when memory was freed.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Seconds elapsed from the earlier snapshot to the later one
+    pub elapsed: f64,
+    /// Change of the global CPU usage in percentage points
+    pub cpu_usage: f64,
+    /// Change of the CPU package temperature in Celsius, unknown if either snapshot lacks it
+    pub cpu_temperature: Option<f64>,
+    /// Change of the one minute load average
+    pub load_one_minute: f64,
+    /// Change of the used physical memory in bytes
+    pub memory_used: i64,
+    /// Change of the physical memory usage in percentage points
+    pub memory_usage: f64,
+    /// Change of the used swap in bytes
+    pub swap_used: i64,
+    /// Changes of the disks mounted in both snapshots
+    pub disks: Vec<DiskDiff>,
+    /// The processes running only in the later snapshot
+    pub new_processes: Vec<ProcessSummary>,
+    /// The processes running only in the earlier snapshot
+    pub exited_processes: Vec<ProcessSummary>,
+}
+
+impl SnapshotDiff {
+    /// Returns the `change` spread over a minute of the elapsed time, `None` if no time elapsed.
+    pub fn per_minute(&self, change: f64) -> Option<f64> {
+        (self.elapsed > 0.0).then(|| change / self.elapsed * 60.0)
+    }
+}
+
+/// The change of a disk between two snapshots.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiskDiff {
+    pub mount: String,
+    /// Change of the used space in bytes
+    pub used_space: i64,
+    /// Change of the used space in percentage points of the size of the disk
+    pub usage: f64,
+}
+
+fn change(before: u64, after: u64) -> i64 {
+    after as i64 - before as i64
+}
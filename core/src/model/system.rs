@@ -1,4 +1,8 @@
 use super::SystemOverviewInfo;
+use crate::clock::ClockStatus;
+use crate::entropy::Entropy;
+use crate::open_files::DescriptorUsage;
+use crate::virtualization::HostEnvironment;
 use serde::{Deserialize, Serialize};
 
 /// Some details describing the host system.
@@ -6,10 +10,44 @@ use serde::{Deserialize, Serialize};
 pub struct SystemInfo {
     pub host_name: String,
     pub kernel_version: String,
+    /// The name and the version of the operating system, like `Linux (Ubuntu 24.04)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<String>,
     pub uptime: u64,
+    /// Time of the last boot in seconds since the Unix epoch
+    #[serde(default)]
+    pub boot_time: u64,
     pub load_one_minute: f64,
     pub load_five_minutes: f64,
     pub load_fifteen_minutes: f64,
+    /// The file handles of the system, unknown outside of Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_descriptors: Option<DescriptorUsage>,
+    /// The entropy of the random number generator of the kernel, unknown outside of Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<Entropy>,
+    /// Whether the host is bare metal, a virtual machine, a container or WSL, unknown outside of Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<HostEnvironment>,
+    /// The installed packages and the available updates, only counted with the `packages` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packages: Option<PackageSummary>,
+    /// The time zone, the locale and the synchronization of the clock, unknown outside of Linux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockStatus>,
+}
+
+/// The packages installed by the package manager of the system and the updates available for them.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageSummary {
+    /// The package manager, like `apt`, `dnf` or `pacman`
+    pub manager: String,
+    /// Count of the installed packages, `None` if they can not be counted
+    pub installed: Option<u32>,
+    /// Count of the packages with an available update, `None` if the updates can not be checked
+    pub updates: Option<u32>,
+    /// Time of the check in seconds since the Unix epoch
+    pub checked_at: f64,
 }
 
 impl SystemOverviewInfo {
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// A user logged in to the host machine, like a console login or an SSH connection.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Session {
+    /// Name of the logged in user
+    pub user: String,
+    /// The terminal of the session, like `pts/0` or `tty1`
+    pub tty: String,
+    /// The host the user is logged in from, `None` for the local logins
+    pub host: Option<String>,
+    /// Time of the login in seconds since the Unix epoch
+    pub login_time: u64,
+    /// Seconds since the last input on the terminal, `None` if it is not known
+    pub idle: Option<u64>,
+    /// The login process of the session, like the shell or the sshd process
+    pub pid: u32,
+}
+
+/// The sessions of the users logged in to the host machine, like listed by `who`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionsInfo {
+    pub sessions: Vec<Session>,
+}
+
+impl SessionsInfo {
+    /// Creates `self` from a JSON reprentation.
+    pub fn from_json(value: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value)
+    }
+
+    /// Creates the JSON representation of `self`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::alerts::AlertNotification;
+use crate::Alert;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::process::Command;
+
+/// The title of the desktop notifications.
+const NOTIFICATION_TITLE: &str = "ocelo alert";
+
+/// The bell character, making the terminal beep or flash.
+const BELL: &[u8] = b"\x07";
+
+/// Notifies the user of the `alerts` the way their rules are configured to.
+/// The bell is rung once, even when several alerts are raised at the same time.
+pub fn notify(alerts: &[Alert]) {
+    let mut ring = false;
+    for alert in alerts {
+        match alert.notification {
+            AlertNotification::None => {}
+            AlertNotification::Bell => ring = true,
+            AlertNotification::Desktop => ring |= !send_desktop_notification(&alert.to_string()),
+        }
+    }
+    if ring {
+        ring_bell();
+    }
+}
+
+/// Rings the bell of the terminal. It is written to the standard error,
+/// so the output of the snapshots is not changed and it does not interleave with the drawing of the user interface.
+pub fn ring_bell() {
+    let mut stderr = io::stderr().lock();
+    let _ = stderr.write_all(BELL).and_then(|_| stderr.flush());
+}
+
+/// Sends a desktop notification with `text`, returns false if it could not be sent.
+/// The notifications are sent by `notify-send` on Linux and the BSDs, by `osascript` on macOS,
+/// elsewhere they are not supported.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn send_desktop_notification(text: &str) -> bool {
+    Command::new("notify-send")
+        .args([
+            "--app-name=ocelo",
+            "--urgency=critical",
+            NOTIFICATION_TITLE,
+            text,
+        ])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+#[cfg(target_os = "macos")]
+pub fn send_desktop_notification(text: &str) -> bool {
+    // the text is passed as an argument of the script, so it needs no escaping
+    let script = format!(
+        "on run argv\ndisplay notification (item 1 of argv) with title \"{}\"\nend run",
+        NOTIFICATION_TITLE
+    );
+    Command::new("osascript")
+        .args(["-e", &script, text])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+#[cfg(not(unix))]
+pub fn send_desktop_notification(_text: &str) -> bool {
+    false
+}
@@ -0,0 +1,291 @@
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+
+/// The kind of the object behind a file descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptorKind {
+    File,
+    Directory,
+    /// A character or a block device, like a terminal
+    Device,
+    Pipe,
+    Socket,
+    /// An object without a file, like an eventfd or an epoll instance
+    Anonymous,
+    Other,
+}
+
+impl DescriptorKind {
+    /// Returns the short name of the kind, like `file` or `socket`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Directory => "dir",
+            Self::Device => "device",
+            Self::Pipe => "pipe",
+            Self::Socket => "socket",
+            Self::Anonymous => "anon",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// A socket opened by a process.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SocketInfo {
+    /// The protocol of the socket, like `tcp`, `udp6` or `unix`
+    pub protocol: String,
+
+    /// The local address and port, or the path of a Unix socket
+    pub local: String,
+
+    /// The address and port of the peer, empty for the Unix sockets
+    pub remote: String,
+
+    /// The state of a TCP connection, like `LISTEN` or `ESTABLISHED`, empty for the other protocols
+    pub state: String,
+}
+
+/// A file descriptor opened by a process, like the output of `lsof -p <pid>`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileDescriptor {
+    /// The number of the descriptor
+    pub fd: u32,
+
+    pub kind: DescriptorKind,
+
+    /// The access mode of the descriptor: `r`, `w` or `rw`
+    pub mode: String,
+
+    /// The path of the opened file, or the description of the object without a path, like `pipe:[1234]`
+    pub path: String,
+
+    /// The protocol and the addresses of the socket, if the descriptor is a known socket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket: Option<SocketInfo>,
+}
+
+/// The file handles allocated by the kernel for every process, like counted by `fs.file-nr`.
+/// Opening files fails with `ENFILE` across the whole system once the maximum is reached.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DescriptorUsage {
+    /// Count of the allocated file handles
+    pub allocated: u64,
+    /// The most file handles the kernel allocates, set by `fs.file-max`
+    pub maximum: u64,
+    /// The process using the most of its own limit of open files,
+    /// unknown if no process can be inspected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub busiest_process: Option<ProcessDescriptors>,
+}
+
+impl DescriptorUsage {
+    /// Returns the allocated file handles in percent of the maximum.
+    pub fn usage(&self) -> Option<f64> {
+        match self.maximum {
+            0 => None,
+            maximum => Some(self.allocated as f64 / maximum as f64 * 100.0),
+        }
+    }
+}
+
+/// The file descriptors opened by a process compared to its limit of open files.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessDescriptors {
+    pub pid: u32,
+    pub name: String,
+    /// Count of the open file descriptors
+    pub open: u32,
+    /// The soft limit of the open files of the process, its `RLIMIT_NOFILE`.
+    /// Opening files fails with `EMFILE` in the process once it is reached
+    pub limit: u32,
+}
+
+impl ProcessDescriptors {
+    /// Returns the open file descriptors in percent of the limit.
+    pub fn usage(&self) -> f64 {
+        match self.limit {
+            0 => 0.0,
+            limit => self.open as f64 / limit as f64 * 100.0,
+        }
+    }
+}
+
+/// Reads the count of the allocated file handles of the system and their maximum from `/proc/sys/fs/file-nr`,
+/// so they are only read on Linux. The busiest process is left unknown.
+#[cfg(target_os = "linux")]
+pub fn descriptor_usage() -> Result<DescriptorUsage, OceloError> {
+    linux::descriptor_usage()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn descriptor_usage() -> Result<DescriptorUsage, OceloError> {
+    Err(OceloError::Unsupported(
+        "counting the file handles is only supported on Linux".to_string(),
+    ))
+}
+
+/// Lists the file descriptors opened by the process `pid`, ordered by their number.
+/// The descriptors are read from `/proc/<pid>/fd`, so they are only listed on Linux.
+#[cfg(target_os = "linux")]
+pub fn open_files(pid: u32) -> Result<Vec<FileDescriptor>, OceloError> {
+    linux::open_files(pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn open_files(_pid: u32) -> Result<Vec<FileDescriptor>, OceloError> {
+    Err(OceloError::Unsupported(
+        "listing the open files is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{DescriptorKind, DescriptorUsage, FileDescriptor, SocketInfo};
+    use crate::connections::linux::{inet_sockets, socket_inode};
+    use crate::OceloError;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+
+    pub fn descriptor_usage() -> Result<DescriptorUsage, OceloError> {
+        let file_nr = fs::read_to_string("/proc/sys/fs/file-nr").map_err(|error| {
+            OceloError::Io(format!(
+                "failed to read the count of the file handles: {}",
+                error
+            ))
+        })?;
+        // the line holds the allocated, the unused and the maximum file handles, the unused ones are always 0
+        let fields = file_nr
+            .split_whitespace()
+            .map(|field| field.parse::<u64>().ok())
+            .collect::<Vec<Option<u64>>>();
+        let (Some(Some(allocated)), Some(Some(maximum))) = (fields.first(), fields.get(2)) else {
+            return Err(OceloError::Io(format!(
+                "malformed count of the file handles: {}",
+                file_nr.trim()
+            )));
+        };
+        Ok(DescriptorUsage {
+            allocated: *allocated,
+            maximum: *maximum,
+            busiest_process: None,
+        })
+    }
+
+    pub fn open_files(pid: u32) -> Result<Vec<FileDescriptor>, OceloError> {
+        let process = Path::new("/proc").join(pid.to_string());
+        let entries = fs::read_dir(process.join("fd")).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => OceloError::ProcessNotFound(pid),
+            _ => OceloError::Io(format!(
+                "failed to list the open files of {}: {}",
+                pid, error
+            )),
+        })?;
+        // the sockets are only looked up if the process has any
+        let mut sockets: Option<HashMap<u64, SocketInfo>> = None;
+
+        let mut descriptors: Vec<FileDescriptor> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let fd = entry.file_name().to_str()?.parse::<u32>().ok()?;
+                // the descriptor may be closed since the listing
+                let target = fs::read_link(entry.path()).ok()?;
+                let path = target.to_string_lossy().into_owned();
+                let mode = fs::read_to_string(process.join("fdinfo").join(fd.to_string()))
+                    .ok()
+                    .map_or(String::new(), |fdinfo| access_mode(&fdinfo));
+
+                let kind = kind_of(&path, &entry.path());
+                let socket = match kind {
+                    DescriptorKind::Socket => socket_inode(&path).and_then(|inode| {
+                        sockets
+                            .get_or_insert_with(|| read_sockets(&process))
+                            .get(&inode)
+                            .cloned()
+                    }),
+                    _ => None,
+                };
+
+                Some(FileDescriptor {
+                    fd,
+                    kind,
+                    mode,
+                    path,
+                    socket,
+                })
+            })
+            .collect();
+        descriptors.sort_by_key(|descriptor| descriptor.fd);
+        Ok(descriptors)
+    }
+
+    /// Determines the kind of the descriptor from the target of its link,
+    /// like `socket:[1234]`, `pipe:[1234]` or `anon_inode:[eventfd]`.
+    fn kind_of(target: &str, link: &Path) -> DescriptorKind {
+        if target.starts_with("socket:") {
+            DescriptorKind::Socket
+        } else if target.starts_with("pipe:") {
+            DescriptorKind::Pipe
+        } else if target.starts_with("anon_inode:") {
+            DescriptorKind::Anonymous
+        } else if target.starts_with('/') {
+            // the metadata of the link is the metadata of the opened file
+            match fs::metadata(link) {
+                Ok(metadata) if metadata.is_dir() => DescriptorKind::Directory,
+                Ok(metadata) if metadata.is_file() => DescriptorKind::File,
+                Ok(_) => DescriptorKind::Device,
+                // the opened file may be deleted
+                Err(_) => DescriptorKind::File,
+            }
+        } else {
+            DescriptorKind::Other
+        }
+    }
+
+    /// Returns the access mode from the `flags:` line of the fdinfo of a descriptor.
+    fn access_mode(fdinfo: &str) -> String {
+        let flags = fdinfo
+            .lines()
+            .find_map(|line| line.strip_prefix("flags:"))
+            .and_then(|flags| u32::from_str_radix(flags.trim(), 8).ok());
+        match flags.map(|flags| flags & libc::O_ACCMODE as u32) {
+            Some(mode) if mode == libc::O_RDONLY as u32 => "r",
+            Some(mode) if mode == libc::O_WRONLY as u32 => "w",
+            Some(mode) if mode == libc::O_RDWR as u32 => "rw",
+            _ => "",
+        }
+        .to_string()
+    }
+
+    /// Reads the sockets of the network namespace of the process by their inode.
+    fn read_sockets(process: &Path) -> HashMap<u64, SocketInfo> {
+        let net = process.join("net");
+        let mut sockets: HashMap<u64, SocketInfo> = inet_sockets(&net)
+            .into_iter()
+            .map(|(inode, socket, _)| (inode, socket))
+            .collect();
+
+        if let Ok(table) = fs::read_to_string(net.join("unix")) {
+            for line in table.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let Some(Ok(inode)) = fields.get(6).map(|inode| inode.parse::<u64>()) else {
+                    continue;
+                };
+                sockets.insert(
+                    inode,
+                    SocketInfo {
+                        protocol: "unix".to_string(),
+                        local: fields.get(7).unwrap_or(&"").to_string(),
+                        remote: String::new(),
+                        state: String::new(),
+                    },
+                );
+            }
+        }
+
+        sockets
+    }
+}
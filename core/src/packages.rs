@@ -0,0 +1,193 @@
+use crate::history::unix_time;
+use crate::model::PackageSummary;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The time between two checks of the packages.
+/// The updates are found from the package lists of the last refresh, which the package manager schedules itself.
+pub const PACKAGE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The package managers whose packages are counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PackageManager {
+    /// Debian, Ubuntu and their derivatives
+    Apt,
+    /// Fedora, RHEL and their derivatives
+    Dnf,
+    /// Arch Linux and its derivatives
+    Pacman,
+}
+
+impl PackageManager {
+    /// Returns the package manager of the system from the databases of the installed packages,
+    /// `None` if none of them is found.
+    fn detect() -> Option<Self> {
+        [
+            (Self::Apt, "/var/lib/dpkg/status"),
+            (Self::Pacman, "/var/lib/pacman/local"),
+            (Self::Dnf, "/var/lib/rpm"),
+        ]
+        .into_iter()
+        .find(|(_, database)| Path::new(database).exists())
+        .map(|(manager, _)| manager)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Apt => "apt",
+            Self::Dnf => "dnf",
+            Self::Pacman => "pacman",
+        }
+    }
+
+    /// Counts the installed packages.
+    fn installed(&self) -> Option<u32> {
+        match self {
+            // the status file lists every package known to dpkg, the removed ones too
+            Self::Apt => Some(
+                fs::read_to_string("/var/lib/dpkg/status")
+                    .ok()?
+                    .lines()
+                    .filter(|line| *line == "Status: install ok installed")
+                    .count() as u32,
+            ),
+            // a directory per installed package, beside the version file of the database
+            Self::Pacman => Some(
+                fs::read_dir("/var/lib/pacman/local")
+                    .ok()?
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .count() as u32,
+            ),
+            Self::Dnf => run("rpm", &["--query", "--all"], &[0]).map(|lines| lines.len() as u32),
+        }
+    }
+
+    /// Counts the packages with an available update, from the package lists of the last refresh,
+    /// so the repositories are not contacted.
+    fn updates(&self) -> Option<u32> {
+        match self {
+            // the simulation lists the upgraded and the newly installed packages like `Inst bash [5.2.15-2] (...)`,
+            // without requiring root privileges
+            Self::Apt => run(
+                "apt-get",
+                &["--simulate", "-o", "Debug::NoLocking=true", "dist-upgrade"],
+                &[0],
+            )
+            .map(|lines| count(&lines, |line| line.starts_with("Inst "))),
+            // the updates are listed like `bash.x86_64  5.2.26-3.fc40  updates`, exiting with 100 if there are any
+            Self::Dnf => run(
+                "dnf",
+                &["check-update", "--cacheonly", "--quiet"],
+                &[0, 100],
+            )
+            .map(|lines| {
+                let obsoleting = lines
+                    .iter()
+                    .position(|line| line.starts_with("Obsoleting"))
+                    .unwrap_or(lines.len());
+                count(&lines[..obsoleting], |line| {
+                    line.split_whitespace().count() == 3
+                })
+            }),
+            // exits with 1 if there are no updates
+            Self::Pacman => run("pacman", &["--query", "--upgrades"], &[0, 1])
+                .map(|lines| count(&lines, |line| !line.is_empty())),
+        }
+    }
+
+    fn summary(&self) -> PackageSummary {
+        PackageSummary {
+            manager: self.name().to_string(),
+            installed: self.installed(),
+            updates: self.updates(),
+            checked_at: unix_time(),
+        }
+    }
+}
+
+/// Runs `program` with `args`, returning the lines of its output if it exits with one of the `statuses`.
+fn run(program: &str, args: &[&str], statuses: &[i32]) -> Option<Vec<String>> {
+    let output = Command::new(program)
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+    if !statuses.contains(&output.status.code()?) {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// Counts the `lines` matching `predicate`.
+fn count(lines: &[String], predicate: impl Fn(&str) -> bool) -> u32 {
+    lines.iter().filter(|line| predicate(line)).count() as u32
+}
+
+/// Counts the installed packages and the available updates with the package manager of the system,
+/// apt, dnf or pacman, at every `PACKAGE_CHECK_INTERVAL` on a background thread, which stops when the monitor is dropped.
+/// Finding the updates takes a few seconds, so the polls are not held up by it.
+///
+/// # Example:
+/// ```no_run
+/// use core::PackageMonitor;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let monitor = PackageMonitor::start();
+/// thread::sleep(Duration::from_secs(5));
+/// println!("{:?}", monitor.summary());
+/// ```
+pub struct PackageMonitor {
+    summary: Arc<Mutex<Option<PackageSummary>>>,
+    /// Stops the worker thread when dropped
+    _stop: Sender<()>,
+}
+
+impl PackageMonitor {
+    pub fn start() -> Self {
+        let summary = Arc::new(Mutex::new(None));
+        let (stop, stopped) = mpsc::channel();
+        let result = Arc::clone(&summary);
+        thread::spawn(move || {
+            let Some(manager) = PackageManager::detect() else {
+                return;
+            };
+            loop {
+                let checked = manager.summary();
+                if let Ok(mut summary) = result.lock() {
+                    *summary = Some(checked);
+                }
+                // sleeps until the next check, or stops when the monitor is dropped
+                if let Err(RecvTimeoutError::Disconnected) =
+                    stopped.recv_timeout(PACKAGE_CHECK_INTERVAL)
+                {
+                    break;
+                }
+            }
+        });
+        PackageMonitor {
+            summary,
+            _stop: stop,
+        }
+    }
+
+    /// Returns the result of the last check, `None` until the first one finishes
+    /// or if the package manager is not known.
+    pub fn summary(&self) -> Option<PackageSummary> {
+        self.summary
+            .lock()
+            .map(|summary| summary.clone())
+            .unwrap_or_default()
+    }
+}
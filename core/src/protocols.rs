@@ -0,0 +1,151 @@
+use crate::rate::RateTracker;
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The health of the TCP and UDP stacks of the system, like reported by `netstat -s`.
+/// The counts are per second since the previous poll, except for the established connections.
+/// TCP is counted over IPv4 and IPv6 together, UDP is summed over both.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolStats {
+    /// Count of the TCP connections currently established or closing from the peer
+    pub established: u64,
+    /// Received TCP segments
+    pub segments_received: f64,
+    /// Sent TCP segments, without the retransmitted ones
+    pub segments_sent: f64,
+    /// Retransmitted TCP segments, the sign of a lossy or congested path
+    pub retransmitted: f64,
+    /// Established TCP connections reset by either side
+    pub connection_resets: f64,
+    /// Sent TCP segments with the RST flag, like the refused connections
+    pub resets_sent: f64,
+    /// Failed attempts to open a TCP connection
+    pub failed_connections: f64,
+    /// Connections dropped because the accept queue of a listening socket was full
+    pub listen_overflows: f64,
+    /// Received UDP datagrams
+    pub datagrams_received: f64,
+    /// Sent UDP datagrams
+    pub datagrams_sent: f64,
+    /// UDP datagrams which could not be delivered, like the malformed ones and the ones overflowing a socket buffer
+    pub udp_errors: f64,
+    /// UDP datagrams to ports without a socket
+    pub udp_no_ports: f64,
+}
+
+impl ProtocolStats {
+    /// Returns the retransmitted segments in percent of the sent ones.
+    /// A few percent already slows down the TCP connections considerably.
+    pub fn retransmission_ratio(&self) -> f64 {
+        match self.segments_sent > 0.0 {
+            true => self.retransmitted / self.segments_sent * 100.0,
+            false => 0.0,
+        }
+    }
+}
+
+/// Reads the protocol counters of the system, calculating their rates since the previous call with `rates`.
+/// The counters are read from `/proc/net/snmp`, `/proc/net/snmp6` and `/proc/net/netstat`,
+/// so they are only read on Linux. The rates of the first call are zero.
+pub fn protocol_stats(rates: &mut RateTracker) -> Result<ProtocolStats, OceloError> {
+    let counters = counters()?;
+    let mut rate = |names: &[&str]| {
+        let total = names
+            .iter()
+            .filter_map(|name| counters.get(*name))
+            .sum::<u64>();
+        rates.rate(&format!("protocols/{}", names.join("+")), total)
+    };
+
+    Ok(ProtocolStats {
+        established: counters.get("Tcp.CurrEstab").copied().unwrap_or_default(),
+        segments_received: rate(&["Tcp.InSegs"]),
+        segments_sent: rate(&["Tcp.OutSegs"]),
+        retransmitted: rate(&["Tcp.RetransSegs"]),
+        connection_resets: rate(&["Tcp.EstabResets"]),
+        resets_sent: rate(&["Tcp.OutRsts"]),
+        failed_connections: rate(&["Tcp.AttemptFails"]),
+        listen_overflows: rate(&["TcpExt.ListenOverflows"]),
+        datagrams_received: rate(&["Udp.InDatagrams", "Udp6.InDatagrams"]),
+        datagrams_sent: rate(&["Udp.OutDatagrams", "Udp6.OutDatagrams"]),
+        udp_errors: rate(&["Udp.InErrors", "Udp6.InErrors"]),
+        udp_no_ports: rate(&["Udp.NoPorts", "Udp6.NoPorts"]),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn counters() -> Result<HashMap<String, u64>, OceloError> {
+    linux::counters()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn counters() -> Result<HashMap<String, u64>, OceloError> {
+    Err(OceloError::Unsupported(
+        "reading the protocol statistics is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use crate::OceloError;
+    use std::collections::HashMap;
+    use std::fs;
+
+    /// Returns the counters by their group and name, like `Tcp.RetransSegs`.
+    pub fn counters() -> Result<HashMap<String, u64>, OceloError> {
+        let snmp = fs::read_to_string("/proc/net/snmp").map_err(|error| {
+            OceloError::Io(format!("failed to read the protocol statistics: {}", error))
+        })?;
+        let mut counters = parse_tables(&snmp);
+        if let Ok(netstat) = fs::read_to_string("/proc/net/netstat") {
+            counters.extend(parse_tables(&netstat));
+        }
+        // IPv6 may be disabled
+        if let Ok(snmp6) = fs::read_to_string("/proc/net/snmp6") {
+            counters.extend(parse_snmp6(&snmp6));
+        }
+        Ok(counters)
+    }
+
+    /// Parses the pairs of lines of `/proc/net/snmp` and `/proc/net/netstat`,
+    /// the names of a group of counters followed by their values, like
+    /// ```text
+    /// Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens ...
+    /// Tcp: 1 200 120000 -1 277 ...
+    /// ```
+    /// The negative values, which are not counters, are left out.
+    fn parse_tables(content: &str) -> HashMap<String, u64> {
+        let mut counters = HashMap::new();
+        let lines = content.lines().collect::<Vec<&str>>();
+        for pair in lines.chunks(2) {
+            let [names, values] = pair else {
+                continue;
+            };
+            let (Some((group, names)), Some((_, values))) =
+                (names.split_once(':'), values.split_once(':'))
+            else {
+                continue;
+            };
+            for (name, value) in names.split_whitespace().zip(values.split_whitespace()) {
+                if let Ok(value) = value.parse::<u64>() {
+                    counters.insert(format!("{}.{}", group, name), value);
+                }
+            }
+        }
+        counters
+    }
+
+    /// Parses the UDP counters of `/proc/net/snmp6`, a counter per line like `Udp6InDatagrams 130`,
+    /// into the names of `/proc/net/snmp` like `Udp6.InDatagrams`.
+    fn parse_snmp6(content: &str) -> HashMap<String, u64> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let (name, value) = line.split_once(char::is_whitespace)?;
+                let name = name.strip_prefix("Udp6")?;
+                Some((format!("Udp6.{}", name), value.trim().parse().ok()?))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,351 @@
+use crate::connections::Connection;
+use crate::limits::SystemLimits;
+use crate::model::{
+    ContainersInfo, CpuCore, CpuMemoryUpdate, CpuTopology, DiskInfo, LogsInfo, MemoryInfo,
+    NetworkInfo, ProcessColumn, ProcessDelta, ProcessInfo, ProcessList, SchemaVersion, SensorsInfo,
+    ServicesInfo, SessionsInfo, SystemOverviewInfo, SystemSnapshot,
+};
+use crate::security::SecurityOverview;
+use crate::{Alert, AlertEngine, AlertRule, CpuInfo, OceloError, SystemInfoPoller};
+
+/// Provides the snapshots of the system information, or the reason why they could not be taken, like the sysinfo backed `SystemInfoPoller`,
+/// or the `MockProvider` replaying canned snapshots.
+pub trait SystemInfoProvider: Send {
+    fn get_cpu_info(&mut self) -> Result<CpuInfo, OceloError>;
+
+    fn get_cpu_amd_memory_info(&mut self) -> Result<CpuMemoryUpdate, OceloError>;
+
+    /// Returns the static layout of the processor, like its sockets, cores and caches.
+    fn get_cpu_topology(&mut self) -> Result<CpuTopology, OceloError>;
+
+    fn get_memory_info(&mut self) -> Result<MemoryInfo, OceloError>;
+
+    fn get_disk_info(&mut self) -> Result<DiskInfo, OceloError>;
+
+    fn get_network_info(&mut self) -> Result<NetworkInfo, OceloError>;
+
+    /// Returns the network statistics with their details, like the routes and the listening ports,
+    /// which are left out of `get_network_info()` by the providers for which they are costly.
+    fn get_network_details(&mut self) -> Result<NetworkInfo, OceloError> {
+        self.get_network_info()
+    }
+
+    fn get_process_list(&mut self) -> Result<ProcessList, OceloError>;
+
+    /// Returns the changes of the processes since the list or the changes returned last,
+    /// `None` if only whole lists are provided.
+    fn get_process_delta(&mut self) -> Result<Option<ProcessDelta>, OceloError> {
+        Ok(None)
+    }
+
+    /// Returns the process `pid` with its details, like the command line and the environment.
+    fn get_process_details(&mut self, pid: u32) -> Result<ProcessInfo, OceloError>;
+
+    /// Reads the values of the processes which are slow to read only for the displayed `columns` from now on.
+    /// Nothing to do for the providers which have every value at hand.
+    fn set_process_columns(&mut self, _columns: Vec<ProcessColumn>) {}
+
+    /// Returns the TCP and UDP sockets of the system with the processes owning them.
+    fn get_connections(&mut self) -> Result<Vec<Connection>, OceloError>;
+
+    /// Returns the kernel tunables and the resource limits bounding the capacity of the system.
+    fn get_limits(&mut self) -> Result<SystemLimits, OceloError>;
+
+    /// Returns the failed SSH logins, the ports open to the other hosts and the state of the firewall.
+    fn get_security(&mut self) -> Result<SecurityOverview, OceloError>;
+
+    /// Returns the temperatures, fan speeds and voltages of the hardware sensors.
+    fn get_sensors_info(&mut self) -> Result<SensorsInfo, OceloError>;
+
+    /// Returns the sessions of the users logged in to the system.
+    fn get_sessions(&mut self) -> Result<SessionsInfo, OceloError>;
+
+    /// Returns the service units of systemd with their state and resource accounting.
+    fn get_services(&mut self) -> Result<ServicesInfo, OceloError>;
+
+    /// Returns the running containers of Docker or Podman with their resource usage.
+    fn get_containers(&mut self) -> Result<ContainersInfo, OceloError>;
+
+    /// Returns the recent messages of the system log.
+    fn get_logs(&mut self) -> Result<LogsInfo, OceloError>;
+
+    fn get_system_overview(&mut self) -> Result<SystemOverviewInfo, OceloError>;
+
+    /// Returns the overview and every running process, to be compared with another snapshot by `SystemSnapshot::diff()`.
+    fn get_snapshot(&mut self) -> Result<SystemSnapshot, OceloError> {
+        let overview = self.get_system_overview()?;
+        Ok(SystemSnapshot::new(overview, self.get_process_list()?))
+    }
+
+    /// Evaluates the alert rules and returns the newly raised alerts.
+    fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, OceloError>;
+}
+
+impl SystemInfoProvider for SystemInfoPoller {
+    fn get_cpu_info(&mut self) -> Result<CpuInfo, OceloError> {
+        SystemInfoPoller::get_cpu_info(self)
+    }
+
+    fn get_cpu_amd_memory_info(&mut self) -> Result<CpuMemoryUpdate, OceloError> {
+        SystemInfoPoller::get_cpu_amd_memory_info(self)
+    }
+
+    fn get_cpu_topology(&mut self) -> Result<CpuTopology, OceloError> {
+        SystemInfoPoller::get_cpu_topology(self)
+    }
+
+    fn get_memory_info(&mut self) -> Result<MemoryInfo, OceloError> {
+        SystemInfoPoller::get_memory_info(self)
+    }
+
+    fn get_disk_info(&mut self) -> Result<DiskInfo, OceloError> {
+        SystemInfoPoller::get_disk_info(self)
+    }
+
+    fn get_network_info(&mut self) -> Result<NetworkInfo, OceloError> {
+        SystemInfoPoller::get_network_info(self)
+    }
+
+    fn get_network_details(&mut self) -> Result<NetworkInfo, OceloError> {
+        SystemInfoPoller::get_network_details(self)
+    }
+
+    fn get_process_list(&mut self) -> Result<ProcessList, OceloError> {
+        SystemInfoPoller::get_process_list(self)
+    }
+
+    fn get_process_delta(&mut self) -> Result<Option<ProcessDelta>, OceloError> {
+        SystemInfoPoller::get_process_delta(self)
+    }
+
+    fn get_process_details(&mut self, pid: u32) -> Result<ProcessInfo, OceloError> {
+        SystemInfoPoller::get_process_details(self, pid)
+    }
+
+    fn set_process_columns(&mut self, columns: Vec<ProcessColumn>) {
+        SystemInfoPoller::set_process_columns(self, columns)
+    }
+
+    fn get_connections(&mut self) -> Result<Vec<Connection>, OceloError> {
+        SystemInfoPoller::get_connections(self)
+    }
+
+    fn get_limits(&mut self) -> Result<SystemLimits, OceloError> {
+        SystemInfoPoller::get_limits(self)
+    }
+
+    fn get_security(&mut self) -> Result<SecurityOverview, OceloError> {
+        SystemInfoPoller::get_security(self)
+    }
+
+    fn get_sensors_info(&mut self) -> Result<SensorsInfo, OceloError> {
+        SystemInfoPoller::get_sensors_info(self)
+    }
+
+    fn get_sessions(&mut self) -> Result<SessionsInfo, OceloError> {
+        SystemInfoPoller::get_sessions(self)
+    }
+
+    fn get_services(&mut self) -> Result<ServicesInfo, OceloError> {
+        SystemInfoPoller::get_services(self)
+    }
+
+    fn get_containers(&mut self) -> Result<ContainersInfo, OceloError> {
+        SystemInfoPoller::get_containers(self)
+    }
+
+    fn get_logs(&mut self) -> Result<LogsInfo, OceloError> {
+        SystemInfoPoller::get_logs(self)
+    }
+
+    fn get_system_overview(&mut self) -> Result<SystemOverviewInfo, OceloError> {
+        SystemInfoPoller::get_system_overview(self)
+    }
+
+    fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, OceloError> {
+        SystemInfoPoller::evaluate_alerts(self)
+    }
+}
+
+/// Replays canned snapshots instead of polling the system,
+/// so the user interface and the command line can be exercised deterministically.
+/// Every `get_*` call returns the corresponding part of the next snapshot,
+/// the last snapshot is repeated once the others are used up.
+///
+/// # Example:
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::model::SystemOverviewInfo;
+/// use core::{MockProvider, SystemInfoProvider};
+///
+/// let mut idle_snapshot = SystemOverviewInfo::default();
+/// idle_snapshot.cpu.usage = 5.0;
+/// let mut busy_snapshot = SystemOverviewInfo::default();
+/// busy_snapshot.cpu.usage = 95.0;
+///
+/// let mut provider = MockProvider::new(vec![idle_snapshot.clone(), busy_snapshot])
+///     .with_alert_rules(vec!["cpu.usage > 90".parse()?]);
+/// assert_eq!(provider.get_system_overview()?.cpu.usage, idle_snapshot.cpu.usage);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MockProvider {
+    snapshots: Vec<SystemOverviewInfo>,
+    /// Index of the snapshot returned by the next call
+    position: usize,
+    processes: Vec<ProcessList>,
+    /// Index of the process list returned by the next call
+    process_position: usize,
+    alerts: AlertEngine,
+    /// The snapshot returned by the last call, which the alert rules are evaluated against
+    last_snapshot: SystemOverviewInfo,
+}
+
+impl MockProvider {
+    pub fn new(snapshots: Vec<SystemOverviewInfo>) -> Self {
+        MockProvider {
+            snapshots,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the process lists replayed by `self.get_process_list()`.
+    pub fn with_processes(mut self, processes: Vec<ProcessList>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    /// Sets the rules evaluated by `self.evaluate_alerts()`.
+    pub fn with_alert_rules(mut self, rules: Vec<AlertRule>) -> Self {
+        self.alerts = AlertEngine::new(rules);
+        self
+    }
+
+    fn next_snapshot(&mut self) -> SystemOverviewInfo {
+        self.last_snapshot = next(&self.snapshots, &mut self.position);
+        self.last_snapshot.clone()
+    }
+}
+
+impl SystemInfoProvider for MockProvider {
+    fn get_cpu_info(&mut self) -> Result<CpuInfo, OceloError> {
+        Ok(self.next_snapshot().cpu)
+    }
+
+    /// Every core reports the usage, frequency and temperature of the whole processor.
+    fn get_cpu_amd_memory_info(&mut self) -> Result<CpuMemoryUpdate, OceloError> {
+        let snapshot = self.next_snapshot();
+        let temperature = snapshot.cpu.temperature.unwrap_or_default().round();
+        let core = CpuCore {
+            usage: snapshot.cpu.usage.round() as u64,
+            frequency: snapshot.cpu.frequency,
+            temperature: temperature as u32,
+            max_frequency: snapshot.cpu.max_frequency,
+        };
+
+        Ok(CpuMemoryUpdate {
+            version: SchemaVersion::default(),
+            usage: snapshot.cpu.usage,
+            frequency: snapshot.cpu.frequency as usize,
+            temperature: temperature as usize,
+            cores: vec![core; snapshot.cpu.core_count],
+            memory_stats: snapshot.memory,
+            throttling: false,
+            throttle_count: 0,
+        })
+    }
+
+    /// The snapshots have no topology, an empty one is returned.
+    fn get_cpu_topology(&mut self) -> Result<CpuTopology, OceloError> {
+        Ok(CpuTopology::default())
+    }
+
+    fn get_memory_info(&mut self) -> Result<MemoryInfo, OceloError> {
+        Ok(self.next_snapshot().memory)
+    }
+
+    fn get_disk_info(&mut self) -> Result<DiskInfo, OceloError> {
+        Ok(self.next_snapshot().disks)
+    }
+
+    fn get_network_info(&mut self) -> Result<NetworkInfo, OceloError> {
+        Ok(self.next_snapshot().network)
+    }
+
+    fn get_process_list(&mut self) -> Result<ProcessList, OceloError> {
+        Ok(next(&self.processes, &mut self.process_position))
+    }
+
+    /// The process is looked up in the last returned process list, its details are the canned ones.
+    fn get_process_details(&mut self, pid: u32) -> Result<ProcessInfo, OceloError> {
+        self.processes
+            .get(self.process_position.saturating_sub(1))
+            .and_then(|processes| processes.iter().find(|process| process.pid == pid))
+            .cloned()
+            .ok_or(OceloError::ProcessNotFound(pid))
+    }
+
+    /// The snapshots have no sockets.
+    fn get_connections(&mut self) -> Result<Vec<Connection>, OceloError> {
+        Ok(Vec::new())
+    }
+
+    /// The snapshots have no limits.
+    fn get_limits(&mut self) -> Result<SystemLimits, OceloError> {
+        Ok(SystemLimits::default())
+    }
+
+    /// The snapshots have no security overview.
+    fn get_security(&mut self) -> Result<SecurityOverview, OceloError> {
+        Ok(SecurityOverview::default())
+    }
+
+    /// The snapshots have no sensors.
+    fn get_sensors_info(&mut self) -> Result<SensorsInfo, OceloError> {
+        Ok(SensorsInfo::default())
+    }
+
+    fn get_sessions(&mut self) -> Result<SessionsInfo, OceloError> {
+        Ok(SessionsInfo {
+            sessions: self.next_snapshot().sessions,
+        })
+    }
+
+    /// The snapshots have no services.
+    fn get_services(&mut self) -> Result<ServicesInfo, OceloError> {
+        Ok(ServicesInfo::default())
+    }
+
+    /// The snapshots have no containers.
+    fn get_containers(&mut self) -> Result<ContainersInfo, OceloError> {
+        Ok(ContainersInfo::default())
+    }
+
+    /// The snapshots have no log messages.
+    fn get_logs(&mut self) -> Result<LogsInfo, OceloError> {
+        Ok(LogsInfo::default())
+    }
+
+    fn get_system_overview(&mut self) -> Result<SystemOverviewInfo, OceloError> {
+        Ok(self.next_snapshot())
+    }
+
+    /// The rules are evaluated against the last returned snapshot, without advancing to the next one.
+    fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, OceloError> {
+        Ok(self.alerts.evaluate(&self.last_snapshot))
+    }
+}
+
+/// Returns the item at `position` and advances it, repeating the last item at the end.
+fn next<T: Clone + Default>(items: &[T], position: &mut usize) -> T {
+    let item = items
+        .get(*position)
+        .or_else(|| items.last())
+        .cloned()
+        .unwrap_or_default();
+    if *position < items.len() {
+        *position += 1;
+    }
+    item
+}
@@ -0,0 +1,172 @@
+use crate::config::PublicIpSettings;
+use crate::history::unix_time;
+use crate::http;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The provider answering the public address as plain text, without logging or geolocating it.
+pub const DEFAULT_PUBLIC_IP_PROVIDER: &str = "http://api.ipify.org";
+
+/// The time between two lookups, the public address of a host rarely changes.
+pub const PUBLIC_IP_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// The time between two lookups after a failed one.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long the provider is waited for.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The address of the host as seen from the internet, like behind a NAT router,
+/// with its location if the provider geolocates it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PublicIp {
+    /// `None` until the first successful lookup
+    pub address: Option<String>,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    /// Time of the last successful lookup in seconds since the Unix epoch
+    pub checked_at: Option<f64>,
+    /// Why the last lookup failed, the address of the previous lookup is kept meanwhile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PublicIp {
+    /// Returns the known parts of the location, like `Berlin, Germany`, `None` if it is not known.
+    /// A city which is its own region is only named once.
+    pub fn location(&self) -> Option<String> {
+        let mut parts = [&self.city, &self.region, &self.country]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+        parts.dedup();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Looks up the public address of the host at the provider of the settings every `PUBLIC_IP_REFRESH`
+/// on a background thread, which stops when the lookup is dropped.
+/// The address is cached between the lookups, so the provider learns about the host as rarely as possible.
+///
+/// The provider is requested like by `http::get()`, so the https:// ones need the `https` feature.
+/// It answers the address either as plain text, or as a JSON object with an `ip` or a `query` field
+/// and optionally the `country`, `region` or `regionName` and `city` fields,
+/// like `http://ip-api.com/json?fields=query,country,regionName,city`.
+///
+/// # Example:
+/// ```no_run
+/// use core::config::PublicIpSettings;
+/// use core::PublicIpLookup;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let lookup = PublicIpLookup::start(PublicIpSettings::default());
+/// thread::sleep(Duration::from_secs(1));
+/// println!("{:?}", lookup.public_ip().address);
+/// ```
+pub struct PublicIpLookup {
+    public_ip: Arc<Mutex<PublicIp>>,
+    /// Stops the worker thread when dropped
+    _stop: Sender<()>,
+}
+
+impl PublicIpLookup {
+    pub fn start(settings: PublicIpSettings) -> Self {
+        let public_ip = Arc::new(Mutex::new(PublicIp::default()));
+        let (stop, stopped) = mpsc::channel();
+        let result = Arc::clone(&public_ip);
+        thread::spawn(move || loop {
+            let interval = match lookup(&settings.provider) {
+                Ok(looked_up) => {
+                    if let Ok(mut public_ip) = result.lock() {
+                        *public_ip = looked_up;
+                    }
+                    PUBLIC_IP_REFRESH
+                }
+                Err(error) => {
+                    if let Ok(mut public_ip) = result.lock() {
+                        public_ip.error = Some(error.to_string());
+                    }
+                    RETRY_INTERVAL
+                }
+            };
+            // sleeps until the next lookup, or stops when the lookup is dropped
+            if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(interval) {
+                break;
+            }
+        });
+        PublicIpLookup {
+            public_ip,
+            _stop: stop,
+        }
+    }
+
+    /// Returns the result of the last lookup.
+    pub fn public_ip(&self) -> PublicIp {
+        self.public_ip
+            .lock()
+            .map(|public_ip| public_ip.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Requests the public address from `provider`, like `http://api.ipify.org`.
+fn lookup(provider: &str) -> io::Result<PublicIp> {
+    let invalid = |reason: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{}: {}", provider, reason),
+        )
+    };
+    let response = http::get(
+        provider,
+        &[("Accept", "application/json, text/plain")],
+        REQUEST_TIMEOUT,
+    )?;
+    if response.status != 200 {
+        return Err(invalid(&format!("answered '{}'", response.status_line)));
+    }
+    parse(response.text().trim()).ok_or_else(|| invalid("no address in the answer"))
+}
+
+/// Parses the answer of a provider, a JSON object or the plain address.
+fn parse(body: &str) -> Option<PublicIp> {
+    let public_ip = match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(fields)) => {
+            let field = |names: &[&str]| {
+                names
+                    .iter()
+                    .find_map(|name| fields.get(*name)?.as_str())
+                    .map(String::from)
+            };
+            PublicIp {
+                address: field(&["ip", "query"]),
+                country: field(&["country"]),
+                region: field(&["region", "regionName"]),
+                city: field(&["city"]),
+                ..PublicIp::default()
+            }
+        }
+        _ => PublicIp {
+            address: Some(body.to_string()),
+            ..PublicIp::default()
+        },
+    };
+    // an address parses, while an error page does not
+    public_ip
+        .address
+        .as_deref()?
+        .parse::<std::net::IpAddr>()
+        .ok()?;
+    Some(PublicIp {
+        checked_at: Some(unix_time()),
+        ..public_ip
+    })
+}
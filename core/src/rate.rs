@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The time after which a counter which is no longer observed is forgotten, like the one of a removed container.
+/// It is longer than the interval of every poll, so the polled counters are kept.
+const FORGET_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// Calculates the per-second rates of cumulative counters, like the total bytes read from a disk.
+/// Every counter is identified by a key and remembers the value and the time of its last observation.
+/// The counters not observed for `FORGET_AFTER` are forgotten, so the keys of the removed disks, interfaces
+/// and containers do not pile up.
+///
+/// # Example:
+/// ```no_run
+/// use core::RateTracker;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let mut tracker = RateTracker::default();
+/// tracker.rate("eth0/received", 1_000);
+/// thread::sleep(Duration::from_secs(3));
+/// let bytes_per_second = tracker.rate("eth0/received", 4_000); // 1000.0
+/// ```
+#[derive(Default)]
+pub struct RateTracker {
+    observations: HashMap<String, (Instant, u64)>,
+    /// When the forgotten counters were last removed
+    last_pruned: Option<Instant>,
+}
+
+impl RateTracker {
+    /// Records the current `total` of the counter identified by `key`
+    /// and returns its increase per second since the previous observation.
+    /// The rate is zero on the first observation and when the counter was reset.
+    pub fn rate(&mut self, key: &str, total: u64) -> f64 {
+        let now = Instant::now();
+        self.prune(now);
+        let previous = self.observations.insert(key.to_string(), (now, total));
+
+        match previous {
+            Some((then, previous_total)) if total >= previous_total => {
+                let elapsed = now.duration_since(then).as_secs_f64();
+                if elapsed > 0.0 {
+                    (total - previous_total) as f64 / elapsed
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Removes the counters not observed for `FORGET_AFTER`, at most once in that time.
+    fn prune(&mut self, now: Instant) {
+        match self.last_pruned {
+            Some(last_pruned) if now.duration_since(last_pruned) < FORGET_AFTER => {}
+            _ => {
+                self.observations
+                    .retain(|_, (then, _)| now.duration_since(*then) < FORGET_AFTER);
+                self.last_pruned = Some(now);
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::encoding::{self, Encoding, EncodingError};
+use crate::history::unix_time;
+use crate::SystemInfoUpdate;
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OpenFlags};
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+/// The default length of time the recorded snapshots are kept for.
+pub const DEFAULT_RECORD_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Appends the polled system information to a SQLite database, so it can be replayed later.
+/// The snapshots older than the retention are pruned at every recording.
+/// They are stored as JSON text by default, or as the more compact MessagePack blobs.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::{Encoding, Recorder, SystemInfoPoller, SystemInfoUpdate, DEFAULT_RECORD_RETENTION};
+///
+/// let mut poller = SystemInfoPoller::default();
+/// poller.init();
+/// let mut recorder =
+///     Recorder::open("ocelo.db", DEFAULT_RECORD_RETENTION)?.with_encoding(Encoding::MessagePack);
+/// recorder.record(&SystemInfoUpdate::Disk(poller.get_disk_info()?))?;
+/// let snapshots = Recorder::load("ocelo.db")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Recorder {
+    connection: Connection,
+    retention: Duration,
+    /// The format of the appended snapshots
+    encoding: Encoding,
+}
+
+impl Recorder {
+    /// Opens or creates the database at `path`.
+    pub fn open(path: impl AsRef<Path>, retention: Duration) -> Result<Self, RecorderError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS snapshots (
+                id INTEGER PRIMARY KEY,
+                timestamp REAL NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS snapshots_timestamp ON snapshots (timestamp);",
+        )?;
+
+        Ok(Recorder {
+            connection,
+            retention,
+            encoding: Encoding::Json,
+        })
+    }
+
+    /// Sets the format of the appended snapshots, the ones already recorded are kept in their own format.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Appends `update` with the current time, then removes the snapshots older than the retention.
+    pub fn record(&mut self, update: &SystemInfoUpdate) -> Result<(), RecorderError> {
+        let now = unix_time();
+        let data = match self.encoding {
+            // JSON is kept as text, so the recordings stay readable with the sqlite3 shell
+            Encoding::Json => {
+                Value::Text(serde_json::to_string(update).map_err(EncodingError::from)?)
+            }
+            Encoding::MessagePack => Value::Blob(self.encoding.encode(update)?),
+        };
+
+        self.connection.execute(
+            "INSERT INTO snapshots (timestamp, data) VALUES (?1, ?2)",
+            params![now, data],
+        )?;
+        self.connection.execute(
+            "DELETE FROM snapshots WHERE timestamp < ?1",
+            params![now - self.retention.as_secs_f64()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Reads every snapshot of the database at `path` in the order of their recording,
+    /// with their time in seconds since the Unix epoch, whichever format they were recorded in.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<(f64, SystemInfoUpdate)>, RecorderError> {
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut statement =
+            connection.prepare("SELECT timestamp, data FROM snapshots ORDER BY timestamp, id")?;
+        let rows = statement.query_map([], |row| {
+            // the JSON snapshots are text, the MessagePack ones are blobs
+            let data = row.get_ref(1)?.as_bytes()?.to_vec();
+            Ok((row.get::<_, f64>(0)?, data))
+        })?;
+
+        let mut snapshots = Vec::new();
+        for row in rows {
+            let (timestamp, data) = row?;
+            snapshots.push((timestamp, encoding::decode(&data)?));
+        }
+
+        Ok(snapshots)
+    }
+}
+
+/// Reasons why the snapshots could not be recorded or loaded.
+#[derive(Debug)]
+pub enum RecorderError {
+    /// The database could not be opened, read or written
+    Database(rusqlite::Error),
+    /// A snapshot could not be serialized or deserialized
+    Encoding(EncodingError),
+}
+
+impl Display for RecorderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(error) => write!(f, "database error: {}", error),
+            Self::Encoding(error) => write!(f, "invalid snapshot: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RecorderError {}
+
+impl From<rusqlite::Error> for RecorderError {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl From<EncodingError> for RecorderError {
+    fn from(error: EncodingError) -> Self {
+        Self::Encoding(error)
+    }
+}
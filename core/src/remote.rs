@@ -0,0 +1,167 @@
+use crate::config::Host;
+use crate::connections::Connection;
+use crate::encoding::{self, Encoding};
+use crate::http;
+use crate::limits::SystemLimits;
+use crate::model::{
+    ContainersInfo, CpuMemoryUpdate, CpuTopology, DiskInfo, LogsInfo, NetworkInfo, ProcessInfo,
+    ProcessList, SensorsInfo, ServicesInfo, SessionsInfo, SystemOverviewInfo,
+};
+use crate::security::SecurityOverview;
+use crate::source::{DataSource, DataSourceError};
+use crate::{Alert, AlertEngine, AlertRule, CpuInfo, SystemInfoPollingContext, SystemInfoUpdate};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// The longest time to wait for connecting to the agent and for its answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Fetches the system information from a remote ocelo agent started with `ocelo serve`.
+/// Every poll is a new request, so the source reconnects by itself once the agent is reachable again.
+/// The answers are requested in MessagePack by default, the older agents answer in JSON regardless.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::{DataSource, RemoteSource, SystemInfoPollingContext};
+///
+/// let mut source = RemoteSource::new("192.168.1.10:8080").with_token(Some("secret".to_string()));
+/// let update = source.poll(SystemInfoPollingContext::Overview)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RemoteSource {
+    /// Host and port of the agent, like `192.168.1.10:8080`
+    address: String,
+    /// Bearer token sent in the `Authorization` header, if the agent requires one
+    token: Option<String>,
+    /// The format of the answers requested in the `Accept` header
+    encoding: Encoding,
+    /// Evaluates the user-defined alert rules against the overview of the agent
+    alerts: AlertEngine,
+}
+
+impl RemoteSource {
+    pub fn new(address: impl Into<String>) -> Self {
+        RemoteSource {
+            address: address.into(),
+            token: None,
+            encoding: Encoding::MessagePack,
+            alerts: AlertEngine::default(),
+        }
+    }
+
+    /// Sets the bearer token required by the agent.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Sets the format of the answers requested from the agent.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Sets the rules evaluated by `self.evaluate_alerts()`.
+    pub fn with_alert_rules(mut self, rules: Vec<AlertRule>) -> Self {
+        self.alerts = AlertEngine::new(rules);
+        self
+    }
+
+    /// Creates a source of the agent `host` from the configuration.
+    pub fn from_host(host: &Host) -> Self {
+        Self::new(host.address.clone()).with_token(host.token.clone())
+    }
+
+    /// Requests `path` from the agent and parses the answer, whichever format it is in.
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, DataSourceError> {
+        let authorization = self.token.as_ref().map(|token| format!("Bearer {}", token));
+        let mut headers = vec![("Accept", self.encoding.media_type())];
+        if let Some(authorization) = &authorization {
+            headers.push(("Authorization", authorization));
+        }
+
+        let url = format!("http://{}{}", self.address, path);
+        let response = http::get(&url, &headers, REQUEST_TIMEOUT)?;
+        if response.status != 200 {
+            return Err(DataSourceError::Status(response.status_line));
+        }
+        Ok(encoding::decode(&response.body)?)
+    }
+}
+
+impl DataSource for RemoteSource {
+    fn poll(&mut self, ctx: SystemInfoPollingContext) -> Result<SystemInfoUpdate, DataSourceError> {
+        Ok(match ctx {
+            SystemInfoPollingContext::Overview => {
+                SystemInfoUpdate::OverView(Box::new(self.get::<SystemOverviewInfo>("/overview")?))
+            }
+            SystemInfoPollingContext::CpuAndMemory => {
+                SystemInfoUpdate::CpuAndMemory(self.get::<CpuMemoryUpdate>("/cpu-memory")?)
+            }
+            SystemInfoPollingContext::Processes => {
+                SystemInfoUpdate::Process(self.get::<ProcessList>("/processes")?)
+            }
+            SystemInfoPollingContext::Disks => {
+                SystemInfoUpdate::Disk(self.get::<DiskInfo>("/disks")?)
+            }
+            SystemInfoPollingContext::Network => {
+                SystemInfoUpdate::Network(Box::new(self.get::<NetworkInfo>("/network")?))
+            }
+            SystemInfoPollingContext::Connections => {
+                SystemInfoUpdate::Connections(self.get::<Vec<Connection>>("/connections")?)
+            }
+            SystemInfoPollingContext::Limits => {
+                SystemInfoUpdate::Limits(self.get::<SystemLimits>("/limits")?)
+            }
+            SystemInfoPollingContext::Security => {
+                SystemInfoUpdate::Security(self.get::<SecurityOverview>("/security")?)
+            }
+            SystemInfoPollingContext::Sensors => {
+                SystemInfoUpdate::Sensors(self.get::<SensorsInfo>("/sensors")?)
+            }
+            SystemInfoPollingContext::Users => {
+                SystemInfoUpdate::Users(self.get::<SessionsInfo>("/users")?)
+            }
+            SystemInfoPollingContext::Services => {
+                SystemInfoUpdate::Services(self.get::<ServicesInfo>("/services")?)
+            }
+            SystemInfoPollingContext::Containers => {
+                SystemInfoUpdate::Containers(self.get::<ContainersInfo>("/containers")?)
+            }
+            SystemInfoPollingContext::Logs => {
+                SystemInfoUpdate::Logs(self.get::<LogsInfo>("/logs")?)
+            }
+        })
+    }
+
+    fn cpu_info(&mut self) -> Result<CpuInfo, DataSourceError> {
+        self.get("/cpu")
+    }
+
+    fn cpu_topology(&mut self) -> Result<CpuTopology, DataSourceError> {
+        self.get("/cpu-topology")
+    }
+
+    /// Nothing is requested when there are no rules.
+    /// Nothing is raised while the agent is unreachable, as that is already reported by `self.poll()`.
+    fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, DataSourceError> {
+        if self.alerts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match self.get::<SystemOverviewInfo>("/overview") {
+            Ok(snapshot) => Ok(self.alerts.evaluate(&snapshot)),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    fn process_details(&mut self, pid: u32) -> Result<ProcessInfo, DataSourceError> {
+        self.get(&format!("/processes/{}", pid))
+    }
+
+    fn remote_address(&self) -> Option<&str> {
+        Some(&self.address)
+    }
+}
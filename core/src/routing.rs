@@ -0,0 +1,199 @@
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+
+/// A route of the kernel routing table, like listed by `ip route`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Route {
+    /// The network reached by the route with its prefix, like `192.168.1.0/24`, `0.0.0.0/0` for the default route
+    pub destination: String,
+    /// The router forwarding the packets, `None` if the network is directly attached to the interface
+    pub gateway: Option<String>,
+    /// Name of the interface sending the packets
+    pub interface: String,
+    /// The priority of the route, the route with the lowest metric is preferred among the matching ones
+    pub metric: u32,
+}
+
+impl Route {
+    /// Returns true if the route matches every address, so it is taken when no other route matches.
+    pub fn is_default(&self) -> bool {
+        self.destination == "0.0.0.0/0" || self.destination == "::/0"
+    }
+}
+
+/// An entry of the ARP cache: a host of a directly attached network whose hardware address is known,
+/// or being resolved.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Neighbor {
+    /// The IP address of the host
+    pub address: String,
+    /// The hardware address of the host, empty while it is not resolved
+    pub mac_address: String,
+    /// Name of the interface the host is reachable on
+    pub interface: String,
+    /// `complete`, `incomplete` while the address is being resolved or `permanent` if it was added manually
+    pub state: String,
+}
+
+/// Lists the IPv4 and IPv6 routes of the system ordered by the default routes first, then by their metric.
+/// The routes are read from `/proc/net`, so they are only listed on Linux.
+/// The local, broadcast and multicast routes maintained by the kernel are left out, like `ip route` does.
+#[cfg(target_os = "linux")]
+pub fn routes() -> Result<Vec<Route>, OceloError> {
+    linux::routes()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn routes() -> Result<Vec<Route>, OceloError> {
+    Err(OceloError::Unsupported(
+        "listing the routes is only supported on Linux".to_string(),
+    ))
+}
+
+/// Lists the IPv4 neighbors of the ARP cache from `/proc/net/arp`, ordered by interface and address.
+/// The IPv6 neighbors are only exposed over netlink, so they are not listed.
+#[cfg(target_os = "linux")]
+pub fn neighbors() -> Result<Vec<Neighbor>, OceloError> {
+    linux::neighbors()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn neighbors() -> Result<Vec<Neighbor>, OceloError> {
+    Err(OceloError::Unsupported(
+        "listing the neighbors is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{Neighbor, Route};
+    use crate::OceloError;
+    use std::cmp::Reverse;
+    use std::fs;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    /// The route is usable
+    const RTF_UP: u32 = 0x0001;
+    /// The route goes through a gateway
+    const RTF_GATEWAY: u32 = 0x0002;
+    /// The route rejects the packets
+    const RTF_REJECT: u32 = 0x0200;
+    /// The route of a local address of the host
+    const RTF_LOCAL: u32 = 0x8000_0000;
+
+    /// The hardware address of the neighbor is resolved
+    const ATF_COM: u32 = 0x02;
+    /// The neighbor was added manually
+    const ATF_PERM: u32 = 0x04;
+
+    pub fn routes() -> Result<Vec<Route>, OceloError> {
+        let table = fs::read_to_string("/proc/net/route")
+            .map_err(|error| OceloError::Io(format!("failed to read the routes: {}", error)))?;
+        let mut routes: Vec<Route> = table.lines().skip(1).filter_map(parse_ipv4_route).collect();
+        // IPv6 may be disabled
+        if let Ok(table) = fs::read_to_string("/proc/net/ipv6_route") {
+            routes.extend(table.lines().filter_map(parse_ipv6_route));
+        }
+        routes.sort_by_key(|route| (Reverse(route.is_default()), route.metric));
+        Ok(routes)
+    }
+
+    /// Parses a line of `/proc/net/route` like
+    /// `eth0 00000000 0101A8C0 0003 0 0 100 00000000 0 0 0`:
+    /// the interface, the destination, the gateway, the flags, the reference count, the use count, the metric
+    /// and the mask, with the addresses in the byte order of the host.
+    fn parse_ipv4_route(line: &str) -> Option<Route> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [interface, destination, gateway, flags, _, _, metric, mask, ..] = fields.as_slice()
+        else {
+            return None;
+        };
+        let flags = u32::from_str_radix(flags, 16).ok()?;
+        if flags & RTF_UP == 0 || flags & RTF_REJECT != 0 {
+            return None;
+        }
+        let address = |hex: &str| {
+            u32::from_str_radix(hex, 16)
+                .ok()
+                .map(|address| Ipv4Addr::from(address.to_ne_bytes()))
+        };
+        let prefix = u32::from_str_radix(mask, 16).ok()?.count_ones();
+
+        Some(Route {
+            destination: format!("{}/{}", address(destination)?, prefix),
+            gateway: (flags & RTF_GATEWAY != 0)
+                .then(|| address(gateway))
+                .flatten()
+                .map(|gateway| gateway.to_string()),
+            interface: interface.to_string(),
+            metric: metric.parse().ok()?,
+        })
+    }
+
+    /// Parses a line of `/proc/net/ipv6_route`: the destination, its prefix, the source, its prefix, the gateway,
+    /// the metric, the reference count, the use count, the flags and the interface,
+    /// with the addresses in the network byte order and the numbers in hexadecimal.
+    fn parse_ipv6_route(line: &str) -> Option<Route> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [destination, prefix, _, _, gateway, metric, _, _, flags, interface] =
+            fields.as_slice()
+        else {
+            return None;
+        };
+        let flags = u32::from_str_radix(flags, 16).ok()?;
+        let destination = parse_ipv6(destination)?;
+        if flags & RTF_UP == 0
+            || flags & (RTF_REJECT | RTF_LOCAL) != 0
+            || destination.is_multicast()
+        {
+            return None;
+        }
+
+        Some(Route {
+            destination: format!("{}/{}", destination, u8::from_str_radix(prefix, 16).ok()?),
+            gateway: (flags & RTF_GATEWAY != 0)
+                .then(|| parse_ipv6(gateway))
+                .flatten()
+                .map(|gateway| gateway.to_string()),
+            interface: interface.to_string(),
+            metric: u32::from_str_radix(metric, 16).ok()?,
+        })
+    }
+
+    fn parse_ipv6(hex: &str) -> Option<Ipv6Addr> {
+        u128::from_str_radix(hex, 16).ok().map(Ipv6Addr::from)
+    }
+
+    pub fn neighbors() -> Result<Vec<Neighbor>, OceloError> {
+        let table = fs::read_to_string("/proc/net/arp")
+            .map_err(|error| OceloError::Io(format!("failed to read the ARP cache: {}", error)))?;
+        // the columns are the address, the hardware type, the flags, the hardware address, the mask and the interface
+        let mut neighbors: Vec<Neighbor> = table
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let [address, _, flags, mac_address, _, interface] = fields.as_slice() else {
+                    return None;
+                };
+                let flags = u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok()?;
+                let state = match flags {
+                    _ if flags & ATF_PERM != 0 => "permanent",
+                    _ if flags & ATF_COM != 0 => "complete",
+                    _ => "incomplete",
+                };
+                Some(Neighbor {
+                    address: address.to_string(),
+                    mac_address: match flags & ATF_COM != 0 {
+                        true => mac_address.to_string(),
+                        false => String::new(),
+                    },
+                    interface: interface.to_string(),
+                    state: state.to_string(),
+                })
+            })
+            .collect();
+        neighbors.sort_by(|a, b| (&a.interface, &a.address).cmp(&(&b.interface, &b.address)));
+        Ok(neighbors)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{SystemInfoPollingContext, MAX_POLLING_INTERVAL, MIN_POLLING_INTERVAL};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The interval of polling the processes, which change the most often.
+pub const PROCESSES_POLLING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The interval of polling the temperatures, fan speeds and voltages of the sensors.
+pub const SENSORS_POLLING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The interval of polling the disks, whose space usage changes slowly.
+pub const DISKS_POLLING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Decides when the polling contexts are polled, each of them at its own interval.
+/// The processes, the sensors and the disks are polled at their natural rates by default,
+/// the other contexts at the default interval, until their interval is changed.
+///
+/// # Example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use core::{DataSource, PollingScheduler, ProviderSource, SystemInfoPoller, SystemInfoPollingContext};
+/// use std::thread;
+/// use std::time::{Duration, Instant};
+///
+/// let mut source = ProviderSource::new(SystemInfoPoller::default());
+/// let mut scheduler = PollingScheduler::new(Duration::from_secs(3));
+/// scheduler.schedule(SystemInfoPollingContext::Overview);
+/// scheduler.schedule(SystemInfoPollingContext::Disks);
+/// loop {
+///     for ctx in scheduler.due(Instant::now()) {
+///         source.poll(ctx)?;
+///     }
+///     thread::sleep(scheduler.until_next(Instant::now()));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PollingScheduler {
+    /// The interval of the contexts without their own interval
+    default_interval: Duration,
+    /// The intervals changed by the user, by context
+    intervals: HashMap<SystemInfoPollingContext, Duration>,
+    /// The polled contexts with the time of their next poll
+    scheduled: Vec<(SystemInfoPollingContext, Instant)>,
+}
+
+impl PollingScheduler {
+    /// Creates a scheduler without any polled context.
+    /// The `default_interval` is clamped between `MIN_POLLING_INTERVAL` and `MAX_POLLING_INTERVAL`.
+    pub fn new(default_interval: Duration) -> Self {
+        PollingScheduler {
+            default_interval: default_interval.clamp(MIN_POLLING_INTERVAL, MAX_POLLING_INTERVAL),
+            intervals: HashMap::new(),
+            scheduled: Vec::new(),
+        }
+    }
+
+    /// Returns the time between two consecutive polls of `ctx`.
+    pub fn interval(&self, ctx: SystemInfoPollingContext) -> Duration {
+        self.intervals
+            .get(&ctx)
+            .copied()
+            .or(match ctx {
+                SystemInfoPollingContext::Processes => Some(PROCESSES_POLLING_INTERVAL),
+                SystemInfoPollingContext::Sensors => Some(SENSORS_POLLING_INTERVAL),
+                SystemInfoPollingContext::Disks => Some(DISKS_POLLING_INTERVAL),
+                _ => None,
+            })
+            .unwrap_or(self.default_interval)
+    }
+
+    /// Changes the time between two consecutive polls of `ctx`, taking effect after its next poll.
+    /// The interval is clamped between `MIN_POLLING_INTERVAL` and `MAX_POLLING_INTERVAL`.
+    pub fn set_interval(&mut self, ctx: SystemInfoPollingContext, interval: Duration) {
+        self.intervals.insert(
+            ctx,
+            interval.clamp(MIN_POLLING_INTERVAL, MAX_POLLING_INTERVAL),
+        );
+    }
+
+    /// Returns the polled contexts.
+    pub fn contexts(&self) -> Vec<SystemInfoPollingContext> {
+        self.scheduled.iter().map(|(ctx, _)| *ctx).collect()
+    }
+
+    /// Starts polling `ctx`, which is due right away.
+    pub fn schedule(&mut self, ctx: SystemInfoPollingContext) {
+        self.unschedule(ctx);
+        self.scheduled.push((ctx, Instant::now()));
+    }
+
+    /// Polls only `contexts` from now on, keeping the next polls of the ones which are already polled,
+    /// while the other ones are due right away.
+    pub fn reschedule(&mut self, contexts: &[SystemInfoPollingContext]) {
+        self.scheduled
+            .retain(|(scheduled, _)| contexts.contains(scheduled));
+        for ctx in contexts {
+            if !self.scheduled.iter().any(|(scheduled, _)| scheduled == ctx) {
+                self.scheduled.push((*ctx, Instant::now()));
+            }
+        }
+    }
+
+    /// Stops polling `ctx`.
+    pub fn unschedule(&mut self, ctx: SystemInfoPollingContext) {
+        self.scheduled.retain(|(scheduled, _)| *scheduled != ctx);
+    }
+
+    /// Makes every polled context due right away, like after switching to another host.
+    pub fn poll_now(&mut self) {
+        let now = Instant::now();
+        for (_, next_poll) in self.scheduled.iter_mut() {
+            *next_poll = now;
+        }
+    }
+
+    /// Returns the contexts which are due at `now`, scheduling their next polls after their intervals.
+    pub fn due(&mut self, now: Instant) -> Vec<SystemInfoPollingContext> {
+        let due: Vec<SystemInfoPollingContext> = self
+            .scheduled
+            .iter()
+            .filter(|(_, next_poll)| *next_poll <= now)
+            .map(|(ctx, _)| *ctx)
+            .collect();
+        for ctx in &due {
+            let next_poll = now + self.interval(*ctx);
+            if let Some(scheduled) = self.scheduled.iter_mut().find(|(other, _)| other == ctx) {
+                scheduled.1 = next_poll;
+            }
+        }
+        due
+    }
+
+    /// Returns the time from `now` until the next context is due,
+    /// `MAX_POLLING_INTERVAL` if no context is polled.
+    pub fn until_next(&self, now: Instant) -> Duration {
+        self.scheduled
+            .iter()
+            .map(|(_, next_poll)| next_poll.saturating_duration_since(now))
+            .min()
+            .unwrap_or(MAX_POLLING_INTERVAL)
+    }
+}
@@ -0,0 +1,341 @@
+use crate::connections::Connection;
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+
+/// The period in seconds over which the failed logins are counted.
+pub const FAILED_LOGIN_WINDOW: u64 = 24 * 60 * 60;
+
+/// The most addresses kept with the count of their failed logins.
+const TOP_SOURCES: usize = 10;
+
+/// An address which the failed logins came from.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoginSource {
+    pub address: String,
+    pub attempts: u32,
+}
+
+/// The failed SSH logins found in the log of the authentications.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailedLogins {
+    /// Count of the rejected authentications and of the logins as unknown users
+    pub attempts: u32,
+    /// The addresses with the most attempts, the most persistent first
+    pub sources: Vec<LoginSource>,
+    /// The log which the attempts were read from, like `journal` or `/var/log/auth.log`
+    pub log: String,
+    /// The seconds since the Unix epoch from which the attempts are counted, `FAILED_LOGIN_WINDOW` ago.
+    /// `None` if the log has no dates, then every attempt in its recent part is counted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<u64>,
+}
+
+/// The state of the firewall filtering the incoming connections.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Firewall {
+    /// The firewall found, like `ufw`, `firewalld`, `nftables` or `iptables`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// True if the incoming connections are filtered, `None` if the rules can not be read,
+    /// which requires root privileges
+    pub active: Option<bool>,
+    /// Count of the rules filtering the incoming connections, `None` if they can not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<u32>,
+}
+
+/// A glance at how the system is exposed to the network: the failed SSH logins,
+/// the ports open to the other hosts and the firewall in front of them.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SecurityOverview {
+    /// `None` if no log of the authentications can be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_logins: Option<FailedLogins>,
+    /// The sockets listening on an address other than the loopback one
+    pub exposed_ports: Vec<Connection>,
+    pub firewall: Firewall,
+}
+
+impl SecurityOverview {
+    /// Returns true if ports are open to the other hosts and no firewall is known to filter them.
+    pub fn is_exposed(&self) -> bool {
+        !self.exposed_ports.is_empty() && self.firewall.active != Some(true)
+    }
+}
+
+/// Reads the failed SSH logins of the last `FAILED_LOGIN_WINDOW` from the journal of systemd or from the log
+/// of the authentications, the ports listening beyond the loopback address and the state of the firewall.
+/// The journal is only read with the `systemd` feature, and the failed logins and the rules of the firewall
+/// are only found with the permission to read them. They are only read on Linux.
+#[cfg(target_os = "linux")]
+pub fn security_overview() -> Result<SecurityOverview, OceloError> {
+    linux::security_overview()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn security_overview() -> Result<SecurityOverview, OceloError> {
+    Err(OceloError::Unsupported(
+        "reading the security overview is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{
+        FailedLogins, Firewall, LoginSource, SecurityOverview, FAILED_LOGIN_WINDOW, TOP_SOURCES,
+    };
+    use crate::connections::{listening_ports, Connection};
+    use crate::history::unix_time;
+    use crate::log_stream::parse_rfc3339;
+    use crate::OceloError;
+    use std::collections::HashMap;
+    use std::fs::{self, File};
+    use std::io::{Read, Seek, SeekFrom};
+    use std::process::Command;
+
+    /// The logs of the authentications of Debian and of Red Hat based distributions.
+    const AUTH_LOGS: [&str; 2] = ["/var/log/auth.log", "/var/log/secure"];
+
+    /// The longest part of the end of an authentication log which is read.
+    const AUTH_LOG_BYTES: u64 = 1024 * 1024;
+
+    /// The names which the SSH daemon logs with, `sshd-session` handles the connections since OpenSSH 9.8.
+    const SSHD_IDENTIFIERS: [&str; 2] = ["sshd", "sshd-session"];
+
+    pub fn security_overview() -> Result<SecurityOverview, OceloError> {
+        let exposed_ports = listening_ports()?
+            .into_iter()
+            .filter(Connection::is_exposed)
+            .collect();
+        Ok(SecurityOverview {
+            failed_logins: failed_logins(),
+            exposed_ports,
+            firewall: firewall(),
+        })
+    }
+
+    fn failed_logins() -> Option<FailedLogins> {
+        #[cfg(feature = "systemd")]
+        if let Some(logins) = journal_failed_logins() {
+            return Some(logins);
+        }
+        AUTH_LOGS.iter().find_map(|path| file_failed_logins(path))
+    }
+
+    /// Reads the messages of the SSH daemon from the journal of systemd with `journalctl`,
+    /// `None` if it fails or if only the messages of the user can be read.
+    #[cfg(feature = "systemd")]
+    fn journal_failed_logins() -> Option<FailedLogins> {
+        let since = (unix_time() as u64).saturating_sub(FAILED_LOGIN_WINDOW);
+        let mut command = Command::new("journalctl");
+        command.args(["--output=cat", "--no-pager", &format!("--since=@{}", since)]);
+        for identifier in SSHD_IDENTIFIERS {
+            command.arg(format!("--identifier={}", identifier));
+        }
+        let output = command.output().ok()?;
+        // without being in the `systemd-journal` or the `adm` group, only the messages of the user are printed
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() || stderr.contains("not seeing messages") {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // `-- No entries --` is printed when nothing matches
+        let messages = stdout.lines().filter(|line| !line.starts_with("-- "));
+        Some(count_failed_logins(messages, "journal", Some(since)))
+    }
+
+    /// Reads the messages of the SSH daemon from the end of the log at `path`, `None` if it can not be read.
+    /// The lines are only filtered by their date if it is written like `2026-10-15T09:30:00.123456+02:00`.
+    fn file_failed_logins(path: &str) -> Option<FailedLogins> {
+        let mut file = File::open(path).ok()?;
+        let length = file.metadata().ok()?.len();
+        let start = length.saturating_sub(AUTH_LOG_BYTES);
+        file.seek(SeekFrom::Start(start)).ok()?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).ok()?;
+        let content = String::from_utf8_lossy(&content);
+        // the first line is cut if the reading started in the middle of the file
+        let lines = content.lines().skip(usize::from(start > 0));
+
+        let since = (unix_time() as u64).saturating_sub(FAILED_LOGIN_WINDOW);
+        let mut dated = true;
+        let messages = lines.filter_map(|line| {
+            let (timestamp, rest) = line.split_once(' ')?;
+            match parse_rfc3339(timestamp) {
+                Some(time) if time < since => return None,
+                Some(_) => {}
+                None => dated = false,
+            }
+            // the lines look like `<date> <host> sshd[812]: Failed password for ...`
+            let (program, message) = rest.split_once("]: ")?;
+            let identifier = program.rsplit(' ').next()?.split('[').next()?;
+            SSHD_IDENTIFIERS.contains(&identifier).then_some(message)
+        });
+        let mut logins = count_failed_logins(messages, path, None);
+        logins.since = dated.then_some(since);
+        Some(logins)
+    }
+
+    /// Counts the failed logins in the `messages` of the SSH daemon, like `Failed password for root from 203.0.113.7
+    /// port 41022 ssh2` and `Invalid user admin from 203.0.113.7 port 41022`. A failed authentication as an unknown
+    /// user follows the message of the unknown user, so it is not counted again.
+    fn count_failed_logins<'a>(
+        messages: impl Iterator<Item = &'a str>,
+        log: &str,
+        since: Option<u64>,
+    ) -> FailedLogins {
+        let mut attempts = 0;
+        let mut sources: HashMap<&str, u32> = HashMap::new();
+        for message in messages {
+            let failed = match message.strip_prefix("Failed ") {
+                Some(rest) => !rest.contains(" for invalid user "),
+                None => message.starts_with("Invalid user "),
+            };
+            if !failed {
+                continue;
+            }
+            attempts += 1;
+            if let Some((_, rest)) = message.split_once(" from ") {
+                if let Some(address) = rest.split_whitespace().next() {
+                    *sources.entry(address).or_default() += 1;
+                }
+            }
+        }
+
+        let mut sources: Vec<LoginSource> = sources
+            .into_iter()
+            .map(|(address, attempts)| LoginSource {
+                address: address.to_string(),
+                attempts,
+            })
+            .collect();
+        sources.sort_by(|a, b| {
+            b.attempts
+                .cmp(&a.attempts)
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        sources.truncate(TOP_SOURCES);
+        FailedLogins {
+            attempts,
+            sources,
+            log: log.to_string(),
+            since,
+        }
+    }
+
+    /// Finds the firewall filtering the incoming connections. ufw and firewalld manage the rules of nftables
+    /// or iptables, so they are told by their own state, the rules only by reading them with root privileges.
+    fn firewall() -> Firewall {
+        let rules = nftables_rules().or_else(iptables_rules);
+        let count = rules.as_ref().map(|rules| rules.count);
+        let ufw = ufw_enabled();
+        if ufw == Some(true) {
+            return Firewall {
+                name: Some("ufw".to_string()),
+                active: Some(true),
+                rules: count,
+            };
+        }
+        if process_running("firewalld") {
+            return Firewall {
+                name: Some("firewalld".to_string()),
+                active: Some(true),
+                rules: count,
+            };
+        }
+        match rules {
+            Some(rules) => Firewall {
+                active: Some(rules.count > 0 || rules.dropping),
+                name: Some(rules.backend.to_string()),
+                rules: Some(rules.count),
+            },
+            None => Firewall {
+                // ufw is installed but disabled, the rules might still be set without it
+                name: ufw.map(|_| "ufw".to_string()),
+                active: None,
+                rules: None,
+            },
+        }
+    }
+
+    /// The rules of the chains filtering the incoming packets.
+    struct InputRules {
+        backend: &'static str,
+        count: u32,
+        /// True if the packets matching no rule are dropped
+        dropping: bool,
+    }
+
+    /// Reads the rules of the chains hooked on the input with `nft`, `None` without root privileges.
+    /// The chains are listed like:
+    /// ```text
+    /// table inet filter {
+    ///     chain input {
+    ///         type filter hook input priority filter; policy drop;
+    ///         tcp dport 22 accept
+    ///     }
+    /// }
+    /// ```
+    fn nftables_rules() -> Option<InputRules> {
+        let ruleset = run("nft", &["list", "ruleset"])?;
+        let mut rules = InputRules {
+            backend: "nftables",
+            count: 0,
+            dropping: false,
+        };
+        let (mut in_chain, mut input) = (false, false);
+        for line in ruleset.lines().map(str::trim) {
+            if line.starts_with("chain ") {
+                (in_chain, input) = (true, false);
+            } else if line.starts_with("type ") && line.contains(" hook input ") {
+                input = true;
+                rules.dropping |= line.contains("policy drop");
+            } else if line == "}" {
+                (in_chain, input) = (false, false);
+            } else if in_chain && input && !line.is_empty() {
+                rules.count += 1;
+            }
+        }
+        Some(rules)
+    }
+
+    /// Reads the rules of the `INPUT` chain with `iptables`, `None` without root privileges.
+    /// The rules are listed like `-A INPUT -p tcp --dport 22 -j ACCEPT` after the policy like `-P INPUT DROP`.
+    fn iptables_rules() -> Option<InputRules> {
+        let rules = run("iptables", &["--list-rules", "INPUT"])?;
+        Some(InputRules {
+            backend: "iptables",
+            count: rules.lines().filter(|line| line.starts_with("-A ")).count() as u32,
+            dropping: rules.lines().any(|line| line == "-P INPUT DROP"),
+        })
+    }
+
+    /// Returns true if ufw is enabled in its configuration, `None` if it is not installed.
+    fn ufw_enabled() -> Option<bool> {
+        let config = fs::read_to_string("/etc/ufw/ufw.conf").ok()?;
+        Some(config.lines().any(|line| {
+            line.trim()
+                .strip_prefix("ENABLED=")
+                .is_some_and(|value| value.trim_matches(['"', '\'']) == "yes")
+        }))
+    }
+
+    /// Returns true if a process is named `name`.
+    fn process_running(name: &str) -> bool {
+        fs::read_dir("/proc").is_ok_and(|entries| {
+            entries.flatten().any(|entry| {
+                fs::read_to_string(entry.path().join("comm"))
+                    .is_ok_and(|comm| comm.trim_end() == name)
+            })
+        })
+    }
+
+    /// Runs `program` with `args`, returning its output if it succeeds.
+    fn run(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
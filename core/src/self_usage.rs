@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// The CPU usage of ocelo in percent of one core above which its cost is highlighted,
+/// a monitor should stay well below the processes it watches.
+pub const SELF_CPU_BUDGET: f32 = 5.0;
+
+/// The resident memory of ocelo in bytes above which its cost is highlighted.
+pub const SELF_MEMORY_BUDGET: u64 = 100 * 1024 * 1024;
+
+/// The resources used by ocelo itself, measured by `SelfMonitor`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SelfUsage {
+    /// CPU usage in percent of one core
+    pub cpu_usage: f32,
+    /// Resident memory in bytes
+    pub memory: u64,
+}
+
+impl SelfUsage {
+    /// Returns true if ocelo uses more than `SELF_CPU_BUDGET` or `SELF_MEMORY_BUDGET`.
+    pub fn is_over_budget(&self) -> bool {
+        self.cpu_usage > SELF_CPU_BUDGET || self.memory > SELF_MEMORY_BUDGET
+    }
+}
+
+/// Measures the CPU and memory cost of the ocelo process, refreshing only its own entry
+/// so the measurement does not add to the cost.
+/// The CPU usage is calculated from the difference of two measurements, the first one is always zero.
+///
+/// # Example:
+/// ```
+/// use core::SelfMonitor;
+///
+/// let mut monitor = SelfMonitor::default();
+/// if let Some(usage) = monitor.measure() {
+///     println!("ocelo: {:.1}% CPU, {} bytes", usage.cpu_usage, usage.memory);
+/// }
+/// ```
+pub struct SelfMonitor {
+    system: System,
+    /// The pid of ocelo, unknown on the platforms which sysinfo does not support
+    pid: Option<Pid>,
+}
+
+impl Default for SelfMonitor {
+    fn default() -> Self {
+        SelfMonitor {
+            system: System::new(),
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+}
+
+impl SelfMonitor {
+    /// Returns the current usage of ocelo, or `None` if its process can not be read.
+    pub fn measure(&mut self) -> Option<SelfUsage> {
+        let pid = self.pid?;
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        self.system.process(pid).map(|process| SelfUsage {
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+        })
+    }
+}
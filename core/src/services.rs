@@ -0,0 +1,179 @@
+use crate::model::Service;
+use std::fmt::{self, Display, Formatter};
+
+/// The actions which can be taken on a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl Display for ServiceAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => write!(f, "start"),
+            Self::Stop => write!(f, "stop"),
+            Self::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+/// Reasons why a service could not be controlled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceControlError {
+    /// The services are only controlled on Linux, with the `systemd` feature
+    Unsupported,
+    /// Controlling the service requires elevated privileges
+    PermissionDenied(String),
+    /// systemd refused or failed to carry out the action, with its message
+    Failed(String, String),
+}
+
+impl Display for ServiceControlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "services are not supported on this platform"),
+            Self::PermissionDenied(name) => write!(
+                f,
+                "permission denied to control {}, it may require elevated privileges",
+                name
+            ),
+            Self::Failed(name, message) => write!(f, "{}: {}", name, message),
+        }
+    }
+}
+
+impl std::error::Error for ServiceControlError {}
+
+/// Lists the service units loaded by systemd with their state and resource accounting.
+/// systemd is queried over D-Bus by `systemctl`, the services are only listed on Linux with the `systemd` feature,
+/// elsewhere and on systems without systemd no services are returned.
+/// The memory and the CPU time are only known for the services with accounting enabled.
+#[cfg(all(target_os = "linux", feature = "systemd"))]
+pub fn services() -> Vec<Service> {
+    systemd::services()
+}
+
+#[cfg(not(all(target_os = "linux", feature = "systemd")))]
+pub fn services() -> Vec<Service> {
+    Vec::new()
+}
+
+/// Takes `action` on the service unit `name`. The job is queued by systemd without waiting for it to finish,
+/// so a service failing to start is only seen in its state later.
+/// systemd is not allowed to ask for a password, the services of the system can usually only be controlled by root.
+#[cfg(all(target_os = "linux", feature = "systemd"))]
+pub fn control_service(name: &str, action: ServiceAction) -> Result<(), ServiceControlError> {
+    systemd::control_service(name, action)
+}
+
+#[cfg(not(all(target_os = "linux", feature = "systemd")))]
+pub fn control_service(_name: &str, _action: ServiceAction) -> Result<(), ServiceControlError> {
+    Err(ServiceControlError::Unsupported)
+}
+
+#[cfg(all(target_os = "linux", feature = "systemd"))]
+mod systemd {
+    use super::{ServiceAction, ServiceControlError};
+    use crate::model::Service;
+    use std::collections::HashMap;
+    use std::process::Command;
+
+    /// The properties of the units which are read.
+    const PROPERTIES: &str =
+        "Id,Description,LoadState,ActiveState,SubState,ActiveEnterTimestamp,MainPID,MemoryCurrent,CPUUsageNSec";
+
+    /// The value of the accounting properties which are not set.
+    const NOT_SET: u64 = u64::MAX;
+
+    pub fn services() -> Vec<Service> {
+        // the timestamps are printed as `@seconds` since systemd 248
+        let Ok(output) = Command::new("systemctl")
+            .args([
+                "show",
+                "--property",
+                PROPERTIES,
+                "--timestamp=unix",
+                "--",
+                "*.service",
+            ])
+            .output()
+        else {
+            return Vec::new();
+        };
+        if !output.status.success() {
+            return Vec::new();
+        }
+
+        // the units are separated by empty lines, each of their properties is printed as `Name=value`
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut services = stdout
+            .split("\n\n")
+            .filter_map(|unit| {
+                let properties = unit
+                    .lines()
+                    .filter_map(|line| line.split_once('='))
+                    .collect::<HashMap<&str, &str>>();
+                parse_service(&properties)
+            })
+            .collect::<Vec<Service>>();
+        services.sort_by(|a, b| a.name.cmp(&b.name));
+        services
+    }
+
+    /// Creates the service from the properties of its unit, `None` if the unit file does not exist.
+    fn parse_service(properties: &HashMap<&str, &str>) -> Option<Service> {
+        let name = properties.get("Id")?;
+        if properties.get("LoadState") == Some(&"not-found") {
+            return None;
+        }
+        let number = |property: &str| {
+            properties
+                .get(property)?
+                .parse::<u64>()
+                .ok()
+                .filter(|value| *value != NOT_SET)
+        };
+        let text = |property: &str| properties.get(property).unwrap_or(&"").to_string();
+
+        Some(Service {
+            name: name.to_string(),
+            description: text("Description"),
+            active_state: text("ActiveState"),
+            sub_state: text("SubState"),
+            active_since: properties
+                .get("ActiveEnterTimestamp")
+                .and_then(|timestamp| timestamp.strip_prefix('@')?.parse::<u64>().ok())
+                .filter(|_| properties.get("ActiveState") == Some(&"active")),
+            main_pid: number("MainPID")
+                .filter(|pid| *pid != 0)
+                .map(|pid| pid as u32),
+            memory: number("MemoryCurrent"),
+            cpu_time: number("CPUUsageNSec").map(|nanoseconds| nanoseconds / 1_000_000),
+        })
+    }
+
+    pub fn control_service(name: &str, action: ServiceAction) -> Result<(), ServiceControlError> {
+        let output = Command::new("systemctl")
+            .args([
+                "--no-ask-password",
+                "--no-block",
+                &action.to_string(),
+                "--",
+                name,
+            ])
+            .output()
+            .map_err(|error| ServiceControlError::Failed(name.to_string(), error.to_string()))?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if message.contains("Access denied") || message.contains("authentication required") {
+            Err(ServiceControlError::PermissionDenied(name.to_string()))
+        } else {
+            Err(ServiceControlError::Failed(name.to_string(), message))
+        }
+    }
+}
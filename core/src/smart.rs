@@ -0,0 +1,111 @@
+use crate::model::DriveHealth;
+use serde_json::Value;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// The shortest time between two readings of the SMART data.
+/// The attributes change slowly, while querying every drive takes a while.
+const MIN_READING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The ATA attribute counting the reallocated sectors
+const REALLOCATED_SECTOR_COUNT: u64 = 5;
+
+/// Reads the SMART health of the physical drives with `smartctl` from smartmontools.
+/// The drives are listed by `smartctl --scan`, then each of them is queried with the JSON output of `smartctl`.
+/// Reading the SMART data usually requires root privileges, the drives which can not be queried are left out.
+/// The drives in standby are not woken up, they are left out until they spin up again.
+///
+/// # Example:
+/// ```no_run
+/// use core::SmartMonitor;
+///
+/// let mut monitor = SmartMonitor::default();
+/// let drives = monitor.drives();
+/// ```
+#[derive(Default)]
+pub struct SmartMonitor {
+    /// The drives of the last reading
+    drives: Vec<DriveHealth>,
+
+    /// The time of the last reading, `None` before the first one
+    last_reading: Option<Instant>,
+}
+
+impl SmartMonitor {
+    /// Returns the health of the drives. Readings sooner than `MIN_READING_INTERVAL`
+    /// return the drives of the previous reading.
+    pub fn drives(&mut self) -> &[DriveHealth] {
+        let now = Instant::now();
+        if self
+            .last_reading
+            .is_none_or(|then| now.duration_since(then) >= MIN_READING_INTERVAL)
+        {
+            self.drives = scan()
+                .iter()
+                .filter_map(|(device, kind)| read(device, kind))
+                .collect();
+            self.last_reading = Some(now);
+        }
+        &self.drives
+    }
+}
+
+/// Runs `smartctl` with `args` and parses its JSON output.
+/// The exit status is a bit mask of the problems found, which still come with an output, so it is ignored.
+fn smartctl(args: &[&str]) -> Option<Value> {
+    let output = Command::new("smartctl").args(args).output().ok()?;
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Returns the device and the device type of every drive found by `smartctl`.
+fn scan() -> Vec<(String, String)> {
+    let Some(scan) = smartctl(&["--scan", "--json"]) else {
+        return Vec::new();
+    };
+    scan["devices"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|device| {
+            Some((
+                device["name"].as_str()?.to_string(),
+                device["type"].as_str()?.to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Reads the SMART data of `device`, `None` if the drive could not be queried.
+fn read(device: &str, kind: &str) -> Option<DriveHealth> {
+    let info = smartctl(&[
+        "--json",
+        "--info",
+        "--health",
+        "--attributes",
+        "--nocheck=standby",
+        "--device",
+        kind,
+        device,
+    ])?;
+    let passed = info["smart_status"]["passed"].as_bool();
+    let temperature = info["temperature"]["current"].as_f64();
+    let power_on_hours = info["power_on_time"]["hours"].as_u64();
+    let reallocated_sectors = info["ata_smart_attributes"]["table"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|attribute| attribute["id"].as_u64() == Some(REALLOCATED_SECTOR_COUNT))
+        .and_then(|attribute| attribute["raw"]["value"].as_u64());
+    if passed.is_none() && temperature.is_none() && power_on_hours.is_none() {
+        return None;
+    }
+
+    Some(DriveHealth {
+        device: device.to_string(),
+        model: info["model_name"].as_str().map(str::to_string),
+        passed,
+        temperature,
+        power_on_hours,
+        reallocated_sectors,
+    })
+}
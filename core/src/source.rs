@@ -0,0 +1,159 @@
+use crate::model::{CpuTopology, ProcessColumn, ProcessInfo};
+use crate::provider::SystemInfoProvider;
+use crate::{
+    Alert, CpuInfo, EncodingError, OceloError, SystemInfoPollingContext, SystemInfoUpdate,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::mem;
+
+/// Provides the system information displayed by the user interface,
+/// like the local system through a `ProviderSource` or a `RemoteSource` connected to an ocelo agent.
+pub trait DataSource: Send {
+    /// Fetches the system information needed by the polling context `ctx`.
+    fn poll(&mut self, ctx: SystemInfoPollingContext) -> Result<SystemInfoUpdate, DataSourceError>;
+
+    /// Fetches the processor information, like its name and core count.
+    fn cpu_info(&mut self) -> Result<CpuInfo, DataSourceError>;
+
+    /// Fetches the static layout of the processor, like its sockets, cores and caches.
+    fn cpu_topology(&mut self) -> Result<CpuTopology, DataSourceError>;
+
+    /// Evaluates the alert rules and returns the newly raised alerts.
+    fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, DataSourceError>;
+
+    /// Fetches the process `pid` with its details, like the command line and the environment.
+    fn process_details(&mut self, pid: u32) -> Result<ProcessInfo, DataSourceError>;
+
+    /// Makes the next poll of the processes fetch their whole list instead of the changes since the previous poll,
+    /// like when the changes polled meanwhile were not displayed.
+    /// Nothing to do for the sources which always fetch the whole list.
+    fn restart_process_changes(&mut self) {}
+
+    /// Fetches the values of the processes which are slow to read only for the displayed `columns` from now on.
+    /// Nothing to do for the sources which are not reading the processes themselves.
+    fn set_process_columns(&mut self, _columns: Vec<ProcessColumn>) {}
+
+    /// Returns the address of the remote host the system information is fetched from,
+    /// or `None` if it is polled from the local system.
+    fn remote_address(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Polls the system information from a `SystemInfoProvider`,
+/// like the local `SystemInfoPoller` or the canned snapshots of a `MockProvider`.
+///
+/// # Example:
+/// ```no_run
+/// use core::{ProviderSource, SystemInfoPoller};
+///
+/// let mut poller = SystemInfoPoller::default();
+/// poller.init();
+/// let source = ProviderSource::new(poller);
+/// ```
+pub struct ProviderSource<P> {
+    provider: P,
+    /// Set when the next poll of the processes must return the whole list instead of its changes,
+    /// see `DataSource::restart_process_changes`
+    full_process_list: bool,
+}
+
+impl<P: SystemInfoProvider> ProviderSource<P> {
+    pub fn new(provider: P) -> Self {
+        ProviderSource {
+            provider,
+            full_process_list: true,
+        }
+    }
+}
+
+impl<P: SystemInfoProvider> DataSource for ProviderSource<P> {
+    fn poll(&mut self, ctx: SystemInfoPollingContext) -> Result<SystemInfoUpdate, DataSourceError> {
+        if ctx == SystemInfoPollingContext::Processes
+            && !mem::replace(&mut self.full_process_list, false)
+        {
+            if let Some(delta) = self.provider.get_process_delta()? {
+                return Ok(SystemInfoUpdate::ProcessDelta(delta));
+            }
+        }
+        Ok(SystemInfoUpdate::poll(ctx, &mut self.provider)?)
+    }
+
+    fn cpu_info(&mut self) -> Result<CpuInfo, DataSourceError> {
+        Ok(self.provider.get_cpu_info()?)
+    }
+
+    fn cpu_topology(&mut self) -> Result<CpuTopology, DataSourceError> {
+        Ok(self.provider.get_cpu_topology()?)
+    }
+
+    fn evaluate_alerts(&mut self) -> Result<Vec<Alert>, DataSourceError> {
+        Ok(self.provider.evaluate_alerts()?)
+    }
+
+    fn process_details(&mut self, pid: u32) -> Result<ProcessInfo, DataSourceError> {
+        Ok(self.provider.get_process_details(pid)?)
+    }
+
+    fn restart_process_changes(&mut self) {
+        self.full_process_list = true;
+    }
+
+    fn set_process_columns(&mut self, columns: Vec<ProcessColumn>) {
+        self.provider.set_process_columns(columns);
+    }
+}
+
+/// Reachability of a remote data source, sent to the user interface when it changes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConnectionStatus {
+    Connected,
+    /// The last poll failed for the given reason, it is retried at the next poll
+    Disconnected(String),
+}
+
+/// Reasons why the system information could not be fetched.
+#[derive(Debug)]
+pub enum DataSourceError {
+    /// The remote host could not be reached
+    Connection(io::Error),
+    /// The remote host answered with an HTTP error, like `401 Unauthorized`
+    Status(String),
+    /// The answer is not valid system information
+    Encoding(EncodingError),
+    /// The local system could not be polled
+    Provider(OceloError),
+}
+
+impl Display for DataSourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connection(error) => write!(f, "connection failed: {}", error),
+            Self::Status(status) => write!(f, "request failed: {}", status),
+            Self::Encoding(error) => write!(f, "invalid response: {}", error),
+            Self::Provider(error) => write!(f, "polling failed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for DataSourceError {}
+
+impl From<io::Error> for DataSourceError {
+    fn from(error: io::Error) -> Self {
+        Self::Connection(error)
+    }
+}
+
+impl From<EncodingError> for DataSourceError {
+    fn from(error: EncodingError) -> Self {
+        Self::Encoding(error)
+    }
+}
+
+impl From<OceloError> for DataSourceError {
+    fn from(error: OceloError) -> Self {
+        Self::Provider(error)
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{SystemInfoPoller, SystemInfoUpdate};
+use tokio::sync::mpsc;
+use tokio::task;
+use tokio::time::{self, MissedTickBehavior};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+/// Count of the updates waiting to be consumed from the stream before the polling waits for them.
+const STREAM_CAPACITY: usize = 16;
+
+impl SystemInfoPoller {
+    /// Polls the system information of the polling context at every interval in the background,
+    /// yielding the updates, the raised alerts and the failures of the polls.
+    /// The polls run on the blocking threads of tokio, as reading the system information blocks.
+    /// The polling stops once the stream is dropped.
+    ///
+    /// It must be called inside a tokio runtime, on an initialized poller.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// use core::{SystemInfoPoller, SystemInfoPollingContext};
+    /// use std::time::Duration;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// async fn forward() -> Result<(), serde_json::Error> {
+    ///     let mut poller = SystemInfoPoller::default().with_interval(Duration::from_secs(5));
+    ///     poller.init();
+    ///     poller.set_polling_context(SystemInfoPollingContext::Processes);
+    ///     let mut updates = poller.stream();
+    ///     while let Some(update) = updates.next().await {
+    ///         println!("{}", serde_json::to_string(&update)?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stream(self) -> impl Stream<Item = SystemInfoUpdate> + Unpin {
+        let (tx, rx) = mpsc::channel(STREAM_CAPACITY);
+        tokio::spawn(async move {
+            let mut poller = self;
+            let mut ticker = time::interval(poller.interval());
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let polled = task::spawn_blocking(move || {
+                    let updates = poller.poll_updates();
+                    (poller, updates)
+                })
+                .await;
+                // the poll has panicked, the poller is lost with it
+                let Ok((returned, updates)) = polled else {
+                    return;
+                };
+                poller = returned;
+
+                for update in updates {
+                    if tx.send(update).await.is_err() {
+                        // the stream has been dropped
+                        return;
+                    }
+                }
+            }
+        });
+        ReceiverStream::new(rx)
+    }
+
+    /// Polls the system information of the polling context and evaluates the alerts,
+    /// the failures are returned as `SystemInfoUpdate::Error`.
+    fn poll_updates(&mut self) -> Vec<SystemInfoUpdate> {
+        let mut updates = vec![SystemInfoUpdate::poll(self.polling_context(), self)
+            .unwrap_or_else(|error| {
+                SystemInfoUpdate::Error(format!("Failed to poll the system information: {}", error))
+            })];
+        match self.evaluate_alerts() {
+            Ok(alerts) if !alerts.is_empty() => updates.push(SystemInfoUpdate::Alerts(alerts)),
+            Ok(_) => {}
+            Err(error) => updates.push(SystemInfoUpdate::Error(format!(
+                "Failed to evaluate the alerts: {}",
+                error
+            ))),
+        }
+        updates
+    }
+}
@@ -0,0 +1,109 @@
+use crate::model::{Sensor, SensorKind};
+use sysinfo::Components;
+
+/// Sensor labels reporting the temperature of the whole processor package.
+/// Intel processors report `coretemp Package id 0`, AMD processors report `k10temp Tctl` or `k10temp Tdie`.
+const PACKAGE_LABELS: [&str; 4] = ["Package", "Tctl", "Tdie", "CPU"];
+
+/// Reads the processor temperatures from the hardware sensors of the host machine.
+///
+/// # Example:
+/// ```
+/// use core::TemperatureProvider;
+///
+/// let mut provider = TemperatureProvider::default();
+/// provider.refresh();
+/// let temperature = provider.package_temperature();
+/// ```
+pub struct TemperatureProvider {
+    components: Components,
+}
+
+impl Default for TemperatureProvider {
+    fn default() -> Self {
+        TemperatureProvider {
+            components: Components::new_with_refreshed_list(),
+        }
+    }
+}
+
+impl TemperatureProvider {
+    /// Reads the current temperature of the sensors.
+    pub fn refresh(&mut self) {
+        self.components.refresh(false);
+    }
+
+    /// Returns the temperature of the processor package in Celsius.
+    /// If the package sensor is not available, the hottest core temperature is returned.
+    /// On some platforms, this information is not available.
+    pub fn package_temperature(&self) -> Option<f32> {
+        self.components
+            .iter()
+            .filter(|component| {
+                PACKAGE_LABELS
+                    .iter()
+                    .any(|label| component.label().contains(label))
+            })
+            .find_map(|component| component.temperature())
+            .or_else(|| {
+                self.core_temperatures()
+                    .into_iter()
+                    .reduce(|hottest, temperature| hottest.max(temperature))
+            })
+    }
+
+    /// Returns the critical temperature of the processor package in Celsius, where the sensor reports one.
+    pub fn package_critical(&self) -> Option<f32> {
+        self.components
+            .iter()
+            .filter(|component| {
+                PACKAGE_LABELS
+                    .iter()
+                    .any(|label| component.label().contains(label))
+            })
+            .find_map(|component| component.critical())
+    }
+
+    /// Returns every temperature sensor with its critical threshold.
+    /// The sensors which could not be read are left out.
+    pub fn sensors(&self) -> Vec<Sensor> {
+        self.components
+            .iter()
+            .filter_map(|component| {
+                Some(Sensor {
+                    label: component.label().to_string(),
+                    kind: SensorKind::Temperature,
+                    value: component.temperature().filter(|t| !t.is_nan())? as f64,
+                    min: None,
+                    // sysinfo reports the highest temperature reached as the maximum, which is not a limit
+                    max: None,
+                    critical: component.critical().map(f64::from),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the temperatures of the physical cores in Celsius, ordered by the core index.
+    pub fn core_temperatures(&self) -> Vec<f32> {
+        let mut cores = self
+            .components
+            .iter()
+            .filter_map(|component| {
+                let index = core_index(component.label())?;
+                Some((index, component.temperature()?))
+            })
+            .collect::<Vec<(usize, f32)>>();
+        cores.sort_by_key(|(index, _)| *index);
+
+        cores
+            .into_iter()
+            .map(|(_, temperature)| temperature)
+            .collect()
+    }
+}
+
+/// Parses the index of a physical core from a sensor label like `coretemp Core 3`.
+fn core_index(label: &str) -> Option<usize> {
+    let (_, index) = label.split_once("Core ")?;
+    index.trim().parse().ok()
+}
@@ -0,0 +1,83 @@
+use crate::alerts::AlertNotification;
+use crate::history::unix_time;
+use crate::Alert;
+
+/// The frequency relative to the maximum under which the processor is considered slowed down.
+const THROTTLE_FREQUENCY_RATIO: f64 = 0.8;
+
+/// The temperature below the critical threshold from which the processor is considered hot enough to be throttled.
+const THROTTLE_TEMPERATURE_MARGIN: f32 = 10.0;
+
+/// The critical temperature where the sensor does not report one, the highest junction temperature of most processors.
+const DEFAULT_CRITICAL_TEMPERATURE: f32 = 100.0;
+
+/// Detects the thermal throttling of the processor: its frequency dropping well below its maximum
+/// while its temperature is close to the critical one.
+/// An event lasts until either the frequency or the temperature recovers, an alert is raised at the start of every event.
+///
+/// # Example:
+/// ```
+/// use core::ThrottleDetector;
+///
+/// let mut detector = ThrottleDetector::default();
+/// if let Some(alert) = detector.observe(1800, Some(4200), Some(97.0), Some(100.0)) {
+///     println!("{}", alert);
+/// }
+/// ```
+#[derive(Default)]
+pub struct ThrottleDetector {
+    /// The processor is being throttled at the last observation
+    throttling: bool,
+    /// Count of the throttling events since the start
+    count: u64,
+    /// The highest frequency seen in MHz, the reference where the maximum of the processor is not known
+    highest_frequency: u64,
+}
+
+impl ThrottleDetector {
+    /// Compares the current `frequency` of the processor in MHz to its maximum, and its `temperature` to the `critical` one.
+    /// Returns the alert of a throttling event when one starts.
+    pub fn observe(
+        &mut self,
+        frequency: u64,
+        max_frequency: Option<u64>,
+        temperature: Option<f32>,
+        critical: Option<f32>,
+    ) -> Option<Alert> {
+        self.highest_frequency = self.highest_frequency.max(frequency);
+        let max_frequency = max_frequency.unwrap_or(self.highest_frequency);
+        let critical = critical.unwrap_or(DEFAULT_CRITICAL_TEMPERATURE);
+
+        let slowed_down = (frequency as f64) < max_frequency as f64 * THROTTLE_FREQUENCY_RATIO;
+        let hot = temperature
+            .is_some_and(|temperature| temperature >= critical - THROTTLE_TEMPERATURE_MARGIN);
+        let started = slowed_down && hot && !self.throttling;
+        self.throttling = slowed_down && hot;
+        if !started {
+            return None;
+        }
+
+        self.count += 1;
+        Some(Alert {
+            rule: format!(
+                "cpu thermal throttling at {:.0}°C, {} of {} MHz",
+                temperature.unwrap_or_default(),
+                frequency,
+                max_frequency
+            ),
+            value: frequency as f64,
+            timestamp: unix_time(),
+            notification: AlertNotification::None,
+        })
+    }
+
+    /// Returns true if the processor was being throttled at the last observation.
+    pub fn is_throttling(&self) -> bool {
+        self.throttling
+    }
+
+    /// Returns the count of the throttling events since the start.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
@@ -0,0 +1,134 @@
+use crate::model::CpuTopology;
+use sysinfo::System;
+
+/// The processor flags worth displaying, the full list of a modern processor has over a hundred entries.
+/// The x86 flags are reported in /proc/cpuinfo as `flags`, the ARM ones as `Features`.
+const NOTABLE_FLAGS: [&str; 21] = [
+    "sse4_2",
+    "avx",
+    "avx2",
+    "avx512f",
+    "avx512_vnni",
+    "amx_tile",
+    "fma",
+    "bmi2",
+    "aes",
+    "sha_ni",
+    "vmx",
+    "svm",
+    "hypervisor",
+    "ht",
+    "rdrand",
+    "neon",
+    "asimd",
+    "sve",
+    "sve2",
+    "sha2",
+    "crc32",
+];
+
+/// Reads the layout of the processor of the host machine, like its core count and its caches.
+/// The sockets, the caches and the flags are only read on Linux, elsewhere a single socket is reported.
+pub fn cpu_topology(system: &System) -> CpuTopology {
+    let logical_cores = system.cpus().len();
+    let mut topology = CpuTopology {
+        vendor: system
+            .cpus()
+            .first()
+            .map(|cpu| cpu.vendor_id().to_string())
+            .unwrap_or_default(),
+        sockets: 1,
+        physical_cores: System::physical_core_count().unwrap_or(logical_cores),
+        logical_cores,
+        caches: Vec::new(),
+        flags: Vec::new(),
+    };
+    #[cfg(target_os = "linux")]
+    linux::read_topology(&mut topology);
+    topology
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::NOTABLE_FLAGS;
+    use crate::model::{CpuCache, CpuTopology};
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::Path;
+
+    const CPU_DIRECTORY: &str = "/sys/devices/system/cpu";
+
+    pub fn read_topology(topology: &mut CpuTopology) {
+        let cpu = Path::new(CPU_DIRECTORY);
+        let packages = (0..topology.logical_cores)
+            .filter_map(|index| {
+                read_string(&cpu.join(format!("cpu{}/topology/physical_package_id", index)))
+            })
+            .collect::<HashSet<String>>();
+        if !packages.is_empty() {
+            topology.sockets = packages.len();
+        }
+
+        topology.caches = read_caches(&cpu.join("cpu0/cache"));
+        topology.flags = read_flags();
+    }
+
+    fn read_caches(path: &Path) -> Vec<CpuCache> {
+        let Ok(entries) = fs::read_dir(path) else {
+            return Vec::new();
+        };
+        let mut caches = entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("index"))
+            .filter_map(|entry| {
+                let path = entry.path();
+                Some(CpuCache {
+                    level: read_string(&path.join("level"))?.parse().ok()?,
+                    kind: read_string(&path.join("type"))?,
+                    size: parse_size(&read_string(&path.join("size"))?)?,
+                })
+            })
+            .collect::<Vec<CpuCache>>();
+        // the data cache of a level comes before its instruction cache, like lscpu lists them
+        caches.sort_by(|a, b| a.level.cmp(&b.level).then_with(|| a.kind.cmp(&b.kind)));
+        caches
+    }
+
+    /// Parses the cache sizes of sysfs, like `48K` or `32M`.
+    fn parse_size(value: &str) -> Option<u64> {
+        let (number, multiplier) = match value.chars().last()? {
+            'K' => (&value[..value.len() - 1], 1024),
+            'M' => (&value[..value.len() - 1], 1024 * 1024),
+            'G' => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+            _ => (value, 1),
+        };
+        Some(number.parse::<u64>().ok()? * multiplier)
+    }
+
+    /// Returns the notable flags of the first processor listed in /proc/cpuinfo, in the order of `NOTABLE_FLAGS`.
+    fn read_flags() -> Vec<String> {
+        let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") else {
+            return Vec::new();
+        };
+        let flags = cpuinfo
+            .lines()
+            .find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                matches!(key.trim(), "flags" | "Features")
+                    .then(|| value.split_whitespace().collect::<HashSet<&str>>())
+            })
+            .unwrap_or_default();
+
+        NOTABLE_FLAGS
+            .iter()
+            .filter(|flag| flags.contains(*flag))
+            .map(|flag| flag.to_string())
+            .collect()
+    }
+
+    fn read_string(path: &Path) -> Option<String> {
+        fs::read_to_string(path)
+            .ok()
+            .map(|content| content.trim().to_string())
+    }
+}
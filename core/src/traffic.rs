@@ -0,0 +1,291 @@
+use crate::model::ProcessTraffic;
+use crate::OceloError;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The shortest time between two observations of the socket counters.
+/// The rates requested sooner are the ones of the previous observation,
+/// like the process list and the top talkers collected for the same snapshot.
+const MIN_OBSERVATION_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Estimates the network throughput of the processes from the byte counters of their TCP sockets,
+/// like `nethogs` without capturing the packets.
+/// The kernel counts the sent and received bytes of every TCP socket, which are summed by the owning processes.
+/// UDP sockets have no such counters, so their traffic is not accounted.
+///
+/// The counters are read with sock_diag netlink like `ss -ti`, so the processes are only accounted on Linux.
+/// The owners are found by looking for the sockets among the open files of every process,
+/// so the traffic of the processes which can not be inspected is not accounted either.
+#[derive(Default)]
+pub struct TrafficAccounting {
+    /// The received and sent bytes of every socket at the last observation, by inode
+    sockets: HashMap<u64, (u64, u64)>,
+
+    /// The time of the last observation, `None` before the first one
+    last_observation: Option<Instant>,
+
+    /// The throughput of the processes with traffic at the last observation, by pid
+    traffic: HashMap<u32, ProcessTraffic>,
+}
+
+impl TrafficAccounting {
+    /// Returns the throughput of the processes with traffic since the previous observation, by pid.
+    /// The first observation is the base of the rates, so it returns no traffic.
+    pub fn traffic(&mut self) -> Result<&HashMap<u32, ProcessTraffic>, OceloError> {
+        let now = Instant::now();
+        if self
+            .last_observation
+            .is_some_and(|then| now.duration_since(then) < MIN_OBSERVATION_INTERVAL)
+        {
+            return Ok(&self.traffic);
+        }
+
+        let counters = tcp_counters()?;
+        let mut traffic: HashMap<u32, ProcessTraffic> = HashMap::new();
+        if let Some(then) = self.last_observation {
+            let elapsed = now.duration_since(then).as_secs_f64();
+            let owners = socket_owners();
+
+            for (inode, (received, sent)) in counters.iter() {
+                let Some((pid, name)) = owners.get(inode) else {
+                    continue;
+                };
+                // the sockets opened since the previous observation are counted from their start
+                let (previous_received, previous_sent) =
+                    self.sockets.get(inode).copied().unwrap_or_default();
+                let process = traffic.entry(*pid).or_insert_with(|| ProcessTraffic {
+                    pid: *pid,
+                    name: name.clone(),
+                    received_rate: 0.0,
+                    transmitted_rate: 0.0,
+                });
+                process.received_rate +=
+                    received.saturating_sub(previous_received) as f64 / elapsed;
+                process.transmitted_rate += sent.saturating_sub(previous_sent) as f64 / elapsed;
+            }
+            traffic
+                .retain(|_, process| process.received_rate > 0.0 || process.transmitted_rate > 0.0);
+        }
+
+        self.sockets = counters;
+        self.last_observation = Some(now);
+        self.traffic = traffic;
+        Ok(&self.traffic)
+    }
+
+    /// Returns the `count` processes with the highest throughput, the busiest first.
+    pub fn top_talkers(&mut self, count: usize) -> Result<Vec<ProcessTraffic>, OceloError> {
+        let mut talkers: Vec<ProcessTraffic> = self.traffic()?.values().cloned().collect();
+        talkers.sort_by(|a, b| {
+            (b.received_rate + b.transmitted_rate)
+                .total_cmp(&(a.received_rate + a.transmitted_rate))
+        });
+        talkers.truncate(count);
+        Ok(talkers)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn tcp_counters() -> Result<HashMap<u64, (u64, u64)>, OceloError> {
+    linux::tcp_counters()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tcp_counters() -> Result<HashMap<u64, (u64, u64)>, OceloError> {
+    Err(OceloError::Unsupported(
+        "accounting the network traffic of the processes is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+    crate::connections::linux::socket_owners()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+    HashMap::new()
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use crate::OceloError;
+    use std::collections::HashMap;
+    use std::io;
+    use std::mem;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    /// The message type of the socket dumps of sock_diag
+    const SOCK_DIAG_BY_FAMILY: u16 = 20;
+
+    /// The attribute of the dumped sockets holding a `struct tcp_info`
+    const INET_DIAG_INFO: u16 = 2;
+
+    /// Size of `struct nlmsghdr`
+    const HEADER_SIZE: usize = 16;
+
+    /// Size of `struct inet_diag_req_v2`
+    const REQUEST_SIZE: usize = 56;
+
+    /// Size of `struct inet_diag_msg` and the offset of its `idiag_inode` field
+    const MESSAGE_SIZE: usize = 72;
+    const INODE_OFFSET: usize = 68;
+
+    /// Offsets of the `tcpi_bytes_acked` and `tcpi_bytes_received` fields of `struct tcp_info`
+    const BYTES_ACKED_OFFSET: usize = 120;
+    const BYTES_RECEIVED_OFFSET: usize = 128;
+
+    /// Returns the received and sent bytes of every TCP socket of the system by their inode.
+    pub fn tcp_counters() -> Result<HashMap<u64, (u64, u64)>, OceloError> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_SOCK_DIAG,
+            )
+        };
+        if fd < 0 {
+            return Err(netlink_error(io::Error::last_os_error()));
+        }
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut counters = HashMap::new();
+        for family in [libc::AF_INET, libc::AF_INET6] {
+            request(&socket, family as u8).map_err(netlink_error)?;
+            receive(&socket, &mut counters).map_err(netlink_error)?;
+        }
+        Ok(counters)
+    }
+
+    fn netlink_error(error: io::Error) -> OceloError {
+        OceloError::Io(format!("failed to query the TCP sockets: {}", error))
+    }
+
+    /// Asks the kernel to dump the TCP sockets of the address `family` in every state with their `tcp_info`.
+    fn request(socket: &OwnedFd, family: u8) -> io::Result<()> {
+        let mut message = Vec::with_capacity(HEADER_SIZE + REQUEST_SIZE);
+        message.extend_from_slice(&((HEADER_SIZE + REQUEST_SIZE) as u32).to_ne_bytes());
+        message.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        message.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+        // sequence number and port of the sender
+        message.extend_from_slice(&[0; 8]);
+        message.extend_from_slice(&[
+            family,
+            libc::IPPROTO_TCP as u8,
+            1 << (INET_DIAG_INFO - 1),
+            0,
+        ]);
+        // every state
+        message.extend_from_slice(&u32::MAX.to_ne_bytes());
+        // the socket id is only used for looking up a single socket
+        message.resize(HEADER_SIZE + REQUEST_SIZE, 0);
+
+        let mut kernel = unsafe { mem::zeroed::<libc::sockaddr_nl>() };
+        kernel.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        let sent = unsafe {
+            libc::sendto(
+                socket.as_raw_fd(),
+                message.as_ptr() as *const libc::c_void,
+                message.len(),
+                0,
+                &kernel as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        match sent {
+            sent if sent < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads the dumped sockets until the end of the dump, collecting their counters.
+    fn receive(socket: &OwnedFd, counters: &mut HashMap<u64, (u64, u64)>) -> io::Result<()> {
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let length = unsafe {
+                libc::recv(
+                    socket.as_raw_fd(),
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                    0,
+                )
+            };
+            if length < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut messages = &buffer[..length as usize];
+            while let (Some(message_length), Some(message_type)) =
+                (read_u32(messages, 0), read_u16(messages, 4))
+            {
+                let message_length = message_length as usize;
+                if message_length < HEADER_SIZE || message_length > messages.len() {
+                    return Err(io::Error::other("truncated netlink message"));
+                }
+                let payload = &messages[HEADER_SIZE..message_length];
+                match message_type {
+                    message_type if message_type == libc::NLMSG_DONE as u16 => return Ok(()),
+                    message_type if message_type == libc::NLMSG_ERROR as u16 => {
+                        let errno = read_u32(payload, 0).unwrap_or_default() as i32;
+                        return Err(io::Error::from_raw_os_error(-errno));
+                    }
+                    SOCK_DIAG_BY_FAMILY => {
+                        if let Some((inode, counter)) = parse_socket(payload) {
+                            counters.insert(inode, counter);
+                        }
+                    }
+                    _ => {}
+                }
+                messages = &messages[align(message_length).min(messages.len())..];
+            }
+        }
+    }
+
+    /// Returns the inode and the received and sent bytes of a dumped socket.
+    /// The sockets without an inode, like the ones in `TIME_WAIT`, are skipped.
+    fn parse_socket(payload: &[u8]) -> Option<(u64, (u64, u64))> {
+        let inode = read_u32(payload, INODE_OFFSET)? as u64;
+        if inode == 0 {
+            return None;
+        }
+
+        let mut attributes = payload.get(MESSAGE_SIZE..)?;
+        while let (Some(length), Some(kind)) = (read_u16(attributes, 0), read_u16(attributes, 2)) {
+            let length = length as usize;
+            if length < 4 || length > attributes.len() {
+                return None;
+            }
+            if kind == INET_DIAG_INFO {
+                let info = &attributes[4..length];
+                let sent = read_u64(info, BYTES_ACKED_OFFSET)?;
+                let received = read_u64(info, BYTES_RECEIVED_OFFSET)?;
+                return Some((inode, (received, sent)));
+            }
+            attributes = &attributes[align(length).min(attributes.len())..];
+        }
+        None
+    }
+
+    /// Rounds `length` up to the 4 byte alignment of the netlink messages and attributes.
+    fn align(length: usize) -> usize {
+        (length + 3) & !3
+    }
+
+    fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+        Some(u16::from_ne_bytes(
+            bytes.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    }
+
+    fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+        Some(u32::from_ne_bytes(
+            bytes.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    }
+
+    fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+        Some(u64::from_ne_bytes(
+            bytes.get(offset..offset + 8)?.try_into().ok()?,
+        ))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::model::Session;
+use std::fs;
+use std::time::SystemTime;
+
+/// Lists the sessions of the logged in users from the utmp database, like `who`.
+/// The database is only read on Linux with glibc, elsewhere no sessions are returned.
+pub fn sessions() -> Vec<Session> {
+    let mut sessions = read_sessions();
+    for session in sessions.iter_mut() {
+        session.idle = idle_time(&session.tty);
+    }
+    sessions.sort_by(|a, b| a.user.cmp(&b.user).then(a.login_time.cmp(&b.login_time)));
+    sessions
+}
+
+/// Returns the seconds since the terminal was last read, which is the time since the last input of the user.
+fn idle_time(tty: &str) -> Option<u64> {
+    let accessed = fs::metadata(format!("/dev/{}", tty))
+        .ok()?
+        .accessed()
+        .ok()?;
+    Some(
+        SystemTime::now()
+            .duration_since(accessed)
+            .unwrap_or_default()
+            .as_secs(),
+    )
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn read_sessions() -> Vec<Session> {
+    use std::os::raw::c_char;
+    use std::sync::Mutex;
+
+    /// The position in the database is shared by the threads of the process
+    static UTMP: Mutex<()> = Mutex::new(());
+
+    let text = |field: &[c_char]| {
+        // the fields are only terminated when they are shorter than their array
+        let bytes = field
+            .iter()
+            .take_while(|c| **c != 0)
+            .map(|c| *c as u8)
+            .collect::<Vec<u8>>();
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+
+    let _guard = UTMP.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut sessions = Vec::new();
+    // SAFETY: the entries returned by getutxent are only read before the next call,
+    // and the database is only read while holding the lock
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+            let entry = &*entry;
+            if entry.ut_type != libc::USER_PROCESS {
+                continue;
+            }
+            let host = text(&entry.ut_host);
+            sessions.push(Session {
+                user: text(&entry.ut_user),
+                tty: text(&entry.ut_line),
+                host: (!host.is_empty()).then_some(host),
+                login_time: entry.ut_tv.tv_sec as u64,
+                idle: None,
+                pid: entry.ut_pid as u32,
+            });
+        }
+        libc::endutxent();
+    }
+    sessions
+}
+
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+fn read_sessions() -> Vec<Session> {
+    Vec::new()
+}
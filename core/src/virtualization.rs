@@ -0,0 +1,398 @@
+use crate::http;
+use crate::OceloError;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The link-local address of the metadata services of the cloud providers.
+const METADATA_ADDRESS: &str = "169.254.169.254";
+
+/// How long the metadata service is waited for, it answers at once where there is one.
+const METADATA_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The time between two lookups of the metadata after a failed one.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// What the host runs on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostKind {
+    #[default]
+    BareMetal,
+    VirtualMachine,
+    /// The processes are isolated in a container, on the kernel of the host running the container
+    Container,
+    /// The Windows Subsystem for Linux
+    Wsl,
+}
+
+impl HostKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BareMetal => "bare metal",
+            Self::VirtualMachine => "virtual machine",
+            Self::Container => "container",
+            Self::Wsl => "WSL",
+        }
+    }
+}
+
+/// The instance of a cloud provider running the host.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CloudInstance {
+    /// The provider, like `aws`, `gcp` or `azure`
+    pub provider: String,
+    /// The type of the instance, like `m5.large`, `None` unless the metadata was fetched
+    pub instance_type: Option<String>,
+    /// The region of the instance, like `eu-west-1`, `None` unless the metadata was fetched
+    pub region: Option<String>,
+}
+
+/// The environment of the host: whether it is bare metal, a virtual machine, a container or WSL,
+/// and the cloud instance running it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostEnvironment {
+    pub kind: HostKind,
+    /// The hypervisor or the container runtime, like `kvm`, `vmware`, `hyper-v` or `docker`,
+    /// `None` if it can not be told
+    pub technology: Option<String>,
+    /// The cloud instance, `None` if the host is not known to run in a cloud
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloud: Option<CloudInstance>,
+}
+
+impl HostEnvironment {
+    /// Describes the environment in a few words, like `virtual machine (kvm), aws m5.large in eu-west-1`.
+    pub fn describe(&self) -> String {
+        let mut description = self.kind.name().to_string();
+        if let Some(technology) = &self.technology {
+            description.push_str(&format!(" ({})", technology));
+        }
+        if let Some(cloud) = &self.cloud {
+            description.push_str(&format!(", {}", cloud.provider));
+            if let Some(instance_type) = &cloud.instance_type {
+                description.push_str(&format!(" {}", instance_type));
+            }
+            if let Some(region) = &cloud.region {
+                description.push_str(&format!(" in {}", region));
+            }
+        }
+        description
+    }
+}
+
+/// Tells the environment of the host from the kernel release, the markers of the container runtimes,
+/// the DMI tables of the firmware and the CPU flags. The cloud provider is told from the DMI tables,
+/// its instance type and region are left to `CloudMetadataLookup`.
+/// The environment is only detected on Linux.
+#[cfg(target_os = "linux")]
+pub fn detect_environment() -> Result<HostEnvironment, OceloError> {
+    Ok(linux::detect_environment())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn detect_environment() -> Result<HostEnvironment, OceloError> {
+    Err(OceloError::Unsupported(
+        "detecting the virtualization is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn cloud_provider() -> Option<String> {
+    linux::cloud_provider()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cloud_provider() -> Option<String> {
+    None
+}
+
+/// Fetches the type and the region of the cloud instance running the host from the metadata service
+/// of its provider on a background thread, which stops when the lookup is dropped.
+/// The metadata is fetched once, a failed lookup is retried after `RETRY_INTERVAL`.
+///
+/// The metadata services of AWS, Google Cloud and Azure are asked, for the other providers nothing is fetched.
+///
+/// # Example:
+/// ```no_run
+/// use core::CloudMetadataLookup;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let lookup = CloudMetadataLookup::start();
+/// thread::sleep(Duration::from_secs(1));
+/// println!("{:?}", lookup.instance());
+/// ```
+pub struct CloudMetadataLookup {
+    instance: Arc<Mutex<Option<CloudInstance>>>,
+    /// Stops the worker thread when dropped
+    _stop: Sender<()>,
+}
+
+impl CloudMetadataLookup {
+    pub fn start() -> Self {
+        let instance = Arc::new(Mutex::new(None));
+        let (stop, stopped) = mpsc::channel();
+        let result = Arc::clone(&instance);
+        thread::spawn(move || {
+            let Some(provider) = cloud_provider() else {
+                return;
+            };
+            loop {
+                if let Ok(fetched) = fetch_metadata(&provider) {
+                    if let Ok(mut instance) = result.lock() {
+                        *instance = Some(fetched);
+                    }
+                    break;
+                }
+                // sleeps until the next lookup, or stops when the lookup is dropped
+                if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(RETRY_INTERVAL) {
+                    break;
+                }
+            }
+        });
+        CloudMetadataLookup {
+            instance,
+            _stop: stop,
+        }
+    }
+
+    /// Returns the fetched cloud instance, `None` until the metadata is fetched.
+    pub fn instance(&self) -> Option<CloudInstance> {
+        self.instance
+            .lock()
+            .map(|instance| instance.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Fetches the instance type and the region from the metadata service of `provider`.
+fn fetch_metadata(provider: &str) -> io::Result<CloudInstance> {
+    let (instance_type, region) = match provider {
+        "aws" => {
+            // IMDSv2 answers only the requests with a session token
+            let token = request(
+                "PUT",
+                "/latest/api/token",
+                ("X-aws-ec2-metadata-token-ttl-seconds", "60"),
+            )?;
+            let header = ("X-aws-ec2-metadata-token", token.as_str());
+            (
+                request("GET", "/latest/meta-data/instance-type", header)?,
+                request("GET", "/latest/meta-data/placement/region", header)?,
+            )
+        }
+        "gcp" => {
+            let header = ("Metadata-Flavor", "Google");
+            // the machine type and the zone are answered as paths, like `projects/1234/zones/europe-west1-b`
+            let last_segment =
+                |path: String| path.rsplit('/').next().unwrap_or_default().to_string();
+            let machine_type = request("GET", "/computeMetadata/v1/instance/machine-type", header)?;
+            let zone = last_segment(request("GET", "/computeMetadata/v1/instance/zone", header)?);
+            let region = zone
+                .rsplit_once('-')
+                .map_or(zone.as_str(), |(region, _)| region);
+            (last_segment(machine_type), region.to_string())
+        }
+        "azure" => {
+            let header = ("Metadata", "true");
+            (
+                request(
+                    "GET",
+                    "/metadata/instance/compute/vmSize?api-version=2021-02-01&format=text",
+                    header,
+                )?,
+                request(
+                    "GET",
+                    "/metadata/instance/compute/location?api-version=2021-02-01&format=text",
+                    header,
+                )?,
+            )
+        }
+        other => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("the metadata of {} is not fetched", other),
+            ))
+        }
+    };
+    Ok(CloudInstance {
+        provider: provider.to_string(),
+        instance_type: Some(instance_type),
+        region: Some(region),
+    })
+}
+
+/// Sends a request with the `header` to the metadata service, returning the body of the answer.
+fn request(method: &str, path: &str, header: (&str, &str)) -> io::Result<String> {
+    let url = format!("http://{}{}", METADATA_ADDRESS, path);
+    let response = http::request(method, &url, &[header], &[], METADATA_TIMEOUT)?;
+    if response.status != 200 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "the metadata service answered '{}' to {}",
+                response.status_line, path
+            ),
+        ));
+    }
+    Ok(response.text().trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{CloudInstance, HostEnvironment, HostKind};
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    /// The hypervisors by the substrings of the DMI vendor or product names they identify themselves with.
+    const HYPERVISORS: [(&str, &str); 11] = [
+        ("KVM", "kvm"),
+        ("QEMU", "qemu"),
+        ("VMware", "vmware"),
+        ("VirtualBox", "virtualbox"),
+        ("innotek", "virtualbox"),
+        ("Xen", "xen"),
+        ("Parallels", "parallels"),
+        ("BHYVE", "bhyve"),
+        ("Bochs", "bochs"),
+        // the instances of AWS and Google Cloud run on KVM, except the bare metal ones of AWS
+        ("Amazon EC2", "kvm"),
+        ("Google", "kvm"),
+    ];
+
+    /// The cloud providers by the substrings of the DMI vendor, product or asset tag they identify themselves with.
+    const CLOUD_PROVIDERS: [(&str, &str); 8] = [
+        ("Amazon EC2", "aws"),
+        ("Google", "gcp"),
+        // the asset tag of the virtual machines of Azure
+        ("7783-7084-3265-9085-8269-3286-77", "azure"),
+        ("DigitalOcean", "digitalocean"),
+        ("Hetzner", "hetzner"),
+        ("OpenStack", "openstack"),
+        ("Alibaba Cloud", "alibaba"),
+        ("OracleCloud", "oracle"),
+    ];
+
+    pub fn detect_environment() -> HostEnvironment {
+        let cloud = cloud_provider().map(|provider| CloudInstance {
+            provider,
+            ..CloudInstance::default()
+        });
+        let (kind, technology) = if let Some(technology) = wsl() {
+            (HostKind::Wsl, Some(technology))
+        } else if let Some(runtime) = container_runtime() {
+            (HostKind::Container, runtime)
+        } else if let Some(hypervisor) = hypervisor() {
+            (HostKind::VirtualMachine, hypervisor)
+        } else {
+            (HostKind::BareMetal, None)
+        };
+        HostEnvironment {
+            kind,
+            technology,
+            cloud,
+        }
+    }
+
+    pub fn cloud_provider() -> Option<String> {
+        let identity = dmi_identity();
+        CLOUD_PROVIDERS
+            .iter()
+            .find(|(marker, _)| identity.contains(marker))
+            .map(|(_, provider)| provider.to_string())
+    }
+
+    /// Returns `wsl1` or `wsl2` if the kernel is the one of WSL, like `5.15.90.1-microsoft-standard-WSL2`.
+    fn wsl() -> Option<String> {
+        let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+        match (
+            release.contains("WSL2"),
+            release.to_lowercase().contains("microsoft"),
+        ) {
+            (true, _) => Some("wsl2".to_string()),
+            (false, true) => Some("wsl1".to_string()),
+            (false, false) => None,
+        }
+    }
+
+    /// Returns the container runtime if the processes run in a container, `Some(None)` if it can not be told.
+    fn container_runtime() -> Option<Option<String>> {
+        // systemd-nspawn, podman and lxc set the variable for the init process of the container
+        if let Ok(environ) = fs::read("/proc/1/environ") {
+            let runtime = environ
+                .split(|byte| *byte == 0)
+                .find_map(|variable| variable.strip_prefix(b"container="));
+            if let Some(runtime) = runtime {
+                return Some(Some(String::from_utf8_lossy(runtime).into_owned()));
+            }
+        }
+        if Path::new("/.dockerenv").exists() {
+            return Some(Some("docker".to_string()));
+        }
+        if Path::new("/run/.containerenv").exists() {
+            return Some(Some("podman".to_string()));
+        }
+        if env::var_os("KUBERNETES_SERVICE_HOST").is_some() {
+            return Some(Some("kubernetes".to_string()));
+        }
+        let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+        [
+            ("/kubepods", "kubernetes"),
+            ("/docker", "docker"),
+            ("/lxc", "lxc"),
+        ]
+        .iter()
+        .find(|(marker, _)| cgroup.contains(marker))
+        .map(|(_, runtime)| Some(runtime.to_string()))
+    }
+
+    /// Returns the hypervisor if the host is a virtual machine, `Some(None)` if it can not be told.
+    fn hypervisor() -> Option<Option<String>> {
+        let identity = dmi_identity();
+        // the bare metal instances of AWS identify as Amazon EC2 too
+        if !identity.contains(".metal") {
+            if let Some((_, hypervisor)) = HYPERVISORS
+                .iter()
+                .find(|(marker, _)| identity.contains(marker))
+            {
+                return Some(Some(hypervisor.to_string()));
+            }
+        }
+        if identity.contains("Microsoft Corporation") && identity.contains("Virtual Machine") {
+            return Some(Some("hyper-v".to_string()));
+        }
+        if let Ok(hypervisor) = fs::read_to_string("/sys/hypervisor/type") {
+            return Some(Some(hypervisor.trim().to_string()));
+        }
+        // the CPUs of the virtual machines have the hypervisor flag, even without DMI tables, like on Firecracker
+        let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        cpuinfo
+            .lines()
+            .find(|line| line.starts_with("flags"))
+            .is_some_and(|flags| flags.split_whitespace().any(|flag| flag == "hypervisor"))
+            .then_some(None)
+    }
+
+    /// Returns the vendor, the product and the asset tag of the system and the vendor of the firmware
+    /// from the DMI tables, separated by new lines. Empty without DMI tables.
+    fn dmi_identity() -> String {
+        [
+            "sys_vendor",
+            "product_name",
+            "product_version",
+            "bios_vendor",
+            "chassis_asset_tag",
+            "board_vendor",
+        ]
+        .iter()
+        .filter_map(|name| fs::read_to_string(Path::new("/sys/class/dmi/id").join(name)).ok())
+        .map(|value| value.trim().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+    }
+}
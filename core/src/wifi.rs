@@ -0,0 +1,69 @@
+use crate::model::WirelessLink;
+
+/// Reads the connection of the wireless interface named `interface` with `iw dev <interface> link`,
+/// which queries the nl80211 interface of the kernel.
+/// Returns `None` if the interface is not wireless or `iw` is not installed, and outside of Linux.
+/// A disconnected interface returns a link without an SSID.
+#[cfg(target_os = "linux")]
+pub fn wireless_link(interface: &str) -> Option<WirelessLink> {
+    linux::wireless_link(interface)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn wireless_link(_interface: &str) -> Option<WirelessLink> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use crate::model::WirelessLink;
+    use std::path::Path;
+    use std::process::Command;
+
+    pub fn wireless_link(interface: &str) -> Option<WirelessLink> {
+        // only the wireless interfaces have the directory, so iw is not run for the others
+        if !Path::new("/sys/class/net")
+            .join(interface)
+            .join("wireless")
+            .is_dir()
+        {
+            return None;
+        }
+        let output = Command::new("iw")
+            .args(["dev", interface, "link"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(parse_link(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Parses the output of `iw dev <interface> link`, like
+    /// ```text
+    /// Connected to 64:66:b3:1c:2e:40 (on wlan0)
+    ///         SSID: home
+    ///         freq: 5180
+    ///         signal: -52 dBm
+    ///         tx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+    /// ```
+    /// or `Not connected.`
+    fn parse_link(output: &str) -> WirelessLink {
+        let mut link = WirelessLink::default();
+        for line in output.lines() {
+            let Some((name, value)) = line.trim().split_once(": ") else {
+                continue;
+            };
+            let number = || value.split_whitespace().next()?.parse::<f64>().ok();
+            match name {
+                "SSID" => link.ssid = Some(value.to_string()),
+                // newer versions of iw print the frequency with a fraction, like 5180.0
+                "freq" => link.frequency = number().map(|frequency| frequency as u32),
+                "signal" => link.signal = number().map(|signal| signal as i32),
+                "tx bitrate" => link.bitrate = number(),
+                _ => {}
+            }
+        }
+        link
+    }
+}
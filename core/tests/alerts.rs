@@ -0,0 +1,44 @@
+use core::alerts::{AlertMetric, AlertRuleError};
+use core::AlertRule;
+use std::time::Duration;
+
+fn parse(rule: &str) -> Result<AlertRule, AlertRuleError> {
+    rule.parse()
+}
+
+#[test]
+fn reads_the_mount_point_of_the_disk_metrics() {
+    let rule = parse("disk \"/home\" > 95%").unwrap();
+    assert_eq!(rule.metric, AlertMetric::DiskUsage("/home".to_string()));
+
+    let rule = parse("disk.growth \"/\" > 1").unwrap();
+    assert_eq!(rule.metric, AlertMetric::DiskGrowth("/".to_string()));
+}
+
+#[test]
+fn refuses_the_metrics_only_starting_like_a_disk_one() {
+    for rule in [
+        "disks \"/\" > 95",
+        "diskfoo > 95",
+        "disk.growthfoo > 1",
+        "disk > 95",
+    ] {
+        assert!(
+            matches!(parse(rule), Err(AlertRuleError::UnknownMetric(_))),
+            "{}",
+            rule
+        );
+    }
+}
+
+#[test]
+fn refuses_a_duration_which_overflows() {
+    let rule = parse("cpu.usage > 90 for 2h").unwrap();
+    assert_eq!(rule.duration, Duration::from_secs(2 * 60 * 60));
+
+    let rule = format!("cpu.usage > 90 for {}h", u64::MAX / 60);
+    assert!(matches!(
+        parse(&rule),
+        Err(AlertRuleError::InvalidDuration(_))
+    ));
+}
@@ -0,0 +1,48 @@
+use core::{Config, ConfigError};
+
+fn with_webhook(url: &str) -> Result<Config, ConfigError> {
+    Config::from_toml(&format!("[[alert_hooks]]\nwebhook = \"{}\"\n", url))
+}
+
+#[test]
+fn accepts_a_plain_http_webhook() {
+    let config = with_webhook("http://alerts.example.com:9000/ocelo").unwrap();
+    assert_eq!(config.alert_hooks.len(), 1);
+}
+
+#[test]
+fn refuses_a_webhook_which_is_not_an_http_url() {
+    let error = with_webhook("ftp://alerts.example.com/ocelo").unwrap_err();
+    assert!(matches!(error, ConfigError::Invalid(_)));
+    assert!(error.to_string().contains("ftp://alerts.example.com/ocelo"));
+}
+
+#[test]
+fn accepts_an_https_webhook_only_with_the_https_feature() {
+    let result = with_webhook("https://alerts.example.com/ocelo");
+    if cfg!(feature = "https") {
+        assert!(result.is_ok());
+    } else {
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("https feature"), "{}", error);
+    }
+}
+
+#[test]
+fn refuses_an_enabled_public_ip_provider_which_is_not_an_http_url() {
+    let settings = |enabled: bool| {
+        format!(
+            "[public_ip]\nenabled = {}\nprovider = \"api.ipify.org\"\n",
+            enabled
+        )
+    };
+
+    let error = Config::from_toml(&settings(true)).unwrap_err().to_string();
+    assert!(
+        error.contains("public IP provider api.ipify.org"),
+        "{}",
+        error
+    );
+    // the provider is not requested while the lookup is disabled
+    assert!(Config::from_toml(&settings(false)).is_ok());
+}
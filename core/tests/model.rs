@@ -0,0 +1,31 @@
+use core::model::{percentage, MemoryInfo};
+
+#[test]
+fn the_percentage_of_a_zero_whole_is_zero() {
+    assert_eq!(percentage(0, 0), 0.0);
+    assert_eq!(percentage(512, 0), 0.0);
+}
+
+#[test]
+fn the_percentage_of_a_part_larger_than_the_whole_is_not_capped() {
+    assert_eq!(percentage(3072, 2048), 150.0);
+}
+
+#[test]
+fn the_percentage_of_a_smaller_part_is_not_truncated() {
+    // the integer division of 1 by 3 used to give 0
+    assert!((percentage(1, 3) - 33.333).abs() < 0.001);
+    assert!(percentage(1, u64::MAX) > 0.0);
+}
+
+#[test]
+fn the_usage_of_a_system_without_swap_is_zero() {
+    let memory = MemoryInfo {
+        total: 8 << 30,
+        used: 2 << 30,
+        ..Default::default()
+    };
+
+    assert_eq!(memory.usage(), 25.0);
+    assert_eq!(memory.swap_usage(), 0.0);
+}
@@ -0,0 +1,122 @@
+use core::model::{ProcessInfo, ProcessList, SystemOverviewInfo};
+use core::{
+    DataSource, MockProvider, OceloError, ProviderSource, SystemInfoPollingContext,
+    SystemInfoProvider, SystemInfoUpdate,
+};
+
+/// A canned snapshot of the host `host_name` with the processor used at `usage` percent.
+fn snapshot(host_name: &str, usage: f32) -> SystemOverviewInfo {
+    let mut snapshot = SystemOverviewInfo::default();
+    snapshot.overview.host_name = host_name.to_string();
+    snapshot.cpu.name = "Mock CPU".to_string();
+    snapshot.cpu.core_count = 4;
+    snapshot.cpu.usage = usage;
+    snapshot.memory.total = 8 << 30;
+    snapshot.memory.used = 2 << 30;
+    snapshot
+}
+
+fn process(pid: u32, name: &str) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn replays_the_snapshots_in_order_then_repeats_the_last() {
+    let mut provider = MockProvider::new(vec![snapshot("first", 10.0), snapshot("second", 20.0)]);
+
+    let host_names = (0..4)
+        .map(|_| provider.get_system_overview().unwrap().overview.host_name)
+        .collect::<Vec<String>>();
+    assert_eq!(host_names, ["first", "second", "second", "second"]);
+}
+
+#[test]
+fn every_part_is_taken_from_the_next_snapshot() {
+    let mut provider = MockProvider::new(vec![snapshot("first", 10.0), snapshot("second", 20.0)]);
+
+    assert_eq!(provider.get_cpu_info().unwrap().usage, 10.0);
+    assert_eq!(provider.get_memory_info().unwrap().used, 2 << 30);
+}
+
+#[test]
+fn an_empty_provider_returns_empty_snapshots() {
+    let mut provider = MockProvider::new(Vec::new());
+
+    assert!(provider
+        .get_system_overview()
+        .unwrap()
+        .overview
+        .host_name
+        .is_empty());
+    assert!(provider.get_process_list().unwrap().is_empty());
+}
+
+#[test]
+fn every_core_reports_the_usage_of_the_whole_processor() {
+    let mut provider = MockProvider::new(vec![snapshot("host", 42.4)]);
+
+    let update = provider.get_cpu_amd_memory_info().unwrap();
+    assert_eq!(update.cores.len(), 4);
+    assert!(update.cores.iter().all(|core| core.usage == 42));
+    assert_eq!(update.memory_stats.total, 8 << 30);
+}
+
+#[test]
+fn looks_up_the_details_in_the_last_process_list() {
+    let mut provider = MockProvider::new(Vec::new()).with_processes(vec![
+        ProcessList::from(vec![process(1, "init")]),
+        ProcessList::from(vec![process(1, "init"), process(2, "shell")]),
+    ]);
+
+    provider.get_process_list().unwrap();
+    assert!(matches!(
+        provider.get_process_details(2),
+        Err(OceloError::ProcessNotFound(2))
+    ));
+
+    provider.get_process_list().unwrap();
+    assert_eq!(provider.get_process_details(2).unwrap().name, "shell");
+}
+
+#[test]
+fn evaluates_the_alerts_against_the_last_snapshot() {
+    let rule = "cpu.usage > 90".parse().unwrap();
+    let mut provider = MockProvider::new(vec![snapshot("host", 10.0), snapshot("host", 95.0)])
+        .with_alert_rules(vec![rule]);
+
+    provider.get_system_overview().unwrap();
+    assert!(provider.evaluate_alerts().unwrap().is_empty());
+
+    provider.get_system_overview().unwrap();
+    let alerts = provider.evaluate_alerts().unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].value, 95.0);
+    // the alert is raised once for the whole breach
+    assert!(provider.evaluate_alerts().unwrap().is_empty());
+}
+
+#[test]
+fn the_source_polls_the_updates_of_the_contexts() {
+    let provider = MockProvider::new(vec![snapshot("host", 10.0)])
+        .with_processes(vec![ProcessList::from(vec![process(1, "init")])]);
+    let mut source = ProviderSource::new(provider);
+
+    match source.poll(SystemInfoPollingContext::Overview).unwrap() {
+        SystemInfoUpdate::OverView(overview) => assert_eq!(overview.overview.host_name, "host"),
+        _ => panic!("the overview was not polled"),
+    }
+    match source.poll(SystemInfoPollingContext::Processes).unwrap() {
+        SystemInfoUpdate::Process(processes) => assert_eq!(processes[0].name, "init"),
+        _ => panic!("the processes were not polled"),
+    }
+    // the mock provides whole lists only, never their changes
+    assert!(matches!(
+        source.poll(SystemInfoPollingContext::Processes).unwrap(),
+        SystemInfoUpdate::Process(_)
+    ));
+    assert_eq!(source.cpu_info().unwrap().name, "Mock CPU");
+}
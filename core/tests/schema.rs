@@ -0,0 +1,149 @@
+use core::encoding::{self, Encoding};
+use core::model::{
+    CpuMemoryUpdate, ProcessInfo, ProcessList, SchemaVersion, SystemOverviewInfo, SCHEMA_VERSION,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Returns the JSON form of `value` with its `version` replaced by `version`, or removed if it is `None`.
+fn with_version<T: Serialize>(value: &T, version: Option<u32>) -> Value {
+    let mut json = serde_json::to_value(value).unwrap();
+    let fields = json.as_object_mut().unwrap();
+    match version {
+        Some(version) => fields.insert("version".to_string(), json!(version)),
+        None => fields.remove("version"),
+    };
+    json
+}
+
+/// Returns the message of the error of reading `json` as a `T`, which must fail.
+fn read_error<T: DeserializeOwned>(json: Value) -> String {
+    match serde_json::from_value::<T>(json) {
+        Ok(_) => panic!("an incompatible version was read"),
+        Err(error) => error.to_string(),
+    }
+}
+
+fn process_list() -> ProcessList {
+    ProcessList::from(vec![ProcessInfo {
+        pid: 1,
+        name: "init".to_string(),
+        ..Default::default()
+    }])
+}
+
+#[test]
+fn the_supported_version_is_checked() {
+    assert_eq!(
+        SchemaVersion::check(SCHEMA_VERSION),
+        Ok(SchemaVersion::default())
+    );
+    assert!(SchemaVersion::check(SCHEMA_VERSION + 1)
+        .unwrap_err()
+        .contains("upgrade ocelo"));
+    assert!(SchemaVersion::check(SCHEMA_VERSION - 1)
+        .unwrap_err()
+        .contains("can no longer be read"));
+}
+
+#[test]
+fn reads_the_overview_without_a_version_as_the_first_one() {
+    let mut overview = SystemOverviewInfo::default();
+    overview.overview.host_name = "old-agent".to_string();
+    let mut json = with_version(&overview, None);
+    // the fields added since the first version are missing from the older forms
+    json.as_object_mut().unwrap().remove("sessions");
+
+    let read: SystemOverviewInfo = serde_json::from_value(json).unwrap();
+    assert_eq!(read.version, SchemaVersion::default());
+    assert_eq!(read.overview.host_name, "old-agent");
+}
+
+#[test]
+fn ignores_the_fields_added_by_a_newer_agent_of_the_same_version() {
+    let mut json = with_version(&SystemOverviewInfo::default(), Some(SCHEMA_VERSION));
+    json.as_object_mut()
+        .unwrap()
+        .insert("added_later".to_string(), json!({"value": 1}));
+
+    assert!(serde_json::from_value::<SystemOverviewInfo>(json).is_ok());
+}
+
+#[test]
+fn refuses_the_overview_of_another_version() {
+    let newer = with_version(&SystemOverviewInfo::default(), Some(SCHEMA_VERSION + 1));
+    let error = read_error::<SystemOverviewInfo>(newer);
+    assert!(
+        error.contains("is newer than the supported version"),
+        "{}",
+        error
+    );
+
+    let older = with_version(&SystemOverviewInfo::default(), Some(0));
+    let error = read_error::<SystemOverviewInfo>(older);
+    assert!(error.contains("can no longer be read"), "{}", error);
+}
+
+#[test]
+fn reads_the_cpu_update_without_a_version_or_the_throttling() {
+    let update = CpuMemoryUpdate {
+        usage: 42.0,
+        ..Default::default()
+    };
+    let mut json = with_version(&update, None);
+    let fields = json.as_object_mut().unwrap();
+    fields.remove("throttling");
+    fields.remove("throttle_count");
+
+    let read: CpuMemoryUpdate = serde_json::from_value(json).unwrap();
+    assert_eq!(read.version, SchemaVersion::default());
+    assert_eq!(read.usage, 42.0);
+    assert!(!read.throttling);
+}
+
+#[test]
+fn refuses_the_cpu_update_of_a_newer_version() {
+    let newer = with_version(&CpuMemoryUpdate::default(), Some(SCHEMA_VERSION + 1));
+    let error = read_error::<CpuMemoryUpdate>(newer);
+    assert!(error.contains("upgrade ocelo"), "{}", error);
+}
+
+#[test]
+fn reads_the_bare_process_list_of_the_first_version() {
+    let json = serde_json::to_value(process_list()).unwrap();
+    let bare = json["processes"].clone();
+    assert!(bare.is_array());
+
+    let read: ProcessList = serde_json::from_value(bare).unwrap();
+    assert_eq!(read[0].name, "init");
+}
+
+#[test]
+fn writes_the_version_of_the_process_list() {
+    let json = serde_json::to_value(process_list()).unwrap();
+    assert_eq!(json["version"], json!(SCHEMA_VERSION));
+
+    let read: ProcessList = serde_json::from_value(json).unwrap();
+    assert_eq!(read[0].pid, 1);
+}
+
+#[test]
+fn refuses_the_process_list_of_a_newer_version() {
+    let newer = with_version(&process_list(), Some(SCHEMA_VERSION + 1));
+    let error = read_error::<ProcessList>(newer);
+    assert!(error.contains("upgrade ocelo"), "{}", error);
+}
+
+#[test]
+fn checks_the_version_of_both_encodings() {
+    let newer = with_version(&CpuMemoryUpdate::default(), Some(SCHEMA_VERSION + 1));
+    for encoding in [Encoding::Json, Encoding::MessagePack] {
+        let current = encoding.encode(&CpuMemoryUpdate::default()).unwrap();
+        assert!(encoding::decode::<CpuMemoryUpdate>(&current).is_ok());
+
+        let bytes = encoding.encode(&newer).unwrap();
+        let error = encoding::decode::<CpuMemoryUpdate>(&bytes).unwrap_err();
+        assert!(error.to_string().contains("upgrade ocelo"), "{}", error);
+    }
+}
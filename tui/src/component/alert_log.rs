@@ -0,0 +1,105 @@
+use super::centered_rect;
+use crate::{Message, UserEvent};
+use core::Alert;
+use ratatui::{
+    layout::Alignment,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+};
+use std::collections::VecDeque;
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// The maximum count of alerts kept in the log, the oldest ones are dropped.
+const LOG_LENGTH: usize = 100;
+
+/// Popup listing the alerts raised by the alert rules, the most recent first.
+/// It is only displayed while `Attribute::Display` is set to `AttrValue::Flag(true)`.
+#[derive(Default)]
+pub struct AlertLog {
+    properties: Props,
+
+    alerts: VecDeque<Alert>,
+}
+
+impl MockComponent for AlertLog {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let visible = self
+            .properties
+            .get_or(Attribute::Display, AttrValue::Flag(false))
+            .unwrap_flag();
+        if !visible {
+            return;
+        }
+
+        let lines = if self.alerts.is_empty() {
+            vec![Line::from("No alerts were raised.").italic()]
+        } else {
+            self.alerts
+                .iter()
+                .rev()
+                .map(|alert| Line::from(format!("{}  {}", format_time(alert.timestamp), alert)))
+                .collect()
+        };
+
+        let popup_area = centered_rect(area.width.min(80), area.height.min(20), area);
+        let popup = Paragraph::new(lines).block(
+            Block::bordered()
+                .title("Alerts")
+                .title_alignment(Alignment::Center)
+                .title_bottom(Line::from(" a: close ").centered())
+                .red(),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(popup, popup_area);
+    }
+}
+
+impl Component<Message, UserEvent> for AlertLog {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Alerts(alerts)) => {
+                for alert in alerts {
+                    if self.alerts.len() == LOG_LENGTH {
+                        self.alerts.pop_front();
+                    }
+                    self.alerts.push_back(alert);
+                }
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Formats the time of day of a Unix timestamp as HH:MM:SS in UTC.
+fn format_time(timestamp: f64) -> String {
+    let seconds = timestamp as u64 % 86400;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
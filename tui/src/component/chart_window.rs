@@ -0,0 +1,148 @@
+use crate::Message;
+use core::history::{unix_time, ExportFormat, History, Metric, DEFAULT_RETENTION};
+use ratatui::{style::Stylize, text::Span};
+use std::time::Duration;
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+
+/// The periods of time the charts can display, selected one after the other with the `w` key.
+pub const CHART_WINDOWS: [Duration; 4] = [
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(15 * 60),
+    Duration::from_secs(60 * 60),
+];
+
+/// The shortest period of time the charts can be zoomed into.
+const MIN_CHART_SPAN: Duration = Duration::from_secs(30);
+
+/// The count of points a series is resampled into, which is more than the width of a chart in braille dots.
+const CHART_POINTS: usize = 400;
+
+/// The period of time displayed by the charts of a view, which can be zoomed and panned.
+/// The x coordinates of the chart points are the seconds before the right edge of the window,
+/// so the chart is bounded by `[-span, 0]`.
+///
+/// Controls:
+/// * w => selects the next window of `CHART_WINDOWS`, displaying the latest samples
+/// * z/Z => zooms into or out of the displayed period
+/// * Left/Right => pans the displayed period back or forward in time, by a quarter of its length
+#[derive(Clone, Copy, Debug)]
+pub struct ChartWindow {
+    /// The length of the displayed period
+    span: Duration,
+    /// How much the right edge of the displayed period is before now
+    offset: Duration,
+}
+
+impl Default for ChartWindow {
+    fn default() -> Self {
+        ChartWindow {
+            span: CHART_WINDOWS[2],
+            offset: Duration::ZERO,
+        }
+    }
+}
+
+impl ChartWindow {
+    /// Changes the window by the pressed key, returns false if the key does not control the window.
+    pub fn on_key(&mut self, event: &KeyEvent) -> bool {
+        match event.code {
+            Key::Char('w') => {
+                self.span = CHART_WINDOWS
+                    .iter()
+                    .find(|window| **window > self.span)
+                    .copied()
+                    .unwrap_or(CHART_WINDOWS[0]);
+                self.offset = Duration::ZERO;
+            }
+            Key::Char('z') if event.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.span = (self.span * 2).min(DEFAULT_RETENTION);
+                self.offset = self.offset.min(DEFAULT_RETENTION - self.span);
+            }
+            Key::Char('z') => self.span = (self.span / 2).max(MIN_CHART_SPAN),
+            Key::Left => {
+                self.offset = (self.offset + self.span / 4).min(DEFAULT_RETENTION - self.span);
+            }
+            Key::Right => self.offset = self.offset.saturating_sub(self.span / 4),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Returns the start and the end of the displayed period in seconds since the Unix epoch.
+    fn period(&self) -> (f64, f64) {
+        let end = unix_time() - self.offset.as_secs_f64();
+        (end - self.span.as_secs_f64(), end)
+    }
+
+    /// The x axis bounds of the charts.
+    pub fn bounds(&self) -> [f64; 2] {
+        [-self.span.as_secs_f64(), 0.0]
+    }
+
+    /// The labels of the x axis at the start, the middle and the end of the displayed period,
+    /// like `-15m`, `-7m30s` and `now`.
+    pub fn labels<'a>(&self) -> Vec<Span<'a>> {
+        let label = |before: Duration| {
+            if before.is_zero() {
+                "now".to_string()
+            } else {
+                format!("-{}", format_span(before))
+            }
+        };
+        vec![
+            label(self.offset + self.span),
+            label(self.offset + self.span / 2),
+            label(self.offset),
+        ]
+        .into_iter()
+        .map(|label| label.gray().bold())
+        .collect()
+    }
+
+    /// Converts the samples of `metric` in the displayed period into chart points.
+    pub fn points(&self, history: &History, metric: &Metric) -> Vec<(f64, f64)> {
+        let (start, end) = self.period();
+        history
+            .resample(metric, start, end, CHART_POINTS)
+            .iter()
+            .map(|sample| (sample.timestamp - end, sample.value))
+            .collect()
+    }
+
+    /// Exports the samples of `metrics` in the displayed period for the chart named `chart`,
+    /// as CSV by the `e` key and as JSON by the `E` key, which is pressed with the `modifiers`.
+    pub fn export(
+        &self,
+        chart: &str,
+        history: &History,
+        metrics: &[Metric],
+        modifiers: KeyModifiers,
+    ) -> Message {
+        let format = if modifiers.contains(KeyModifiers::SHIFT) {
+            ExportFormat::Json
+        } else {
+            ExportFormat::Csv
+        };
+        let (start, end) = self.period();
+        Message::ExportChart(
+            chart.to_string(),
+            format,
+            history.export(metrics, start, end, format),
+        )
+    }
+}
+
+/// Formats a length of time compactly, like `1h`, `7m30s` or `30s`.
+fn format_span(span: Duration) -> String {
+    let seconds = span.as_secs();
+    [
+        (seconds / 3600, "h"),
+        ((seconds % 3600) / 60, "m"),
+        (seconds % 60, "s"),
+    ]
+    .iter()
+    .filter(|(value, _)| *value > 0)
+    .map(|(value, unit)| format!("{}{}", value, unit))
+    .collect::<String>()
+}
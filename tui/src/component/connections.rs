@@ -0,0 +1,252 @@
+use crate::{Message, UserEvent};
+use core::connections::{sort_connections, Connection, ConnectionColumn};
+use core::model::SortOrder;
+use ratatui::{
+    layout::{Alignment, Constraint},
+    style::{Style, Stylize},
+    widgets::{Block, Cell, Clear, Row, Table, TableState},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// The columns of the table with their titles and widths.
+const COLUMNS: [(ConnectionColumn, &str, Constraint); 6] = [
+    (ConnectionColumn::Protocol, "proto", Constraint::Length(7)),
+    (
+        ConnectionColumn::Local,
+        "local address",
+        Constraint::Fill(1),
+    ),
+    (
+        ConnectionColumn::Remote,
+        "remote address",
+        Constraint::Fill(1),
+    ),
+    (ConnectionColumn::State, "state", Constraint::Length(13)),
+    (ConnectionColumn::Pid, "pid", Constraint::Length(8)),
+    (ConnectionColumn::Process, "process", Constraint::Length(16)),
+];
+
+/// Pane displaying the TCP and UDP sockets of the system over the network interfaces,
+/// with the processes owning them.
+///
+/// Controls:
+/// * t => sorts the table by protocol
+/// * l => sorts the table by local address
+/// * r => sorts the table by remote address
+/// * s => sorts the table by state
+/// * p => sorts the table by pid
+/// * n => sorts the table by process name
+/// * Up/Down => moves the selection
+/// * PageUp/PageDown => moves the selection by a screen of rows
+/// * / => starts searching, the typed text filters the connections by any of their fields.
+///   Enter finishes the typing, while the filter stays active. Searching for an empty text removes the filter.
+/// * Enter/c => closes the pane
+///
+/// Pressing the key of the currently sorted column toggles between ascending and descending order.
+/// The connections are only listed on Linux.
+#[derive(Default)]
+pub struct Connections {
+    properties: Props,
+
+    /// Every connection of the last update
+    list: Vec<Connection>,
+
+    /// The connections displayed in the table, matching the search filter
+    visible: Vec<Connection>,
+
+    /// The text which the connections are filtered by
+    filter: String,
+
+    /// True while the search filter is being typed
+    searching: bool,
+
+    /// Holds the selected row
+    table_state: TableState,
+
+    /// Count of the rows fitting into the table at the last rendering
+    page_size: usize,
+
+    /// The column which the table is sorted by
+    sort_column: ConnectionColumn,
+
+    sort_order: SortOrder,
+}
+
+impl MockComponent for Connections {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let header = Row::new(COLUMNS.iter().map(|(column, title, _)| {
+            if *column == self.sort_column {
+                let indicator = match self.sort_order {
+                    SortOrder::Ascending => '▲',
+                    SortOrder::Descending => '▼',
+                };
+                Cell::from(format!("{} {}", title, indicator).bold())
+            } else {
+                Cell::from(*title)
+            }
+        }));
+
+        let rows = self.visible.iter().map(|connection| {
+            Row::new(vec![
+                Cell::from(connection.protocol.as_str()),
+                Cell::from(connection.local.as_str()),
+                Cell::from(connection.remote.as_str()),
+                Cell::from(connection.state.as_str()),
+                Cell::from(connection.pid.map_or(String::new(), |pid| pid.to_string())),
+                Cell::from(connection.process.as_deref().unwrap_or_default()),
+            ])
+        });
+
+        let mut block = Block::bordered()
+            .title(format!("Connections ({})", self.visible.len()))
+            .title_alignment(Alignment::Center);
+        if self.searching || !self.filter.is_empty() {
+            let cursor = if self.searching { "_" } else { "" };
+            block = block.title_bottom(format!(" /{}{} ", self.filter, cursor).yellow());
+        }
+
+        let table = Table::default()
+            .block(block)
+            .widths(COLUMNS.iter().map(|(_, _, width)| *width))
+            .header(header)
+            .row_highlight_style(Style::default().reversed())
+            .rows(rows);
+
+        // the borders and the header take three lines
+        self.page_size = area.height.saturating_sub(3) as usize;
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+}
+
+impl Component<Message, UserEvent> for Connections {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Connections(mut connections)) => {
+                sort_connections(&mut connections, self.sort_column, self.sort_order);
+                self.list = connections;
+                self.update_visible();
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.searching => self.on_search(code),
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let column = match code {
+                    Key::Char('t') => ConnectionColumn::Protocol,
+                    Key::Char('l') => ConnectionColumn::Local,
+                    Key::Char('r') => ConnectionColumn::Remote,
+                    Key::Char('s') => ConnectionColumn::State,
+                    Key::Char('p') => ConnectionColumn::Pid,
+                    Key::Char('n') => ConnectionColumn::Process,
+                    Key::Up => {
+                        self.select_previous(1);
+                        return Some(Message::Redraw);
+                    }
+                    Key::Down => {
+                        self.select_next(1);
+                        return Some(Message::Redraw);
+                    }
+                    Key::PageUp => {
+                        self.select_previous(self.page_size.max(1));
+                        return Some(Message::Redraw);
+                    }
+                    Key::PageDown => {
+                        self.select_next(self.page_size.max(1));
+                        return Some(Message::Redraw);
+                    }
+                    Key::Char('/') => {
+                        self.searching = true;
+                        return Some(Message::Redraw);
+                    }
+                    Key::Enter | Key::Char('c') => return Some(Message::CloseConnections),
+                    _ => return None,
+                };
+                self.sort_by(column);
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Connections {
+    /// Handles the key presses while the search filter is being typed.
+    fn on_search(&mut self, code: Key) -> Option<Message> {
+        match code {
+            Key::Char(c) => self.filter.push(c),
+            Key::Backspace => {
+                self.filter.pop();
+            }
+            Key::Enter => self.searching = false,
+            _ => return None,
+        }
+        self.update_visible();
+        Some(Message::Redraw)
+    }
+
+    /// Applies the search filter on the connections, keeping the selection inside the table.
+    fn update_visible(&mut self) {
+        self.visible = self
+            .list
+            .iter()
+            .filter(|connection| connection.matches(&self.filter))
+            .cloned()
+            .collect();
+
+        let selected = match self.table_state.selected() {
+            _ if self.visible.is_empty() => None,
+            Some(i) => Some(i.min(self.visible.len() - 1)),
+            None => Some(0),
+        };
+        self.table_state.select(selected);
+    }
+
+    fn select_next(&mut self, step: usize) {
+        let last = self.visible.len().saturating_sub(1);
+        let next = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(step).min(last));
+        self.table_state.select(Some(next));
+    }
+
+    fn select_previous(&mut self, step: usize) {
+        let previous = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(step));
+        self.table_state.select(Some(previous));
+    }
+
+    /// Sorts the table by `column`. If the table is already sorted by `column`, the order is reversed.
+    fn sort_by(&mut self, column: ConnectionColumn) {
+        if self.sort_column == column {
+            self.sort_order = self.sort_order.toggle();
+        } else {
+            self.sort_column = column;
+            self.sort_order = SortOrder::Ascending;
+        }
+        sort_connections(&mut self.list, self.sort_column, self.sort_order);
+        self.update_visible();
+    }
+}
@@ -0,0 +1,265 @@
+use super::{centered_rect, size_format, MODAL};
+use crate::{Message, UserEvent};
+use core::config::Units;
+use core::model::ContainersInfo;
+use core::ContainerAction;
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Alignment, Constraint},
+    style::{Style, Stylize},
+    widgets::{Block, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent, KeyModifiers},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// Component for displaying the running containers of Docker or Podman
+/// with the processor, memory, network and block device usage of each.
+///
+/// Controls:
+/// * Up/Down/PageUp/PageDown => moves the selection
+/// * k/Delete => stops or kills the selected container after confirmation
+#[derive(Default)]
+pub struct Containers {
+    properties: Props,
+
+    containers_info: ContainersInfo,
+
+    /// Holds the selected row
+    table_state: TableState,
+
+    /// The id of the selected container, which stays selected when containers start or exit
+    selected_id: Option<String>,
+
+    /// Count of the rows fitting into the table at the last rendering
+    page_size: usize,
+
+    /// The id and the name of the container waiting for the confirmation of its stopping
+    stop_dialog: Option<(String, String)>,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+}
+
+impl Containers {
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Selects the row at `index`, clamped inside the table.
+    fn select(&mut self, index: usize) {
+        let containers = &self.containers_info.containers;
+        if containers.is_empty() {
+            self.table_state.select(None);
+            self.selected_id = None;
+        } else {
+            let index = index.min(containers.len() - 1);
+            self.table_state.select(Some(index));
+            self.selected_id = Some(containers[index].id.clone());
+        }
+    }
+
+    fn process_update(&mut self, update: ContainersInfo) {
+        self.containers_info = update;
+        let index = self.selected_id.as_ref().and_then(|id| {
+            self.containers_info
+                .containers
+                .iter()
+                .position(|container| &container.id == id)
+        });
+        match (index, self.table_state.selected()) {
+            (Some(index), _) => self.select(index),
+            // the selected container has exited, the one taking its place is selected
+            (None, Some(selected)) => self.select(selected),
+            (None, None) => {}
+        }
+    }
+
+    fn open_stop_dialog(&mut self) {
+        let selected = self
+            .table_state
+            .selected()
+            .and_then(|index| self.containers_info.containers.get(index));
+        if let Some(container) = selected {
+            self.stop_dialog = Some((container.id.clone(), container.name.clone()));
+            self.set_modal(true);
+        }
+    }
+
+    fn close_stop_dialog(&mut self) {
+        self.stop_dialog = None;
+        self.set_modal(false);
+    }
+
+    /// Marks whether a dialog is displayed, so the global key bindings are disabled meanwhile.
+    fn set_modal(&mut self, modal: bool) {
+        self.properties
+            .set(Attribute::Custom(MODAL), AttrValue::Flag(modal));
+    }
+
+    /// Handles the key presses while the stop dialog is open.
+    fn on_stop_dialog(&mut self, event: KeyEvent) -> Option<Message> {
+        let id = self.stop_dialog.as_ref()?.0.clone();
+        let message = match event.code {
+            // the uppercase letters are received as lowercase ones with the shift modifier
+            Key::Char('k') if event.modifiers.contains(KeyModifiers::SHIFT) => {
+                Some(Message::ControlContainer(id, ContainerAction::Kill))
+            }
+            Key::Enter | Key::Char('t') => {
+                Some(Message::ControlContainer(id, ContainerAction::Stop))
+            }
+            // Esc is not used for cancelling, because the menu would receive it after closing the dialog
+            Key::Char('n') => Some(Message::Redraw),
+            _ => return None,
+        };
+        self.close_stop_dialog();
+        message
+    }
+
+    fn render_stop_dialog(&self, frame: &mut Frame, area: Rect) {
+        if let Some((id, name)) = &self.stop_dialog {
+            let popup_area = centered_rect(60, 6, area);
+            let text = format!(
+                "Stop {} ({})?\n\nEnter/t: SIGTERM   K: SIGKILL   n: cancel",
+                name, id
+            );
+            let popup = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::bordered()
+                        .title("Stop container")
+                        .title_alignment(Alignment::Center)
+                        .red(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_widget(popup, popup_area);
+        }
+    }
+}
+
+impl MockComponent for Containers {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let containers = &self.containers_info.containers;
+        let title = match &self.containers_info.runtime {
+            Some(runtime) => format!("Containers of {} ({})", runtime, containers.len()),
+            None => "Containers".to_string(),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_bottom(" k: stop ")
+            .title_alignment(Alignment::Center);
+        if containers.is_empty() {
+            let message = match self.containers_info.runtime {
+                Some(_) => "No containers are running",
+                None => "No Docker or Podman socket was found",
+            };
+            frame.render_widget(
+                Paragraph::new(message)
+                    .alignment(Alignment::Center)
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        let format_size_options = size_format(self.units);
+        let rate = |rate: f64| format!("{}/s", (rate as u64).format_size(format_size_options));
+
+        let rows = containers.iter().map(|container| {
+            Row::new(vec![
+                Cell::from(container.id.as_str()),
+                Cell::from(container.name.as_str()),
+                Cell::from(container.image.as_str()),
+                Cell::from(format!("{:.1}%", container.cpu_usage)),
+                Cell::from(format!(
+                    "{} / {}",
+                    container.memory.format_size(format_size_options),
+                    container.memory_limit.format_size(format_size_options)
+                )),
+                Cell::from(rate(container.net_received_rate)),
+                Cell::from(rate(container.net_transmitted_rate)),
+                Cell::from(rate(container.read_rate)),
+                Cell::from(rate(container.write_rate)),
+                Cell::from(container.status.as_str()),
+            ])
+        });
+
+        // the borders and the header are not part of a page
+        self.page_size = area.height.saturating_sub(3) as usize;
+        let table = Table::default()
+            .block(block)
+            .widths([
+                Constraint::Length(12),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Length(7),
+                Constraint::Length(21),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Fill(1),
+            ])
+            .header(
+                Row::new(vec![
+                    "id", "name", "image", "cpu", "memory", "net rx", "net tx", "read", "write",
+                    "status",
+                ])
+                .bold(),
+            )
+            .row_highlight_style(Style::default().reversed())
+            .rows(rows);
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+        self.render_stop_dialog(frame, area);
+    }
+}
+
+impl Component<Message, UserEvent> for Containers {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Containers(containers_info)) => {
+                self.process_update(containers_info);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(event) if self.stop_dialog.is_some() => self.on_stop_dialog(event),
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let selected = self.table_state.selected();
+                match code {
+                    Key::Up => self.select(selected.map_or(0, |i| i.saturating_sub(1))),
+                    Key::Down => self.select(selected.map_or(0, |i| i + 1)),
+                    Key::PageUp => {
+                        self.select(selected.map_or(0, |i| i.saturating_sub(self.page_size)))
+                    }
+                    Key::PageDown => self.select(selected.map_or(0, |i| i + self.page_size)),
+                    Key::Char('k') | Key::Delete => self.open_stop_dialog(),
+                    _ => return None,
+                }
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
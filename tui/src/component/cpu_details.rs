@@ -1,21 +1,68 @@
-use crate::Message;
-use core::model::{CpuCore, CpuMemoryUpdate};
-use humansize::{BaseUnit, FormatSize, FormatSizeOptions};
+use super::{chart_dataset, get_color_for, memory_bar, render_chart, size_format, ChartWindow};
+use crate::{Message, UserEvent};
+use core::config::{ChartStyle, Units};
+use core::history::{History, Metric};
+use core::model::{CpuCore, CpuMemoryUpdate, CpuTopology};
+use humansize::FormatSize;
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Margin},
     style::{Color, Style, Stylize},
-    symbols::Marker,
+    text::{Line, Span},
     widgets::{
-        Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, LegendPosition,
-        Paragraph,
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, LegendPosition, Paragraph, Sparkline,
+        Wrap,
     },
 };
+use std::collections::BTreeSet;
+use std::mem;
 use tuirealm::{
     command::{Cmd, CmdResult},
+    event::{Key, KeyEvent, KeyModifiers},
     ratatui::prelude::Rect,
-    AttrValue, Attribute, Component, Event, Frame, MockComponent, NoUserEvent, Props, State,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
 };
 
+/// The narrowest area the bars of a core are rendered in, wide enough for the `Core N` label.
+const MIN_CORE_WIDTH: u16 = 8;
+
+/// The lowest area the bars of a core are rendered in, including the labels under the bars.
+const MIN_CORE_HEIGHT: u16 = 5;
+
+/// The widths of a core in the heatmap including the gap after it, from the widest.
+/// The narrowest one fitting every core into the area is used, the narrowest cells have no label.
+const HEATMAP_CELL_WIDTHS: [u16; 3] = [6, 4, 2];
+
+/// Width of a core in the frequency list including the gap after it.
+const FREQUENCY_CELL_WIDTH: u16 = 38;
+
+/// Width of the bar showing the frequency of a core relative to its maximum.
+const FREQUENCY_BAR_WIDTH: usize = 10;
+
+/// The usage in percent from which a core running well below its maximum frequency is considered throttled.
+const BUSY_CORE_USAGE: u64 = 50;
+
+/// How the statistics of the individual cores are displayed.
+#[derive(Clone, Copy, Default)]
+enum CoreView {
+    /// Usage, frequency and temperature bars of every core
+    #[default]
+    Bars,
+    /// One cell per core colored by its usage, fitting any core count
+    Heatmap,
+    /// Current and maximum frequency of every core
+    Frequencies,
+}
+
+/// Component for displaying the CPU usage over time, the statistics of the individual cores
+/// and the memory usage.
+///
+/// Controls:
+/// * h => toggles between the bars and the heatmap of the cores
+/// * f => toggles between the bars and the frequencies of the cores
+/// * t => shows or hides the topology of the processor
+/// * c/C => charts the frequency over time of the next/previous core next to the usage, after the last core the chart is hidden
+/// * e/E => exports the charted CPU, memory, swap and core frequency history into a CSV/JSON file
+/// * w/z/Z/Left/Right => changes, zooms and pans the period of time displayed by the charts, see `ChartWindow`
 #[derive(Default)]
 pub struct CpuMemoryDetails {
     properties: Props,
@@ -28,36 +75,50 @@ pub struct CpuMemoryDetails {
     /// Name of the CPU
     cpu_name: String,
 
-    /// CPU load/usage over time in percent
-    cpu_usage: Vec<(f64, f64)>,
+    /// CPU, memory and swap usage over time in percent, and the frequency of every core in MHz
+    history: History,
+
+    /// The period of time displayed by the usage charts
+    window: ChartWindow,
 
     /// Indiviudal CPU core stats
     cpu_core_stats: Vec<CpuCore>,
 
     /// The maximum frequency that the CPU reached during runtime.
-    /// It is needed for normalization in the core graphs.
+    /// It is needed for normalization in the core graphs where the maximum of a core is not known.
     max_frequency: usize,
 
-    /// Physical memory usage over time in percent
-    memory_usage: Vec<(f64, f64)>,
+    /// The governor choosing the frequency of the cores
+    governor: Option<String>,
+
+    /// The lowest and the highest frequency the governor may choose in MHz
+    scaling_range: (Option<u64>, Option<u64>),
+
+    /// Whether the processor may run above its base frequency
+    boost: Option<bool>,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+
+    /// How the series of the charts are drawn
+    chart_style: ChartStyle,
+
+    /// How the individual cores are displayed
+    core_view: CoreView,
+
+    /// The layout of the processor, like its sockets and caches
+    topology: CpuTopology,
+
+    /// The topology panel is expanded
+    show_topology: bool,
 
-    /// Swap memory usage over time in percent
-    swap_usage: Vec<(f64, f64)>,
+    /// Index of the core whose frequency over time is charted next to the usage
+    selected_core: Option<usize>,
 }
 
 impl MockComponent for CpuMemoryDetails {
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        if matches!(attr, Attribute::Value) {
-            if let Some(json_str) = value.as_string() {
-                if let Ok(update) = CpuMemoryUpdate::from_json(json_str) {
-                    self.process_update(update);
-                } else {
-                    eprintln!("failed to create from json");
-                }
-            } else {
-                self.properties.set(attr, value);
-            }
-        }
+        self.properties.set(attr, value);
     }
 
     fn perform(&mut self, _cmd: Cmd) -> CmdResult {
@@ -86,14 +147,90 @@ impl MockComponent for CpuMemoryDetails {
 
         frame.render_widget(block, area);
         self.render_cpu_usage_chart(frame, layout[0]);
-        self.render_core_details(frame, layout[1]);
+        match self.core_view {
+            CoreView::Bars => self.render_core_details(frame, layout[1]),
+            CoreView::Heatmap => self.render_core_heatmap(frame, layout[1]),
+            CoreView::Frequencies => self.render_core_frequencies(frame, layout[1]),
+        }
         self.render_memory_details(frame, layout[2]);
     }
 }
 
-impl Component<Message, NoUserEvent> for CpuMemoryDetails {
-    fn on(&mut self, _event: Event<NoUserEvent>) -> Option<Message> {
-        None
+impl Component<Message, UserEvent> for CpuMemoryDetails {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::CpuAndMemory(update)) => {
+                self.process_update(update);
+                Some(Message::Redraw)
+            }
+            Event::User(UserEvent::CpuInfo(info)) => {
+                let (cpu_info, topology) = *info;
+                *self = mem::take(self)
+                    .with_core_count(cpu_info.core_count)
+                    .with_cpu_name(cpu_info.name)
+                    .with_frequency_scaling(
+                        cpu_info.governor,
+                        cpu_info.min_frequency,
+                        cpu_info.max_frequency,
+                        cpu_info.boost,
+                    )
+                    .with_topology(topology);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('h'),
+                ..
+            }) => {
+                self.core_view = match self.core_view {
+                    CoreView::Heatmap => CoreView::Bars,
+                    _ => CoreView::Heatmap,
+                };
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('t'),
+                ..
+            }) => {
+                self.show_topology = !self.show_topology;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('f'),
+                ..
+            }) => {
+                self.core_view = match self.core_view {
+                    CoreView::Frequencies => CoreView::Bars,
+                    _ => CoreView::Frequencies,
+                };
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('c'),
+                modifiers,
+            }) => {
+                self.select_core(!modifiers.contains(KeyModifiers::SHIFT));
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('e'),
+                modifiers,
+            }) => {
+                let mut metrics = vec![
+                    Metric::CpuUsage,
+                    Metric::MemoryUsage,
+                    Metric::SwapUsage,
+                    Metric::SwapIn,
+                    Metric::SwapOut,
+                ];
+                metrics.extend(self.selected_core.map(Metric::CoreFrequency));
+                Some(
+                    self.window
+                        .export("cpu", &self.history, &metrics, modifiers),
+                )
+            }
+            Event::Keyboard(key) if self.window.on_key(&key) => Some(Message::Redraw),
+            _ => None,
+        }
     }
 }
 
@@ -111,41 +248,96 @@ impl CpuMemoryDetails {
         self
     }
 
-    fn process_update(&mut self, update: CpuMemoryUpdate) {
-        let last_index = self.cpu_usage.len() as f64;
-        let cpu_usage = update.usage as f64;
-        self.cpu_usage.push((last_index, cpu_usage));
+    /// Sets the frequency scaling of the processor to be displayed, with the frequencies in MHz.
+    pub fn with_frequency_scaling(
+        mut self,
+        governor: Option<String>,
+        min_frequency: Option<u64>,
+        max_frequency: Option<u64>,
+        boost: Option<bool>,
+    ) -> Self {
+        self.governor = governor;
+        self.scaling_range = (min_frequency, max_frequency);
+        self.boost = boost;
+        self
+    }
 
-        let memory_used_percent = if update.memory_stats.total > 0 {
-            (update.memory_stats.used as f64 / update.memory_stats.total as f64) * 100.0
-        } else {
-            0.0
-        };
-        let swap_used_percent = if update.memory_stats.swap_total > 0 {
-            (update.memory_stats.swap_used / update.memory_stats.total) as f64 * 100.0
-        } else {
-            0.0
-        };
-        let last_index = self.swap_usage.len() as f64;
+    /// Sets the topology of the processor to be displayed.
+    pub fn with_topology(mut self, topology: CpuTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets the history which the usage charts are drawn from.
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
 
-        self.memory_usage.push((last_index, memory_used_percent));
-        self.swap_usage.push((last_index, swap_used_percent));
+    /// Sets how the series of the charts are drawn.
+    pub fn with_chart_style(mut self, chart_style: ChartStyle) -> Self {
+        self.chart_style = chart_style;
+        self
+    }
 
-        if self.max_frequency < update.frequency {
-            self.max_frequency = update.frequency;
+    fn process_update(&mut self, update: CpuMemoryUpdate) {
+        self.history.record(Metric::CpuUsage, update.usage as f64);
+
+        self.history
+            .record(Metric::MemoryUsage, update.memory_stats.usage());
+        self.history
+            .record(Metric::SwapUsage, update.memory_stats.swap_usage());
+        self.history
+            .record(Metric::SwapIn, update.memory_stats.swap_in_rate);
+        self.history
+            .record(Metric::SwapOut, update.memory_stats.swap_out_rate);
+        for (index, core) in update.cores.iter().enumerate() {
+            self.history
+                .record(Metric::CoreFrequency(index), core.frequency as f64);
         }
 
+        let fastest_core = update
+            .cores
+            .iter()
+            .map(|core| core.frequency as usize)
+            .max()
+            .unwrap_or_default();
+        self.max_frequency = self.max_frequency.max(update.frequency).max(fastest_core);
+
         self.cpu_update = update;
     }
 
+    /// Selects the next core to chart if `forward`, or the previous one.
+    /// The chart is hidden after the last core, then it starts again from the first one.
+    fn select_core(&mut self, forward: bool) {
+        let count = self.cpu_update.cores.len();
+        self.selected_core = match (self.selected_core, forward) {
+            _ if count == 0 => None,
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(index), true) => Some(index + 1).filter(|next| *next < count),
+            (Some(index), false) => index.checked_sub(1),
+        };
+    }
+
     /// Renders the CPU details in the left side and an usage over time chart in the right side of the top third of the screen.
+    /// The topology of the processor is displayed between them when it is expanded,
+    /// the frequency over time of the selected core is charted next to the usage.
     fn render_cpu_usage_chart(&self, frame: &mut Frame, area: Rect) {
-        let layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(25), Constraint::Fill(1)])
-            .split(area);
-
-        let cpu_main_info = format!(
+        let [info_area, topology_area, chart_area] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(if self.show_topology { 25 } else { 0 }),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let mut cpu_main_info = format!(
             "Name: {}\nCore count: {}\nUsage: {}%\nFrequency: {}Mhz\nTemperature: {}°C",
             self.cpu_name,
             self.core_count,
@@ -153,7 +345,32 @@ impl CpuMemoryDetails {
             self.cpu_update.frequency,
             self.cpu_update.temperature
         );
-        let cpu_label = Paragraph::new(cpu_main_info).block(Block::bordered().reset());
+        if let Some(governor) = &self.governor {
+            cpu_main_info.push_str(&format!("\nGovernor: {}", governor));
+        }
+        if let (Some(min), Some(max)) = self.scaling_range {
+            cpu_main_info.push_str(&format!("\nScaling: {}-{}Mhz", min, max));
+        }
+        if let Some(boost) = self.boost {
+            cpu_main_info.push_str(match boost {
+                true => "\nBoost: on",
+                false => "\nBoost: off",
+            });
+        }
+        // the badge stays after the throttling is over, so a short event is not missed
+        let mut cpu_block = Block::bordered().reset();
+        if self.cpu_update.throttling {
+            cpu_block = cpu_block.title(Line::from(" THROTTLING ").light_red().bold().reversed());
+        }
+        if self.cpu_update.throttle_count > 0 {
+            cpu_block = cpu_block.title_bottom(
+                Line::from(format!(" throttled {}x ", self.cpu_update.throttle_count)).yellow(),
+            );
+        }
+        if !self.show_topology {
+            cpu_block = cpu_block.title_bottom(Line::from(" t: topology ").right_aligned());
+        }
+        let cpu_label = Paragraph::new(cpu_main_info).block(cpu_block);
 
         //--- CPU Usage Over Time ---
         let percent_axis = Axis::default()
@@ -162,22 +379,16 @@ impl CpuMemoryDetails {
                 "50".yellow().bold(),
                 "100".red().bold(),
             ])
-            // updates coming at every 3 seconds, keep only last 15 minutes
-            .bounds([0.0, (15.0 * 60.0) / 3.0]);
-        let time_axis = Axis::default()
-            .labels(vec![
-                "1m".gray().bold(),
-                "5m".gray().bold(),
-                "15m".gray().bold(),
-            ])
             .bounds([0.0, 100.0]);
+        let time_axis = Axis::default()
+            .labels(self.window.labels())
+            .bounds(self.window.bounds());
 
-        let cpu_dataset = Dataset::default()
+        let cpu_usage = self.window.points(&self.history, &Metric::CpuUsage);
+        let cpu_dataset = chart_dataset(self.chart_style)
             .name("CPU Usage")
-            .marker(Marker::Dot)
             .style(Style::default().light_green())
-            .graph_type(GraphType::Scatter)
-            .data(&self.cpu_usage);
+            .data(&cpu_usage);
 
         let cpu_chart = Chart::new(vec![cpu_dataset])
             .block(
@@ -190,67 +401,381 @@ impl CpuMemoryDetails {
             .legend_position(Some(LegendPosition::TopRight))
             .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
 
-        frame.render_widget(cpu_label, layout[0]);
-        frame.render_widget(cpu_chart, layout[1]);
+        frame.render_widget(cpu_label, info_area);
+        if self.show_topology {
+            self.render_topology(frame, topology_area);
+        }
+        match self.selected_core {
+            Some(index) => {
+                let [usage_area, frequency_area] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                        .areas(chart_area);
+                render_chart(frame, cpu_chart, usage_area, self.chart_style);
+                self.render_core_frequency_chart(frame, frequency_area, index);
+            }
+            None => render_chart(frame, cpu_chart, chart_area, self.chart_style),
+        }
+    }
+
+    /// Renders the frequency over time of the core at `index`, up to the maximum frequency of the core,
+    /// or the highest frequency seen where the maximum is not known.
+    fn render_core_frequency_chart(&self, frame: &mut Frame, area: Rect, index: usize) {
+        let max = self
+            .cpu_update
+            .cores
+            .get(index)
+            .and_then(|core| core.max_frequency)
+            .unwrap_or(self.max_frequency as u64)
+            .max(1);
+        let frequency_axis = Axis::default()
+            .labels(vec![
+                "0".bold(),
+                format!("{}", max / 2).bold(),
+                format!("{}", max).bold(),
+            ])
+            .bounds([0.0, max as f64]);
+        let time_axis = Axis::default()
+            .labels(self.window.labels())
+            .bounds(self.window.bounds());
+
+        let frequency = self
+            .window
+            .points(&self.history, &Metric::CoreFrequency(index));
+        let frequency_dataset = chart_dataset(self.chart_style)
+            .name("MHz")
+            .style(Style::default().light_cyan())
+            .data(&frequency);
+
+        let frequency_chart = Chart::new(vec![frequency_dataset])
+            .block(
+                Block::bordered()
+                    .title(format!("Core {} frequency over time", index))
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" c/C: next/previous core ").right_aligned()),
+            )
+            .x_axis(time_axis)
+            .y_axis(frequency_axis)
+            .legend_position(Some(LegendPosition::TopRight))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+        render_chart(frame, frequency_chart, area, self.chart_style);
+    }
+
+    /// Renders the sockets, cores, caches and notable flags of the processor.
+    fn render_topology(&self, frame: &mut Frame, area: Rect) {
+        let opts = size_format(self.units).decimal_places(0);
+        let topology = &self.topology;
+
+        let mut lines = vec![
+            Line::from(format!("Vendor: {}", topology.vendor)),
+            Line::from(format!("Sockets: {}", topology.sockets)),
+            Line::from(format!(
+                "Cores: {} physical, {} logical",
+                topology.physical_cores, topology.logical_cores
+            )),
+        ];
+        // the caches of a level are listed in the same line, like L1d and L1i
+        for level in topology
+            .caches
+            .iter()
+            .map(|cache| cache.level)
+            .collect::<BTreeSet<u8>>()
+        {
+            let caches = topology
+                .caches
+                .iter()
+                .filter(|cache| cache.level == level)
+                .map(|cache| {
+                    let suffix = match cache.kind.as_str() {
+                        "Data" => "d",
+                        "Instruction" => "i",
+                        _ => "",
+                    };
+                    format!("L{}{}: {}", level, suffix, cache.size.format_size(opts))
+                })
+                .collect::<Vec<String>>();
+            lines.push(Line::from(caches.join("  ")));
+        }
+        if !topology.flags.is_empty() {
+            lines.push(Line::from(format!("Flags: {}", topology.flags.join(" "))));
+        }
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::bordered()
+                .title("Topology")
+                .title_bottom(Line::from(" t: hide ").right_aligned()),
+        );
+        frame.render_widget(paragraph, area);
     }
 
+    /// Renders the usage, frequency and temperature bars of every core, wrapping the cores into
+    /// as many rows as needed. The cores not fitting into the area are counted below the bars.
     fn render_core_details(&self, frame: &mut Frame, area: Rect) {
-        let layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Fill(1); 4])
-            .split(area);
+        let cores = &self.cpu_update.cores;
+        if cores.is_empty() {
+            return;
+        }
 
-        for (i, core) in self.cpu_update.cores.iter().enumerate() {
-            let usage = core.usage;
-            let usage_bar_color = match usage {
-                usage if usage < 50 => Color::Green,
-                usage if usage < 80 => Color::Yellow,
-                _ => Color::Red,
-            };
+        let columns = ((area.width / MIN_CORE_WIDTH) as usize).clamp(1, cores.len());
+        let rows = cores.len().div_ceil(columns);
+        let fitting_rows = ((area.height / MIN_CORE_HEIGHT) as usize).max(1);
+        let (area, visible_rows) = if rows > fitting_rows {
+            // a line is taken from the bars for the count of the hidden cores
+            let [bars_area, hint_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+            let visible_rows = ((bars_area.height / MIN_CORE_HEIGHT) as usize).max(1);
+            let hidden = cores.len().saturating_sub(visible_rows * columns);
+            let hint = Line::from(format!(" {} more cores, h: heatmap ", hidden)).centered();
+            frame.render_widget(hint, hint_area);
+            (bars_area, visible_rows)
+        } else {
+            (area, rows)
+        };
 
-            // normalize frequency to a common 0.=100 scale to avoid very different bar heights
-            let frequency =
-                ((core.frequency as f64 / self.max_frequency as f64) * 100.0).round() as u64;
-            let freq_bar_color = match frequency {
-                temp if temp < 50 => Color::Green,
-                temp if temp < 80 => Color::Yellow,
-                _ => Color::Red,
-            };
+        let row_areas = Layout::vertical(vec![Constraint::Fill(1); visible_rows]).split(area);
+        let core_areas = row_areas.iter().flat_map(|row_area| {
+            Layout::horizontal(vec![Constraint::Fill(1); columns])
+                .split(*row_area)
+                .to_vec()
+        });
+        for (index, (core_area, core)) in core_areas.zip(cores).enumerate() {
+            self.render_core(frame, core_area, index, core);
+        }
+    }
 
-            let temp = core.temperature;
-            let temp_bar_color = match temp {
-                temp if temp < 50 => Color::Green,
-                temp if temp < 80 => Color::Yellow,
-                _ => Color::Red,
+    /// Renders the usage, frequency and temperature bars of the core at `index`.
+    fn render_core(&self, frame: &mut Frame, area: Rect, index: usize, core: &CpuCore) {
+        let usage = core.usage;
+        let usage_bar_color = match usage {
+            usage if usage < 50 => Color::Green,
+            usage if usage < 80 => Color::Yellow,
+            _ => Color::Red,
+        };
+
+        // normalize frequency to a common 0.=100 scale to avoid very different bar heights
+        let frequency = (self.frequency_ratio(core) * 100.0).round() as u64;
+        let freq_bar_color = match frequency {
+            temp if temp < 50 => Color::Green,
+            temp if temp < 80 => Color::Yellow,
+            _ => Color::Red,
+        };
+
+        let temp = core.temperature;
+        let temp_bar_color = match temp {
+            temp if temp < 50 => Color::Green,
+            temp if temp < 80 => Color::Yellow,
+            _ => Color::Red,
+        };
+        let bar_group = BarGroup::default()
+            .label(format!("Core {}", index).into())
+            .bars(&[
+                Bar::default()
+                    .label("%".into())
+                    .style(Style::default().fg(usage_bar_color))
+                    .value(core.usage),
+                Bar::default()
+                    .label("f".into())
+                    .style(Style::default().fg(freq_bar_color))
+                    .value(frequency),
+                Bar::default()
+                    .label("t".into())
+                    // FIXME: two character long label throws the label offset from the bar
+                    //.label("°C".into())
+                    .style(Style::default().fg(temp_bar_color))
+                    .value(core.temperature as u64),
+            ]);
+
+        let bar_chart = BarChart::default()
+            .bar_width(1)
+            .bar_gap(1)
+            .group_gap(1)
+            .data(bar_group)
+            .max(100);
+
+        frame.render_widget(bar_chart, area);
+    }
+
+    /// Renders one cell per core colored by its usage, wrapping the cores into as many rows as needed.
+    /// The cells are narrowed down until every core fits into the area.
+    fn render_core_heatmap(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title("Core usage")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" h: bars ").right_aligned());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let cores = &self.cpu_update.cores;
+        if cores.is_empty() || inner.width == 0 {
+            return;
+        }
+        let fits = |cell_width: u16| {
+            let columns = (inner.width / cell_width).max(1) as usize;
+            cores.len().div_ceil(columns) <= inner.height as usize
+        };
+        let cell_width = HEATMAP_CELL_WIDTHS
+            .into_iter()
+            .find(|width| fits(*width))
+            .unwrap_or(HEATMAP_CELL_WIDTHS[HEATMAP_CELL_WIDTHS.len() - 1]);
+        let columns = (inner.width / cell_width).max(1) as usize;
+        // the gap after the cell is left uncolored
+        let label_width = cell_width as usize - 1;
+
+        let lines = cores
+            .chunks(columns)
+            .map(|row| {
+                let cells = row.iter().flat_map(|core| {
+                    let label = format!("{}%", core.usage);
+                    let label = if label.len() <= label_width {
+                        label
+                    } else {
+                        String::new()
+                    };
+                    // the colors are only defined below 100%
+                    let color = get_color_for((core.usage as f64).min(99.0))
+                        .fg
+                        .unwrap_or(Color::Reset);
+                    [
+                        Span::styled(
+                            format!("{:^width$}", label, width = label_width),
+                            Style::default().bg(color).fg(Color::Black),
+                        ),
+                        Span::from(" "),
+                    ]
+                });
+                Line::from(cells.collect::<Vec<Span>>())
+            })
+            .collect::<Vec<Line>>();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// Renders the current frequency of every core next to its maximum, wrapping the cores into as many columns as fit.
+    /// A busy core running well below its maximum is throttled, like by its temperature or the power limits,
+    /// its bar is yellow or red.
+    fn render_core_frequencies(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title("Core frequencies")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" f: bars ").right_aligned());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let cores = &self.cpu_update.cores;
+        if cores.is_empty() || inner.height == 0 {
+            return;
+        }
+        // the cores are listed top to bottom, then left to right
+        let fitting_columns = ((inner.width / FREQUENCY_CELL_WIDTH) as usize).max(1);
+        let mut rows = (inner.height as usize).min(cores.len());
+        let mut hint = None;
+        if cores.len() > rows * fitting_columns {
+            // a line is taken from the list for the count of the hidden cores
+            rows = rows.saturating_sub(1).max(1);
+            let hidden = cores.len().saturating_sub(rows * fitting_columns);
+            hint = Some(Line::from(format!(" {} more cores, h: heatmap ", hidden)).centered());
+        }
+
+        let mut lines = vec![Vec::new(); rows];
+        for (index, core) in cores.iter().enumerate().take(rows * fitting_columns) {
+            let ratio = self.frequency_ratio(core);
+            let filled = (ratio * FREQUENCY_BAR_WIDTH as f64).round() as usize;
+            let color = match core.usage >= BUSY_CORE_USAGE {
+                true if ratio < 0.5 => Color::LightRed,
+                true if ratio < 0.8 => Color::Yellow,
+                _ => Color::Green,
             };
-            let bar_group = BarGroup::default()
-                .label(format!("Core {}", i).into())
-                .bars(&[
-                    Bar::default()
-                        .label("%".into())
-                        .style(Style::default().fg(usage_bar_color))
-                        .value(core.usage),
-                    Bar::default()
-                        .label("f".into())
-                        .style(Style::default().fg(freq_bar_color))
-                        .value(frequency),
-                    Bar::default()
-                        .label("t".into())
-                        // FIXME: two character long label throws the label offset from the bar
-                        //.label("°C".into())
-                        .style(Style::default().fg(temp_bar_color))
-                        .value(core.temperature as u64),
-                ]);
-
-            let bar_chart = BarChart::default()
-                .bar_width(1)
-                .bar_gap(1)
-                .group_gap(1)
-                .data(bar_group)
-                .max(100);
-
-            frame.render_widget(bar_chart, layout[i]);
+            let maximum = core
+                .max_frequency
+                .map_or(String::new(), |max| format!("/{}", max));
+
+            lines[index % rows].extend([
+                Span::raw(format!(
+                    "{:<8}{:>11} MHz ",
+                    format!("Core {}", index),
+                    format!("{}{}", core.frequency, maximum)
+                )),
+                Span::styled("█".repeat(filled), Style::default().fg(color)),
+                Span::raw(format!(
+                    "{}{}",
+                    "·".repeat(FREQUENCY_BAR_WIDTH - filled),
+                    " ".repeat(FREQUENCY_CELL_WIDTH as usize - FREQUENCY_BAR_WIDTH - 24)
+                )),
+            ]);
         }
+        let lines = lines
+            .into_iter()
+            .map(Line::from)
+            .chain(hint)
+            .collect::<Vec<Line>>();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// Returns the frequency of `core` relative to its maximum, or to the highest frequency seen
+    /// where the maximum of the core is not known.
+    fn frequency_ratio(&self, core: &CpuCore) -> f64 {
+        let max = core
+            .max_frequency
+            .unwrap_or(self.max_frequency as u64)
+            .max(1);
+        (core.frequency as f64 / max as f64).min(1.0)
+    }
+
+    /// Renders the swap in and swap out rates over time as sparklines next to the swap figures of the memory panel,
+    /// telling apart a thrashing machine from one just holding stale pages in the swap.
+    fn render_swap_activity(&self, frame: &mut Frame, area: Rect) {
+        let inner = area.inner(Margin::new(1, 1));
+        // the swap figures are under the three lines of the memory figures
+        let [_, swap_lines] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).areas(inner);
+        let [_, activity] =
+            Layout::horizontal([Constraint::Length(18), Constraint::Fill(1)]).areas(swap_lines);
+        let [in_area, out_area, rates_area] =
+            Layout::vertical([Constraint::Length(1); 3]).areas(activity);
+
+        let samples = |metric: &Metric| {
+            self.history
+                .window(metric, activity.width as usize)
+                .iter()
+                .map(|sample| sample.value as u64)
+                .collect::<Vec<u64>>()
+        };
+        let swap_in = samples(&Metric::SwapIn);
+        let swap_out = samples(&Metric::SwapOut);
+        // both directions are drawn on the same scale
+        let max = swap_in
+            .iter()
+            .chain(swap_out.iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let opts = size_format(self.units)
+            .decimal_places(0)
+            .space_after_value(false);
+        let rates = Line::from(vec![
+            Span::raw("si ").light_green(),
+            Span::raw(format!(
+                "{}/s ",
+                (self.cpu_update.memory_stats.swap_in_rate as u64).format_size(opts)
+            )),
+            Span::raw("so ").light_red(),
+            Span::raw(format!(
+                "{}/s",
+                (self.cpu_update.memory_stats.swap_out_rate as u64).format_size(opts)
+            )),
+        ]);
+
+        frame.render_widget(
+            Sparkline::default().data(&swap_in).max(max).light_green(),
+            in_area,
+        );
+        frame.render_widget(
+            Sparkline::default().data(&swap_out).max(max).light_red(),
+            out_area,
+        );
+        frame.render_widget(Paragraph::new(rates), rates_area);
     }
 
     fn render_memory_details(&self, frame: &mut Frame, area: Rect) {
@@ -259,13 +784,7 @@ impl CpuMemoryDetails {
             .constraints([Constraint::Percentage(25), Constraint::Fill(1)])
             .split(area);
 
-        let opts = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .decimal_places(1)
-            .decimal_zeroes(0)
-            .kilo(humansize::Kilo::Decimal)
-            .long_units(false)
-            .space_after_value(false);
+        let opts = size_format(self.units).space_after_value(false);
 
         // --- Memory + Swap ---
         let mem_block = Block::default()
@@ -288,18 +807,16 @@ impl CpuMemoryDetails {
             .block(Block::bordered().reset());
 
         // --- Memory Usage Over Time ---
-        let mem_dataset = Dataset::default()
+        let memory_usage = self.window.points(&self.history, &Metric::MemoryUsage);
+        let swap_usage = self.window.points(&self.history, &Metric::SwapUsage);
+        let mem_dataset = chart_dataset(self.chart_style)
             .name("Memory")
-            .marker(Marker::Dot)
             .style(Style::default().magenta())
-            .graph_type(GraphType::Scatter)
-            .data(&self.memory_usage);
-        let swap_dataset = Dataset::default()
+            .data(&memory_usage);
+        let swap_dataset = chart_dataset(self.chart_style)
             .name("Swap")
-            .marker(Marker::Dot)
             .style(Style::default().yellow())
-            .graph_type(GraphType::Scatter)
-            .data(&self.swap_usage);
+            .data(&swap_usage);
         let percent_axis = Axis::default()
             .labels(vec![
                 "0".green().bold(),
@@ -309,13 +826,8 @@ impl CpuMemoryDetails {
             .bounds([0.0, 100.0]);
         let time_axis = Axis::default()
             .gray()
-            .labels(vec![
-                "1m".gray().bold(),
-                "5m".gray().bold(),
-                "15m".gray().bold(),
-            ])
-            // updates coming at every 3 seconds, keep only last 15 minutes
-            .bounds([0.0, (15.0 * 60.0) / 3.0]);
+            .labels(self.window.labels())
+            .bounds(self.window.bounds());
 
         let mem_chart = Chart::new(vec![mem_dataset, swap_dataset])
             .block(
@@ -329,7 +841,20 @@ impl CpuMemoryDetails {
             .legend_position(Some(LegendPosition::TopRight))
             .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
 
+        // the breakdown of the memory is displayed over the chart
+        let [bar_area, chart_area] =
+            Layout::vertical([Constraint::Length(5), Constraint::Fill(1)]).areas(layout[1]);
+        let breakdown = Paragraph::new(memory_bar(
+            &self.cpu_update.memory_stats,
+            bar_area.width.saturating_sub(2),
+            opts.space_after_value(true),
+        ))
+        .wrap(Wrap { trim: true })
+        .block(Block::bordered().title("Memory breakdown"));
+
         frame.render_widget(mem_para, layout[0]);
-        frame.render_widget(mem_chart, layout[1]);
+        self.render_swap_activity(frame, layout[0]);
+        frame.render_widget(breakdown, bar_area);
+        render_chart(frame, mem_chart, chart_area, self.chart_style);
     }
 }
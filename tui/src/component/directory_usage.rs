@@ -0,0 +1,323 @@
+use super::size_format;
+use crate::{Message, UserEvent};
+use core::config::Units;
+use core::{disk_usage_scan, DiskUsage, DiskUsageScan, UsageEntry};
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Alignment, Constraint},
+    style::{Style, Stylize},
+    widgets::{Block, Cell, Clear, Paragraph, Row, Table, TableState},
+};
+use std::path::PathBuf;
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// The levels of directories listed by a scan, the deeper ones are scanned again when they are opened.
+const SCAN_DEPTH: usize = 3;
+
+/// Width of the bar showing the share of an entry in its directory.
+const SHARE_BAR_WIDTH: usize = 20;
+
+/// Pane displaying the largest directories and files under a mount point, like `du`.
+/// The directories are scanned in the background, the progress is displayed until the scan finishes.
+///
+/// Controls:
+/// * Up/Down => moves the selection
+/// * PageUp/PageDown => moves the selection by a screen of rows
+/// * Enter/Right => opens the selected directory
+/// * Backspace/Left => goes back to the parent directory, up to the mount point
+/// * r => scans the displayed directory again
+/// * Esc/u => closes the pane, cancelling the scan
+pub struct DirectoryUsage {
+    properties: Props,
+
+    /// The mount point which the browsing started from, the parent directories are not scanned
+    mount: PathBuf,
+
+    /// The scan in progress, `None` after it finished
+    scan: Option<DiskUsageScan>,
+
+    /// The result of the last finished scan
+    usage: Option<DiskUsage>,
+
+    /// The failure of the last scan
+    error: Option<String>,
+
+    /// Indexes of the opened directories from the root of the scan to the displayed one
+    opened: Vec<usize>,
+
+    /// Holds the selected row
+    table_state: TableState,
+
+    /// Count of the rows fitting into the table at the last rendering
+    page_size: usize,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+}
+
+impl DirectoryUsage {
+    /// Starts scanning the directories under `mount`.
+    pub fn new(mount: PathBuf) -> Self {
+        DirectoryUsage {
+            properties: Props::default(),
+            scan: Some(disk_usage_scan(mount.clone(), SCAN_DEPTH)),
+            mount,
+            usage: None,
+            error: None,
+            opened: Vec::new(),
+            table_state: TableState::default(),
+            page_size: 0,
+            units: Units::default(),
+        }
+    }
+
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+}
+
+impl MockComponent for DirectoryUsage {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let size_opts = size_format(self.units);
+        frame.render_widget(Clear, area);
+
+        if let Some(scan) = &self.scan {
+            let progress = Paragraph::new(format!(
+                "Scanning {}: {} files and directories so far",
+                scan.path().display(),
+                scan.scanned()
+            ))
+            .block(Block::bordered().title("Disk usage"));
+            frame.render_widget(progress, area);
+            return;
+        }
+        let Some(directory) = self.displayed() else {
+            let message = Paragraph::new(self.error.clone().unwrap_or_default())
+                .block(Block::bordered().title("Disk usage"));
+            frame.render_widget(message, area);
+            return;
+        };
+
+        let rows = directory.children.iter().map(|entry| {
+            let share = match directory.size {
+                0 => 0.0,
+                total => entry.size as f64 / total as f64,
+            };
+            let filled = (share * SHARE_BAR_WIDTH as f64).round() as usize;
+            let name = match entry.is_directory {
+                true => format!("{}/", entry.name()),
+                false => entry.name(),
+            };
+            Row::new(vec![
+                Cell::from(entry.size.format_size(size_opts)),
+                Cell::from(format!(
+                    "{}{} {:>5.1}%",
+                    "█".repeat(filled),
+                    "·".repeat(SHARE_BAR_WIDTH - filled),
+                    share * 100.0
+                )),
+                Cell::from(name),
+            ])
+        });
+
+        let mut block = Block::bordered()
+            .title(format!(
+                "Disk usage of {} ({})",
+                directory.path.display(),
+                directory.size.format_size(size_opts)
+            ))
+            .title_alignment(Alignment::Center);
+        let unreadable = self.usage.as_ref().map_or(0, |usage| usage.unreadable);
+        if unreadable > 0 {
+            block = block
+                .title_bottom(format!(" {} directories could not be read ", unreadable).yellow());
+        }
+
+        let table = Table::default()
+            .block(block)
+            .widths([
+                Constraint::Length(10),
+                Constraint::Length(SHARE_BAR_WIDTH as u16 + 7),
+                Constraint::Fill(1),
+            ])
+            .header(Row::new(vec!["size", "share", "name"]).bold())
+            .row_highlight_style(Style::default().reversed())
+            .rows(rows);
+
+        // the borders and the header take three lines
+        self.page_size = area.height.saturating_sub(3) as usize;
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+}
+
+impl Component<Message, UserEvent> for DirectoryUsage {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::Tick => self.check_scan(),
+            Event::Keyboard(KeyEvent { code, .. }) => match code {
+                Key::Esc | Key::Char('u') => Some(Message::CloseDiskUsage),
+                Key::Up => {
+                    self.select_previous(1);
+                    Some(Message::Redraw)
+                }
+                Key::Down => {
+                    self.select_next(1);
+                    Some(Message::Redraw)
+                }
+                Key::PageUp => {
+                    self.select_previous(self.page_size.max(1));
+                    Some(Message::Redraw)
+                }
+                Key::PageDown => {
+                    self.select_next(self.page_size.max(1));
+                    Some(Message::Redraw)
+                }
+                Key::Enter | Key::Right => {
+                    self.open_selected();
+                    Some(Message::Redraw)
+                }
+                Key::Backspace | Key::Left => {
+                    self.open_parent();
+                    Some(Message::Redraw)
+                }
+                Key::Char('r') => {
+                    if let Some(directory) = self.displayed() {
+                        let path = directory.path.clone();
+                        self.start_scan(path);
+                    }
+                    Some(Message::Redraw)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl DirectoryUsage {
+    /// Takes the result of the scan if it has finished. The progress is redrawn while it is running.
+    fn check_scan(&mut self) -> Option<Message> {
+        let result = self.scan.as_ref()?.try_result();
+        match result {
+            Some(Ok(usage)) => {
+                self.usage = Some(usage);
+                self.opened.clear();
+                self.scan = None;
+                self.select_first();
+            }
+            Some(Err(error)) => {
+                self.error = Some(format!("Failed to scan the disk usage: {}", error));
+                self.scan = None;
+            }
+            None => {}
+        }
+        Some(Message::Redraw)
+    }
+
+    /// Starts scanning `path`, replacing the displayed result once it finishes.
+    fn start_scan(&mut self, path: PathBuf) {
+        self.scan = Some(disk_usage_scan(path, SCAN_DEPTH));
+        self.error = None;
+    }
+
+    /// Returns the displayed directory.
+    fn displayed(&self) -> Option<&UsageEntry> {
+        let mut directory = &self.usage.as_ref()?.root;
+        for index in self.opened.iter() {
+            directory = directory.children.get(*index)?;
+        }
+        Some(directory)
+    }
+
+    /// Opens the selected directory, scanning it if it is deeper than the previous scan.
+    fn open_selected(&mut self) {
+        let Some(index) = self.table_state.selected() else {
+            return;
+        };
+        let Some(entry) = self
+            .displayed()
+            .and_then(|directory| directory.children.get(index))
+        else {
+            return;
+        };
+        match (entry.is_directory, entry.listed) {
+            (true, true) => {
+                self.opened.push(index);
+                self.select_first();
+            }
+            (true, false) => {
+                let path = entry.path.clone();
+                self.start_scan(path);
+            }
+            _ => {}
+        }
+    }
+
+    /// Goes back to the parent directory, scanning it if the displayed directory is the root of the scan.
+    /// The directories above the mount point are not opened.
+    fn open_parent(&mut self) {
+        if let Some(index) = self.opened.pop() {
+            self.table_state.select(Some(index));
+            return;
+        }
+        let Some(usage) = &self.usage else {
+            return;
+        };
+        if usage.root.path != self.mount {
+            if let Some(parent) = usage.root.path.parent() {
+                let parent = parent.to_path_buf();
+                self.start_scan(parent);
+            }
+        }
+    }
+
+    fn select_first(&mut self) {
+        let is_empty = self
+            .displayed()
+            .is_none_or(|directory| directory.children.is_empty());
+        self.table_state
+            .select(if is_empty { None } else { Some(0) });
+    }
+
+    fn select_next(&mut self, step: usize) {
+        let count = self
+            .displayed()
+            .map_or(0, |directory| directory.children.len());
+        let next = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(step).min(count.saturating_sub(1)));
+        self.table_state.select(Some(next));
+    }
+
+    fn select_previous(&mut self, step: usize) {
+        let previous = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(step));
+        self.table_state.select(Some(previous));
+    }
+}
@@ -0,0 +1,447 @@
+use super::{chart_dataset, get_color_for, render_chart, size_format, ChartWindow};
+use crate::{Message, UserEvent};
+use core::config::{ChartStyle, Units};
+use core::history::{History, Metric};
+use core::model::{DiskInfo, DriveHealth, Storage, DRIVE_TEMPERATURE_WARNING};
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Axis, Block, Cell, Chart, Dataset, Gauge, LegendPosition, Paragraph, Row, Table},
+};
+use std::path::PathBuf;
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// Height of a single storage device row, including its borders.
+const DEVICE_ROW_HEIGHT: u16 = 5;
+
+/// The smallest height of the IOPS and latency charts, they are hidden in a smaller area.
+const MIN_CHART_HEIGHT: u16 = 8;
+
+/// Colors of the devices in the charts, repeated if there are more devices.
+const DEVICE_COLORS: [Color; 6] = [
+    Color::LightGreen,
+    Color::LightBlue,
+    Color::Yellow,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::LightRed,
+];
+
+/// Component for displaying the details of every storage device on the host machine,
+/// followed by the SMART health of the physical drives if it is collected.
+/// The attributes of the degrading drives are highlighted.
+/// The IOPS and the latency of the devices over time are charted in the remaining space.
+///
+/// Controls:
+/// * Up/Down => moves the selection of the devices
+/// * u => shows the largest directories and files under the mount point of the selected device
+/// * e/E => exports the IOPS and latency history of every device into a CSV/JSON file
+/// * w/z/Z/Left/Right => changes, zooms and pans the period of time displayed by the charts, see `ChartWindow`
+#[derive(Default)]
+pub struct DiskDetails {
+    properties: Props,
+
+    disk_info: DiskInfo,
+
+    /// IOPS and latency history of the devices
+    history: History,
+
+    /// The period of time displayed by the IOPS and latency charts
+    window: ChartWindow,
+
+    /// Index of the selected device
+    selected: usize,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+
+    /// How the series of the charts are drawn
+    chart_style: ChartStyle,
+}
+
+impl DiskDetails {
+    /// Sets the history which the IOPS and latency charts are drawn from.
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets how the series of the charts are drawn.
+    pub fn with_chart_style(mut self, chart_style: ChartStyle) -> Self {
+        self.chart_style = chart_style;
+        self
+    }
+
+    fn process_update(&mut self, update: DiskInfo) {
+        // forget the devices which are no longer mounted
+        self.history.retain(|metric| match metric {
+            Metric::DiskOperations(mount) | Metric::DiskLatency(mount) => {
+                update.disks.iter().any(|storage| &storage.mount == mount)
+            }
+            _ => true,
+        });
+
+        for storage in update.disks.iter() {
+            self.history
+                .record(Metric::DiskOperations(storage.mount.clone()), storage.iops);
+            self.history
+                .record(Metric::DiskLatency(storage.mount.clone()), storage.latency);
+        }
+
+        // keep the selection on an existing device
+        self.selected = self.selected.min(update.disks.len().saturating_sub(1));
+        self.disk_info = update;
+    }
+
+    /// Renders the IOPS and the latency of every device over time side by side.
+    fn render_charts(&self, frame: &mut Frame, area: Rect) {
+        let [operations_area, latency_area] =
+            Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)]).areas(area);
+
+        let operations = self.device_points(Metric::DiskOperations);
+        let latency = self.device_points(Metric::DiskLatency);
+        render_chart(
+            frame,
+            self.chart("IOPS over time", &operations, |value| {
+                format!("{:.0}", value)
+            }),
+            operations_area,
+            self.chart_style,
+        );
+        render_chart(
+            frame,
+            self.chart("Latency over time", &latency, |value| {
+                format!("{:.1} ms", value)
+            }),
+            latency_area,
+            self.chart_style,
+        );
+    }
+
+    /// Returns the chart points of the `metric` of every device with the mount point.
+    fn device_points(&self, metric: impl Fn(String) -> Metric) -> Vec<(&str, Vec<(f64, f64)>)> {
+        self.disk_info
+            .disks
+            .iter()
+            .map(|storage| {
+                let points = self
+                    .window
+                    .points(&self.history, &metric(storage.mount.clone()));
+                (storage.mount.as_str(), points)
+            })
+            .collect()
+    }
+
+    /// Creates a chart of the `devices` over time, labelling the values of the vertical axis with `label`.
+    fn chart<'a>(
+        &self,
+        title: &'a str,
+        devices: &'a [(&'a str, Vec<(f64, f64)>)],
+        label: impl Fn(f64) -> String,
+    ) -> Chart<'a> {
+        let datasets = devices
+            .iter()
+            .zip(DEVICE_COLORS.iter().cycle())
+            .map(|((mount, points), color)| {
+                chart_dataset(self.chart_style)
+                    .name(*mount)
+                    .style(Style::default().fg(*color))
+                    .data(points)
+            })
+            .collect::<Vec<Dataset>>();
+
+        let peak = devices
+            .iter()
+            .flat_map(|(_, points)| points.iter().map(|(_, value)| *value))
+            .fold(0.0, f64::max)
+            .max(1.0);
+        let value_axis = Axis::default()
+            .labels(vec![
+                "0".gray().bold(),
+                label(peak / 2.0).gray().bold(),
+                label(peak).gray().bold(),
+            ])
+            .bounds([0.0, peak]);
+        let time_axis = Axis::default()
+            .labels(self.window.labels())
+            .bounds(self.window.bounds());
+
+        Chart::new(datasets)
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .title_alignment(Alignment::Center),
+            )
+            .x_axis(time_axis)
+            .y_axis(value_axis)
+            .legend_position(Some(LegendPosition::TopRight))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+    }
+}
+
+impl MockComponent for DiskDetails {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title("Disks")
+            .title_alignment(Alignment::Center);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                self.disk_info
+                    .disks
+                    .iter()
+                    .map(|_| Constraint::Length(DEVICE_ROW_HEIGHT))
+                    .chain([
+                        // the borders and the header of the drive table take three lines
+                        Constraint::Length(match self.disk_info.drives.len() {
+                            0 => 0,
+                            drives => drives as u16 + 3,
+                        }),
+                        Constraint::Fill(1),
+                    ]),
+            )
+            .split(inner_area);
+
+        for (i, (storage, row)) in self.disk_info.disks.iter().zip(rows.iter()).enumerate() {
+            render_storage(storage, self.units, i == self.selected, frame, *row);
+        }
+        if !self.disk_info.drives.is_empty() {
+            render_drives(
+                &self.disk_info.drives,
+                frame,
+                rows[self.disk_info.disks.len()],
+            );
+        }
+        let charts_area = rows[self.disk_info.disks.len() + 1];
+        if !self.disk_info.disks.is_empty() && charts_area.height >= MIN_CHART_HEIGHT {
+            self.render_charts(frame, charts_area);
+        }
+    }
+}
+
+impl Component<Message, UserEvent> for DiskDetails {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Disk(disk_info)) => {
+                self.process_update(disk_info);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.selected = self.selected.saturating_sub(1);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.selected =
+                    (self.selected + 1).min(self.disk_info.disks.len().saturating_sub(1));
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('u'),
+                ..
+            }) => self
+                .disk_info
+                .disks
+                .get(self.selected)
+                .map(|storage| Message::ShowDiskUsage(PathBuf::from(&storage.mount))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('e'),
+                modifiers,
+            }) => {
+                let metrics = self
+                    .disk_info
+                    .disks
+                    .iter()
+                    .flat_map(|storage| {
+                        [
+                            Metric::DiskOperations(storage.mount.clone()),
+                            Metric::DiskLatency(storage.mount.clone()),
+                        ]
+                    })
+                    .collect::<Vec<Metric>>();
+                Some(
+                    self.window
+                        .export("disk", &self.history, &metrics, modifiers),
+                )
+            }
+            Event::Keyboard(key) if self.window.on_key(&key) => Some(Message::Redraw),
+            _ => None,
+        }
+    }
+}
+
+/// Renders a single storage device in one row: its name, mount point and file system in the left side,
+/// a gauge of the used space in the middle with the used inodes in its title,
+/// and the read/write rates with the operations in the right side.
+/// The device name of the `selected` device is highlighted.
+fn render_storage(storage: &Storage, units: Units, selected: bool, frame: &mut Frame, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Fill(1),
+            Constraint::Percentage(25),
+        ])
+        .split(area);
+
+    let size_opts = size_format(units);
+
+    let device_text = format!(
+        "{} ({})\n{}",
+        storage.mount, storage.file_system, storage.name
+    );
+    let device_block = match selected {
+        true => Block::bordered().yellow(),
+        false => Block::bordered(),
+    };
+    let device = Paragraph::new(device_text).block(device_block);
+
+    let percent = if storage.total_space == 0 {
+        0.0
+    } else {
+        storage.used_space as f64 / storage.total_space as f64 * 100.0
+    };
+    let mut usage_block = Block::bordered();
+    if let Some(inodes_percent) = storage.inodes_percent() {
+        usage_block = usage_block.title(
+            Line::styled(
+                format!(" inodes {:.1}% ", inodes_percent),
+                get_color_for(inodes_percent),
+            )
+            .right_aligned(),
+        );
+    }
+    let usage_gauge = Gauge::default()
+        .block(usage_block)
+        .percent(percent as u16)
+        .label(format!(
+            "{} / {} ({:.1}%)",
+            storage.used_space.format_size(size_opts),
+            storage.total_space.format_size(size_opts),
+            percent
+        ))
+        .gauge_style(get_color_for(percent));
+
+    let io_text = format!(
+        "Read: {}/s\nWrite: {}/s\nIOPS: {:.0}  {:.1} ms  qd {:.1}",
+        (storage.read_rate as u64).format_size(size_opts),
+        (storage.write_rate as u64).format_size(size_opts),
+        storage.iops,
+        storage.latency,
+        storage.queue_depth
+    );
+    let io_stat = Paragraph::new(io_text).block(Block::bordered());
+
+    frame.render_widget(device, layout[0]);
+    frame.render_widget(usage_gauge, layout[1]);
+    frame.render_widget(io_stat, layout[2]);
+}
+
+/// Renders the SMART health of the physical drives in a table, one drive per row.
+/// The failed self-assessment, the reallocated sectors and the overheating are colored red.
+fn render_drives(drives: &[DriveHealth], frame: &mut Frame, area: Rect) {
+    let warning = |degraded: bool| {
+        if degraded {
+            Style::default().light_red()
+        } else {
+            Style::default()
+        }
+    };
+    let unknown = || "-".to_string();
+
+    let rows = drives.iter().map(|drive| {
+        let health = match drive.passed {
+            Some(true) => "PASSED",
+            Some(false) => "FAILED",
+            None => "-",
+        };
+        Row::new(vec![
+            Cell::from(drive.device.as_str()),
+            Cell::from(drive.model.as_deref().unwrap_or("-")),
+            Cell::from(health).style(warning(drive.passed == Some(false))),
+            Cell::from(
+                drive
+                    .temperature
+                    .map_or_else(unknown, |temperature| format!("{:.0} °C", temperature)),
+            )
+            .style(warning(
+                drive
+                    .temperature
+                    .is_some_and(|temperature| temperature >= DRIVE_TEMPERATURE_WARNING),
+            )),
+            Cell::from(
+                drive
+                    .power_on_hours
+                    .map_or_else(unknown, |hours| format!("{} h", hours)),
+            ),
+            Cell::from(
+                drive
+                    .reallocated_sectors
+                    .map_or_else(unknown, |sectors| sectors.to_string()),
+            )
+            .style(warning(
+                drive.reallocated_sectors.is_some_and(|sectors| sectors > 0),
+            )),
+        ])
+    });
+
+    let degraded = drives.iter().filter(|drive| drive.is_degraded()).count();
+    let title = match degraded {
+        0 => Line::from("Drive health"),
+        _ => Line::from(format!("Drive health ({} degraded)", degraded)).light_red(),
+    };
+    let table = Table::default()
+        .block(Block::bordered().title(title))
+        .header(Row::new(vec![
+            "device",
+            "model",
+            "health",
+            "temperature",
+            "power on",
+            "reallocated sectors",
+        ]))
+        .widths([
+            Constraint::Length(14),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(20),
+        ])
+        .rows(rows);
+
+    frame.render_widget(table, area);
+}
@@ -0,0 +1,175 @@
+use super::size_format;
+use crate::{Message, UserEvent};
+use core::config::Units;
+use core::limits::{ResourceLimit, SystemLimits, Tunable};
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    style::Stylize,
+    widgets::{Block, Clear, Row, Table},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// The percentage of a limit in use above which the limit is highlighted.
+const USAGE_WARNING: f64 = 80.0;
+
+/// Pane displaying the kernel tunables and the resource limits bounding the capacity of the system
+/// over the overview, with the current use of the resources they bound where it is measured.
+/// The limits closing in on their use are highlighted.
+///
+/// The resource limits are the ones of ocelo, which the processes started from the same session share.
+///
+/// Controls:
+/// * Enter/l => closes the pane
+///
+/// The limits are only read on Linux.
+#[derive(Default)]
+pub struct Limits {
+    properties: Props,
+
+    limits: SystemLimits,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+}
+
+impl Limits {
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    fn tunable_row(tunable: &Tunable) -> Row<'_> {
+        let usage = tunable.usage_percent();
+        let row = Row::new(vec![
+            tunable.name.clone(),
+            tunable.value.clone().unwrap_or_else(|| "N/A".to_string()),
+            match (tunable.usage, usage) {
+                (Some(used), Some(usage)) => format!("{} ({:.0}%)", used, usage),
+                (Some(used), None) => used.to_string(),
+                (None, _) => String::new(),
+            },
+            tunable.description.clone(),
+        ]);
+        match usage.is_some_and(|usage| usage >= USAGE_WARNING) {
+            true => row.light_red(),
+            false => row,
+        }
+    }
+
+    fn resource_limit_row(&self, limit: &ResourceLimit) -> Row<'_> {
+        let opts = size_format(self.units);
+        let format = |value: Option<u64>| match (value, limit.unit.as_str()) {
+            (None, _) => "unlimited".to_string(),
+            (Some(value), "bytes") => value.format_size(opts),
+            (Some(value), _) => value.to_string(),
+        };
+        let usage = limit
+            .usage
+            .zip(limit.soft)
+            .filter(|(_, soft)| *soft > 0)
+            .map(|(used, soft)| used as f64 / soft as f64 * 100.0);
+        let row = Row::new(vec![
+            limit.name.clone(),
+            format(limit.soft),
+            format(limit.hard),
+            match (limit.usage, usage) {
+                (Some(used), Some(usage)) => format!("{} ({:.0}%)", used, usage),
+                (Some(used), None) => used.to_string(),
+                (None, _) => String::new(),
+            },
+        ]);
+        match usage.is_some_and(|usage| usage >= USAGE_WARNING) {
+            true => row.light_red(),
+            false => row,
+        }
+    }
+}
+
+impl MockComponent for Limits {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        // the borders and the header take three lines
+        let [tunables_area, resource_limits_area] = Layout::vertical([
+            Constraint::Length(self.limits.tunables.len() as u16 + 3),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let tunables = Table::default()
+            .block(
+                Block::bordered()
+                    .title("Kernel tunables")
+                    .title_alignment(Alignment::Center),
+            )
+            .widths([
+                Constraint::Length(30),
+                Constraint::Length(20),
+                Constraint::Length(16),
+                Constraint::Fill(1),
+            ])
+            .header(Row::new(vec!["tunable", "value", "in use", "description"]).bold())
+            .rows(self.limits.tunables.iter().map(Self::tunable_row));
+
+        let resource_limits = Table::default()
+            .block(
+                Block::bordered()
+                    .title("Resource limits of ocelo")
+                    .title_alignment(Alignment::Center),
+            )
+            .widths([
+                Constraint::Length(30),
+                Constraint::Length(20),
+                Constraint::Length(20),
+                Constraint::Fill(1),
+            ])
+            .header(Row::new(vec!["limit", "soft", "hard", "in use"]).bold())
+            .rows(
+                self.limits
+                    .resource_limits
+                    .iter()
+                    .map(|limit| self.resource_limit_row(limit)),
+            );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(tunables, tunables_area);
+        frame.render_widget(resource_limits, resource_limits_area);
+    }
+}
+
+impl Component<Message, UserEvent> for Limits {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Limits(limits)) => {
+                self.limits = limits;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter | Key::Char('l'),
+                ..
+            }) => Some(Message::CloseLimits),
+            _ => None,
+        }
+    }
+}
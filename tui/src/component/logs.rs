@@ -0,0 +1,237 @@
+use super::{format_date_time, MODAL};
+use crate::{Message, UserEvent};
+use core::model::{LogEntry, LogPriority, LogsInfo};
+use ratatui::{
+    layout::{Alignment, Constraint},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph, Row, Table},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// Component for tailing the system log, the journal of systemd, the syslog files or the kernel ring buffer,
+/// colored by the priority of the messages.
+///
+/// Controls:
+/// * Up/Down/PageUp/PageDown/Home => scrolls the messages, scrolling back pauses following the log
+/// * End => scrolls to the newest message and follows the log again
+/// * p => pauses or resumes following the log, the messages logged meanwhile are displayed after resuming
+/// * / => starts searching, the typed text filters the messages by their source and text.
+///   Enter finishes the typing, while the filter stays active. Searching for an empty text removes the filter.
+#[derive(Default)]
+pub struct Logs {
+    properties: Props,
+
+    /// The displayed messages
+    logs_info: LogsInfo,
+
+    /// The last update received while paused, displayed after resuming
+    pending: Option<LogsInfo>,
+
+    /// True while the displayed messages are not updated
+    paused: bool,
+
+    /// Count of the matching messages below the bottom of the table, zero when the newest one is displayed
+    scroll: usize,
+
+    /// Count of the rows fitting into the table at the last rendering
+    page_size: usize,
+
+    /// The text which the messages are filtered by
+    filter: String,
+
+    /// True while the search filter is being typed
+    searching: bool,
+}
+
+impl Logs {
+    /// Returns the messages matching the search filter, from the oldest one.
+    fn visible(&self) -> Vec<&LogEntry> {
+        self.logs_info
+            .entries
+            .iter()
+            .filter(|entry| self.filter.is_empty() || entry.matches(&self.filter))
+            .collect()
+    }
+
+    fn process_update(&mut self, update: LogsInfo) {
+        if self.paused {
+            self.pending = Some(update);
+        } else {
+            self.logs_info = update;
+        }
+    }
+
+    /// Scrolls back by `rows`, which pauses following the log.
+    fn scroll_up(&mut self, rows: usize) {
+        let last = self.visible().len().saturating_sub(self.page_size.max(1));
+        self.scroll = self.scroll.saturating_add(rows).min(last);
+        self.paused = true;
+    }
+
+    fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Displays the messages logged while paused and follows the log again.
+    fn resume(&mut self) {
+        self.paused = false;
+        self.scroll = 0;
+        if let Some(update) = self.pending.take() {
+            self.logs_info = update;
+        }
+    }
+
+    /// Signals that every key press is captured by this component.
+    fn set_modal(&mut self, modal: bool) {
+        self.properties
+            .set(Attribute::Custom(MODAL), AttrValue::Flag(modal));
+    }
+
+    /// Handles the key presses while the search filter is being typed.
+    fn on_search(&mut self, code: Key) -> Option<Message> {
+        match code {
+            Key::Char(c) => self.filter.push(c),
+            Key::Backspace => {
+                self.filter.pop();
+            }
+            Key::Enter => {
+                self.searching = false;
+                self.set_modal(false);
+            }
+            _ => return None,
+        }
+        self.scroll = 0;
+        Some(Message::Redraw)
+    }
+}
+
+/// Returns the style of the messages with `priority`, the more severe ones stand out more.
+fn priority_style(priority: LogPriority) -> Style {
+    match priority {
+        LogPriority::Emergency | LogPriority::Alert | LogPriority::Critical => {
+            Style::default().light_red().bold()
+        }
+        LogPriority::Error => Style::default().light_red(),
+        LogPriority::Warning => Style::default().yellow(),
+        LogPriority::Notice => Style::default().bold(),
+        LogPriority::Info => Style::default(),
+        LogPriority::Debug => Style::default().dark_gray(),
+    }
+}
+
+impl MockComponent for Logs {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        // the borders and the header are not part of a page
+        self.page_size = area.height.saturating_sub(3) as usize;
+
+        let visible = self.visible();
+        let mut title = match &self.logs_info.source {
+            Some(source) => format!("Logs of {} ({})", source, visible.len()),
+            None => "Logs".to_string(),
+        };
+        if self.paused {
+            title.push_str(" PAUSED");
+        }
+        let mut block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center);
+        if self.searching || !self.filter.is_empty() {
+            let cursor = if self.searching { "_" } else { "" };
+            block = block.title_bottom(format!(" /{}{} ", self.filter, cursor).yellow());
+        } else {
+            block = block.title_bottom(" p: pause  /: search  End: follow ");
+        }
+
+        if visible.is_empty() {
+            let message = match (&self.logs_info.source, self.filter.is_empty()) {
+                (None, _) => "No system log could be read",
+                (Some(_), true) => "No messages were logged",
+                (Some(_), false) => "No messages match the search",
+            };
+            frame.render_widget(
+                Paragraph::new(message)
+                    .alignment(Alignment::Center)
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        let end = visible.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(self.page_size);
+        let rows = visible[start..end].iter().map(|entry| {
+            Row::new(vec![
+                entry.timestamp.map_or(String::new(), format_date_time),
+                entry.priority.to_string(),
+                entry.source.clone(),
+                // the continuation lines of the messages are not displayed
+                entry.message.lines().next().unwrap_or_default().to_string(),
+            ])
+            .style(priority_style(entry.priority))
+        });
+
+        let table = Table::default()
+            .block(block)
+            .widths([
+                Constraint::Length(23),
+                Constraint::Length(8),
+                Constraint::Length(16),
+                Constraint::Fill(1),
+            ])
+            .header(Row::new(vec!["time", "priority", "source", "message"]).bold())
+            .rows(rows);
+        frame.render_widget(table, area);
+    }
+}
+
+impl Component<Message, UserEvent> for Logs {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Logs(logs_info)) => {
+                self.process_update(logs_info);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.searching => self.on_search(code),
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                match code {
+                    Key::Up => self.scroll_up(1),
+                    Key::Down => self.scroll_down(1),
+                    Key::PageUp => self.scroll_up(self.page_size),
+                    Key::PageDown => self.scroll_down(self.page_size),
+                    Key::Home => self.scroll_up(usize::MAX),
+                    Key::End => self.resume(),
+                    Key::Char('p') if self.paused => self.resume(),
+                    Key::Char('p') => self.paused = true,
+                    Key::Char('/') => {
+                        self.searching = true;
+                        self.set_modal(true);
+                    }
+                    _ => return None,
+                }
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
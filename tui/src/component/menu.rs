@@ -1,30 +1,50 @@
-use crate::Message;
+use crate::{Message, UserEvent};
+use core::config::Tab;
+use core::ConnectionStatus;
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
     widgets::{Block, Borders, Tabs},
 };
 use tuirealm::{
     command::{Cmd, CmdResult},
     event::{Key, KeyEvent},
     ratatui::prelude::Rect,
-    AttrValue, Attribute, Component, Event, Frame, MockComponent, NoUserEvent, Props, State,
-    StateValue,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State, StateValue,
 };
 
+/// Attribute holding the index of the host whose system information is displayed.
+pub const CURRENT_HOST: &str = "_CURRENT_HOST";
+
 /// The upper menu component in the UI.
 /// It is displaying the available menu titles, and highlights the currently selected tab.
 ///
 /// Controls:
 /// * Tab => sends a message to the app to change the currently selected menu item to the next one
 /// * Backspace => sends message to the app to change the currently selected menu item to the previous one
+/// * + => sends message to the app to refresh the system information more frequently
+/// * - => sends message to the app to refresh the system information less frequently
+/// * a => sends message to the app to show or hide the alert log
+/// * F9 => sends message to the app to pause or resume updating the displayed system information
+/// * 1-9 => sends message to the app to display the system information of the host with the number
 ///
 /// # Example:
-/// ```norun
+/// ```
+/// use tui::component::Menu;
+///
 /// let menu = Menu::default().with_tab_index(0);
 /// ```
 #[derive(Default)]
 pub struct Menu {
     properties: Props,
+
+    /// Count of the alerts raised since the alert log was last opened
+    unseen_alerts: usize,
+
+    /// Names of the hosts which can be switched between
+    hosts: Vec<String>,
+    /// Reachability of the current host, unknown until the first poll
+    connection: Option<ConnectionStatus>,
 }
 
 impl Menu {
@@ -34,10 +54,36 @@ impl Menu {
             .set(Attribute::Value, AttrValue::Length(idx));
         self
     }
+
+    /// Sets the color of the selected tab and the other tabs.
+    pub fn with_colors(mut self, highlight: Color, text: Color) -> Self {
+        self.properties
+            .set(Attribute::HighlightedColor, AttrValue::Color(highlight));
+        self.properties
+            .set(Attribute::Foreground, AttrValue::Color(text));
+        self
+    }
+
+    /// Sets the names of the hosts, the first one is displayed initially.
+    /// The host list is only displayed if there are more hosts to switch between.
+    pub fn with_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.hosts = hosts;
+        self
+    }
+
+    fn current_host(&self) -> usize {
+        self.properties
+            .get_or(Attribute::Custom(CURRENT_HOST), AttrValue::Length(0))
+            .unwrap_length()
+    }
 }
 
 impl MockComponent for Menu {
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if attr == Attribute::Custom(CURRENT_HOST) {
+            // the status of the previous host does not apply to the new one
+            self.connection = None;
+        }
         self.properties.set(attr, value);
     }
 
@@ -58,38 +104,140 @@ impl MockComponent for Menu {
     }
 
     fn view(&mut self, frame: &mut Frame, area: Rect) {
-        let titles = ["Overview", "CPU & Memory", "Processes", "Disk", "Network"]
-            .iter()
-            .map(|t| (*t).into())
-            .collect::<Vec<String>>();
+        let titles = [
+            "Overview",
+            "CPU & Memory",
+            "Processes",
+            "Disk",
+            "Network",
+            "Sensors",
+            "Users",
+            "Services",
+            "Containers",
+            "Logs",
+        ]
+        .iter()
+        .map(|t| (*t).into())
+        .collect::<Vec<String>>();
 
         let tab_index = self
             .properties
             .get_or(Attribute::Value, AttrValue::Length(0))
             .unwrap_length();
 
+        let mut block = Block::default().borders(Borders::ALL);
+        let current_host = self.current_host();
+        let host_name = self
+            .hosts
+            .get(current_host)
+            .map(String::as_str)
+            .unwrap_or_default();
+        match &self.connection {
+            Some(ConnectionStatus::Connected) => {
+                block =
+                    block.title_top(Line::from(format!(" ● {} ", host_name)).centered().green());
+            }
+            Some(ConnectionStatus::Disconnected(reason)) => {
+                block = block.title_top(
+                    Line::from(format!(" ○ {}: {}, reconnecting ", host_name, reason))
+                        .centered()
+                        .red(),
+                );
+            }
+            None => {}
+        }
+        if self.hosts.len() > 1 {
+            let hosts = self
+                .hosts
+                .iter()
+                .enumerate()
+                .flat_map(|(index, name)| {
+                    let label = Span::from(format!(" {}:{} ", index + 1, name));
+                    if index == current_host {
+                        [label.reversed(), Span::from(" ")]
+                    } else {
+                        [label, Span::from(" ")]
+                    }
+                })
+                .collect::<Vec<Span>>();
+            block = block.title_bottom(Line::from(hosts).right_aligned());
+        }
+        if self.unseen_alerts > 0 {
+            block = block.title_top(
+                Line::from(format!(" ! {} alerts (a) ", self.unseen_alerts))
+                    .left_aligned()
+                    .red()
+                    .bold()
+                    .slow_blink(),
+            );
+        }
+
+        let highlight_color = self
+            .properties
+            .get_or(Attribute::HighlightedColor, AttrValue::Color(Color::Yellow))
+            .unwrap_color();
+        let text_color = self
+            .properties
+            .get_or(Attribute::Foreground, AttrValue::Color(Color::White))
+            .unwrap_color();
+
         let tabs = Tabs::new(titles)
-            .block(Block::default().borders(Borders::ALL))
+            .block(block)
             .select(tab_index)
             .highlight_style(
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(highlight_color)
                     .add_modifier(Modifier::BOLD),
             )
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(text_color));
 
         frame.render_widget(tabs, area);
     }
 }
 
-impl Component<Message, NoUserEvent> for Menu {
-    fn on(&mut self, event: Event<NoUserEvent>) -> Option<Message> {
+impl Component<Message, UserEvent> for Menu {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
         match event {
+            Event::User(UserEvent::Alerts(alerts)) => {
+                self.unseen_alerts += alerts.len();
+                Some(Message::Redraw)
+            }
+            Event::User(UserEvent::Connection(status)) => {
+                self.connection = Some(status);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('a'),
+                ..
+            }) => {
+                self.unseen_alerts = 0;
+                Some(Message::ToggleAlertLog)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(digit @ '1'..='9'),
+                ..
+            }) => {
+                let index = digit as usize - '1' as usize;
+                (index < self.hosts.len() && index != self.current_host())
+                    .then_some(Message::SwitchHost(index))
+            }
             Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => Some(Message::ChangeNextMenu),
             Event::Keyboard(KeyEvent {
                 code: Key::Backspace,
                 ..
             }) => Some(Message::ChangePreviousMenu),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('+'),
+                ..
+            }) => Some(Message::SpeedUpPolling),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('-'),
+                ..
+            }) => Some(Message::SlowDownPolling),
+            Event::Keyboard(KeyEvent {
+                code: Key::Function(9),
+                ..
+            }) => Some(Message::TogglePause),
             Event::Keyboard(KeyEvent {
                 code: Key::Char('q') | Key::Esc | Key::Function(10),
                 ..
@@ -108,6 +256,28 @@ pub enum MenuState {
     ProcessDetails,
     DiskDetails,
     NetworkDetails,
+    SensorsDetails,
+    UsersDetails,
+    ServicesDetails,
+    ContainersDetails,
+    LogsDetails,
+}
+
+impl From<Tab> for MenuState {
+    fn from(tab: Tab) -> Self {
+        match tab {
+            Tab::Overview => Self::OverView,
+            Tab::Cpu => Self::CpuMemoryDetails,
+            Tab::Processes => Self::ProcessDetails,
+            Tab::Disk => Self::DiskDetails,
+            Tab::Network => Self::NetworkDetails,
+            Tab::Sensors => Self::SensorsDetails,
+            Tab::Users => Self::UsersDetails,
+            Tab::Services => Self::ServicesDetails,
+            Tab::Containers => Self::ContainersDetails,
+            Tab::Logs => Self::LogsDetails,
+        }
+    }
 }
 
 impl MenuState {
@@ -118,6 +288,31 @@ impl MenuState {
             Self::ProcessDetails => 2,
             Self::DiskDetails => 3,
             Self::NetworkDetails => 4,
+            Self::SensorsDetails => 5,
+            Self::UsersDetails => 6,
+            Self::ServicesDetails => 7,
+            Self::ContainersDetails => 8,
+            Self::LogsDetails => 9,
+        }
+    }
+
+    /// The most important key bindings of the tab, displayed in the status bar.
+    pub fn key_hints(&self) -> &'static str {
+        match *self {
+            Self::OverView => "l: limits  s: security  w: window  z/Z: zoom  ←/→: pan  e/E: export",
+            Self::CpuMemoryDetails => {
+                "h: heatmap  f: frequencies  c/C: core chart  t: topology  w: window  e/E: export"
+            }
+            Self::ProcessDetails => {
+                "Enter: details  /: search  k: kill  s: signal  g: group  v: columns  x: active  u: avg cpu"
+            }
+            Self::DiskDetails => "↑/↓: select  u: usage  w: window  e/E: export",
+            Self::NetworkDetails => "c: connections  p: ports  r: routing  b: bits  w: window  e/E: export",
+            Self::SensorsDetails => "↑/↓: scroll",
+            Self::UsersDetails => "↑/↓: select",
+            Self::ServicesDetails => "s: start  x: stop  r: restart",
+            Self::ContainersDetails => "k: stop",
+            Self::LogsDetails => "p: pause  /: search  End: follow",
         }
     }
 
@@ -127,17 +322,27 @@ impl MenuState {
             Self::CpuMemoryDetails => *self = Self::ProcessDetails,
             Self::ProcessDetails => *self = Self::DiskDetails,
             Self::DiskDetails => *self = Self::NetworkDetails,
-            Self::NetworkDetails => *self = Self::OverView,
+            Self::NetworkDetails => *self = Self::SensorsDetails,
+            Self::SensorsDetails => *self = Self::UsersDetails,
+            Self::UsersDetails => *self = Self::ServicesDetails,
+            Self::ServicesDetails => *self = Self::ContainersDetails,
+            Self::ContainersDetails => *self = Self::LogsDetails,
+            Self::LogsDetails => *self = Self::OverView,
         }
     }
 
     pub fn previous(&mut self) {
         match self {
-            Self::OverView => *self = Self::NetworkDetails,
+            Self::OverView => *self = Self::LogsDetails,
             Self::CpuMemoryDetails => *self = Self::OverView,
             Self::ProcessDetails => *self = Self::CpuMemoryDetails,
             Self::DiskDetails => *self = Self::ProcessDetails,
             Self::NetworkDetails => *self = Self::DiskDetails,
+            Self::SensorsDetails => *self = Self::NetworkDetails,
+            Self::UsersDetails => *self = Self::SensorsDetails,
+            Self::ServicesDetails => *self = Self::UsersDetails,
+            Self::ContainersDetails => *self = Self::ServicesDetails,
+            Self::LogsDetails => *self = Self::ContainersDetails,
         }
     }
 }
@@ -1,14 +1,258 @@
-use ratatui::style::{Style, Stylize};
+use core::config::{ChartStyle, Units};
+use core::model::MemoryInfo;
+use humansize::{BaseUnit, FormatSize, FormatSizeOptions, Kilo};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Chart, Dataset, GraphType},
+    Frame,
+};
 
+mod alert_log;
+mod chart_window;
+mod connections;
+mod containers;
 mod cpu_details;
+mod directory_usage;
+mod disk_details;
+mod limits;
+mod logs;
 mod menu;
+mod network_details;
 mod overview;
+mod process_details;
 mod processes;
+mod security;
+mod sensors;
+mod services;
+mod status_bar;
+mod users;
 
+pub use self::alert_log::*;
+pub use self::chart_window::*;
+pub use self::connections::*;
+pub use self::containers::*;
 pub use self::cpu_details::*;
+pub use self::directory_usage::*;
+pub use self::disk_details::*;
+pub use self::limits::*;
+pub use self::logs::*;
 pub use self::menu::*;
+pub use self::network_details::*;
 pub use self::overview::*;
+pub use self::process_details::*;
 pub use self::processes::*;
+pub use self::security::*;
+pub use self::sensors::*;
+pub use self::services::*;
+pub use self::status_bar::*;
+pub use self::users::*;
+
+/// Attribute set to `AttrValue::Flag(true)` by a component while it captures every key press,
+/// like when it displays a dialog. The global key bindings of the menu are disabled meanwhile.
+pub const MODAL: &str = "_MODAL";
+
+/// Returns an area with the given size in the center of `area`, for displaying popups.
+pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// Formats a Unix timestamp as a date and time of day in UTC, like `2024-03-01 12:30:00 UTC`.
+pub fn format_date_time(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // converts the days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+/// Formats a duration in seconds like `2d 3h 4m 5s`, leaving out the leading zero units.
+pub fn format_duration(seconds: u64) -> String {
+    let units = [
+        (seconds / 86400, "d"),
+        ((seconds % 86400) / 3600, "h"),
+        ((seconds % 3600) / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let parts = units
+        .iter()
+        .skip_while(|(value, unit)| *value == 0 && *unit != "s")
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect::<Vec<String>>();
+    parts.join(" ")
+}
+
+/// Formats a duration in seconds compactly for the narrow columns, like `2d 3h`, `5:04:32` or `14:32`,
+/// or as the count of the seconds, like `9604s`, if `exact` is set.
+pub fn format_compact_duration(seconds: u64, exact: bool) -> String {
+    let (days, hours, minutes) = (
+        seconds / 86400,
+        (seconds % 86400) / 3600,
+        (seconds % 3600) / 60,
+    );
+    if exact {
+        format!("{}s", seconds)
+    } else if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds % 60)
+    } else {
+        format!("{}:{:02}", minutes, seconds % 60)
+    }
+}
+
+/// Returns the multiplier of the data size prefixes for the configured `units`.
+pub fn kilo_for(units: Units) -> Kilo {
+    match units {
+        Units::Binary => Kilo::Binary,
+        Units::Decimal => Kilo::Decimal,
+    }
+}
+
+/// Returns the options formatting the data sizes in the configured `units` with one decimal place,
+/// like `1.5 MiB` or `1.6 MB`. Every displayed size is formatted by these options, so the units are consistent.
+pub fn size_format(units: Units) -> FormatSizeOptions {
+    FormatSizeOptions::default()
+        .base_unit(BaseUnit::Byte)
+        .kilo(kilo_for(units))
+        .units(kilo_for(units))
+        .decimal_places(1)
+        .decimal_zeroes(0)
+        .long_units(false)
+        .space_after_value(true)
+}
+
+/// Formats a throughput of `bytes_per_second` by `opts`, in bits per second if `bits` is set,
+/// like `1.5 MiB/s` or `12.6 Mbit/s`.
+pub fn format_rate(bytes_per_second: f64, opts: FormatSizeOptions, bits: bool) -> String {
+    if bits {
+        let bits_per_second = (bytes_per_second * 8.0) as u64;
+        format!(
+            "{}/s",
+            bits_per_second.format_size(opts.base_unit(BaseUnit::Bit))
+        )
+    } else {
+        format!("{}/s", (bytes_per_second as u64).format_size(opts))
+    }
+}
+
+/// Creates a series of a chart drawn in `style`.
+pub fn chart_dataset<'a>(style: ChartStyle) -> Dataset<'a> {
+    let dataset = Dataset::default();
+    match style {
+        ChartStyle::Braille => dataset.marker(Marker::Braille).graph_type(GraphType::Line),
+        ChartStyle::Dots => dataset.marker(Marker::Dot).graph_type(GraphType::Scatter),
+        ChartStyle::Ascii => dataset.marker(Marker::Dot).graph_type(GraphType::Line),
+    }
+}
+
+/// Renders `chart` into `area`. In the ASCII style the Unicode symbols of the chart are replaced
+/// by ASCII ones afterwards, like the borders by `|`, `-` and `+` and the dots of the series by `*`.
+pub fn render_chart(frame: &mut Frame, chart: Chart, area: Rect, style: ChartStyle) {
+    frame.render_widget(chart, area);
+    if style != ChartStyle::Ascii {
+        return;
+    }
+
+    let buffer = frame.buffer_mut();
+    let area = area.intersection(buffer.area);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let Some(cell) = buffer.cell_mut((x, y)) else {
+                continue;
+            };
+            let ascii = match cell.symbol() {
+                symbol if symbol.is_ascii() => continue,
+                "│" | "┃" | "║" => "|",
+                "─" | "━" | "═" => "-",
+                // the corners and the junctions of the box drawing characters
+                symbol
+                    if symbol
+                        .chars()
+                        .all(|c| ('\u{2500}'..='\u{257f}').contains(&c)) =>
+                {
+                    "+"
+                }
+                _ => "*",
+            };
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+/// Creates the lines of a bar `width` cells wide showing the parts of the physical memory in different colors,
+/// with the legend of the parts under it: the memory of the applications, the kernel slab, the buffers,
+/// the page cache and the free memory. The shared and the dirty memory are part of the page cache,
+/// so they are only listed in the legend.
+pub fn memory_bar(memory: &MemoryInfo, width: u16, opts: FormatSizeOptions) -> Vec<Line<'static>> {
+    let parts = [
+        ("apps", memory.applications(), Color::LightGreen),
+        ("slab", memory.slab, Color::LightMagenta),
+        ("buffers", memory.buffers, Color::LightBlue),
+        ("cache", memory.cached, Color::Yellow),
+    ];
+
+    // the cells are given by the cumulative sizes, so the rounding errors do not add up
+    let total = memory.total.max(1) as f64;
+    let mut bar = Vec::new();
+    let mut cumulative = 0;
+    let mut filled = 0;
+    for (_, size, color) in parts {
+        cumulative += size;
+        let end = ((cumulative as f64 / total) * width as f64).round() as usize;
+        let end = end.min(width as usize);
+        bar.push(Span::styled("█".repeat(end.saturating_sub(filled)), color));
+        filled = filled.max(end);
+    }
+    bar.push(Span::styled(
+        "░".repeat((width as usize).saturating_sub(filled)),
+        Color::DarkGray,
+    ));
+
+    let mut legend = Vec::new();
+    for (name, size, color) in parts {
+        legend.push(Span::styled("■ ", color));
+        legend.push(Span::raw(format!("{} {}  ", name, size.format_size(opts))));
+    }
+    legend.push(Span::raw(format!(
+        "shared {}  dirty {}",
+        memory.shared.format_size(opts),
+        memory.dirty.format_size(opts)
+    )));
+
+    vec![Line::from(bar), Line::from(legend)]
+}
 
 pub fn get_color_for(percentage: f64) -> Style {
     match percentage {
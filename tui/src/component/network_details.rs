@@ -0,0 +1,975 @@
+use super::{chart_dataset, format_rate, render_chart, size_format, ChartWindow};
+use crate::{Message, UserEvent};
+use core::config::{ChartStyle, Units};
+use core::connections::ConnectionStates;
+use core::history::{History, Metric};
+use core::latency::LatencyProbe;
+use core::link::LinkInfo;
+use core::model::{signal_quality, NetworkInfo, ProcessTraffic, WirelessLink};
+use core::protocols::ProtocolStats;
+use humansize::{FormatSize, FormatSizeOptions};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Axis, Block, Cell, Chart, LegendPosition, Paragraph, Row, Sparkline, Table, TableState,
+    },
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// Height of the Wi-Fi panel: the four lines of the connection and the signal over time in the borders.
+const WIRELESS_HEIGHT: u16 = 9;
+
+/// Height of the expanded routing section with the most routes or neighbors.
+const MAX_ROUTING_HEIGHT: u16 = 12;
+
+/// Width of the TCP and UDP statistics next to the throughput chart.
+const PROTOCOLS_WIDTH: u16 = 36;
+
+/// The percentage of the retransmitted TCP segments above which the retransmissions are highlighted.
+const RETRANSMISSION_WARNING: f64 = 2.0;
+
+/// The percentage of the ephemeral ports in use above which their use is highlighted.
+const EPHEMERAL_WARNING: f64 = 80.0;
+
+/// Height of the states of the connections: the bar, two lines of legend and the ephemeral ports.
+const CONNECTION_STATES_HEIGHT: u16 = 4;
+
+/// Component for displaying the statistics of every network interface
+/// and the throughput over time of the selected one.
+/// The interfaces whose link is down are gray.
+/// The connection of a selected wireless interface is shown with its signal strength over time.
+/// The round-trip times of the ping targets of the configuration are drawn over time under the interfaces.
+/// The public address of the host is shown over the interfaces if its lookup is enabled, or why it failed.
+/// The rates of the TCP and UDP counters are listed next to the throughput chart with the retransmissions over time,
+/// highlighted when the retransmissions slow down the connections or packets are dropped.
+/// Under them the TCP sockets are split by their state in a bar, with the use of the ephemeral ports,
+/// which runs out with too many short outgoing connections.
+///
+/// Controls:
+/// * Up/Down => moves the selection of the interfaces
+/// * c => shows the TCP and UDP connections of the system with the processes owning them
+/// * p => shows the listening ports instead of the throughput chart, or the chart again.
+///   The ports bound to an address other than the loopback one are highlighted,
+///   as they are reachable from the network.
+/// * r => expands the routing section under the interfaces: the default gateway, the routes
+///   and the neighbors of the ARP cache, or collapses it again
+/// * b => toggles the throughput between bytes and bits per second
+/// * e/E => exports the throughput history of the selected interface into a CSV/JSON file
+/// * w/z/Z/Left/Right => changes, zooms and pans the period of time displayed by the charts, see `ChartWindow`
+#[derive(Default)]
+pub struct NetworkDetails {
+    properties: Props,
+
+    network_info: NetworkInfo,
+
+    /// Throughput history of the interfaces in bytes per second
+    history: History,
+
+    /// The period of time displayed by the throughput chart
+    window: ChartWindow,
+
+    /// Selection of the interface whose throughput is displayed
+    table_state: TableState,
+
+    /// True while the listening ports are displayed instead of the throughput chart
+    show_ports: bool,
+
+    /// True while the routing section is expanded
+    show_routing: bool,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+
+    /// The throughput is displayed in bits per second instead of bytes
+    bits: bool,
+
+    /// How the series of the charts are drawn
+    chart_style: ChartStyle,
+}
+
+impl MockComponent for NetworkDetails {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let routing_height = match self.show_routing {
+            true => self.routing_height(),
+            false => 0,
+        };
+        // the borders around the targets
+        let latency_height = match &self.network_info.latency {
+            Some(probes) if !probes.is_empty() => probes.len() as u16 + 2,
+            _ => 0,
+        };
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Length(routing_height),
+                Constraint::Length(latency_height),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+
+        self.render_interface_table(frame, layout[0]);
+        if self.show_routing {
+            self.render_routing(frame, layout[1]);
+        }
+        if let Some(probes) = &self.network_info.latency {
+            self.render_latency(frame, layout[2], probes);
+        }
+        self.render_throughput(frame, layout[3]);
+    }
+}
+
+impl Component<Message, UserEvent> for NetworkDetails {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Network(network_info)) => {
+                self.process_update(*network_info);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.select_previous();
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.select_next();
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('c'),
+                ..
+            }) => Some(Message::ShowConnections),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('p'),
+                ..
+            }) => {
+                self.show_ports = !self.show_ports;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('r'),
+                ..
+            }) => {
+                self.show_routing = !self.show_routing;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('b'),
+                ..
+            }) => {
+                self.bits = !self.bits;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('e'),
+                modifiers,
+            }) => self.selected_interface().map(|name| {
+                self.window.export(
+                    "network",
+                    &self.history,
+                    &[
+                        Metric::InterfaceReceived(name.clone()),
+                        Metric::InterfaceTransmitted(name.clone()),
+                    ],
+                    modifiers,
+                )
+            }),
+            Event::Keyboard(key) if self.window.on_key(&key) => Some(Message::Redraw),
+            _ => None,
+        }
+    }
+}
+
+impl NetworkDetails {
+    /// Sets the history which the throughput charts are drawn from.
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets whether the throughput is displayed in bits per second instead of bytes.
+    pub fn with_bits(mut self, bits: bool) -> Self {
+        self.bits = bits;
+        self
+    }
+
+    /// Sets how the series of the charts are drawn.
+    pub fn with_chart_style(mut self, chart_style: ChartStyle) -> Self {
+        self.chart_style = chart_style;
+        self
+    }
+
+    fn process_update(&mut self, update: NetworkInfo) {
+        // forget the interfaces which are no longer present
+        self.history.retain(|metric| match metric {
+            Metric::InterfaceReceived(name)
+            | Metric::InterfaceTransmitted(name)
+            | Metric::WifiSignal(name) => update
+                .interface_details
+                .iter()
+                .any(|interface| &interface.name == name),
+            _ => true,
+        });
+
+        for interface in update.interface_details.iter() {
+            self.history.record(
+                Metric::InterfaceReceived(interface.name.clone()),
+                interface.received_rate,
+            );
+            self.history.record(
+                Metric::InterfaceTransmitted(interface.name.clone()),
+                interface.transmitted_rate,
+            );
+            if let Some(signal) = interface.wireless.as_ref().and_then(|link| link.signal) {
+                self.history
+                    .record(Metric::WifiSignal(interface.name.clone()), signal as f64);
+            }
+        }
+        if let Some(protocols) = &update.protocols {
+            self.history
+                .record(Metric::TcpRetransmissions, protocols.retransmitted);
+        }
+        for probe in update.latency.iter().flatten() {
+            if let Some(rtt) = probe.rtt {
+                self.history
+                    .record(Metric::PingRtt(probe.target.clone()), rtt);
+            }
+        }
+
+        // keep the selection on an existing interface
+        let interface_count = update.interface_details.len();
+        let selected = match self.table_state.selected() {
+            _ if interface_count == 0 => None,
+            Some(i) => Some(i.min(interface_count - 1)),
+            None => Some(0),
+        };
+        self.table_state.select(selected);
+
+        self.network_info = update;
+    }
+
+    fn select_next(&mut self) {
+        let next = match self.table_state.selected() {
+            Some(i) if i + 1 < self.network_info.interface_details.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.table_state.select(Some(next));
+    }
+
+    fn select_previous(&mut self) {
+        let previous = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.table_state.select(Some(previous));
+    }
+
+    /// Returns the name of the selected interface.
+    fn selected_interface(&self) -> Option<&String> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.network_info.interface_details.get(i))
+            .map(|interface| &interface.name)
+    }
+
+    fn render_interface_table(&mut self, frame: &mut Frame, area: Rect) {
+        let opts = size_format(self.units);
+
+        let header = Row::new(vec![
+            Cell::from("interface"),
+            Cell::from("mac"),
+            Cell::from("ip"),
+            Cell::from("rx/s"),
+            Cell::from("tx/s"),
+            Cell::from("rx total"),
+            Cell::from("tx total"),
+            Cell::from("rx pkts"),
+            Cell::from("tx pkts"),
+            Cell::from("rx err"),
+            Cell::from("tx err"),
+        ])
+        .bold();
+
+        let rows = self
+            .network_info
+            .interface_details
+            .iter()
+            .map(|interface| {
+                let style = match interface.is_down() {
+                    true => Style::default().gray(),
+                    false => Style::default(),
+                };
+                Row::new(vec![
+                    Cell::from(interface.name.clone()),
+                    Cell::from(interface.mac_address.clone()),
+                    Cell::from(interface.ip_addresses.join("\n")),
+                    Cell::from(format_rate(interface.received_rate, opts, self.bits)),
+                    Cell::from(format_rate(interface.transmitted_rate, opts, self.bits)),
+                    Cell::from(interface.total_received.format_size(opts)),
+                    Cell::from(interface.total_transmitted.format_size(opts)),
+                    Cell::from(interface.total_packets_received.to_string()),
+                    Cell::from(interface.total_packets_transmitted.to_string()),
+                    Cell::from(interface.total_errors_on_received.to_string()),
+                    Cell::from(interface.total_errors_on_transmitted.to_string()),
+                ])
+                .height(interface.ip_addresses.len().max(1) as u16)
+                .style(style)
+            })
+            .collect::<Vec<Row>>();
+
+        let mut block = Block::bordered()
+            .title("Network interfaces")
+            .title_alignment(Alignment::Center);
+        if let Some(public_ip) = &self.network_info.public_ip {
+            let address = match (&public_ip.address, &public_ip.error) {
+                (Some(address), _) => match public_ip.location() {
+                    Some(location) => format!("{} ({})", address, location),
+                    None => address.clone(),
+                },
+                (None, Some(error)) => format!("unknown, {}", error),
+                (None, None) => "looking up".to_string(),
+            };
+            block = block.title(Line::from(format!(" public IP: {} ", address)).right_aligned());
+        }
+        let table = Table::default()
+            .block(block)
+            .widths([
+                Constraint::Length(10),
+                Constraint::Length(17),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ])
+            .header(header)
+            .row_highlight_style(Style::default().reversed())
+            .rows(rows);
+
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+
+    /// Renders the peak and average throughput and the link of the selected interface in the left side,
+    /// its throughput over time in the middle and the TCP and UDP statistics in the right side.
+    fn render_throughput(&self, frame: &mut Frame, area: Rect) {
+        let protocols_width = match self.network_info.protocols {
+            Some(_) => PROTOCOLS_WIDTH,
+            None => 0,
+        };
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Fill(1),
+                Constraint::Length(protocols_width),
+            ])
+            .split(area);
+        if let Some(protocols) = &self.network_info.protocols {
+            self.render_protocols(frame, layout[2], protocols);
+        }
+        let opts = size_format(self.units);
+
+        let Some(name) = self.selected_interface() else {
+            frame.render_widget(
+                Block::bordered().title("Throughput over time"),
+                layout[0].union(layout[1]),
+            );
+            return;
+        };
+
+        let received = self
+            .window
+            .points(&self.history, &Metric::InterfaceReceived(name.clone()));
+        let transmitted = self
+            .window
+            .points(&self.history, &Metric::InterfaceTransmitted(name.clone()));
+
+        let mut readouts = format!(
+            "Received\n  peak: {}\n  average: {}\n\nTransmitted\n  peak: {}\n  average: {}",
+            format_rate(peak(&received), opts, self.bits),
+            format_rate(average(&received), opts, self.bits),
+            format_rate(peak(&transmitted), opts, self.bits),
+            format_rate(average(&transmitted), opts, self.bits),
+        );
+        let interface = self
+            .table_state
+            .selected()
+            .and_then(|i| self.network_info.interface_details.get(i));
+        let link = interface.and_then(|interface| interface.link.as_ref());
+        if let Some(link) = link {
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "unknown".to_string());
+            readouts.push_str(&format!(
+                "\n\nLink: {}\n  speed: {}\n  mtu: {}\n  carrier changes: {}",
+                link.operational_state,
+                optional(Some(format_link(link)).filter(|speed| !speed.is_empty())),
+                optional(link.mtu.map(|mtu| mtu.to_string())),
+                optional(link.carrier_changes.map(|changes| changes.to_string())),
+            ));
+        }
+        let readouts_height = readouts.lines().count() as u16 + 2;
+        let readouts = Paragraph::new(readouts).block(Block::bordered().title(name.as_str()));
+        // the connection of a wireless interface and the top talkers are listed under the readouts,
+        // the latter while the traffic of the processes is accounted
+        let wireless = interface.and_then(|interface| interface.wireless.as_ref());
+        let talkers = self.network_info.top_talkers.as_ref();
+        let [readouts_area, wireless_area, talkers_area] = Layout::vertical([
+            match (wireless, talkers) {
+                (None, None) => Constraint::Fill(1),
+                _ => Constraint::Length(readouts_height),
+            },
+            match (wireless, talkers) {
+                (None, _) => Constraint::Length(0),
+                (Some(_), Some(_)) => Constraint::Length(WIRELESS_HEIGHT),
+                (Some(_), None) => Constraint::Fill(1),
+            },
+            match talkers {
+                Some(_) => Constraint::Fill(1),
+                None => Constraint::Length(0),
+            },
+        ])
+        .areas(layout[0]);
+        if let Some(wireless) = wireless {
+            self.render_wireless(frame, wireless_area, name, wireless);
+        }
+        if let Some(talkers) = talkers {
+            frame.render_widget(top_talkers_table(talkers, opts, self.bits), talkers_area);
+        }
+
+        // the chart is drawn in KiB/s or kB/s, or in Kibit/s or kbit/s
+        let (kilo, unit) = match (self.units, self.bits) {
+            (Units::Binary, false) => (1024.0, "KiB/s"),
+            (Units::Decimal, false) => (1000.0, "kB/s"),
+            (Units::Binary, true) => (1024.0 / 8.0, "Kibit/s"),
+            (Units::Decimal, true) => (1000.0 / 8.0, "kbit/s"),
+        };
+        let to_kilo = |points: Vec<(f64, f64)>| {
+            points
+                .into_iter()
+                .map(|(x, bytes)| (x, bytes / kilo))
+                .collect::<Vec<(f64, f64)>>()
+        };
+        let received = to_kilo(received);
+        let transmitted = to_kilo(transmitted);
+
+        let datasets = vec![
+            chart_dataset(self.chart_style)
+                .name("rx")
+                .style(Style::default().light_green())
+                .data(&received),
+            chart_dataset(self.chart_style)
+                .name("tx")
+                .style(Style::default().light_blue())
+                .data(&transmitted),
+        ];
+
+        let max_throughput = peak(&received).max(peak(&transmitted)).max(1.0);
+        let throughput_axis = Axis::default()
+            .labels(vec![
+                "0".gray().bold(),
+                format!("{:.1} {}", max_throughput / 2.0, unit)
+                    .gray()
+                    .bold(),
+                format!("{:.1} {}", max_throughput, unit).gray().bold(),
+            ])
+            .bounds([0.0, max_throughput]);
+        let time_axis = Axis::default()
+            .labels(self.window.labels())
+            .bounds(self.window.bounds());
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::bordered()
+                    .title(format!("{} throughput over time", name))
+                    .title_alignment(Alignment::Center),
+            )
+            .x_axis(time_axis)
+            .y_axis(throughput_axis)
+            .legend_position(Some(LegendPosition::TopRight))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+
+        frame.render_widget(readouts, readouts_area);
+        if self.show_ports {
+            self.render_listening_ports(frame, layout[1]);
+        } else {
+            render_chart(frame, chart, layout[1], self.chart_style);
+        }
+    }
+
+    /// Renders the rates of the TCP and UDP counters and the states of the connections
+    /// over the retransmissions over time.
+    fn render_protocols(&self, frame: &mut Frame, area: Rect, protocols: &ProtocolStats) {
+        let block = Block::bordered().title("TCP/UDP");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let per_second = |rate: f64| format!("{:.1}/s", rate);
+        // the drops are highlighted, as they are not expected at all
+        let dropped = |rate: f64| match rate > 0.0 {
+            true => Style::default().light_red(),
+            false => Style::default(),
+        };
+        let ratio = protocols.retransmission_ratio();
+        let retransmissions = match ratio >= RETRANSMISSION_WARNING {
+            true => Style::default().yellow().bold(),
+            false => Style::default(),
+        };
+        let states = self.network_info.connection_states.as_ref();
+        let mut rows = Vec::new();
+        // the established connections are counted in the states otherwise
+        if states.is_none() {
+            rows.push(Row::new(vec![
+                "established".to_string(),
+                protocols.established.to_string(),
+            ]));
+        }
+        rows.extend([
+            Row::new(vec![
+                "segments in".to_string(),
+                per_second(protocols.segments_received),
+            ]),
+            Row::new(vec![
+                "segments out".to_string(),
+                per_second(protocols.segments_sent),
+            ]),
+            Row::new(vec![
+                "retransmitted".to_string(),
+                format!("{} ({:.1}%)", per_second(protocols.retransmitted), ratio),
+            ])
+            .style(retransmissions),
+            Row::new(vec![
+                "resets".to_string(),
+                per_second(protocols.connection_resets),
+            ]),
+            Row::new(vec![
+                "resets sent".to_string(),
+                per_second(protocols.resets_sent),
+            ]),
+            Row::new(vec![
+                "failed opens".to_string(),
+                per_second(protocols.failed_connections),
+            ]),
+            Row::new(vec![
+                "listen overflows".to_string(),
+                per_second(protocols.listen_overflows),
+            ])
+            .style(dropped(protocols.listen_overflows)),
+            Row::new(vec![
+                "udp in".to_string(),
+                per_second(protocols.datagrams_received),
+            ]),
+            Row::new(vec![
+                "udp out".to_string(),
+                per_second(protocols.datagrams_sent),
+            ]),
+            Row::new(vec![
+                "udp errors".to_string(),
+                per_second(protocols.udp_errors),
+            ])
+            .style(dropped(protocols.udp_errors)),
+            Row::new(vec![
+                "udp no port".to_string(),
+                per_second(protocols.udp_no_ports),
+            ]),
+        ]);
+        let [table_area, states_area, sparkline_area] = Layout::vertical([
+            Constraint::Length(rows.len() as u16),
+            Constraint::Length(match states {
+                Some(_) => CONNECTION_STATES_HEIGHT,
+                None => 0,
+            }),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+        let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(16)]);
+        frame.render_widget(table, table_area);
+        if let Some(states) = states {
+            frame.render_widget(
+                Paragraph::new(connection_states_lines(states, states_area.width)),
+                states_area,
+            );
+        }
+
+        // the sparkline only draws integers, so the rates are scaled up to keep their fraction
+        let samples = self
+            .history
+            .window(&Metric::TcpRetransmissions, sparkline_area.width as usize)
+            .iter()
+            .map(|sample| (sample.value * 100.0) as u64)
+            .collect::<Vec<u64>>();
+        frame.render_widget(Sparkline::default().data(&samples).yellow(), sparkline_area);
+    }
+
+    /// Renders the connection of the wireless interface `name` over its signal strength over time.
+    fn render_wireless(&self, frame: &mut Frame, area: Rect, name: &str, link: &WirelessLink) {
+        let block = Block::bordered().title("Wi-Fi");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(ssid) = &link.ssid else {
+            frame.render_widget(Paragraph::new("not connected").italic(), inner);
+            return;
+        };
+        let unknown = || "unknown".to_string();
+        let lines = [
+            format!("SSID: {}", ssid),
+            format!(
+                "signal: {}",
+                link.signal
+                    .zip(link.quality())
+                    .map_or_else(unknown, |(signal, quality)| {
+                        format!("{} dBm ({}%)", signal, quality)
+                    })
+            ),
+            format!(
+                "bitrate: {}",
+                link.bitrate
+                    .map_or_else(unknown, |bitrate| format!("{:.1} Mbit/s", bitrate))
+            ),
+            format!(
+                "channel: {}",
+                link.frequency
+                    .map_or_else(unknown, |frequency| match link.band() {
+                        Some(band) => format!("{} MHz ({})", frequency, band),
+                        None => format!("{} MHz", frequency),
+                    })
+            ),
+        ];
+        let [text_area, sparkline_area] =
+            Layout::vertical([Constraint::Length(lines.len() as u16), Constraint::Fill(1)])
+                .areas(inner);
+        frame.render_widget(Paragraph::new(lines.join("\n")), text_area);
+
+        // the signal is drawn in percent, as the sparkline only draws positive values
+        let samples = self
+            .history
+            .window(
+                &Metric::WifiSignal(name.to_string()),
+                sparkline_area.width as usize,
+            )
+            .iter()
+            .map(|sample| signal_quality(sample.value as i32) as u64)
+            .collect::<Vec<u64>>();
+        frame.render_widget(
+            Sparkline::default().data(&samples).max(100).light_green(),
+            sparkline_area,
+        );
+    }
+
+    /// Returns the height of the routing section fitting the longer of the routes and the neighbors,
+    /// at most `MAX_ROUTING_HEIGHT`.
+    fn routing_height(&self) -> u16 {
+        let count = |list: Option<usize>| list.unwrap_or_default() as u16;
+        let rows = count(self.network_info.routes.as_ref().map(Vec::len))
+            .max(count(self.network_info.neighbors.as_ref().map(Vec::len)))
+            .max(1);
+        // the borders and the header
+        (rows + 3).min(MAX_ROUTING_HEIGHT)
+    }
+
+    /// Renders the routes in the left side and the neighbors in the right side,
+    /// with the default gateway in the title.
+    fn render_routing(&self, frame: &mut Frame, area: Rect) {
+        let routes = self.network_info.routes.as_deref().unwrap_or_default();
+        let neighbors = self.network_info.neighbors.as_deref().unwrap_or_default();
+        let gateway = routes
+            .iter()
+            .find_map(|route| match (route.is_default(), &route.gateway) {
+                (true, Some(gateway)) => Some(format!("{} via {}", gateway, route.interface)),
+                _ => None,
+            })
+            .unwrap_or_else(|| "none".to_string());
+        let [routes_area, neighbors_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Fill(1)]).areas(area);
+
+        let rows = routes.iter().map(|route| {
+            let row = Row::new(vec![
+                route.destination.clone(),
+                route.gateway.clone().unwrap_or_default(),
+                route.interface.clone(),
+                route.metric.to_string(),
+            ]);
+            match route.is_default() {
+                true => row.bold(),
+                false => row,
+            }
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(10),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(vec!["destination", "gateway", "interface", "metric"]).bold())
+        .block(Block::bordered().title(format!("Routes, default gateway: {}", gateway)));
+        frame.render_widget(table, routes_area);
+
+        let rows = neighbors.iter().map(|neighbor| {
+            let row = Row::new(vec![
+                neighbor.address.clone(),
+                neighbor.mac_address.clone(),
+                neighbor.interface.clone(),
+                neighbor.state.clone(),
+            ]);
+            match neighbor.state.as_str() {
+                "incomplete" => row.gray(),
+                _ => row,
+            }
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(17),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(Row::new(vec!["address", "mac", "interface", "state"]).bold())
+        .block(Block::bordered().title("Neighbors"));
+        frame.render_widget(table, neighbors_area);
+    }
+
+    /// Renders a line for every ping target: its last round-trip time and packet loss and its round-trip time over time.
+    /// The lost probes and the targets which can not be probed are red.
+    fn render_latency(&self, frame: &mut Frame, area: Rect, probes: &[LatencyProbe]) {
+        let block = Block::bordered().title("Latency");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical(vec![Constraint::Length(1); probes.len()]).split(inner);
+        for (probe, row) in probes.iter().zip(rows.iter()) {
+            let [target_area, readout_area, sparkline_area] = Layout::horizontal([
+                Constraint::Length(24),
+                Constraint::Length(24),
+                Constraint::Fill(1),
+            ])
+            .areas(*row);
+            let target = match &probe.address {
+                Some(address) if address != &probe.target => {
+                    format!("{} ({})", probe.target, address)
+                }
+                _ => probe.target.clone(),
+            };
+            frame.render_widget(Paragraph::new(target), target_area);
+
+            // the error takes the place of the readout and the sparkline
+            if let Some(error) = &probe.error {
+                let error_area = readout_area.union(sparkline_area);
+                frame.render_widget(Paragraph::new(error.as_str()).light_red(), error_area);
+                continue;
+            }
+            let readout = match probe.rtt {
+                Some(rtt) => Paragraph::new(format!("{:.1} ms  {:.0}% lost", rtt, probe.loss)),
+                None => Paragraph::new(format!("no reply  {:.0}% lost", probe.loss)).light_red(),
+            };
+            frame.render_widget(readout, readout_area);
+
+            // the sparkline only draws integers, so the times are scaled up to keep their fraction
+            let samples = self
+                .history
+                .window(
+                    &Metric::PingRtt(probe.target.clone()),
+                    sparkline_area.width as usize,
+                )
+                .iter()
+                .map(|sample| (sample.value * 100.0) as u64)
+                .collect::<Vec<u64>>();
+            frame.render_widget(Sparkline::default().data(&samples).cyan(), sparkline_area);
+        }
+    }
+
+    /// Renders the listening ports, highlighting the ones bound beyond the loopback address.
+    fn render_listening_ports(&self, frame: &mut Frame, area: Rect) {
+        let Some(ports) = &self.network_info.listening_ports else {
+            let message = Paragraph::new("The listening ports are only listed on Linux")
+                .block(Block::bordered().title("Listening ports"));
+            frame.render_widget(message, area);
+            return;
+        };
+
+        let rows = ports.iter().map(|port| {
+            let row = Row::new(vec![
+                Cell::from(port.protocol.as_str()),
+                Cell::from(port.local.as_str()),
+                Cell::from(port.pid.map_or(String::new(), |pid| pid.to_string())),
+                Cell::from(port.process.as_deref().unwrap_or_default()),
+                Cell::from(port.user.as_deref().unwrap_or_default()),
+            ]);
+            match port.is_exposed() {
+                true => row.light_red(),
+                false => row,
+            }
+        });
+        let exposed = ports.iter().filter(|port| port.is_exposed()).count();
+
+        let table = Table::default()
+            .block(
+                Block::bordered()
+                    .title(format!(
+                        "Listening ports ({} reachable from the network)",
+                        exposed
+                    ))
+                    .title_alignment(Alignment::Center),
+            )
+            .widths([
+                Constraint::Length(5),
+                Constraint::Fill(1),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Length(12),
+            ])
+            .header(Row::new(vec!["proto", "address", "pid", "process", "user"]).bold())
+            .rows(rows);
+        frame.render_widget(table, area);
+    }
+}
+
+/// Creates the table of the processes with the highest network throughput, in bits per second if `bits` is set.
+fn top_talkers_table(talkers: &[ProcessTraffic], opts: FormatSizeOptions, bits: bool) -> Table<'_> {
+    let rows = talkers.iter().map(|talker| {
+        Row::new(vec![
+            Cell::from(talker.name.as_str()),
+            Cell::from(format_rate(talker.received_rate, opts, bits)),
+            Cell::from(format_rate(talker.transmitted_rate, opts, bits)),
+        ])
+    });
+
+    Table::default()
+        .block(Block::bordered().title("Top talkers"))
+        .widths([
+            Constraint::Fill(1),
+            Constraint::Length(11),
+            Constraint::Length(11),
+        ])
+        .header(Row::new(vec!["process", "rx/s", "tx/s"]).bold())
+        .rows(rows)
+}
+
+/// Returns the highest value of the chart `points`.
+/// Returns a bar of `width` split by the states of the connections in proportion to their counts,
+/// the legend of the states and the use of the ephemeral ports.
+fn connection_states_lines(states: &ConnectionStates, width: u16) -> Vec<Line<'static>> {
+    let parts = [
+        ("established", states.established, Color::Green),
+        ("time wait", states.time_wait, Color::Yellow),
+        ("close wait", states.close_wait, Color::LightRed),
+        ("listen", states.listen, Color::Cyan),
+        ("other", states.other, Color::DarkGray),
+    ];
+    let total = states.total();
+    let bar = match total {
+        0 => Line::from("no connections").dark_gray(),
+        _ => {
+            // the bounds of the parts are rounded from their running total, so the parts fill the bar exactly
+            let mut counted = 0;
+            let mut drawn = 0;
+            let spans = parts
+                .iter()
+                .map(|(_, count, color)| {
+                    counted += count;
+                    let end = (counted as f64 / total as f64 * width as f64).round() as usize;
+                    let span = Span::styled("█".repeat(end - drawn), Style::default().fg(*color));
+                    drawn = end;
+                    span
+                })
+                .collect::<Vec<Span>>();
+            Line::from(spans)
+        }
+    };
+    // the other states are only drawn in the bar, as they are rarely more than a sliver
+    let legend = |parts: &[(&str, u32, Color)]| {
+        Line::from(
+            parts
+                .iter()
+                .flat_map(|(name, count, color)| {
+                    [
+                        Span::styled("■ ", Style::default().fg(*color)),
+                        Span::from(format!("{:<15}", format!("{} {}", name, count))),
+                    ]
+                })
+                .collect::<Vec<Span>>(),
+        )
+    };
+    let ephemeral = match (states.ephemeral_ports, states.ephemeral_usage()) {
+        (Some(ports), Some(usage)) => {
+            let line = Line::from(format!(
+                "ephemeral ports {}/{} ({:.0}%)",
+                states.ephemeral_in_use, ports, usage
+            ));
+            match usage >= EPHEMERAL_WARNING {
+                true => line.light_red().bold(),
+                false => line,
+            }
+        }
+        _ => Line::from(format!(
+            "ephemeral ports in use {}",
+            states.ephemeral_in_use
+        )),
+    };
+    vec![bar, legend(&parts[0..2]), legend(&parts[2..4]), ephemeral]
+}
+
+fn peak(points: &[(f64, f64)]) -> f64 {
+    points.iter().map(|(_, value)| *value).fold(0.0, f64::max)
+}
+
+/// Returns the average value of the chart `points`.
+fn average(points: &[(f64, f64)]) -> f64 {
+    if points.is_empty() {
+        0.0
+    } else {
+        points.iter().map(|(_, value)| value).sum::<f64>() / points.len() as f64
+    }
+}
+
+/// Formats the negotiated speed and duplex of a link, like `1G full` or `100M half`,
+/// empty if the speed is unknown.
+fn format_link(link: &LinkInfo) -> String {
+    let speed = match link.speed {
+        Some(speed) if speed >= 1000 && speed % 1000 == 0 => format!("{}G", speed / 1000),
+        Some(speed) => format!("{}M", speed),
+        None => return String::new(),
+    };
+    match &link.duplex {
+        Some(duplex) => format!("{} {}", speed, duplex),
+        None => speed,
+    }
+}
@@ -1,27 +1,60 @@
-use super::get_color_for;
+use super::{
+    chart_dataset, format_date_time, get_color_for, memory_bar, render_chart, size_format,
+    ChartWindow,
+};
 use crate::view::Message;
+use crate::UserEvent;
+use core::config::{ChartStyle, Units};
+use core::history::{History, Metric};
 use core::model::SystemOverviewInfo;
-use humansize::{BaseUnit, FormatSize, FormatSizeOptions, Kilo};
-use ratatui::widgets::{Block, Gauge};
+use humansize::FormatSize;
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Axis, Block, Cell, Chart, Gauge, GraphType, Row, Table};
 use tuirealm::{
     command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
     props::Layout,
     ratatui::{
         layout::{Constraint, Direction},
         prelude::Rect,
-        widgets::{Borders, Paragraph},
+        widgets::{Borders, Paragraph, Wrap},
     },
-    AttrValue, Attribute, Component, Event, Frame, MockComponent, NoUserEvent, Props, State,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
 };
 
+/// Component for displaying the summary of the system.
+///
+/// Controls:
+/// * l => shows the kernel tunables and the resource limits of the system with the use of the resources they bound
+/// * e/E => exports the charted load history into a CSV/JSON file
+/// * w/z/Z/Left/Right => changes, zooms and pans the period of time displayed by the charts, see `ChartWindow`
 #[derive(Default)]
 pub struct OverView {
     properties: Props,
     sysinfo: SystemOverviewInfo,
     /// Pre-calculated information for the top 3 used space drive
     disk_usage: String,
+    /// Prefixes of the displayed data sizes
+    units: Units,
+    /// How the series of the load chart are drawn
+    chart_style: ChartStyle,
+    /// The load averages over time
+    history: History,
+    /// The period of time displayed by the load chart
+    window: ChartWindow,
 }
 
+/// The load averages with their labels and chart colors, from the shortest period.
+const LOAD_SERIES: [(Metric, &str, Color); 3] = [
+    (Metric::LoadOneMinute, "1m", Color::LightCyan),
+    (Metric::LoadFiveMinutes, "5m", Color::LightBlue),
+    (Metric::LoadFifteenMinutes, "15m", Color::Magenta),
+];
+
+/// The load relative to the core count from which the system is considered busy.
+const BUSY_LOAD_RATIO: f64 = 0.7;
+
 impl OverView {
     /// Sets the system information during initalization of the component.
     pub fn with_system_info(mut self, system_info: SystemOverviewInfo) -> Self {
@@ -30,14 +63,26 @@ impl OverView {
         self
     }
 
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets how the series of the charts are drawn.
+    pub fn with_chart_style(mut self, chart_style: ChartStyle) -> Self {
+        self.chart_style = chart_style;
+        self
+    }
+
+    /// Sets the history which the load chart is drawn from.
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
     fn calculate_disk_usage_info(&self) -> String {
-        let format_opts = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .decimal_places(1)
-            .decimal_zeroes(0)
-            .kilo(humansize::Kilo::Binary)
-            .long_units(false)
-            .space_after_value(true);
+        let format_opts = size_format(self.units);
 
         let text = self
             .sysinfo
@@ -67,15 +112,6 @@ impl OverView {
 
 impl MockComponent for OverView {
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        if matches!(attr, Attribute::Custom(_)) {
-            let str = value.as_string().unwrap();
-            match SystemOverviewInfo::from_json(str) {
-                Ok(update) => {
-                    self.sysinfo = update;
-                }
-                Err(error) => eprintln!("Cannot convert SystemOverviewInfo from JSON: {}", error),
-            }
-        }
         self.properties.set(attr, value);
     }
 
@@ -101,11 +137,21 @@ impl MockComponent for OverView {
                 Constraint::Percentage(25),
             ])
             .chunks(area);
+        let system_chunk = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(&[
+                Constraint::Percentage(40),
+                Constraint::Percentage(20),
+                Constraint::Percentage(40),
+            ])
+            .chunks(chunks[0]);
         let cpu_memory_chunk = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(&[Constraint::Percentage(50), Constraint::Percentage(50)])
             .chunks(chunks[1]);
-        self.render_system_info(frame, chunks[0]);
+        self.render_system_info(frame, system_chunk[0]);
+        self.render_sessions(frame, system_chunk[1]);
+        self.render_top_processes(frame, system_chunk[2]);
         self.render_cpu_info(frame, cpu_memory_chunk[0]);
         self.render_memory_info(frame, cpu_memory_chunk[1]);
         self.render_disks_info(frame, chunks[2]);
@@ -113,9 +159,42 @@ impl MockComponent for OverView {
     }
 }
 
-impl Component<Message, NoUserEvent> for OverView {
-    fn on(&mut self, _event: Event<NoUserEvent>) -> Option<Message> {
-        None
+impl Component<Message, UserEvent> for OverView {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::OverView(update)) => {
+                self.history
+                    .record(Metric::LoadOneMinute, update.overview.load_one_minute);
+                self.history
+                    .record(Metric::LoadFiveMinutes, update.overview.load_five_minutes);
+                self.history.record(
+                    Metric::LoadFifteenMinutes,
+                    update.overview.load_fifteen_minutes,
+                );
+                self.sysinfo = *update;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('e'),
+                modifiers,
+            }) => {
+                let metrics = LOAD_SERIES.map(|(metric, _, _)| metric);
+                Some(
+                    self.window
+                        .export("load", &self.history, &metrics, modifiers),
+                )
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('l'),
+                ..
+            }) => Some(Message::ShowLimits),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('s'),
+                ..
+            }) => Some(Message::ShowSecurity),
+            Event::Keyboard(key) if self.window.on_key(&key) => Some(Message::Redraw),
+            _ => None,
+        }
     }
 }
 
@@ -176,13 +255,7 @@ impl OverView {
             .title("Mass storage")
             .title_alignment(ratatui::layout::Alignment::Left);
 
-        let format_size_options = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .decimal_places(1)
-            .decimal_zeroes(0)
-            .kilo(humansize::Kilo::Binary)
-            .long_units(false)
-            .space_after_value(true);
+        let format_size_options = size_format(self.units);
 
         let total_space: u64 = self.sysinfo.disks.disks.iter().map(|d| d.total_space).sum();
         let used_space: u64 = self.sysinfo.disks.disks.iter().map(|d| d.used_space).sum();
@@ -204,27 +277,23 @@ impl OverView {
 
         let top3_usage = Paragraph::new(self.disk_usage.clone());
 
-        let read_bytes_sum = self
+        let read_rate_sum = self
             .sysinfo
             .disks
             .disks
             .iter()
-            .map(|s| s.bytes_read)
-            .sum::<u64>();
-        let written_bytes_sum = self
+            .map(|s| s.read_rate)
+            .sum::<f64>();
+        let write_rate_sum = self
             .sysinfo
             .disks
             .disks
             .iter()
-            .map(|s| s.bytes_written)
-            .sum::<u64>();
-        let io_format_opts = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .kilo(Kilo::Binary)
-            .decimal_places(1)
-            .long_units(false);
-        let read_speed = (read_bytes_sum / 3).format_size(io_format_opts);
-        let write_speed = (written_bytes_sum / 3).format_size(io_format_opts);
+            .map(|s| s.write_rate)
+            .sum::<f64>();
+        let io_format_opts = size_format(self.units).space_after_value(false);
+        let read_speed = (read_rate_sum as u64).format_size(io_format_opts);
+        let write_speed = (write_rate_sum as u64).format_size(io_format_opts);
         let io_stat_text = format!("Read: {} /s Write: {} /s", read_speed, write_speed);
         let io_stat = Paragraph::new(io_stat_text);
 
@@ -235,12 +304,16 @@ impl OverView {
         frame.render_widget(io_stat, disks_area[3]);
     }
 
+    /// Renders the memory and swap statistics with the breakdown of the physical memory under them.
     fn render_memory_info(&self, frame: &mut Frame, area: Rect) {
+        let [text_area, bar_area] =
+            ratatui::layout::Layout::vertical([Constraint::Length(3), Constraint::Fill(1)])
+                .margin(1)
+                .areas(area);
         let memory_area = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(&[Constraint::Percentage(50), Constraint::Percentage(50)])
-            .margin(1)
-            .chunks(area);
+            .chunks(text_area);
 
         let block = tuirealm::ratatui::widgets::Block::default()
             .border_type(tuirealm::props::BorderType::Rounded)
@@ -248,13 +321,7 @@ impl OverView {
             .title("Memory")
             .title_alignment(ratatui::layout::Alignment::Left);
 
-        let format_size_options = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .decimal_places(1)
-            .decimal_zeroes(0)
-            .kilo(humansize::Kilo::Binary)
-            .long_units(false)
-            .space_after_value(true);
+        let format_size_options = size_format(self.units);
 
         let memory_text = format!(
             "Total: {}\nUsed: {}\nAvailable: {}\n",
@@ -285,8 +352,15 @@ impl OverView {
             Paragraph::new(memory_text).alignment(ratatui::layout::Alignment::Left);
         let swap_paragraph = Paragraph::new(swap_text).alignment(ratatui::layout::Alignment::Left);
         frame.render_widget(block, area);
+        let bar = Paragraph::new(memory_bar(
+            &self.sysinfo.memory,
+            bar_area.width,
+            format_size_options,
+        ))
+        .wrap(Wrap { trim: true });
         frame.render_widget(memory_paragraph, memory_area[0]);
         frame.render_widget(swap_paragraph, memory_area[1]);
+        frame.render_widget(bar, bar_area);
     }
 
     fn render_network_info(&self, frame: &mut Frame, area: Rect) {
@@ -300,12 +374,7 @@ impl OverView {
             .borders(Borders::ALL)
             .border_type(tuirealm::props::BorderType::Rounded);
 
-        let format_opts = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .kilo(Kilo::Binary)
-            .decimal_places(1)
-            .space_after_value(true)
-            .long_units(false);
+        let format_opts = size_format(self.units);
 
         let text = format!(
             "Interfaces: {}\nTotal received: {} Total packets received: {} Total errors on receive: {}\nTotal transmitted: {} Total packets transmitted: {} Total errors on transmitted: {}",
@@ -325,12 +394,8 @@ impl OverView {
         frame.render_widget(paragraph, network_area[0]);
     }
 
+    /// Renders the host details with the chart of the load averages under them.
     fn render_system_info(&self, frame: &mut Frame, area: Rect) {
-        let sysinfo_area = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(&[Constraint::Fill(1)])
-            .chunks(area);
-
         let block = tuirealm::ratatui::widgets::Block::default()
             .border_type(tuirealm::props::BorderType::Rounded)
             .borders(Borders::ALL)
@@ -338,19 +403,254 @@ impl OverView {
             .title_alignment(ratatui::layout::Alignment::Left);
 
         let uptime = format_uptime(self.sysinfo.overview.uptime);
-
+        // the agents older than the boot time only report the uptime
+        let boot = match self.sysinfo.overview.boot_time {
+            0 => format!("Uptime: {}", uptime),
+            boot_time => format!("Booted: {} (up {})", format_date_time(boot_time), uptime),
+        };
+
+        let overview = &self.sysinfo.overview;
+        let environment = match &overview.environment {
+            Some(environment) => format!(", {}", environment.describe()),
+            None => String::new(),
+        };
+        // the kernel release follows the name of the distribution, like `Linux (Debian GNU/Linux 12), kernel 6.1.0`
+        let system = match &overview.distribution {
+            Some(distribution) => {
+                let release = overview.kernel_version.rsplit(' ').next().unwrap_or_default();
+                format!("{}, kernel {}", distribution, release)
+            }
+            None => overview.kernel_version.clone(),
+        };
         let text = format!(
-            "Hostname: {}\nSystem: {}\nUptime: {}\nLoad average: 1m:{}% 5m:{}% 15m:{}%\n",
-            self.sysinfo.overview.host_name,
-            self.sysinfo.overview.kernel_version,
-            uptime,
-            self.sysinfo.overview.load_one_minute,
-            self.sysinfo.overview.load_five_minutes,
-            self.sysinfo.overview.load_fifteen_minutes
+            "Hostname: {}{}\nSystem: {}\n{}",
+            overview.host_name, environment, system, boot,
         );
+        let clock = self.clock_line();
+        let health = self.health_line();
+        let inner = block.inner(area);
+        let [text_area, clock_area, health_area, load_area, chart_area] =
+            ratatui::layout::Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Length(clock.as_ref().map_or(0, |_| 1)),
+                Constraint::Length(health.as_ref().map_or(0, |_| 1)),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas(inner);
 
-        let paragraph = Paragraph::new(text).block(block);
-        frame.render_widget(paragraph, sysinfo_area[0]);
+        frame.render_widget(block, area);
+        frame.render_widget(Paragraph::new(text), text_area);
+        if let Some(clock) = clock {
+            frame.render_widget(clock, clock_area);
+        }
+        if let Some(health) = health {
+            frame.render_widget(health, health_area);
+        }
+        self.render_load_average(frame, load_area, chart_area);
+    }
+
+    /// Returns the line of the time zone, the locale and the synchronization of the clock, `None` if it is not known,
+    /// like `Clock: Europe/Berlin +01:00, en_US.UTF-8, synced +0.4 ms`.
+    /// The unsynchronized or drifting clock is highlighted.
+    fn clock_line(&self) -> Option<Line<'static>> {
+        let clock = self.sysinfo.overview.clock.as_ref()?;
+        let mut settings = vec![format!(
+            "{} {}",
+            clock.timezone.as_deref().unwrap_or("UTC"),
+            clock.format_utc_offset()
+        )];
+        settings.extend(clock.locale.clone());
+        let mut spans = vec![Span::raw(format!("Clock: {}", settings.join(", ")))];
+
+        let mut sync = match clock.synchronized {
+            Some(true) => vec!["synced".to_string()],
+            Some(false) => vec!["not synced".to_string()],
+            None => Vec::new(),
+        };
+        if let Some(offset) = &clock.offset {
+            sync.push(format!("{:+.1} ms", offset.offset));
+        }
+        if !sync.is_empty() {
+            let style = match clock.is_drifting() {
+                true => Style::default().light_red().bold(),
+                false => Style::default(),
+            };
+            spans.push(Span::raw(", "));
+            spans.push(Span::styled(sync.join(" "), style));
+        }
+        Some(Line::from(spans))
+    }
+
+    /// Returns the line of the available entropy and the package updates, `None` if neither of them is known.
+    /// The low entropy is highlighted, like the pending updates.
+    fn health_line(&self) -> Option<Line<'static>> {
+        let overview = &self.sysinfo.overview;
+        let mut spans = Vec::new();
+        if let Some(entropy) = &overview.entropy {
+            let style = match entropy.is_low() {
+                true => Style::default().light_red().bold(),
+                false => Style::default(),
+            };
+            spans.push(Span::raw("Entropy: "));
+            spans.push(Span::styled(entropy.available.to_string(), style));
+            spans.push(Span::raw(format!(" of {} bits", entropy.pool_size)));
+        }
+        if let Some(packages) = &overview.packages {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            let installed = packages
+                .installed
+                .map_or_else(|| "N/A".to_string(), |installed| installed.to_string());
+            spans.push(Span::raw(format!("Packages: {}", installed)));
+            if let Some(updates) = packages.updates {
+                let style = match updates {
+                    0 => Style::default(),
+                    _ => Style::default().yellow(),
+                };
+                spans.push(Span::raw(", "));
+                spans.push(Span::styled(format!("{} updates", updates), style));
+            }
+        }
+        (!spans.is_empty()).then(|| Line::from(spans))
+    }
+
+    /// Renders the current load averages in `text_area` and their history in `chart_area`.
+    /// The loads are drawn relative to the core count, so the top of the chart is a fully loaded system
+    /// unless the load exceeds the cores.
+    fn render_load_average(&self, frame: &mut Frame, text_area: Rect, chart_area: Rect) {
+        let cores = self.sysinfo.cpu.core_count.max(1) as f64;
+        let overview = &self.sysinfo.overview;
+        let loads = [
+            overview.load_one_minute,
+            overview.load_five_minutes,
+            overview.load_fifteen_minutes,
+        ];
+
+        let mut spans = vec![Span::raw("Load average:")];
+        for ((_, label, color), load) in LOAD_SERIES.iter().zip(loads) {
+            let style = match load / cores {
+                ratio if ratio > 1.0 => Style::default().light_red().bold(),
+                ratio if ratio > BUSY_LOAD_RATIO => Style::default().yellow(),
+                _ => Style::default(),
+            };
+            spans.push(Span::styled(
+                format!(" {}", label),
+                Style::default().fg(*color),
+            ));
+            spans.push(Span::styled(format!(" {:.2}", load), style));
+        }
+        spans.push(Span::raw(format!(" / {} cores", self.sysinfo.cpu.core_count)).gray());
+        frame.render_widget(Line::from(spans), text_area);
+
+        if chart_area.height < 2 {
+            return;
+        }
+        let series = LOAD_SERIES
+            .iter()
+            .map(|(metric, _, color)| {
+                let points = self
+                    .window
+                    .points(&self.history, metric)
+                    .into_iter()
+                    .map(|(x, load)| (x, load / cores * 100.0))
+                    .collect::<Vec<(f64, f64)>>();
+                (points, *color)
+            })
+            .collect::<Vec<(Vec<(f64, f64)>, Color)>>();
+        let top = series
+            .iter()
+            .flat_map(|(points, _)| points.iter().map(|(_, percent)| *percent))
+            .fold(100.0, f64::max);
+        let [start, end] = self.window.bounds();
+        // the load of every core is marked, above it the processes are waiting for a core
+        let full_load = [(start, 100.0), (end, 100.0)];
+
+        let mut datasets = vec![chart_dataset(self.chart_style)
+            .graph_type(GraphType::Line)
+            .dark_gray()
+            .data(&full_load)];
+        datasets.extend(series.iter().map(|(points, color)| {
+            chart_dataset(self.chart_style)
+                .style(Style::default().fg(*color))
+                .data(points)
+        }));
+        let chart = Chart::new(datasets)
+            .x_axis(Axis::default().bounds(self.window.bounds()))
+            .y_axis(Axis::default().bounds([0.0, top]));
+        render_chart(frame, chart, chart_area, self.chart_style);
+    }
+
+    /// Renders the logged in users with their terminals and the hosts they are logged in from.
+    fn render_sessions(&self, frame: &mut Frame, area: Rect) {
+        let sessions = &self.sysinfo.sessions;
+        let block = Block::default()
+            .border_type(tuirealm::props::BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(format!("Users ({})", sessions.len()))
+            .title_alignment(ratatui::layout::Alignment::Left);
+
+        let rows = sessions.iter().map(|session| {
+            Row::new(vec![
+                Cell::from(session.user.clone()),
+                Cell::from(session.tty.clone()),
+                Cell::from(session.host.clone().unwrap_or_default()).gray(),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(1)
+        .block(block);
+        frame.render_widget(table, area);
+    }
+
+    /// Renders the biggest CPU consumers in the left side and the biggest memory consumers in the right side.
+    fn render_top_processes(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .border_type(tuirealm::props::BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title("Top processes")
+            .title_alignment(ratatui::layout::Alignment::Left);
+        let tables_area = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(&[Constraint::Percentage(50), Constraint::Percentage(50)])
+            .margin(1)
+            .chunks(area);
+
+        let format_size_options = size_format(self.units);
+
+        let top = &self.sysinfo.top_processes;
+        let cpu_rows = top.by_cpu.iter().map(|process| {
+            Row::new(vec![
+                Cell::from(process.name.clone()),
+                Cell::from(format!("{:.1}%", process.cpu_usage)),
+            ])
+        });
+        let memory_rows = top.by_memory.iter().map(|process| {
+            Row::new(vec![
+                Cell::from(process.name.clone()),
+                Cell::from(process.memory.format_size(format_size_options)),
+            ])
+        });
+
+        let widths = [Constraint::Fill(1), Constraint::Length(10)];
+        let cpu_table = Table::new(cpu_rows, widths)
+            .header(Row::new(vec!["name", "cpu"]).bold())
+            .column_spacing(1);
+        let memory_table = Table::new(memory_rows, widths)
+            .header(Row::new(vec!["name", "mem"]).bold())
+            .column_spacing(1);
+
+        frame.render_widget(block, area);
+        frame.render_widget(cpu_table, tables_area[0]);
+        frame.render_widget(memory_table, tables_area[1]);
     }
 }
 
@@ -0,0 +1,514 @@
+use super::{format_compact_duration, format_date_time, size_format};
+use crate::{Message, UserEvent};
+use core::config::Units;
+use core::model::ProcessInfo;
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Row, Table, TableState, Tabs, Wrap},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+const NOT_AVAILABLE: &str = "N/A";
+
+/// The percentage of the limit of the open files above which the open files are highlighted.
+const OPEN_FILES_WARNING: f64 = 80.0;
+
+/// The pages of the pane, switched between with the Tab key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Page {
+    Details,
+    OpenFiles,
+    MemoryMap,
+}
+
+/// Pane displaying the details of a single process over the process list,
+/// like its command line, environment variables and working directory.
+///
+/// The memory map breaks down the resident memory of the process into the memory shared with other processes,
+/// like the loaded libraries, and its private memory. The proportional memory divides the shared pages
+/// between the processes sharing them, so it is the real cost of the process, unlike the resident memory.
+///
+/// Controls:
+/// * Tab => switches between the details, the open files including the sockets, and the memory map
+/// * Up/Down => scrolls the details, the open files or the memory map,
+///   or moves the selection of the threads while they are expanded
+/// * PageUp/PageDown => scrolls by a screen of lines
+/// * t => expands or collapses the table of the threads
+/// * Enter => closes the pane
+///
+/// The threads, the open files and the memory map are only listed on Linux.
+pub struct ProcessDetails {
+    properties: Props,
+
+    process: ProcessInfo,
+
+    /// Index of the first displayed line
+    scroll: u16,
+
+    /// Count of the lines fitting into the pane at the last rendering
+    page_size: u16,
+
+    /// The displayed page
+    page: Page,
+
+    /// Holds the selected open file
+    file_state: TableState,
+
+    /// Holds the selected memory region
+    region_state: TableState,
+
+    /// True while the table of the threads is displayed under the details
+    threads_expanded: bool,
+
+    /// Holds the selected thread
+    thread_state: TableState,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+
+    /// Displays the running time and the CPU time in seconds instead of compactly
+    exact_durations: bool,
+}
+
+impl ProcessDetails {
+    pub fn new(process: ProcessInfo) -> Self {
+        ProcessDetails {
+            properties: Props::default(),
+            process,
+            scroll: 0,
+            page_size: 0,
+            page: Page::Details,
+            file_state: TableState::default(),
+            region_state: TableState::default(),
+            threads_expanded: false,
+            thread_state: TableState::default(),
+            units: Units::default(),
+            exact_durations: false,
+        }
+    }
+
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Displays the running time and the CPU time in seconds, instead of like `2d 3h` or `14:32`.
+    pub fn with_exact_durations(mut self, exact_durations: bool) -> Self {
+        self.exact_durations = exact_durations;
+        self
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        let opts = size_format(self.units).space_after_value(false);
+        let process = &self.process;
+        let field = |name: &'static str, value: String| {
+            Line::from(vec![
+                Span::from(format!("{:<14}", name)).bold(),
+                Span::from(value),
+            ])
+        };
+        let or_not_available = |value: Option<String>| value.unwrap_or(NOT_AVAILABLE.to_string());
+
+        let mut lines = vec![
+            field(
+                "Parent:",
+                or_not_available(process.parent.map(|pid| pid.to_string())),
+            ),
+            field("User:", process.username.clone()),
+            field(
+                "Nice:",
+                or_not_available(process.nice.map(|nice| nice.to_string())),
+            ),
+            field(
+                "Started:",
+                or_not_available(process.start_time.map(format_date_time)),
+            ),
+            field(
+                "Running for:",
+                format_compact_duration(process.running_time, self.exact_durations),
+            ),
+            field(
+                "CPU time:",
+                // the CPU time is measured in milliseconds
+                format_compact_duration(process.cpu_time / 1000, self.exact_durations),
+            ),
+            field(
+                "Memory:",
+                format!(
+                    "{} resident ({:.1}% of the system), {} virtual{}",
+                    process.memory.format_size(opts),
+                    process.memory_share,
+                    process.virtual_memory.format_size(opts),
+                    process.swap.map_or(String::new(), |swap| format!(
+                        ", {} swapped",
+                        swap.format_size(opts)
+                    ))
+                ),
+            ),
+            field(
+                "OOM score:",
+                or_not_available(process.oom_score.map(|score| match process.oom_score_adj {
+                    Some(adjustment) if adjustment != 0 => {
+                        format!("{} (adjusted by {:+})", score, adjustment)
+                    }
+                    _ => score.to_string(),
+                })),
+            ),
+            field(
+                "Cores:",
+                or_not_available(process.affinity.as_ref().map(|cores| {
+                    let cores: Vec<String> = cores.iter().map(|core| core.to_string()).collect();
+                    cores.join(", ")
+                })),
+            ),
+            field(
+                "Threads:",
+                or_not_available(process.threads.map(|threads| {
+                    match (&process.thread_list, self.threads_expanded) {
+                        (Some(_), false) => format!("{} (t: expand)", threads),
+                        _ => threads.to_string(),
+                    }
+                })),
+            ),
+            match (process.open_files, process.open_files_limit) {
+                (Some(files), Some(limit)) if limit > 0 => {
+                    let usage = files as f64 / limit as f64 * 100.0;
+                    let line = field(
+                        "Open files:",
+                        format!("{} of {} allowed ({:.0}%)", files, limit, usage),
+                    );
+                    match usage >= OPEN_FILES_WARNING {
+                        true => line.light_red(),
+                        false => line,
+                    }
+                }
+                (files, _) => field(
+                    "Open files:",
+                    or_not_available(files.map(|files| files.to_string())),
+                ),
+            },
+            field("Executable:", process.command.clone()),
+            field("Working dir:", or_not_available(process.cwd.clone())),
+            field("Cgroup:", or_not_available(process.cgroup.clone())),
+            field(
+                "Command line:",
+                or_not_available(process.command_line.as_ref().map(|args| args.join(" "))),
+            ),
+            Line::default(),
+            Line::from("Environment:").bold(),
+        ];
+        match &process.environment {
+            Some(environment) if !environment.is_empty() => lines.extend(
+                environment
+                    .iter()
+                    .map(|variable| Line::from(variable.as_str())),
+            ),
+            _ => lines.push(Line::from(NOT_AVAILABLE).italic()),
+        }
+        lines
+    }
+}
+
+/// Moves the selection of a table with `count` rows down by `step` rows, stopping at the last row.
+fn select_next(state: &mut TableState, step: usize, count: usize) {
+    if count > 0 {
+        let next = state.selected().map_or(0, |i| i.saturating_add(step));
+        state.select(Some(next.min(count - 1)));
+    }
+}
+
+impl MockComponent for ProcessDetails {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let title = format!(" {} ({}) ", self.process.name, self.process.pid);
+        let block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Tab: switch page  ↑↓: scroll  Enter: close ").centered())
+            .cyan();
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [tabs_area, page_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        let selected = match self.page {
+            Page::Details => 0,
+            Page::OpenFiles => 1,
+            Page::MemoryMap => 2,
+        };
+        let tabs = Tabs::new(vec!["Details", "Open files", "Memory map"])
+            .select(selected)
+            .highlight_style(Style::default().bold().reversed());
+        frame.render_widget(tabs, tabs_area);
+
+        match self.page {
+            Page::Details => self.render_details(frame, page_area),
+            Page::OpenFiles => self.render_open_files(frame, page_area),
+            Page::MemoryMap => self.render_memory_map(frame, page_area),
+        }
+    }
+}
+
+impl ProcessDetails {
+    fn render_details(&mut self, frame: &mut Frame, inner: Rect) {
+        let threads = self
+            .process
+            .thread_list
+            .as_ref()
+            .filter(|_| self.threads_expanded);
+        let (details_area, threads_area) = match threads {
+            Some(_) => {
+                let [details, threads] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+                (details, Some(threads))
+            }
+            None => (inner, None),
+        };
+
+        self.page_size = details_area.height;
+        let details = Paragraph::new(self.lines())
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(details, details_area);
+
+        if let (Some(threads), Some(threads_area)) = (threads, threads_area) {
+            let rows = threads.iter().map(|thread| {
+                Row::new(vec![
+                    thread.tid.to_string(),
+                    thread.name.clone(),
+                    thread.state.clone(),
+                    format!("{:.1}%", thread.cpu_usage),
+                ])
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(8),
+                    Constraint::Fill(1),
+                    Constraint::Length(12),
+                    Constraint::Length(8),
+                ],
+            )
+            .header(Row::new(vec!["tid", "name", "state", "cpu"]).bold())
+            .row_highlight_style(Style::default().reversed())
+            .block(
+                Block::bordered()
+                    .title(format!(" Threads ({}) ", threads.len()))
+                    .title_bottom(Line::from(" t: collapse ").centered()),
+            );
+            frame.render_stateful_widget(table, threads_area, &mut self.thread_state);
+        }
+    }
+
+    fn render_open_files(&mut self, frame: &mut Frame, area: Rect) {
+        self.page_size = area.height.saturating_sub(1);
+        let Some(descriptors) = &self.process.descriptors else {
+            frame.render_widget(Paragraph::new(NOT_AVAILABLE).italic(), area);
+            return;
+        };
+
+        let rows = descriptors.iter().map(|descriptor| {
+            // the sockets are described by their addresses instead of their inode
+            let name = match &descriptor.socket {
+                Some(socket) if socket.remote.is_empty() => {
+                    format!("{} {} {}", socket.protocol, socket.local, socket.state)
+                }
+                Some(socket) => format!(
+                    "{} {} -> {} {}",
+                    socket.protocol, socket.local, socket.remote, socket.state
+                ),
+                None => descriptor.path.clone(),
+            };
+            Row::new(vec![
+                descriptor.fd.to_string(),
+                descriptor.kind.name().to_string(),
+                descriptor.mode.clone(),
+                name,
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(vec!["fd", "type", "mode", "name"]).bold())
+        .row_highlight_style(Style::default().reversed());
+        frame.render_stateful_widget(table, area, &mut self.file_state);
+    }
+
+    /// Renders the totals of the memory map over the table of the regions.
+    fn render_memory_map(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(memory_map) = &self.process.memory_map else {
+            frame.render_widget(Paragraph::new(NOT_AVAILABLE).italic(), area);
+            return;
+        };
+        let opts = size_format(self.units).space_after_value(false);
+        let size = |bytes: u64| bytes.format_size(opts);
+
+        let [totals_area, regions_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+        let total = &memory_map.total;
+        let totals = vec![
+            Line::from(vec![
+                Span::from("Resident: ").bold(),
+                Span::from(format!("{}  ", size(total.rss))),
+                Span::from("Proportional: ").bold(),
+                Span::from(format!("{}  ", size(total.pss))),
+                Span::from("Swap: ").bold(),
+                Span::from(size(total.swap)),
+            ]),
+            Line::from(vec![
+                Span::from("Shared: ").bold(),
+                Span::from(format!(
+                    "{} ({} dirty)  ",
+                    size(total.shared()),
+                    size(total.shared_dirty)
+                )),
+                Span::from("Private: ").bold(),
+                Span::from(format!(
+                    "{} ({} dirty)",
+                    size(total.private()),
+                    size(total.private_dirty)
+                )),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(totals), totals_area);
+
+        self.page_size = regions_area.height.saturating_sub(1);
+        let rows = memory_map.regions.iter().map(|region| {
+            let usage = &region.usage;
+            Row::new(vec![
+                format!("{:x}", region.start),
+                region.permissions.clone(),
+                size(region.size()),
+                size(usage.rss),
+                size(usage.pss),
+                size(usage.shared()),
+                size(usage.private()),
+                size(usage.swap),
+                region.path.clone(),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(5),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "address", "perm", "size", "rss", "pss", "shared", "private", "swap", "mapping",
+            ])
+            .bold(),
+        )
+        .row_highlight_style(Style::default().reversed());
+        frame.render_stateful_widget(table, regions_area, &mut self.region_state);
+    }
+
+    /// Returns the selection and the count of the rows of the table on the displayed page,
+    /// or `None` on the details page, which is scrolled instead.
+    fn page_table(&mut self) -> Option<(&mut TableState, usize)> {
+        match self.page {
+            Page::Details => None,
+            Page::OpenFiles => Some((
+                &mut self.file_state,
+                self.process.descriptors.as_ref().map_or(0, Vec::len),
+            )),
+            Page::MemoryMap => Some((
+                &mut self.region_state,
+                self.process
+                    .memory_map
+                    .as_ref()
+                    .map_or(0, |memory_map| memory_map.regions.len()),
+            )),
+        }
+    }
+}
+
+impl Component<Message, UserEvent> for ProcessDetails {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let thread_count = self.process.thread_list.as_ref().map_or(0, Vec::len);
+                let on_details = self.page == Page::Details;
+                let page_size = self.page_size as usize;
+                if let (Some((state, count)), Key::Up | Key::Down | Key::PageUp | Key::PageDown) =
+                    (self.page_table(), code)
+                {
+                    match code {
+                        Key::Up => state.select_previous(),
+                        Key::Down => select_next(state, 1, count),
+                        Key::PageUp => {
+                            let previous = state.selected().unwrap_or_default();
+                            state.select(Some(previous.saturating_sub(page_size)));
+                        }
+                        _ => select_next(state, page_size, count),
+                    }
+                    return Some(Message::Redraw);
+                }
+                match code {
+                    Key::Enter => return Some(Message::CloseProcessDetails),
+                    Key::Tab => {
+                        self.page = match self.page {
+                            Page::Details => Page::OpenFiles,
+                            Page::OpenFiles => Page::MemoryMap,
+                            Page::MemoryMap => Page::Details,
+                        };
+                    }
+                    Key::Char('t') if thread_count > 0 && on_details => {
+                        self.threads_expanded = !self.threads_expanded;
+                        self.thread_state.select(Some(0));
+                    }
+                    Key::Up if self.threads_expanded => self.thread_state.select_previous(),
+                    Key::Down if self.threads_expanded => {
+                        select_next(&mut self.thread_state, 1, thread_count)
+                    }
+                    Key::Up => self.scroll = self.scroll.saturating_sub(1),
+                    Key::Down => self.scroll = self.scroll.saturating_add(1),
+                    Key::PageUp => self.scroll = self.scroll.saturating_sub(self.page_size),
+                    Key::PageDown => self.scroll = self.scroll.saturating_add(self.page_size),
+                    _ => return None,
+                }
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
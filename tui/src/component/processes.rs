@@ -1,37 +1,1090 @@
-use core::model::{process_list_from_json, ProcessList};
+use core::config::Units;
+use core::model::{
+    ProcessColumn, ProcessDiff, ProcessGroup, ProcessGrouping, ProcessInfo, ProcessList,
+    ProcessState, SortOrder,
+};
+use core::{ProcessSignal, NICE_RANGE};
 
-use humansize::{BaseUnit, FormatSize, FormatSizeOptions};
+use humansize::{FormatSize, FormatSizeOptions};
 use ratatui::{
     layout::{Alignment, Constraint, Flex},
-    widgets::{Block, Cell, Row, Table},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Clear, List, ListState, Paragraph, Row, Table, TableState, Wrap},
 };
 use tuirealm::{
     command::{Cmd, CmdResult},
+    event::{Key, KeyEvent, KeyModifiers},
     ratatui::prelude::Rect,
-    AttrValue, Attribute, Component, Event, Frame, MockComponent, NoUserEvent, Props, State,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
 };
 
-use crate::Message;
+use super::{centered_rect, format_compact_duration, format_date_time, size_format, MODAL};
+use crate::{Message, UserEvent};
+
+/// The columns of the process table in their default order of appearance, with their header titles.
+const COLUMNS: [(ProcessColumn, &str); 21] = [
+    (ProcessColumn::Pid, "pid"),
+    (ProcessColumn::Name, "name"),
+    (ProcessColumn::Memory, "mem"),
+    (ProcessColumn::MemoryShare, "mem%"),
+    (ProcessColumn::VirtualMemory, "virtmem"),
+    (ProcessColumn::Swap, "swap"),
+    (ProcessColumn::OomScore, "oom"),
+    (ProcessColumn::CpuUsage, "cpu"),
+    (ProcessColumn::AverageCpuUsage, "avg cpu"),
+    (ProcessColumn::CpuTime, "cputime"),
+    (ProcessColumn::User, "user"),
+    (ProcessColumn::RunningTime, "runtime"),
+    (ProcessColumn::Threads, "threads"),
+    (ProcessColumn::State, "state"),
+    (ProcessColumn::Nice, "nice"),
+    (ProcessColumn::StartTime, "started"),
+    (ProcessColumn::DiskRead, "disk r/s"),
+    (ProcessColumn::DiskWrite, "disk w/s"),
+    (ProcessColumn::NetReceive, "net rx/s"),
+    (ProcessColumn::NetTransmit, "net tx/s"),
+    (ProcessColumn::Command, "command"),
+];
+
+/// The columns which are only displayed when they are picked.
+const OPTIONAL_COLUMNS: [ProcessColumn; 6] = [
+    ProcessColumn::Threads,
+    ProcessColumn::Nice,
+    ProcessColumn::StartTime,
+    ProcessColumn::AverageCpuUsage,
+    ProcessColumn::Swap,
+    ProcessColumn::OomScore,
+];
+
+/// Count of the processes with the highest OOM scores, highlighted as the likely next victims of the OOM killer.
+const OOM_VICTIMS: usize = 3;
+
+/// The columns of the table of the process groups, with their header titles.
+/// The title of the name column is replaced by the field which the processes are grouped by.
+const GROUP_COLUMNS: [(ProcessColumn, &str); 9] = [
+    (ProcessColumn::Name, "name"),
+    (ProcessColumn::Pid, "count"),
+    (ProcessColumn::Memory, "mem"),
+    (ProcessColumn::MemoryShare, "mem%"),
+    (ProcessColumn::VirtualMemory, "virtmem"),
+    (ProcessColumn::CpuUsage, "cpu"),
+    (ProcessColumn::CpuTime, "cputime"),
+    (ProcessColumn::DiskRead, "disk r/s"),
+    (ProcessColumn::DiskWrite, "disk w/s"),
+];
+
+/// The widest a column can be made by its values, except the command which takes the remaining space.
+const MAX_COLUMN_WIDTH: usize = 32;
 
 /// Component for displaying process list in a table style.
+///
+/// Controls:
+/// * c => sorts the table by CPU usage
+/// * m => sorts the table by memory usage
+/// * % => sorts the table by the share of the system memory
+/// * p => sorts the table by pid
+/// * n => sorts the table by name
+/// * r => sorts the table by disk read rate
+/// * w => sorts the table by disk write rate
+/// * i => sorts the table by network receive rate
+/// * o => sorts the table by network transmit rate
+/// * b => sorts the table by OOM score, the badness the kernel picks the process to kill by when the memory runs out
+/// * Up/Down => moves the selection
+/// * PageUp/PageDown => moves the selection by a screen of rows
+/// * Home/End => selects the first or the last process
+/// * k/Delete => opens a dialog for terminating the selected process
+/// * s => opens a dialog for sending a signal to the selected process, like SIGSTOP or SIGCONT
+/// * F7/F8 => opens a dialog for raising or lowering the priority of the selected process
+/// * f => opens a dialog for pinning the selected process to specific cores
+/// * v => opens a dialog for picking the displayed columns and their order,
+///   like the optional threads, nice and start time columns
+/// * x => hides the sleeping and idle processes, or shows them again
+/// * u => switches the CPU column between the current usage and the usage averaged over the lifetime of the processes,
+///   which reveals the total cost of the long-running jobs
+/// * g => groups the processes by user, then by application, then by cgroup, then ungroups them.
+///   The groups sum the resource usage of their processes, p sorts them by the count of their processes.
+/// * Enter => shows the details of the selected process, like its command line and environment
+/// * / => starts searching, the typed text filters the processes by name, command and username.
+///   Enter finishes the typing, while the filter stays active. Searching for an empty text removes the filter.
+///
+/// Pressing the key of the currently sorted column toggles between ascending and descending order.
+///
+/// The processes started since the previous update are highlighted in green,
+/// the exited ones are kept in faded red until the next update, which makes the bursts of short-lived processes visible.
+/// The OOM scores of the likely next victims of the OOM killer are highlighted in red.
 #[derive(Default)]
 pub struct Processes {
     properties: Props,
 
+    /// Every process of the last update
     list: ProcessList,
+
+    /// The processes started and exited since the update before the last one
+    churn: ProcessDiff,
+
+    /// The processes displayed in the table, matching the search filter
+    visible: ProcessList,
+
+    /// The text which the processes are filtered by
+    filter: String,
+
+    /// True while the search filter is being typed
+    searching: bool,
+
+    /// True while only the processes which are not sleeping are displayed
+    hide_sleeping: bool,
+
+    /// True while the CPU column displays the usage averaged over the lifetime of the processes
+    average_cpu: bool,
+
+    /// Holds the selected row
+    table_state: TableState,
+
+    /// The pid of the selected process, which stays selected when the rows are reordered
+    selected_pid: Option<u32>,
+
+    /// Count of the rows fitting into the table at the last rendering
+    page_size: usize,
+
+    /// The process waiting for the confirmation of its termination
+    kill_dialog: Option<KillDialog>,
+
+    /// The process waiting for a signal to be picked
+    signal_dialog: Option<SignalDialog>,
+
+    /// The process waiting for its new nice value
+    renice_dialog: Option<ReniceDialog>,
+
+    /// The field which the processes are grouped by, they are listed one by one if `None`
+    grouping: Option<ProcessGrouping>,
+
+    /// The groups of the visible processes, while they are grouped
+    groups: Vec<ProcessGroup>,
+
+    /// The process waiting for the cores it is pinned to
+    affinity_dialog: Option<AffinityDialog>,
+
+    /// The columns being picked
+    column_dialog: Option<ColumnDialog>,
+
+    /// Count of the cores which the processes can be pinned to
+    core_count: usize,
+
+    /// The column which the table is sorted by
+    sort_column: ProcessColumn,
+
+    sort_order: SortOrder,
+
+    /// The displayed columns in the order of their appearance, the default columns are displayed if empty
+    columns: Vec<ProcessColumn>,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+
+    /// Displays the running time and the CPU time in seconds instead of compactly
+    exact_durations: bool,
+
+    /// The pids of the processes which the OOM killer would most likely pick next, from the most likely
+    oom_victims: Vec<u32>,
 }
 
-impl MockComponent for Processes {
-    fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        if matches!(attr, Attribute::Value) {
-            if let Some(json) = value.as_string() {
-                if let Ok(process_list) = process_list_from_json(json) {
-                    self.list = process_list;
+/// Confirmation dialog for terminating a process.
+struct KillDialog {
+    pid: u32,
+    name: String,
+}
+
+/// Dialog for picking the signal sent to a process.
+struct SignalDialog {
+    pid: u32,
+    name: String,
+    /// Holds the index of the selected signal in `ProcessSignal::ALL`
+    list_state: ListState,
+}
+
+/// Dialog for changing the nice value of a process.
+struct ReniceDialog {
+    pid: u32,
+    name: String,
+    /// The current nice value, unknown on the platforms without nice values
+    current: Option<i32>,
+    /// The nice value applied on confirmation
+    nice: i32,
+}
+
+/// Dialog for picking the cores which a process is allowed to run on.
+struct AffinityDialog {
+    pid: u32,
+    name: String,
+    /// True for every core which the process is allowed to run on, by the index of the core
+    cores: Vec<bool>,
+    /// Holds the index of the selected core
+    list_state: ListState,
+}
+
+/// Dialog for picking the displayed columns and their order.
+struct ColumnDialog {
+    /// Every column in the order of their appearance, true for the displayed ones
+    columns: Vec<(ProcessColumn, bool)>,
+    /// Holds the index of the selected column
+    list_state: ListState,
+}
+
+impl Processes {
+    /// Sets the displayed columns in the order of their appearance.
+    /// The default columns are displayed if `columns` is empty.
+    pub fn with_columns(mut self, columns: Vec<ProcessColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Displays the running time and the CPU time in seconds, instead of like `2d 3h` or `14:32`.
+    pub fn with_exact_durations(mut self, exact_durations: bool) -> Self {
+        self.exact_durations = exact_durations;
+        self
+    }
+
+    /// Sets the count of the cores which the processes can be pinned to.
+    pub fn with_core_count(mut self, core_count: usize) -> Self {
+        self.core_count = core_count;
+        self
+    }
+
+    /// Returns the displayed columns with their header titles.
+    fn visible_columns(&self) -> Vec<(ProcessColumn, &'static str)> {
+        let columns: Vec<ProcessColumn> = if self.columns.is_empty() {
+            // the network rates are only known while the traffic of the processes is accounted
+            let accounted = self
+                .list
+                .iter()
+                .any(|process| process.net_received_rate.is_some());
+            COLUMNS
+                .iter()
+                .map(|(column, _)| *column)
+                .filter(|column| {
+                    !OPTIONAL_COLUMNS.contains(column)
+                        && (accounted
+                            || !matches!(
+                                column,
+                                ProcessColumn::NetReceive | ProcessColumn::NetTransmit
+                            ))
+                })
+                .collect()
+        } else {
+            self.columns.clone()
+        };
+
+        columns
+            .iter()
+            // the CPU column displays the average usage while it is switched to it
+            .map(|column| match column {
+                ProcessColumn::CpuUsage => self.cpu_column(),
+                _ => *column,
+            })
+            .filter_map(|column| COLUMNS.iter().find(|(c, _)| *c == column).copied())
+            .collect()
+    }
+
+    /// Returns the column of the CPU usage, which is either the current or the average usage.
+    fn cpu_column(&self) -> ProcessColumn {
+        if self.average_cpu {
+            ProcessColumn::AverageCpuUsage
+        } else {
+            ProcessColumn::CpuUsage
+        }
+    }
+
+    /// Switches the CPU column between the current and the average usage, keeping the table sorted by it.
+    fn toggle_average_cpu(&mut self) {
+        let previous = self.cpu_column();
+        self.average_cpu = !self.average_cpu;
+        if self.sort_column == previous {
+            self.sort_column = self.cpu_column();
+            self.list.sort(self.sort_column, self.sort_order);
+            self.update_visible();
+        }
+    }
+
+    /// Formats the value of `column` for `group`.
+    fn group_text(
+        &self,
+        group: &ProcessGroup,
+        column: ProcessColumn,
+        opts: FormatSizeOptions,
+    ) -> String {
+        match column {
+            ProcessColumn::Pid => group.count.to_string(),
+            ProcessColumn::Memory => group.memory.format_size(opts),
+            ProcessColumn::VirtualMemory => group.virtual_memory.format_size(opts),
+            ProcessColumn::MemoryShare => format!("{:.1}%", group.memory_share),
+            ProcessColumn::CpuUsage => format!("{:.1}%", group.cpu_usage),
+            ProcessColumn::CpuTime => {
+                format_compact_duration(group.cpu_time / 1000, self.exact_durations)
+            }
+            ProcessColumn::DiskRead => (group.read_rate as u64).format_size(opts),
+            ProcessColumn::DiskWrite => (group.write_rate as u64).format_size(opts),
+            _ => group.name.clone(),
+        }
+    }
+
+    /// Creates the header row of the table, marking the column which the table is sorted by.
+    fn header(&self, columns: &[(ProcessColumn, &'static str)]) -> Row<'static> {
+        Row::new(columns.iter().map(|(column, title)| {
+            // the name column of the groups holds the username too
+            let sorted = *column == self.sort_column
+                || (self.grouping.is_some()
+                    && *column == ProcessColumn::Name
+                    && self.sort_column == ProcessColumn::User);
+            if sorted {
+                let indicator = match self.sort_order {
+                    SortOrder::Ascending => '▲',
+                    SortOrder::Descending => '▼',
+                };
+                Cell::from(format!("{} {}", title, indicator).bold())
+            } else {
+                Cell::from(*title)
+            }
+        }))
+    }
+
+    /// Switches from the single processes to the groups by user, then by application, then by cgroup, then back.
+    fn toggle_grouping(&mut self) {
+        self.grouping = match self.grouping {
+            None => Some(ProcessGrouping::User),
+            Some(ProcessGrouping::User) => Some(ProcessGrouping::Application),
+            Some(ProcessGrouping::Application) => Some(ProcessGrouping::Cgroup),
+            Some(ProcessGrouping::Cgroup) => None,
+        };
+        self.update_visible();
+    }
+
+    /// Returns the count of the rows in the table.
+    fn row_count(&self) -> usize {
+        match self.grouping {
+            Some(_) => self.groups.len(),
+            None => self.visible.len(),
+        }
+    }
+
+    /// Creates the cell displaying the value of `column` for `process`,
+    /// highlighting the search filter in the searched columns.
+    fn cell<'a>(
+        &self,
+        process: &'a ProcessInfo,
+        column: ProcessColumn,
+        opts: FormatSizeOptions,
+    ) -> Cell<'a> {
+        match column {
+            ProcessColumn::Name => Cell::from(highlight(&process.name, &self.filter)),
+            ProcessColumn::User => Cell::from(highlight(&process.username, &self.filter)),
+            ProcessColumn::Command => Cell::from(highlight(&process.command, &self.filter)),
+            ProcessColumn::OomScore if self.oom_victims.contains(&process.pid) => {
+                Cell::from(process_text(process, column, opts, self.exact_durations))
+                    .red()
+                    .bold()
+            }
+            _ => Cell::from(process_text(process, column, opts, self.exact_durations)),
+        }
+    }
+
+    /// Selects the row at `index`, clamped inside the table.
+    fn select(&mut self, index: usize) {
+        if self.row_count() == 0 {
+            self.table_state.select(None);
+            self.selected_pid = None;
+        } else {
+            let index = index.min(self.row_count() - 1);
+            self.table_state.select(Some(index));
+            if self.grouping.is_none() {
+                self.selected_pid = Some(self.visible[index].pid);
+            }
+        }
+    }
+
+    /// Moves the selection down by `count` rows.
+    fn select_next(&mut self, count: usize) {
+        let next = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(count));
+        self.select(next);
+    }
+
+    /// Moves the selection up by `count` rows.
+    fn select_previous(&mut self, count: usize) {
+        let previous = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(count));
+        self.select(previous);
+    }
+
+    /// Returns the selected process, or `None` while the processes are grouped.
+    fn selected_process(&self) -> Option<&ProcessInfo> {
+        if self.grouping.is_some() {
+            return None;
+        }
+        self.table_state
+            .selected()
+            .and_then(|i| self.visible.get(i))
+    }
+
+    fn open_kill_dialog(&mut self) {
+        if let Some(process) = self.selected_process() {
+            self.kill_dialog = Some(KillDialog {
+                pid: process.pid,
+                name: process.name.clone(),
+            });
+            self.set_modal(true);
+        }
+    }
+
+    fn close_kill_dialog(&mut self) {
+        self.kill_dialog = None;
+        self.set_modal(false);
+    }
+
+    fn open_signal_dialog(&mut self) {
+        if let Some(process) = self.selected_process() {
+            self.signal_dialog = Some(SignalDialog {
+                pid: process.pid,
+                name: process.name.clone(),
+                list_state: ListState::default().with_selected(Some(0)),
+            });
+            self.set_modal(true);
+        }
+    }
+
+    fn close_signal_dialog(&mut self) {
+        self.signal_dialog = None;
+        self.set_modal(false);
+    }
+
+    /// Opens the renice dialog with the nice value of the selected process changed by `change`.
+    /// Opens the renice dialog of the selected process, requesting its details if its nice value is unknown,
+    /// as the nice values are only read for every process while their column is displayed.
+    fn open_renice_dialog(&mut self, change: i32) -> Option<Message> {
+        let process = self.selected_process()?;
+        let (pid, current) = (process.pid, process.nice);
+        self.renice_dialog = Some(ReniceDialog {
+            pid,
+            name: process.name.clone(),
+            current,
+            nice: change_nice(current.unwrap_or_default(), change),
+        });
+        self.set_modal(true);
+        Some(match current {
+            Some(_) => Message::Redraw,
+            None => Message::ShowProcessDetails(pid),
+        })
+    }
+
+    fn close_renice_dialog(&mut self) {
+        self.renice_dialog = None;
+        self.set_modal(false);
+    }
+
+    /// Opens the affinity dialog of the selected process, requesting its details,
+    /// as the affinity of the processes is only read with their details.
+    fn open_affinity_dialog(&mut self) -> Option<Message> {
+        let process = self.selected_process()?;
+        let pid = process.pid;
+        self.affinity_dialog = Some(AffinityDialog {
+            pid,
+            name: process.name.clone(),
+            // every core is allowed until the affinity is known
+            cores: vec![true; self.core_count],
+            list_state: ListState::default().with_selected(Some(0)),
+        });
+        self.set_modal(true);
+        Some(Message::ShowProcessDetails(pid))
+    }
+
+    /// Fills the open dialog of the process with its fetched `details`,
+    /// or displays them if they were not requested by a dialog.
+    fn on_process_details(&mut self, details: Box<ProcessInfo>) -> Option<Message> {
+        if let Some(dialog) = self
+            .renice_dialog
+            .as_mut()
+            .filter(|dialog| dialog.pid == details.pid)
+        {
+            if let (None, Some(current)) = (dialog.current, details.nice) {
+                // the change picked so far was applied to zero
+                dialog.nice = change_nice(current, dialog.nice);
+                dialog.current = Some(current);
+            }
+            return Some(Message::Redraw);
+        }
+        if let Some(dialog) = self
+            .affinity_dialog
+            .as_mut()
+            .filter(|dialog| dialog.pid == details.pid)
+        {
+            if let Some(affinity) = &details.affinity {
+                dialog.cores = (0..self.core_count)
+                    .map(|core| affinity.contains(&core))
+                    .collect();
+            }
+            return Some(Message::Redraw);
+        }
+        Some(Message::DisplayProcessDetails(details))
+    }
+
+    fn close_affinity_dialog(&mut self) {
+        self.affinity_dialog = None;
+        self.set_modal(false);
+    }
+
+    fn open_column_dialog(&mut self) {
+        // the displayed columns come first, followed by the hidden ones in their default order
+        let visible = self.visible_columns();
+        let columns = visible
+            .iter()
+            .map(|(column, _)| (*column, true))
+            .chain(
+                COLUMNS
+                    .iter()
+                    .filter(|(column, _)| !visible.iter().any(|(c, _)| c == column))
+                    .map(|(column, _)| (*column, false)),
+            )
+            .collect();
+        self.column_dialog = Some(ColumnDialog {
+            columns,
+            list_state: ListState::default().with_selected(Some(0)),
+        });
+        self.set_modal(true);
+    }
+
+    fn close_column_dialog(&mut self) {
+        self.column_dialog = None;
+        self.set_modal(false);
+    }
+
+    /// Signals that every key press is captured by this component.
+    fn set_modal(&mut self, modal: bool) {
+        self.properties
+            .set(Attribute::Custom(MODAL), AttrValue::Flag(modal));
+    }
+
+    /// Handles the key presses while the search filter is being typed.
+    fn on_search(&mut self, code: Key) -> Option<Message> {
+        match code {
+            Key::Char(c) => self.filter.push(c),
+            Key::Backspace => {
+                self.filter.pop();
+            }
+            Key::Enter => {
+                self.searching = false;
+                self.set_modal(false);
+            }
+            _ => return None,
+        }
+        self.update_visible();
+        Some(Message::Redraw)
+    }
+
+    /// Counts the processes by their state, like `312 total, 2 running, 1 zombie`.
+    /// The rarer states are only mentioned when there are processes in them.
+    fn summary(&self) -> String {
+        let count = |state: ProcessState| {
+            self.list
+                .iter()
+                .filter(|process| process.state == Some(state))
+                .count()
+        };
+        let mut summary = format!(
+            "{} total, {} running",
+            self.list.len(),
+            count(ProcessState::Running)
+        );
+        for state in [
+            ProcessState::DiskSleep,
+            ProcessState::Stopped,
+            ProcessState::Zombie,
+        ] {
+            let count = count(state);
+            if count > 0 {
+                summary.push_str(&format!(", {} {}", count, state));
+            }
+        }
+        if self.hide_sleeping {
+            summary.push_str(", sleeping hidden");
+        }
+        summary
+    }
+
+    /// Replaces the process list with `list`, remembering the processes started and exited meanwhile.
+    fn process_update(&mut self, mut list: ProcessList) {
+        list.sort(self.sort_column, self.sort_order);
+        // every process would be new at the first update
+        self.churn = if self.list.is_empty() {
+            ProcessDiff::default()
+        } else {
+            list.diff(&self.list)
+        };
+        self.oom_victims = list.likely_oom_victims(OOM_VICTIMS);
+        self.list = list;
+        self.update_visible();
+    }
+
+    /// Returns true if `process` is kept in the table only to show that it has exited.
+    fn has_exited(&self, process: &ProcessInfo) -> bool {
+        self.churn
+            .exited
+            .iter()
+            .any(|exited| exited.pid == process.pid && exited.start_time == process.start_time)
+    }
+
+    /// Applies the search filter on the process list, keeping the selected process selected.
+    /// If the selected process is gone, the selection stays at the same row inside the table.
+    /// The exited processes are listed among the others while they are not grouped.
+    fn update_visible(&mut self) {
+        self.visible = if self.grouping.is_none() && !self.churn.exited.is_empty() {
+            let mut list: ProcessList = self
+                .list
+                .iter()
+                .chain(self.churn.exited.iter())
+                .cloned()
+                .collect();
+            list.sort(self.sort_column, self.sort_order);
+            list.filter(&self.filter)
+        } else {
+            self.list.filter(&self.filter)
+        };
+        if self.hide_sleeping {
+            self.visible = self
+                .visible
+                .iter()
+                .filter(|process| !process.is_sleeping())
+                .cloned()
+                .collect();
+        }
+        self.groups = match self.grouping {
+            Some(grouping) => self
+                .visible
+                .group(grouping, self.sort_column, self.sort_order),
+            None => Vec::new(),
+        };
+
+        let Some(selected) = self.table_state.selected() else {
+            return;
+        };
+        // the groups have no pid, so their selection stays at the same row
+        let position = self
+            .selected_pid
+            .filter(|_| self.grouping.is_none())
+            .and_then(|pid| self.visible.iter().position(|process| process.pid == pid));
+        self.select(position.unwrap_or(selected));
+    }
+
+    /// Handles the key presses while the kill dialog is open.
+    fn on_kill_dialog(&mut self, event: KeyEvent) -> Option<Message> {
+        let pid = self.kill_dialog.as_ref()?.pid;
+        let message = match event.code {
+            Key::Enter | Key::Char('t') => Some(Message::TerminateProcess(pid)),
+            // the uppercase letters are received as lowercase ones with the shift modifier
+            Key::Char('k') if event.modifiers.contains(KeyModifiers::SHIFT) => {
+                Some(Message::KillProcess(pid))
+            }
+            // Esc is not used for cancelling, because the menu would receive it after closing the dialog
+            Key::Char('n') => Some(Message::Redraw),
+            _ => return None,
+        };
+        self.close_kill_dialog();
+        message
+    }
+
+    /// Handles the key presses while the signal dialog is open.
+    fn on_signal_dialog(&mut self, code: Key) -> Option<Message> {
+        let dialog = self.signal_dialog.as_mut()?;
+        let message = match code {
+            Key::Up => {
+                dialog.list_state.select_previous();
+                return Some(Message::Redraw);
+            }
+            Key::Down => {
+                // the selection is not clamped to the list until the next rendering
+                let next = dialog.list_state.selected().map_or(0, |i| i + 1);
+                dialog
+                    .list_state
+                    .select(Some(next.min(ProcessSignal::ALL.len() - 1)));
+                return Some(Message::Redraw);
+            }
+            Key::Enter => {
+                let signal = dialog
+                    .list_state
+                    .selected()
+                    .and_then(|i| ProcessSignal::ALL.get(i))?;
+                Some(Message::SendSignal(dialog.pid, *signal))
+            }
+            Key::Char('n') => Some(Message::Redraw),
+            _ => return None,
+        };
+        self.close_signal_dialog();
+        message
+    }
+
+    /// Handles the key presses while the renice dialog is open.
+    fn on_renice_dialog(&mut self, code: Key) -> Option<Message> {
+        let dialog = self.renice_dialog.as_mut()?;
+        let message = match code {
+            Key::Function(7) | Key::Up => {
+                dialog.nice = change_nice(dialog.nice, -1);
+                return Some(Message::Redraw);
+            }
+            Key::Function(8) | Key::Down => {
+                dialog.nice = change_nice(dialog.nice, 1);
+                return Some(Message::Redraw);
+            }
+            Key::Enter => Some(Message::SetPriority(dialog.pid, dialog.nice)),
+            Key::Char('n') => Some(Message::Redraw),
+            _ => return None,
+        };
+        self.close_renice_dialog();
+        message
+    }
+
+    /// Handles the key presses while the affinity dialog is open.
+    fn on_affinity_dialog(&mut self, code: Key) -> Option<Message> {
+        let dialog = self.affinity_dialog.as_mut()?;
+        let selected = dialog.list_state.selected().unwrap_or_default();
+        let message = match code {
+            Key::Up => {
+                dialog.list_state.select(Some(selected.saturating_sub(1)));
+                return Some(Message::Redraw);
+            }
+            Key::Down => {
+                let last = dialog.cores.len().saturating_sub(1);
+                dialog.list_state.select(Some((selected + 1).min(last)));
+                return Some(Message::Redraw);
+            }
+            Key::Char(' ') => {
+                if let Some(allowed) = dialog.cores.get_mut(selected) {
+                    *allowed = !*allowed;
+                }
+                return Some(Message::Redraw);
+            }
+            Key::Char('a') => {
+                // selects every core, or none of them if all are already selected
+                let all = dialog.cores.iter().all(|allowed| *allowed);
+                dialog.cores.iter_mut().for_each(|allowed| *allowed = !all);
+                return Some(Message::Redraw);
+            }
+            Key::Enter => {
+                let cores: Vec<usize> = (0..dialog.cores.len())
+                    .filter(|core| dialog.cores[*core])
+                    .collect();
+                // a process has to be allowed to run somewhere
+                if cores.is_empty() {
+                    return None;
                 }
+                Some(Message::SetAffinity(dialog.pid, cores))
             }
+            Key::Char('n') => Some(Message::Redraw),
+            _ => return None,
+        };
+        self.close_affinity_dialog();
+        message
+    }
+
+    /// Handles the key presses while the column dialog is open.
+    fn on_column_dialog(&mut self, code: Key) -> Option<Message> {
+        let dialog = self.column_dialog.as_mut()?;
+        let selected = dialog.list_state.selected().unwrap_or_default();
+        let last = dialog.columns.len() - 1;
+        match code {
+            Key::Up => dialog.list_state.select(Some(selected.saturating_sub(1))),
+            Key::Down => dialog.list_state.select(Some((selected + 1).min(last))),
+            Key::Char(' ') => dialog.columns[selected].1 = !dialog.columns[selected].1,
+            Key::Char('u') if selected > 0 => {
+                dialog.columns.swap(selected, selected - 1);
+                dialog.list_state.select(Some(selected - 1));
+            }
+            Key::Char('d') if selected < last => {
+                dialog.columns.swap(selected, selected + 1);
+                dialog.list_state.select(Some(selected + 1));
+            }
+            Key::Enter => {
+                let columns: Vec<ProcessColumn> = dialog
+                    .columns
+                    .iter()
+                    .filter(|(_, displayed)| *displayed)
+                    .map(|(column, _)| *column)
+                    .collect();
+                // the table has to display something
+                if columns.is_empty() {
+                    return None;
+                }
+                self.columns = columns.clone();
+                self.close_column_dialog();
+                return Some(Message::ChangeProcessColumns(columns));
+            }
+            Key::Char('n') => self.close_column_dialog(),
+            _ => return None,
+        }
+        Some(Message::Redraw)
+    }
+
+    fn render_column_dialog(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(dialog) = &mut self.column_dialog {
+            // the borders take two lines
+            let height = (dialog.columns.len() as u16 + 2).min(area.height);
+            let popup_area = centered_rect(56, height, area);
+            let items = dialog.columns.iter().map(|(column, displayed)| {
+                let check = if *displayed { "[x]" } else { "[ ]" };
+                let title = COLUMNS
+                    .iter()
+                    .find(|(c, _)| c == column)
+                    .map_or("", |(_, title)| title);
+                format!("{} {}", check, title)
+            });
+            let list = List::new(items)
+                .highlight_style(Style::default().reversed())
+                .block(
+                    Block::bordered()
+                        .title(" Columns ")
+                        .title_alignment(Alignment::Center)
+                        .title_bottom(
+                            Line::from(" Space: toggle  u/d: move  Enter: apply  n: cancel ")
+                                .centered(),
+                        )
+                        .yellow(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_stateful_widget(list, popup_area, &mut dialog.list_state);
+        }
+    }
+
+    fn render_affinity_dialog(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(dialog) = &mut self.affinity_dialog {
+            // the borders take two lines
+            let height = (dialog.cores.len() as u16 + 2).min(area.height);
+            let popup_area = centered_rect(50, height, area);
+            let items = dialog.cores.iter().enumerate().map(|(core, allowed)| {
+                let check = if *allowed { "[x]" } else { "[ ]" };
+                format!("{} Core {}", check, core)
+            });
+            let list = List::new(items)
+                .highlight_style(Style::default().reversed())
+                .block(
+                    Block::bordered()
+                        .title(format!(" Pin {} ({}) to cores ", dialog.name, dialog.pid))
+                        .title_alignment(Alignment::Center)
+                        .title_bottom(
+                            Line::from(" Space: toggle  a: all  Enter: apply  n: cancel ")
+                                .centered(),
+                        )
+                        .yellow(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_stateful_widget(list, popup_area, &mut dialog.list_state);
+        }
+    }
+
+    fn render_renice_dialog(&self, frame: &mut Frame, area: Rect) {
+        if let Some(dialog) = &self.renice_dialog {
+            let popup_area = centered_rect(50, 6, area);
+            let current = dialog
+                .current
+                .map_or("N/A".to_string(), |nice| nice.to_string());
+            let text = format!(
+                "Nice value of {} ({}): {} → {}\n\nF7/↑: higher priority   F8/↓: lower priority\nEnter: apply   n: cancel",
+                dialog.name, dialog.pid, current, dialog.nice
+            );
+            let popup = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::bordered()
+                        .title("Renice process")
+                        .title_alignment(Alignment::Center)
+                        .yellow(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_widget(popup, popup_area);
+        }
+    }
+
+    fn render_signal_dialog(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(dialog) = &mut self.signal_dialog {
+            // the borders take two lines
+            let popup_area = centered_rect(40, ProcessSignal::ALL.len() as u16 + 2, area);
+            let list = List::new(ProcessSignal::ALL.iter().map(|signal| signal.name()))
+                .highlight_style(Style::default().reversed())
+                .block(
+                    Block::bordered()
+                        .title(format!(" Signal {} ({}) ", dialog.name, dialog.pid))
+                        .title_alignment(Alignment::Center)
+                        .title_bottom(Line::from(" Enter: send  n: cancel ").centered())
+                        .red(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_stateful_widget(list, popup_area, &mut dialog.list_state);
+        }
+    }
+
+    fn render_kill_dialog(&self, frame: &mut Frame, area: Rect) {
+        if let Some(dialog) = &self.kill_dialog {
+            let popup_area = centered_rect(50, 6, area);
+            let text = format!(
+                "Terminate {} ({})?\n\nEnter/t: SIGTERM   K: SIGKILL   n: cancel",
+                dialog.name, dialog.pid
+            );
+            let popup = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::bordered()
+                        .title("Terminate process")
+                        .title_alignment(Alignment::Center)
+                        .red(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_widget(popup, popup_area);
+        }
+    }
+
+    /// Sorts the table by `column`. If the table is already sorted by `column`, the order is reversed.
+    fn sort_by(&mut self, column: ProcessColumn) {
+        if self.sort_column == column {
+            self.sort_order = self.sort_order.toggle();
         } else {
-            self.properties.set(attr, value);
+            self.sort_column = column;
+            // the biggest consumers are the most interesting ones
+            self.sort_order = match column {
+                ProcessColumn::CpuUsage
+                | ProcessColumn::AverageCpuUsage
+                | ProcessColumn::Memory
+                | ProcessColumn::MemoryShare
+                | ProcessColumn::Swap
+                | ProcessColumn::OomScore
+                | ProcessColumn::DiskRead
+                | ProcessColumn::DiskWrite
+                | ProcessColumn::NetReceive
+                | ProcessColumn::NetTransmit => SortOrder::Descending,
+                _ => SortOrder::Ascending,
+            };
+        }
+        self.list.sort(self.sort_column, self.sort_order);
+        self.update_visible();
+    }
+}
+
+/// Formats the value of `column` for `process`, the durations in seconds if `exact_durations` is set.
+fn process_text(
+    process: &ProcessInfo,
+    column: ProcessColumn,
+    opts: FormatSizeOptions,
+    exact_durations: bool,
+) -> String {
+    let unknown = || "N/A".to_string();
+    match column {
+        ProcessColumn::Pid => process.pid.to_string(),
+        ProcessColumn::Name => process.name.clone(),
+        ProcessColumn::Memory => process.memory.format_size(opts),
+        ProcessColumn::VirtualMemory => process.virtual_memory.format_size(opts),
+        ProcessColumn::Swap => process
+            .swap
+            .map_or_else(unknown, |swap| swap.format_size(opts)),
+        ProcessColumn::OomScore => process
+            .oom_score
+            .map_or_else(unknown, |score| score.to_string()),
+        ProcessColumn::MemoryShare => format!("{:.1}%", process.memory_share),
+        ProcessColumn::CpuUsage => format!("{}%", process.cpu_usage),
+        ProcessColumn::AverageCpuUsage => format!("{:.1}%", process.average_cpu_usage()),
+        // the CPU time is measured in milliseconds
+        ProcessColumn::CpuTime => format_compact_duration(process.cpu_time / 1000, exact_durations),
+        ProcessColumn::User => process.username.clone(),
+        ProcessColumn::RunningTime => {
+            format_compact_duration(process.running_time, exact_durations)
+        }
+        ProcessColumn::DiskRead => (process.read_rate as u64).format_size(opts),
+        ProcessColumn::DiskWrite => (process.write_rate as u64).format_size(opts),
+        ProcessColumn::NetReceive => network_rate(process.net_received_rate, opts),
+        ProcessColumn::NetTransmit => network_rate(process.net_transmitted_rate, opts),
+        ProcessColumn::Command => process.command.clone(),
+        ProcessColumn::Threads => process.threads.map_or_else(unknown, |t| t.to_string()),
+        ProcessColumn::State => process
+            .state
+            .map_or_else(unknown, |state| state.letter().to_string()),
+        ProcessColumn::Nice => process.nice.map_or_else(unknown, |nice| nice.to_string()),
+        ProcessColumn::StartTime => process.start_time.map_or_else(unknown, format_date_time),
+    }
+}
+
+/// Computes the widths of `columns` from their longest title or value in `values`, up to `MAX_COLUMN_WIDTH`.
+/// The command takes the remaining space, the name takes it when there is no command column.
+fn column_widths(
+    columns: &[(ProcessColumn, &str)],
+    values: impl Fn(ProcessColumn) -> usize,
+) -> Vec<Constraint> {
+    let has_command = columns
+        .iter()
+        .any(|(column, _)| *column == ProcessColumn::Command);
+    columns
+        .iter()
+        .map(|(column, title)| {
+            // the sort indicator takes two more characters beside the title
+            let width = values(*column)
+                .max(title.chars().count() + 2)
+                .min(MAX_COLUMN_WIDTH) as u16;
+            match column {
+                ProcessColumn::Command => Constraint::Fill(1),
+                ProcessColumn::Name if !has_command => Constraint::Min(width),
+                _ => Constraint::Length(width),
+            }
+        })
+        .collect()
+}
+
+/// Formats the network `rate` of a process, which is unknown while the traffic is not accounted.
+fn network_rate(rate: Option<f64>, opts: FormatSizeOptions) -> String {
+    rate.map_or("N/A".to_string(), |rate| (rate as u64).format_size(opts))
+}
+
+/// Returns `nice` changed by `change`, kept inside the range of the valid nice values.
+fn change_nice(nice: i32, change: i32) -> i32 {
+    (nice + change).clamp(*NICE_RANGE.start(), *NICE_RANGE.end())
+}
+
+/// Creates a line from `text` where the first case insensitive occurrence of `query` is highlighted.
+fn highlight<'a>(text: &'a str, query: &str) -> Line<'a> {
+    let lowercase_text = text.to_lowercase();
+    // lowercasing can change the byte length of some characters, then the positions would not match
+    let position = if query.is_empty() || lowercase_text.len() != text.len() {
+        None
+    } else {
+        lowercase_text.find(&query.to_lowercase())
+    };
+
+    match position {
+        Some(start) => {
+            let end = start + query.len();
+            Line::from(vec![
+                Span::raw(&text[..start]),
+                Span::raw(&text[start..end]).black().on_yellow(),
+                Span::raw(&text[end..]),
+            ])
         }
+        None => Line::from(text),
+    }
+}
+
+impl MockComponent for Processes {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
     }
 
     fn perform(&mut self, _cmd: Cmd) -> CmdResult {
@@ -47,72 +1100,213 @@ impl MockComponent for Processes {
     }
 
     fn view(&mut self, frame: &mut Frame, area: Rect) {
-        let opts = FormatSizeOptions::default()
-            .base_unit(BaseUnit::Byte)
-            .decimal_places(1)
-            .decimal_zeroes(0)
-            .kilo(humansize::Kilo::Binary)
-            .long_units(false)
-            .space_after_value(false);
-
-        let header = Row::new(vec![
-            Cell::from("pid"),
-            Cell::from("name"),
-            Cell::from("mem"),
-            Cell::from("virtmem"),
-            Cell::from("cpu"),
-            Cell::from("cputime"),
-            Cell::from("user"),
-            Cell::from("runtime"),
-            Cell::from("command"),
-        ]);
-
-        let rows: Vec<Row<'_>> = self
-            .list
-            .iter()
-            .map(|process| {
-                let cells = vec![
-                    Cell::from(process.pid.to_string()),
-                    Cell::from(process.name.clone()),
-                    Cell::from(process.memory.format_size(opts)),
-                    Cell::from(process.virtual_memory.format_size(opts)),
-                    Cell::from(format!("{}%", process.cpu_usage.to_string())),
-                    Cell::from(process.cpu_time.to_string()),
-                    Cell::from(process.username.clone()),
-                    Cell::from(process.running_time.to_string()),
-                    Cell::from(process.command.clone()),
-                ];
-                Row::new(cells)
-            })
-            .collect();
+        let opts = size_format(self.units).space_after_value(false);
+
+        let (title, columns, widths, rows) = match self.grouping {
+            Some(grouping) => {
+                let mut columns = GROUP_COLUMNS.to_vec();
+                let (title, name) = match grouping {
+                    ProcessGrouping::User => ("Processes by user", "user"),
+                    ProcessGrouping::Application => ("Processes by application", "application"),
+                    ProcessGrouping::Cgroup => ("Processes by cgroup", "cgroup"),
+                };
+                columns[0].1 = name;
+                let widths = column_widths(&columns, |column| {
+                    self.groups
+                        .iter()
+                        .map(|group| self.group_text(group, column, opts).chars().count())
+                        .max()
+                        .unwrap_or_default()
+                });
+                let rows: Vec<Row<'_>> = self
+                    .groups
+                    .iter()
+                    .map(|group| {
+                        Row::new(
+                            columns
+                                .iter()
+                                .map(|(column, _)| self.group_text(group, *column, opts)),
+                        )
+                    })
+                    .collect();
+                (title, columns, widths, rows)
+            }
+            None => {
+                let columns = self.visible_columns();
+                let widths = column_widths(&columns, |column| {
+                    self.visible
+                        .iter()
+                        .map(|process| {
+                            process_text(process, column, opts, self.exact_durations)
+                                .chars()
+                                .count()
+                        })
+                        .max()
+                        .unwrap_or_default()
+                });
+                let rows: Vec<Row<'_>> = self
+                    .visible
+                    .iter()
+                    .map(|process| {
+                        let row = Row::new(
+                            columns
+                                .iter()
+                                .map(|(column, _)| self.cell(process, *column, opts)),
+                        );
+                        if self.has_exited(process) {
+                            row.red().dim()
+                        } else if self.churn.started.contains(&process.pid) {
+                            row.green()
+                        } else {
+                            row
+                        }
+                    })
+                    .collect();
+                ("Processes", columns, widths, rows)
+            }
+        };
+        let header = self.header(&columns);
+
+        let mut block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_top(Line::from(format!(" {} ", self.summary())).left_aligned());
+        if self.searching || !self.filter.is_empty() {
+            let cursor = if self.searching { "_" } else { "" };
+            block = block.title_bottom(format!(" /{}{} ", self.filter, cursor).yellow());
+        }
 
         let table = Table::default()
-            .block(
-                Block::bordered()
-                    .title("Processes")
-                    .title_alignment(Alignment::Center),
-            )
-            .widths([
-                Constraint::Length(6),
-                Constraint::Fill(1),
-                Constraint::Length(8),
-                Constraint::Length(8),
-                Constraint::Length(8),
-                Constraint::Length(8),
-                Constraint::Fill(1),
-                Constraint::Length(8),
-                Constraint::Fill(1),
-            ])
+            .block(block)
+            .widths(widths)
             .header(header)
             .flex(Flex::Center)
+            .row_highlight_style(Style::default().reversed())
             .rows(rows);
 
-        frame.render_widget(table, area);
+        // the borders and the header take three lines
+        self.page_size = area.height.saturating_sub(3) as usize;
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+        self.render_kill_dialog(frame, area);
+        self.render_signal_dialog(frame, area);
+        self.render_renice_dialog(frame, area);
+        self.render_affinity_dialog(frame, area);
+        self.render_column_dialog(frame, area);
     }
 }
 
-impl Component<Message, NoUserEvent> for Processes {
-    fn on(&mut self, _event: Event<NoUserEvent>) -> Option<Message> {
-        None
+impl Component<Message, UserEvent> for Processes {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Process(process_list)) => {
+                self.process_update(process_list);
+                Some(Message::Redraw)
+            }
+            Event::User(UserEvent::ProcessDelta(delta)) => {
+                let mut list = self.list.clone();
+                list.apply(delta);
+                self.process_update(list);
+                Some(Message::Redraw)
+            }
+            Event::User(UserEvent::CpuInfo(info)) => {
+                self.core_count = info.0.core_count;
+                Some(Message::Redraw)
+            }
+            Event::User(UserEvent::ProcessDetails(details)) => self.on_process_details(details),
+            Event::Keyboard(event) if self.kill_dialog.is_some() => self.on_kill_dialog(event),
+            Event::Keyboard(KeyEvent { code, .. }) if self.signal_dialog.is_some() => {
+                self.on_signal_dialog(code)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.renice_dialog.is_some() => {
+                self.on_renice_dialog(code)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.affinity_dialog.is_some() => {
+                self.on_affinity_dialog(code)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.column_dialog.is_some() => {
+                self.on_column_dialog(code)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.searching => self.on_search(code),
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let column = match code {
+                    Key::Char('c') => self.cpu_column(),
+                    Key::Char('m') => ProcessColumn::Memory,
+                    Key::Char('%') => ProcessColumn::MemoryShare,
+                    Key::Char('p') => ProcessColumn::Pid,
+                    Key::Char('n') => ProcessColumn::Name,
+                    Key::Char('r') => ProcessColumn::DiskRead,
+                    Key::Char('w') => ProcessColumn::DiskWrite,
+                    Key::Char('i') => ProcessColumn::NetReceive,
+                    Key::Char('o') => ProcessColumn::NetTransmit,
+                    Key::Char('b') => ProcessColumn::OomScore,
+                    Key::Up => {
+                        self.select_previous(1);
+                        return Some(Message::Redraw);
+                    }
+                    Key::Down => {
+                        self.select_next(1);
+                        return Some(Message::Redraw);
+                    }
+                    Key::PageUp => {
+                        self.select_previous(self.page_size.max(1));
+                        return Some(Message::Redraw);
+                    }
+                    Key::PageDown => {
+                        self.select_next(self.page_size.max(1));
+                        return Some(Message::Redraw);
+                    }
+                    Key::Home => {
+                        self.select(0);
+                        return Some(Message::Redraw);
+                    }
+                    Key::End => {
+                        self.select(usize::MAX);
+                        return Some(Message::Redraw);
+                    }
+                    Key::Char('k') | Key::Delete => {
+                        self.open_kill_dialog();
+                        return Some(Message::Redraw);
+                    }
+                    Key::Function(7) => return self.open_renice_dialog(-1),
+                    Key::Function(8) => return self.open_renice_dialog(1),
+                    Key::Char('f') => return self.open_affinity_dialog(),
+                    Key::Char('v') => {
+                        self.open_column_dialog();
+                        return Some(Message::Redraw);
+                    }
+                    Key::Char('u') => {
+                        self.toggle_average_cpu();
+                        return Some(Message::Redraw);
+                    }
+                    Key::Char('x') => {
+                        self.hide_sleeping = !self.hide_sleeping;
+                        self.update_visible();
+                        return Some(Message::Redraw);
+                    }
+                    Key::Char('g') => {
+                        self.toggle_grouping();
+                        return Some(Message::Redraw);
+                    }
+                    Key::Char('s') => {
+                        self.open_signal_dialog();
+                        return Some(Message::Redraw);
+                    }
+                    Key::Enter => {
+                        return self
+                            .selected_process()
+                            .map(|process| Message::ShowProcessDetails(process.pid));
+                    }
+                    Key::Char('/') => {
+                        self.searching = true;
+                        self.set_modal(true);
+                        return Some(Message::Redraw);
+                    }
+                    _ => return None,
+                };
+                self.sort_by(column);
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
     }
 }
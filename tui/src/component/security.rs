@@ -0,0 +1,200 @@
+use crate::{Message, UserEvent};
+use core::connections::Connection;
+use core::security::{LoginSource, SecurityOverview, FAILED_LOGIN_WINDOW};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Row, Table},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// Pane displaying how exposed the system is to the network over the overview: whether a firewall filters
+/// the incoming connections, the ports listening beyond the loopback address and the failed SSH logins
+/// of the last day with the addresses they came from. The open ports are highlighted while no firewall is known
+/// to filter them.
+///
+/// The rules of the firewall and the log of the authentications can only be read with root privileges,
+/// or as a member of the `adm` or the `systemd-journal` group for the log.
+///
+/// Controls:
+/// * Enter/s => closes the pane
+///
+/// The security overview is only read on Linux.
+#[derive(Default)]
+pub struct Security {
+    properties: Props,
+
+    security: SecurityOverview,
+}
+
+impl Security {
+    fn firewall_line(&self) -> Line<'_> {
+        let firewall = &self.security.firewall;
+        let name = firewall.name.as_deref();
+        let rules = firewall
+            .rules
+            .map(|rules| format!(", {} input rules", rules))
+            .unwrap_or_default();
+        let state = match (firewall.active, name) {
+            (Some(true), Some(name)) => format!("active ({}{})", name, rules).light_green(),
+            (Some(true), None) => format!("active{}", rules).light_green(),
+            (Some(false), _) => "inactive, the incoming connections are not filtered"
+                .to_string()
+                .light_red(),
+            (None, Some(name)) => format!(
+                "{} is installed but disabled, reading the rules requires root",
+                name
+            )
+            .yellow(),
+            (None, None) => "unknown, reading the rules requires root"
+                .to_string()
+                .yellow(),
+        };
+        Line::from(vec![Span::from("Firewall: "), state])
+    }
+
+    fn exposed_ports_line(&self) -> Line<'_> {
+        let count = self.security.exposed_ports.len();
+        let ports = format!("{} listening beyond the loopback address", count);
+        Line::from(vec![
+            Span::from("Open ports: "),
+            match self.security.is_exposed() {
+                true => ports.light_red(),
+                false => ports.into(),
+            },
+        ])
+    }
+
+    fn failed_logins_line(&self) -> Line<'_> {
+        let attempts = match &self.security.failed_logins {
+            None => "no readable log of the authentications"
+                .to_string()
+                .yellow(),
+            Some(logins) => {
+                let period = match logins.since {
+                    Some(_) => format!("in the last {} hours", FAILED_LOGIN_WINDOW / 3600),
+                    None => "recently".to_string(),
+                };
+                let attempts = format!("{} {} ({})", logins.attempts, period, logins.log);
+                match logins.attempts > 0 {
+                    true => attempts.yellow(),
+                    false => attempts.into(),
+                }
+            }
+        };
+        Line::from(vec![Span::from("Failed SSH logins: "), attempts])
+    }
+
+    fn port_row(connection: &Connection) -> Row<'_> {
+        Row::new(vec![
+            connection.protocol.clone(),
+            connection.local.clone(),
+            match (&connection.process, connection.pid) {
+                (Some(process), Some(pid)) => format!("{} ({})", process, pid),
+                (Some(process), None) => process.clone(),
+                (None, _) => String::new(),
+            },
+            connection.user.clone().unwrap_or_default(),
+        ])
+    }
+
+    fn source_row(source: &LoginSource) -> Row<'_> {
+        Row::new(vec![source.address.clone(), source.attempts.to_string()])
+    }
+}
+
+impl MockComponent for Security {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        // the three lines of the summary and its borders
+        let [summary_area, tables_area] =
+            Layout::vertical([Constraint::Length(5), Constraint::Fill(1)]).areas(area);
+        let [ports_area, sources_area] =
+            Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .areas(tables_area);
+
+        let summary = Paragraph::new(vec![
+            self.firewall_line(),
+            self.exposed_ports_line(),
+            self.failed_logins_line(),
+        ])
+        .block(
+            Block::bordered()
+                .title("Security")
+                .title_alignment(Alignment::Center),
+        );
+
+        let ports = Table::default()
+            .block(
+                Block::bordered()
+                    .title("Ports open to the network")
+                    .title_alignment(Alignment::Center),
+            )
+            .widths([
+                Constraint::Length(6),
+                Constraint::Length(24),
+                Constraint::Fill(1),
+                Constraint::Length(12),
+            ])
+            .header(Row::new(vec!["proto", "address", "process", "user"]).bold())
+            .rows(self.security.exposed_ports.iter().map(Self::port_row));
+
+        let sources = self
+            .security
+            .failed_logins
+            .as_ref()
+            .map(|logins| logins.sources.as_slice())
+            .unwrap_or_default();
+        let sources = Table::default()
+            .block(
+                Block::bordered()
+                    .title("Failed logins by address")
+                    .title_alignment(Alignment::Center),
+            )
+            .widths([Constraint::Fill(1), Constraint::Length(10)])
+            .header(Row::new(vec!["address", "attempts"]).bold())
+            .rows(sources.iter().map(Self::source_row));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(summary, summary_area);
+        frame.render_widget(ports, ports_area);
+        frame.render_widget(sources, sources_area);
+    }
+}
+
+impl Component<Message, UserEvent> for Security {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Security(security)) => {
+                self.security = security;
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter | Key::Char('s'),
+                ..
+            }) => Some(Message::CloseSecurity),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{Message, UserEvent};
+use core::history::{History, Metric};
+use core::model::{Sensor, SensorKind, SensorStatus, SensorsInfo};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph, Sparkline},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// The groups of sensors in the order of their appearance, with their titles.
+const GROUPS: [(SensorKind, &str); 3] = [
+    (SensorKind::Temperature, "Temperatures"),
+    (SensorKind::Fan, "Fans"),
+    (SensorKind::Voltage, "Voltages"),
+];
+
+/// Component for displaying the hardware sensors grouped by their kind,
+/// each with its value, its limits and a sparkline of its history.
+/// The sensors approaching their limits are yellow, the ones beyond their limits are red.
+///
+/// Controls:
+/// * Up/Down => scrolls the sensors
+#[derive(Default)]
+pub struct Sensors {
+    properties: Props,
+
+    sensors_info: SensorsInfo,
+
+    /// History of the sensor values by their label
+    history: History,
+
+    /// Count of the lines scrolled off the top
+    scroll: usize,
+}
+
+impl Sensors {
+    /// Sets the history which the sparklines are drawn from.
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
+    fn process_update(&mut self, update: SensorsInfo) {
+        // forget the sensors which are no longer present
+        self.history.retain(|metric| match metric {
+            Metric::Sensor(label) => update.sensors.iter().any(|sensor| &sensor.label == label),
+            _ => true,
+        });
+
+        for sensor in update.sensors.iter() {
+            self.history
+                .record(Metric::Sensor(sensor.label.clone()), sensor.value);
+        }
+
+        self.sensors_info = update;
+    }
+
+    /// Returns the lines of the sensor list: the title of every group followed by its sensors.
+    fn lines(&self) -> Vec<(&'static str, Option<&Sensor>)> {
+        let mut lines = Vec::new();
+        for (kind, title) in GROUPS {
+            let mut sensors = self
+                .sensors_info
+                .sensors
+                .iter()
+                .filter(|sensor| sensor.kind == kind)
+                .peekable();
+            if sensors.peek().is_none() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(("", None));
+            }
+            lines.push((title, None));
+            lines.extend(sensors.map(|sensor| (title, Some(sensor))));
+        }
+        lines
+    }
+
+    /// Renders a single sensor in one line: its label, value and limits,
+    /// and the sparkline of its history in the right side.
+    fn render_sensor(&self, sensor: &Sensor, frame: &mut Frame, area: Rect) {
+        let [label_area, value_area, limits_area, sparkline_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(28),
+            Constraint::Percentage(40),
+        ])
+        .spacing(1)
+        .areas(area);
+
+        let style = match sensor.status() {
+            SensorStatus::Normal => Style::default(),
+            SensorStatus::Warning => Style::default().yellow(),
+            SensorStatus::Critical => Style::default().light_red().bold(),
+        };
+        let limits = [
+            ("min", sensor.min),
+            ("max", sensor.max),
+            ("crit", sensor.critical),
+        ]
+        .iter()
+        .filter_map(|(name, limit)| {
+            Some(format!("{} {}", name, format_value(sensor.kind, (*limit)?)))
+        })
+        .collect::<Vec<String>>()
+        .join("  ");
+
+        // the sparkline only draws integers, so the values are scaled up to keep their fraction
+        let samples = self
+            .history
+            .window(
+                &Metric::Sensor(sensor.label.clone()),
+                sparkline_area.width as usize,
+            )
+            .iter()
+            .map(|sample| (sample.value.max(0.0) * 100.0) as u64)
+            .collect::<Vec<u64>>();
+        let mut sparkline = Sparkline::default().data(&samples).style(style);
+        // the temperatures are drawn relative to the critical one, so the rise of a cool sensor is not exaggerated
+        if let (SensorKind::Temperature, Some(critical)) = (sensor.kind, sensor.critical) {
+            sparkline = sparkline.max((critical * 100.0) as u64);
+        }
+
+        frame.render_widget(Paragraph::new(sensor.label.as_str()), label_area);
+        frame.render_widget(
+            Paragraph::new(format_value(sensor.kind, sensor.value))
+                .style(style)
+                .alignment(Alignment::Right),
+            value_area,
+        );
+        frame.render_widget(Paragraph::new(limits).gray(), limits_area);
+        frame.render_widget(sparkline, sparkline_area);
+    }
+}
+
+impl MockComponent for Sensors {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title("Sensors")
+            .title_alignment(Alignment::Center);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        // keep the last line on the screen
+        let count = self.lines().len();
+        self.scroll = self
+            .scroll
+            .min(count.saturating_sub(inner_area.height as usize));
+
+        let lines = self.lines();
+        if lines.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No hardware sensors were found").alignment(Alignment::Center),
+                inner_area,
+            );
+            return;
+        }
+
+        let rows = Layout::vertical(vec![Constraint::Length(1); inner_area.height as usize])
+            .split(inner_area);
+        for ((title, sensor), row) in lines.iter().skip(self.scroll).zip(rows.iter()) {
+            match sensor {
+                Some(sensor) => self.render_sensor(sensor, frame, *row),
+                None => frame.render_widget(Line::from(*title).bold(), *row),
+            }
+        }
+    }
+}
+
+impl Component<Message, UserEvent> for Sensors {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Sensors(sensors_info)) => {
+                self.process_update(sensors_info);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                // the scrolling is limited to the sensor list at the next rendering
+                self.scroll += 1;
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Formats `value` in the unit of the sensor `kind`.
+fn format_value(kind: SensorKind, value: f64) -> String {
+    match kind {
+        SensorKind::Temperature => format!("{:.1} {}", value, kind.unit()),
+        SensorKind::Fan => format!("{:.0} {}", value, kind.unit()),
+        SensorKind::Voltage => format!("{:.3} {}", value, kind.unit()),
+    }
+}
@@ -0,0 +1,297 @@
+use super::{centered_rect, format_duration, size_format, MODAL};
+use crate::{Message, UserEvent};
+use core::config::Units;
+use core::model::ServicesInfo;
+use core::ServiceAction;
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Alignment, Constraint},
+    style::{Style, Stylize},
+    widgets::{Block, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// Component for displaying the service units of systemd with their state,
+/// the time since they are active and the resources used by their processes.
+/// The failed services are red, the inactive ones are gray.
+///
+/// Controls:
+/// * Up/Down/PageUp/PageDown => moves the selection
+/// * s => starts the selected service after confirmation
+/// * x => stops the selected service after confirmation
+/// * r => restarts the selected service after confirmation
+#[derive(Default)]
+pub struct Services {
+    properties: Props,
+
+    services_info: ServicesInfo,
+
+    /// Holds the selected row
+    table_state: TableState,
+
+    /// The name of the selected service, which stays selected when services appear or disappear
+    selected_name: Option<String>,
+
+    /// Count of the rows fitting into the table at the last rendering
+    page_size: usize,
+
+    /// The service and the action waiting for confirmation
+    action_dialog: Option<(String, ServiceAction)>,
+
+    /// Prefixes of the displayed data sizes
+    units: Units,
+}
+
+impl Services {
+    /// Sets the prefixes of the displayed data sizes.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Selects the row at `index`, clamped inside the table.
+    fn select(&mut self, index: usize) {
+        let services = &self.services_info.services;
+        if services.is_empty() {
+            self.table_state.select(None);
+            self.selected_name = None;
+        } else {
+            let index = index.min(services.len() - 1);
+            self.table_state.select(Some(index));
+            self.selected_name = Some(services[index].name.clone());
+        }
+    }
+
+    fn process_update(&mut self, update: ServicesInfo) {
+        self.services_info = update;
+        let index = self.selected_name.as_ref().and_then(|name| {
+            self.services_info
+                .services
+                .iter()
+                .position(|service| &service.name == name)
+        });
+        match (index, self.table_state.selected()) {
+            (Some(index), _) => self.select(index),
+            // the selected service has disappeared, the one taking its place is selected
+            (None, Some(selected)) => self.select(selected),
+            (None, None) => {}
+        }
+    }
+
+    fn open_action_dialog(&mut self, action: ServiceAction) {
+        let selected = self
+            .table_state
+            .selected()
+            .and_then(|index| self.services_info.services.get(index));
+        if let Some(service) = selected {
+            self.action_dialog = Some((service.name.clone(), action));
+            self.set_modal(true);
+        }
+    }
+
+    fn close_action_dialog(&mut self) {
+        self.action_dialog = None;
+        self.set_modal(false);
+    }
+
+    /// Marks whether a dialog is displayed, so the global key bindings are disabled meanwhile.
+    fn set_modal(&mut self, modal: bool) {
+        self.properties
+            .set(Attribute::Custom(MODAL), AttrValue::Flag(modal));
+    }
+
+    /// Handles the key presses while the action dialog is open.
+    fn on_action_dialog(&mut self, code: Key) -> Option<Message> {
+        let (name, action) = self.action_dialog.clone()?;
+        let message = match code {
+            Key::Enter | Key::Char('y') => Some(Message::ControlService(name, action)),
+            // Esc is not used for cancelling, because the menu would receive it after closing the dialog
+            Key::Char('n') => Some(Message::Redraw),
+            _ => return None,
+        };
+        self.close_action_dialog();
+        message
+    }
+
+    fn render_action_dialog(&self, frame: &mut Frame, area: Rect) {
+        if let Some((name, action)) = &self.action_dialog {
+            let popup_area = centered_rect(50, 6, area);
+            let text = format!(
+                "{} {}?\n\nEnter/y: confirm   n: cancel",
+                capitalize(&action.to_string()),
+                name
+            );
+            let popup = Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::bordered()
+                        .title(format!("{} service", capitalize(&action.to_string())))
+                        .title_alignment(Alignment::Center)
+                        .red(),
+                );
+
+            frame.render_widget(Clear, popup_area);
+            frame.render_widget(popup, popup_area);
+        }
+    }
+}
+
+impl MockComponent for Services {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let services = &self.services_info.services;
+        let failed = services
+            .iter()
+            .filter(|service| service.is_failed())
+            .count();
+        let title = match failed {
+            0 => format!("Services ({})", services.len()),
+            failed => format!("Services ({}, {} failed)", services.len(), failed),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_bottom(" s: start  x: stop  r: restart ")
+            .title_alignment(Alignment::Center);
+        if services.is_empty() {
+            let message = Paragraph::new("No services were found, systemd may not be running")
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let format_size_options = size_format(self.units);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let rows = services.iter().map(|service| {
+            let style = if service.is_failed() {
+                Style::default().light_red()
+            } else if service.active_state == "active" {
+                Style::default()
+            } else {
+                Style::default().gray()
+            };
+            Row::new(vec![
+                Cell::from(service.name.as_str()),
+                Cell::from(format!("{}/{}", service.active_state, service.sub_state)),
+                Cell::from(service.active_since.map_or(String::new(), |since| {
+                    format_duration(now.saturating_sub(since))
+                })),
+                Cell::from(
+                    service
+                        .main_pid
+                        .map_or(String::new(), |pid| pid.to_string()),
+                ),
+                Cell::from(service.memory.map_or(String::new(), |memory| {
+                    memory.format_size(format_size_options)
+                })),
+                Cell::from(service.cpu_time.map_or(String::new(), format_cpu_time)),
+                Cell::from(service.description.as_str()),
+            ])
+            .style(style)
+        });
+
+        // the borders and the header are not part of a page
+        self.page_size = area.height.saturating_sub(3) as usize;
+        let table = Table::default()
+            .block(block)
+            .widths([
+                Constraint::Fill(1),
+                Constraint::Length(20),
+                Constraint::Length(14),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Fill(2),
+            ])
+            .header(
+                Row::new(vec![
+                    "name",
+                    "state",
+                    "active for",
+                    "pid",
+                    "memory",
+                    "cpu",
+                    "description",
+                ])
+                .bold(),
+            )
+            .row_highlight_style(Style::default().reversed())
+            .rows(rows);
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+        self.render_action_dialog(frame, area);
+    }
+}
+
+impl Component<Message, UserEvent> for Services {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Services(services_info)) => {
+                self.process_update(services_info);
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) if self.action_dialog.is_some() => {
+                self.on_action_dialog(code)
+            }
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let selected = self.table_state.selected();
+                match code {
+                    Key::Up => self.select(selected.map_or(0, |i| i.saturating_sub(1))),
+                    Key::Down => self.select(selected.map_or(0, |i| i + 1)),
+                    Key::PageUp => {
+                        self.select(selected.map_or(0, |i| i.saturating_sub(self.page_size)))
+                    }
+                    Key::PageDown => self.select(selected.map_or(0, |i| i + self.page_size)),
+                    Key::Char('s') => self.open_action_dialog(ServiceAction::Start),
+                    Key::Char('x') => self.open_action_dialog(ServiceAction::Stop),
+                    Key::Char('r') => self.open_action_dialog(ServiceAction::Restart),
+                    _ => return None,
+                }
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Formats the CPU time given in milliseconds, with the fraction of the seconds under a minute.
+fn format_cpu_time(milliseconds: u64) -> String {
+    match milliseconds {
+        0..60_000 => format!("{:.1}s", milliseconds as f64 / 1000.0),
+        _ => format_duration(milliseconds / 1000),
+    }
+}
+
+/// Returns `text` with its first letter in uppercase.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
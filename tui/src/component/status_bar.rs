@@ -0,0 +1,181 @@
+use super::{format_date_time, size_format};
+use crate::{Message, UserEvent};
+use core::config::Units;
+use core::SelfUsage;
+use humansize::FormatSize;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State, StateValue,
+};
+
+/// The time an error message is displayed for.
+const MESSAGE_DURATION: Duration = Duration::from_secs(10);
+
+/// Attribute holding the current polling interval in seconds to be displayed in the status bar.
+pub const POLLING_INTERVAL: &str = "_POLLING_INTERVAL";
+
+/// Attribute holding the name of the displayed host.
+pub const HOST_NAME: &str = "_HOST_NAME";
+
+/// Attribute holding the key bindings of the active tab, like `MenuState::key_hints`.
+pub const KEY_HINTS: &str = "_KEY_HINTS";
+
+/// Attribute set to `AttrValue::Flag(true)` while the updates of the system information are paused.
+pub const PAUSED: &str = "_PAUSED";
+
+/// Single line at the bottom of the screen displaying the key bindings of the active tab on the left,
+/// and whether the updates are paused, the name of the host, the polling interval and the time in UTC on the right.
+/// If ocelo measures its own cost, its CPU and memory usage is displayed among them too,
+/// highlighted while it is over its budget.
+///
+/// The last error, like a failed poll or a process which could not be killed, is displayed instead of
+/// the key bindings for a while. The errors of the background thread are received as user events,
+/// the errors of the application are set with `Attribute::Text`.
+///
+/// The state is the displayed error, or `State::None` when there is none.
+#[derive(Default)]
+pub struct StatusBar {
+    properties: Props,
+
+    /// The displayed message and the time it was received
+    message: Option<(String, Instant)>,
+
+    /// The last measured usage of ocelo, received with `UserEvent::SelfUsage`
+    self_usage: Option<SelfUsage>,
+
+    units: Units,
+}
+
+impl StatusBar {
+    /// Sets the prefixes of the displayed memory usage of ocelo.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    fn current_message(&self) -> Option<&str> {
+        self.message
+            .as_ref()
+            .filter(|(_, received)| received.elapsed() < MESSAGE_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    fn paused(&self) -> bool {
+        self.properties
+            .get_or(Attribute::Custom(PAUSED), AttrValue::Flag(false))
+            .unwrap_flag()
+    }
+
+    /// The indicators on the right, separated by spaces.
+    fn indicators(&self) -> Line<'static> {
+        let mut spans = Vec::new();
+        if self.paused() {
+            spans.push(" PAUSED ".black().on_yellow().bold());
+            spans.push(Span::from(" "));
+        }
+        if let Some(AttrValue::String(host_name)) =
+            self.properties.get(Attribute::Custom(HOST_NAME))
+        {
+            spans.push(host_name.bold());
+            spans.push(Span::from("  "));
+        }
+        if let Some(usage) = self.self_usage {
+            let cost = Span::from(format!(
+                "ocelo: {:.1}% {}",
+                usage.cpu_usage,
+                usage.memory.format_size(size_format(self.units))
+            ));
+            spans.push(if usage.is_over_budget() {
+                cost.yellow().bold()
+            } else {
+                cost
+            });
+            spans.push(Span::from("  "));
+        }
+        if let Some(AttrValue::Size(interval)) =
+            self.properties.get(Attribute::Custom(POLLING_INTERVAL))
+        {
+            spans.push(Span::from(format!("refresh: {}s", interval)));
+            spans.push(Span::from("  "));
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        // `2024-03-01 12:30:00 UTC` becomes `12:30:00 UTC`
+        let date_time = format_date_time(now);
+        let time = date_time.split_once(' ').map_or("", |(_, time)| time);
+        spans.push(Span::from(format!("{} ", time)));
+        Line::from(spans)
+    }
+}
+
+impl MockComponent for StatusBar {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if let (Attribute::Text, AttrValue::String(message)) = (attr, &value) {
+            self.message = Some((message.clone(), Instant::now()));
+        }
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        match self.current_message() {
+            Some(message) => State::One(StateValue::String(message.to_string())),
+            None => State::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let indicators = self.indicators();
+        let [left, right] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(indicators.width() as u16),
+        ])
+        .areas(area);
+
+        let line = match self.current_message() {
+            Some(message) => Line::from(format!(" {} ", message)).red().bold(),
+            None => {
+                let hints = match self.properties.get(Attribute::Custom(KEY_HINTS)) {
+                    Some(AttrValue::String(hints)) => hints,
+                    _ => String::new(),
+                };
+                let pause = if self.paused() { "resume" } else { "pause" };
+                Line::from(format!(" {}  F9: {}", hints, pause)).dark_gray()
+            }
+        };
+        frame.render_widget(Paragraph::new(line), left);
+        frame.render_widget(Paragraph::new(indicators), right);
+    }
+}
+
+impl Component<Message, UserEvent> for StatusBar {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Error(message)) => {
+                self.message = Some((message, Instant::now()));
+                Some(Message::Redraw)
+            }
+            Event::User(UserEvent::SelfUsage(usage)) => {
+                self.self_usage = Some(usage);
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
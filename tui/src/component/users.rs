@@ -0,0 +1,128 @@
+use super::{format_date_time, format_duration};
+use crate::{Message, UserEvent};
+use core::model::SessionsInfo;
+use ratatui::{
+    layout::{Alignment, Constraint},
+    style::{Style, Stylize},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+};
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::prelude::Rect,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+/// The idle time from which a terminal is displayed as idle, like `who -u` does.
+const IDLE_SECONDS: u64 = 60;
+
+/// Component for displaying the sessions of the logged in users,
+/// with the host they are logged in from and the time since their last input.
+///
+/// Controls:
+/// * Up/Down => moves the selection
+#[derive(Default)]
+pub struct Users {
+    properties: Props,
+
+    sessions_info: SessionsInfo,
+
+    /// Holds the selected row
+    table_state: TableState,
+}
+
+impl MockComponent for Users {
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.properties.set(attr, value);
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.properties.get(attr)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title(format!("Users ({})", self.sessions_info.sessions.len()))
+            .title_alignment(Alignment::Center);
+        if self.sessions_info.sessions.is_empty() {
+            let message = Paragraph::new("No users are logged in")
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let rows = self.sessions_info.sessions.iter().map(|session| {
+            let idle = match session.idle {
+                Some(idle) if idle >= IDLE_SECONDS => format_duration(idle),
+                Some(_) => ".".to_string(),
+                None => "?".to_string(),
+            };
+            Row::new(vec![
+                Cell::from(session.user.as_str()),
+                Cell::from(session.tty.as_str()),
+                Cell::from(session.host.as_deref().unwrap_or("local")),
+                Cell::from(format_date_time(session.login_time)),
+                Cell::from(idle),
+                Cell::from(session.pid.to_string()),
+            ])
+        });
+
+        let table = Table::default()
+            .block(block)
+            .widths([
+                Constraint::Length(16),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+                Constraint::Length(24),
+                Constraint::Length(14),
+                Constraint::Length(8),
+            ])
+            .header(Row::new(vec!["user", "tty", "from", "login", "idle", "pid"]).bold())
+            .row_highlight_style(Style::default().reversed())
+            .rows(rows);
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+}
+
+impl Component<Message, UserEvent> for Users {
+    fn on(&mut self, event: Event<UserEvent>) -> Option<Message> {
+        match event {
+            Event::User(UserEvent::Users(sessions_info)) => {
+                self.sessions_info = sessions_info;
+                // keep the selection within the sessions which are still present
+                let count = self.sessions_info.sessions.len();
+                if let Some(selected) = self.table_state.selected() {
+                    self.table_state
+                        .select(count.checked_sub(1).map(|last| selected.min(last)));
+                }
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                let previous = self
+                    .table_state
+                    .selected()
+                    .map_or(0, |i| i.saturating_sub(1));
+                self.table_state.select(Some(previous));
+                Some(Message::Redraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                let last = self.sessions_info.sessions.len().saturating_sub(1);
+                let next = self.table_state.selected().map_or(0, |i| (i + 1).min(last));
+                self.table_state.select(Some(next));
+                Some(Message::Redraw)
+            }
+            _ => None,
+        }
+    }
+}
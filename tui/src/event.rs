@@ -0,0 +1,141 @@
+use core::connections::Connection;
+use core::limits::SystemLimits;
+use core::model::{
+    ContainersInfo, CpuMemoryUpdate, CpuTopology, DiskInfo, LogsInfo, NetworkInfo, ProcessDelta,
+    ProcessInfo, ProcessList, SensorsInfo, ServicesInfo, SessionsInfo, SystemOverviewInfo,
+};
+use core::security::SecurityOverview;
+use core::{Alert, ConnectionStatus, CpuInfo, SelfUsage, SystemInfoUpdate};
+use std::cmp;
+use std::mem::discriminant;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use tuirealm::listener::{ListenerResult, Poll};
+use tuirealm::Event;
+
+/// Application specific events delivered to the components beside the terminal input events.
+/// The system information updates are passed to the components as they are,
+/// without serializing them.
+///
+/// Two events are equal when they are the same kind of update regardless of their payload,
+/// so a component can subscribe to a kind of update with a default payload, like
+/// `SubEventClause::User(UserEvent::Disk(DiskInfo::default()))`.
+#[derive(Clone, Debug)]
+pub enum UserEvent {
+    Alerts(Vec<Alert>),
+    Connection(ConnectionStatus),
+    Connections(Vec<Connection>),
+    Containers(ContainersInfo),
+    CpuAndMemory(CpuMemoryUpdate),
+    /// The processor information and layout, answering `PollerCommand::RequestCpuInfo`
+    CpuInfo(Box<(CpuInfo, CpuTopology)>),
+    Disk(DiskInfo),
+    /// Polling the system information has failed for the given reason
+    Error(String),
+    Limits(SystemLimits),
+    Logs(LogsInfo),
+    Network(Box<NetworkInfo>),
+    OverView(Box<SystemOverviewInfo>),
+    Process(ProcessList),
+    /// The changes of the process list since the previous `Process` or `ProcessDelta` event
+    ProcessDelta(ProcessDelta),
+    /// The details of a process, answering `PollerCommand::RequestProcessDetails`
+    ProcessDetails(Box<ProcessInfo>),
+    Security(SecurityOverview),
+    /// The resources used by ocelo itself, measured after every poll if enabled
+    SelfUsage(SelfUsage),
+    Sensors(SensorsInfo),
+    Services(ServicesInfo),
+    Users(SessionsInfo),
+}
+
+impl From<SystemInfoUpdate> for UserEvent {
+    fn from(update: SystemInfoUpdate) -> Self {
+        match update {
+            SystemInfoUpdate::Alerts(alerts) => Self::Alerts(alerts),
+            SystemInfoUpdate::Connection(status) => Self::Connection(status),
+            SystemInfoUpdate::Connections(connections) => Self::Connections(connections),
+            SystemInfoUpdate::Containers(containers) => Self::Containers(containers),
+            SystemInfoUpdate::CpuAndMemory(cpu_update) => Self::CpuAndMemory(cpu_update),
+            SystemInfoUpdate::Disk(disk_info) => Self::Disk(disk_info),
+            SystemInfoUpdate::Error(message) => Self::Error(message),
+            SystemInfoUpdate::Limits(limits) => Self::Limits(limits),
+            SystemInfoUpdate::Security(security) => Self::Security(security),
+            SystemInfoUpdate::Logs(logs) => Self::Logs(logs),
+            SystemInfoUpdate::Network(network_info) => Self::Network(network_info),
+            SystemInfoUpdate::OverView(overview) => Self::OverView(overview),
+            SystemInfoUpdate::Process(process_list) => Self::Process(process_list),
+            SystemInfoUpdate::ProcessDelta(delta) => Self::ProcessDelta(delta),
+            SystemInfoUpdate::Sensors(sensors) => Self::Sensors(sensors),
+            SystemInfoUpdate::Services(services) => Self::Services(services),
+            SystemInfoUpdate::Users(sessions) => Self::Users(sessions),
+        }
+    }
+}
+
+impl PartialEq for UserEvent {
+    fn eq(&self, other: &Self) -> bool {
+        discriminant(self) == discriminant(other)
+    }
+}
+
+impl Eq for UserEvent {}
+
+impl PartialOrd for UserEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        if self == other {
+            Some(cmp::Ordering::Equal)
+        } else {
+            None
+        }
+    }
+}
+
+/// Event source forwarding the updates of the background polling thread to the application.
+/// The updates are tagged with the generation of the displayed host they were polled from,
+/// the updates of the previously displayed hosts still waiting in the channel are dropped.
+/// While the display is paused, only the errors, the alerts, the connection status,
+/// the usage of ocelo and the answers of the requests are forwarded, the system information is dropped
+/// so the displayed values stay still.
+pub struct SystemInfoPort {
+    rx: Receiver<(usize, UserEvent)>,
+    generation: Arc<AtomicUsize>,
+    paused: Arc<AtomicBool>,
+}
+
+impl SystemInfoPort {
+    pub fn new(
+        rx: Receiver<(usize, UserEvent)>,
+        generation: Arc<AtomicUsize>,
+        paused: Arc<AtomicBool>,
+    ) -> Self {
+        SystemInfoPort {
+            rx,
+            generation,
+            paused,
+        }
+    }
+}
+
+impl Poll<UserEvent> for SystemInfoPort {
+    fn poll(&mut self) -> ListenerResult<Option<Event<UserEvent>>> {
+        let current_generation = self.generation.load(Ordering::SeqCst);
+        while let Ok((generation, event)) = self.rx.try_recv() {
+            let dropped = self.paused.load(Ordering::SeqCst)
+                && !matches!(
+                    event,
+                    UserEvent::Error(_)
+                        | UserEvent::Alerts(_)
+                        | UserEvent::Connection(_)
+                        | UserEvent::CpuInfo(_)
+                        | UserEvent::ProcessDetails(_)
+                        | UserEvent::SelfUsage(_)
+                );
+            if generation == current_generation && !dropped {
+                return Ok(Some(Event::User(event)));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -1,4 +1,7 @@
 pub mod component;
+mod event;
+mod polling;
 mod view;
 
+pub use self::event::*;
 pub use self::view::*;
@@ -0,0 +1,259 @@
+use crate::UserEvent;
+use core::model::ProcessColumn;
+use core::{
+    AlertHooks, ConnectionStatus, DataSource, PollingScheduler, Recorder, SelfMonitor,
+    SystemInfoPollingContext, SystemInfoUpdate,
+};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The user interface has been closed, so nothing is polled for it anymore.
+struct Closed;
+
+/// Commands controlling the background thread polling the system information.
+/// The user interface only sends commands, so it never waits for the system information to be read.
+pub enum PollerCommand {
+    /// Polls only the given contexts from now on, each at its own interval.
+    /// The contexts which were not polled yet are polled right away
+    SetContexts(Vec<SystemInfoPollingContext>),
+    /// Changes the time between two polls of the given context
+    SetInterval(SystemInfoPollingContext, Duration),
+    /// Polls the scheduled contexts right away, fetching the whole process list
+    RequestSnapshot,
+    /// Fetches the processor information and layout, answered with `UserEvent::CpuInfo`
+    RequestCpuInfo,
+    /// Fetches the details of the process with the given pid, answered with `UserEvent::ProcessDetails`
+    RequestProcessDetails(u32),
+    /// Reads the values of the processes which are slow to read only for the given displayed columns
+    SetProcessColumns(Vec<ProcessColumn>),
+    /// Polls the host with the given index from now on, tagging its updates with the given generation
+    SwitchHost(usize, usize),
+    /// Stops polling, ending the thread once the current poll is finished
+    Shutdown,
+}
+
+/// Polls the system information of the displayed host in a background thread, see `PollerCommand`.
+/// The updates are sent to the user interface tagged with the generation of the host they were polled from.
+///
+/// # Example:
+/// ```ignore
+/// let (commands, thread) = Poller::new(vec![Box::new(source)], scheduler, tx).spawn();
+/// commands.send(PollerCommand::SetContexts(vec![SystemInfoPollingContext::Processes]))?;
+/// commands.send(PollerCommand::Shutdown)?;
+/// thread.join();
+/// ```
+pub struct Poller {
+    /// The sources of the hosts which can be displayed
+    sources: Vec<Box<dyn DataSource>>,
+    /// Index of the displayed host in `sources`
+    current: usize,
+    /// The generation of the displayed host, changed by the user interface at every host switch
+    generation: usize,
+    scheduler: PollingScheduler,
+    updates: Sender<(usize, UserEvent)>,
+    /// Appends every polled update, if given
+    recorder: Option<Recorder>,
+    /// Dispatches the raised alerts to the user-defined hooks
+    hooks: AlertHooks,
+    /// Reports the reachability of the local system too, which is only displayed among other hosts
+    report_local_connection: bool,
+    /// Measures the cost of ocelo after every poll, if given
+    self_monitor: Option<SelfMonitor>,
+}
+
+impl Poller {
+    /// Polls the first of `sources` as scheduled by `scheduler`, sending the updates on `updates`.
+    pub fn new(
+        sources: Vec<Box<dyn DataSource>>,
+        scheduler: PollingScheduler,
+        updates: Sender<(usize, UserEvent)>,
+    ) -> Self {
+        let report_local_connection = sources.len() > 1;
+        Poller {
+            sources,
+            current: 0,
+            generation: 0,
+            scheduler,
+            updates,
+            recorder: None,
+            hooks: AlertHooks::new(Vec::new()),
+            report_local_connection,
+            self_monitor: None,
+        }
+    }
+
+    /// Appends every polled update to the `recorder` if given.
+    pub fn with_recorder(mut self, recorder: Option<Recorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Dispatches the raised alerts to the `hooks`.
+    pub fn with_hooks(mut self, hooks: AlertHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Sends the CPU and memory usage of ocelo with `UserEvent::SelfUsage` after every poll if `enabled`.
+    pub fn with_self_usage(mut self, enabled: bool) -> Self {
+        self.self_monitor = enabled.then(SelfMonitor::default);
+        self
+    }
+
+    /// Reads the values of the processes which are slow to read only for the displayed `columns`.
+    pub fn with_process_columns(mut self, columns: Vec<ProcessColumn>) -> Self {
+        self.set_process_columns(columns);
+        self
+    }
+
+    /// Sets the displayed `columns` of the processes on every source, so they are kept after a host switch.
+    fn set_process_columns(&mut self, columns: Vec<ProcessColumn>) {
+        for source in &mut self.sources {
+            source.set_process_columns(columns.clone());
+        }
+    }
+
+    /// Starts polling in a new thread, returning the channel of its commands and the thread to join.
+    /// The thread stops on `PollerCommand::Shutdown` or when the user interface is closed.
+    pub fn spawn(self) -> (Sender<PollerCommand>, JoinHandle<()>) {
+        let (commands, receiver) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            // the only failure is the user interface being closed
+            let _ = self.run(receiver);
+        });
+        (commands, thread)
+    }
+
+    fn run(mut self, commands: Receiver<PollerCommand>) -> Result<(), Closed> {
+        loop {
+            let due = self.scheduler.due(Instant::now());
+            if !due.is_empty() {
+                self.poll(due)?;
+            }
+
+            // waiting on the commands instead of sleeping, so they are served right away
+            match commands.recv_timeout(self.scheduler.until_next(Instant::now())) {
+                Ok(command) => {
+                    self.execute(command)?;
+                    // the commands piled up during the poll are served before the next one
+                    while let Ok(command) = commands.try_recv() {
+                        self.execute(command)?;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+    }
+
+    fn execute(&mut self, command: PollerCommand) -> Result<(), Closed> {
+        match command {
+            PollerCommand::SetContexts(contexts) => {
+                self.scheduler.reschedule(&contexts);
+                self.sources[self.current].restart_process_changes();
+            }
+            PollerCommand::SetInterval(ctx, interval) => {
+                self.scheduler.set_interval(ctx, interval);
+                self.scheduler.poll_now();
+            }
+            PollerCommand::RequestSnapshot => {
+                self.sources[self.current].restart_process_changes();
+                self.scheduler.poll_now();
+            }
+            PollerCommand::RequestCpuInfo => {
+                let source = &mut self.sources[self.current];
+                let cpu_info = source.cpu_info().unwrap_or_default();
+                let topology = source.cpu_topology().unwrap_or_default();
+                self.send(UserEvent::CpuInfo(Box::new((cpu_info, topology))))?;
+            }
+            PollerCommand::RequestProcessDetails(pid) => {
+                let event = match self.sources[self.current].process_details(pid) {
+                    Ok(details) => UserEvent::ProcessDetails(Box::new(details)),
+                    Err(error) => UserEvent::Error(format!(
+                        "Failed to get the details of the process: {}",
+                        error
+                    )),
+                };
+                self.send(event)?;
+            }
+            PollerCommand::SetProcessColumns(columns) => self.set_process_columns(columns),
+            PollerCommand::SwitchHost(index, generation) if index < self.sources.len() => {
+                self.current = index;
+                self.generation = generation;
+                self.sources[self.current].restart_process_changes();
+                self.scheduler.poll_now();
+            }
+            PollerCommand::SwitchHost(..) => {}
+            PollerCommand::Shutdown => return Err(Closed),
+        }
+        Ok(())
+    }
+
+    /// Polls the `due` contexts of the displayed host and evaluates the alerts,
+    /// sending the updates, the reachability of the host and the failures.
+    fn poll(&mut self, due: Vec<SystemInfoPollingContext>) -> Result<(), Closed> {
+        let source = &mut self.sources[self.current];
+        let is_remote = source.remote_address().is_some();
+        let results: Vec<_> = due.into_iter().map(|ctx| source.poll(ctx)).collect();
+        let alerts = source.evaluate_alerts();
+
+        if self.report_local_connection || is_remote {
+            let status = match results.iter().find_map(|result| result.as_ref().err()) {
+                Some(error) if is_remote => ConnectionStatus::Disconnected(error.to_string()),
+                // the local system is always reachable, its failures are displayed in the status bar
+                _ => ConnectionStatus::Connected,
+            };
+            self.send(UserEvent::Connection(status))?;
+        }
+
+        let mut updates = Vec::new();
+        for result in results {
+            match result {
+                Ok(update) => updates.push(update),
+                Err(error) if !is_remote => updates.push(SystemInfoUpdate::Error(format!(
+                    "Failed to poll the system information: {}",
+                    error
+                ))),
+                Err(_) => {}
+            }
+        }
+        match alerts {
+            Ok(alerts) if !alerts.is_empty() => {
+                core::notification::notify(&alerts);
+                self.hooks.dispatch(&alerts);
+                updates.push(SystemInfoUpdate::Alerts(alerts))
+            }
+            Ok(_) => {}
+            Err(error) => updates.push(SystemInfoUpdate::Error(format!(
+                "Failed to evaluate the alerts: {}",
+                error
+            ))),
+        }
+        updates.extend(self.hooks.failures().into_iter().map(|error| {
+            SystemInfoUpdate::Error(format!("Failed to run the alert hook: {}", error))
+        }));
+
+        for update in updates {
+            // the failures are only displayed, they are not part of the recording
+            let is_error = matches!(update, SystemInfoUpdate::Error(_));
+            if let Some(recorder) = self.recorder.as_mut().filter(|_| !is_error) {
+                if let Err(error) = recorder.record(&update) {
+                    let message = format!("Failed to record the update: {}", error);
+                    self.send(UserEvent::Error(message))?;
+                }
+            }
+            self.send(UserEvent::from(update))?;
+        }
+        if let Some(usage) = self.self_monitor.as_mut().and_then(SelfMonitor::measure) {
+            self.send(UserEvent::SelfUsage(usage))?;
+        }
+        Ok(())
+    }
+
+    fn send(&self, event: UserEvent) -> Result<(), Closed> {
+        self.updates
+            .send((self.generation, event))
+            .map_err(|_| Closed)
+    }
+}
@@ -1,23 +1,65 @@
-use crate::component::{CpuMemoryDetails, Menu, MenuState, OverView, Processes};
-use core::model::process_list_to_json;
-use core::{SharedSystemInfoPoller, SystemInfoPoller, SystemInfoPollingContext, SystemInfoUpdate};
+use crate::component::{
+    format_date_time, AlertLog, Connections, Containers, CpuMemoryDetails, DirectoryUsage,
+    DiskDetails, Limits, Logs, Menu, MenuState, NetworkDetails, OverView, ProcessDetails,
+    Processes, Security, Sensors, Services, StatusBar, Users, CURRENT_HOST, HOST_NAME, KEY_HINTS,
+    MODAL, PAUSED, POLLING_INTERVAL,
+};
+use crate::polling::{Poller, PollerCommand};
+use crate::{SystemInfoPort, UserEvent};
+use core::limits::SystemLimits;
+use core::model::{
+    ContainersInfo, CpuMemoryUpdate, DiskInfo, LogsInfo, ProcessColumn, ProcessDelta, ProcessInfo,
+    ProcessList, SensorsInfo, ServicesInfo, SessionsInfo,
+};
+use core::security::SecurityOverview;
+use core::{
+    AlertHooks, Config, ContainerAction, DataSource, ExportFormat, History, OceloError,
+    PollingScheduler, ProcessController, ProcessSignal, ProviderSource, Recorder, RemoteSource,
+    SelfUsage, ServiceAction, SystemInfoPoller, SystemInfoPollingContext, SystemInfoProvider,
+    SystemInfoUpdate, DEFAULT_POLLING_INTERVAL, DEFAULT_RETENTION,
+};
+use ratatui::backend::{Backend, TermionBackend};
 use ratatui::layout::{Constraint, Layout};
-use std::sync::mpsc::{self, Receiver};
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
-use tuirealm::terminal::{TerminalBridge, TermionTerminalAdapter};
+use ratatui::style::Color;
+use ratatui::Terminal;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Stdout};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use termion::input::MouseTerminal;
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::screen::{AlternateScreen, IntoAlternateScreen};
 use tuirealm::{
-    Application, AttrValue, Attribute, EventListenerCfg, NoUserEvent, PollStrategy, Sub, SubClause,
-    Update,
+    Application, AttrValue, Attribute, EventListenerCfg, PollStrategy, Sub, SubClause,
+    SubEventClause, Update,
 };
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Components {
+    AlertLog,
+    Connections,
+    Containers,
     CpuDetails,
+    DirectoryUsage,
+    DiskDetails,
+    Limits,
+    Logs,
     Menu,
+    NetworkDetails,
     Overvieww,
+    ProcessDetails,
     Processes,
+    Security,
+    Sensors,
+    Services,
+    StatusBar,
+    Users,
 }
 
 impl From<&MenuState> for Components {
@@ -26,7 +68,13 @@ impl From<&MenuState> for Components {
             MenuState::OverView => Self::Overvieww,
             MenuState::CpuMemoryDetails => Self::CpuDetails,
             MenuState::ProcessDetails => Self::Processes,
-            _ => Self::Overvieww,
+            MenuState::DiskDetails => Self::DiskDetails,
+            MenuState::NetworkDetails => Self::NetworkDetails,
+            MenuState::SensorsDetails => Self::Sensors,
+            MenuState::UsersDetails => Self::Users,
+            MenuState::ServicesDetails => Self::Services,
+            MenuState::ContainersDetails => Self::Containers,
+            MenuState::LogsDetails => Self::Logs,
         }
     }
 }
@@ -36,10 +84,80 @@ pub enum Message {
     ChangeNextMenu,
     ChangePreviousMenu,
     Quit,
+    /// A component has received new data and the user interface needs to be re-rendered
+    Redraw,
+    /// Shortens the time between two polls of the system information
+    SpeedUpPolling,
+    /// Lengthens the time between two polls of the system information
+    SlowDownPolling,
+    /// Asks the process with the given pid to terminate
+    TerminateProcess(u32),
+    /// Kills the process with the given pid immediately
+    KillProcess(u32),
+    /// Shows or hides the log of the raised alerts
+    ToggleAlertLog,
+    /// Stops or continues updating the displayed system information
+    TogglePause,
+    /// Displays the system information of the host with the given index
+    SwitchHost(usize),
+    /// Sends the signal to the process with the given pid
+    SendSignal(u32, ProcessSignal),
+    /// Changes the nice value of the process with the given pid
+    SetPriority(u32, i32),
+    /// Restricts the process with the given pid to run only on the given cores
+    SetAffinity(u32, Vec<usize>),
+    /// Displays the details of the process with the given pid over the process list
+    ShowProcessDetails(u32),
+    /// Displays the fetched details of a process over the process list
+    DisplayProcessDetails(Box<ProcessInfo>),
+    /// The process list displays the given columns from now on
+    ChangeProcessColumns(Vec<ProcessColumn>),
+    /// Closes the details of the process
+    CloseProcessDetails,
+    /// Displays the connections of the system over the network interfaces
+    ShowConnections,
+    /// Closes the connections
+    CloseConnections,
+    /// Displays the kernel tunables and the resource limits of the system over the overview
+    ShowLimits,
+    /// Closes the limits
+    CloseLimits,
+    /// Displays the failed logins, the exposed ports and the firewall of the system over the overview
+    ShowSecurity,
+    /// Closes the security overview
+    CloseSecurity,
+    /// Displays the largest directories and files under the given mount point over the storage devices
+    ShowDiskUsage(PathBuf),
+    /// Closes the disk usage, cancelling its scan
+    CloseDiskUsage,
+    /// Takes the action on the service unit with the given name
+    ControlService(String, ServiceAction),
+    /// Takes the action on the container with the given id
+    ControlContainer(String, ContainerAction),
+    /// Writes the exported samples of the named chart into a new file in the format
+    ExportChart(String, ExportFormat, String),
     Tick,
 }
 
-pub struct View {
+/// The amount of time the polling interval is changed by at a time.
+const POLLING_INTERVAL_STEP: Duration = Duration::from_secs(1);
+
+/// The longest pause between two replayed snapshots, skipping the periods when nothing was recorded.
+const MAX_REPLAY_PAUSE: Duration = Duration::from_secs(5);
+
+/// A system which can be displayed, switched between with the number keys.
+/// Its source is polled by the background thread, at the same index.
+struct HostSource {
+    name: String,
+    /// True for the local system, whose processes can be signaled
+    local: bool,
+}
+
+/// The output of the terminal the user interface is drawn on by default, in raw mode and in the alternate screen.
+type TermionOutput = MouseTerminal<AlternateScreen<RawTerminal<Stdout>>>;
+
+/// The user interface, drawn by the ratatui `B`ackend, the terminal by default.
+pub struct View<B: Backend = TermionBackend<TermionOutput>> {
     /// The currently selected tab in the upper menu
     current_tab: MenuState,
 
@@ -50,266 +168,1301 @@ pub struct View {
     /// Should only set to true when there are changes in the user interface.
     redraw: bool,
 
-    tuirealm: Application<Components, Message, NoUserEvent>,
-    terminal: TerminalBridge<TermionTerminalAdapter>,
+    tuirealm: Application<Components, Message, UserEvent>,
+    terminal: Terminal<B>,
+
+    /// Controls the background thread polling the system information
+    commands: Sender<PollerCommand>,
+    /// The background thread polling the system information, `None` once it is joined
+    poller: Option<JoinHandle<()>>,
+    /// The polled contexts and their intervals, as requested from the background thread
+    schedule: PollingScheduler,
+    /// True while a recording is displayed, nothing is polled then
+    replaying: bool,
 
-    system_info: SharedSystemInfoPoller,
+    /// The hosts which can be displayed
+    hosts: Vec<HostSource>,
+    /// Index of the displayed host in `hosts`
+    current_host: usize,
+    /// Incremented at every host switch, so the updates of the previous host are dropped
+    host_generation: Arc<AtomicUsize>,
+    /// True while the updates of the system information are dropped instead of displayed
+    paused: Arc<AtomicBool>,
 
-    /// Receives updates from the background thread.
-    sysinfo_rx: Receiver<SystemInfoUpdate>,
+    /// Signals the displayed processes, `None` if they are not running on the local system,
+    /// like the processes of a remote agent or a replayed recording
+    process_controller: Option<ProcessController>,
+
+    /// User settings applied on the components
+    config: Config,
 }
 
-impl Default for View {
-    /// Initializing terminal with termion terminal backend and ratatui renderer
-    fn default() -> Self {
-        let mut terminal = TerminalBridge::new_termion();
-        terminal.clear_screen().expect("Failed to clear screen!");
-        terminal
-            .raw_mut()
-            .hide_cursor()
-            .expect("Failed to hide cursor!");
-        let mut tuirealm = Application::init(
-            // 30 fps
-            EventListenerCfg::default().termion_input_listener(Duration::from_millis(33), 1),
-        );
+impl View {
+    /// Displays the live system information polled in the background, applying the user settings from `config`.
+    /// Every update is also appended to the `recorder` if given.
+    pub fn new(config: Config, recorder: Option<Recorder>) -> Result<Self, OceloError> {
+        let mut poller = SystemInfoPoller::default()
+            .with_alert_rules(config.alerts.clone())
+            .with_network_accounting(config.network_accounting)
+            .with_ping_targets(config.ping_targets.clone())
+            .with_public_ip(config.public_ip.clone())
+            .with_cloud_metadata(config.cloud_metadata)
+            .with_clock_offset(config.ntp_server.clone())
+            .with_packages()
+            // a replay could only start from the whole process list
+            .with_incremental_processes(config.incremental_processes && recorder.is_none())
+            .with_disk_filter(config.disks.clone());
+        poller.init();
+        Self::with_provider(config, poller, recorder)
+    }
 
-        let overview = OverView::default();
+    /// Displays the system information of `provider` as the local system,
+    /// like the canned snapshots of a `MockProvider` to exercise the user interface deterministically.
+    /// Every update is also appended to the `recorder` if given.
+    pub fn with_provider<P: SystemInfoProvider + 'static>(
+        config: Config,
+        provider: P,
+        recorder: Option<Recorder>,
+    ) -> Result<Self, OceloError> {
+        let source = ProviderSource::new(provider);
+        Self::with_source(config, "local".to_string(), Box::new(source), recorder)
+    }
 
-        tuirealm
-            .mount(
-                Components::Menu,
-                Box::new(Menu::default()),
-                vec![Sub::new(tuirealm::SubEventClause::Any, SubClause::Always)],
-            )
-            .unwrap();
-        tuirealm
-            .mount(Components::Overvieww, Box::new(overview), vec![])
-            .expect("Failed to mount overview component!");
-        tuirealm.active(&Components::Overvieww).unwrap();
+    /// Displays the system information fetched from `source` in the background,
+    /// like a remote agent instead of the local system.
+    /// The remote agents of the configuration can be switched to beside the host `name`.
+    /// Every update is also appended to the `recorder` if given.
+    pub fn with_source(
+        config: Config,
+        name: String,
+        source: Box<dyn DataSource>,
+        recorder: Option<Recorder>,
+    ) -> Result<Self, OceloError> {
+        let terminal = termion_terminal()?;
+        Self::with_sources(config, terminal, true, name, source, recorder)
+    }
 
+    /// Displays the recorded `snapshots` instead of the live system information,
+    /// keeping the time between them divided by `speed`.
+    pub fn replay(
+        config: Config,
+        snapshots: Vec<(f64, SystemInfoUpdate)>,
+        speed: f64,
+    ) -> Result<Self, OceloError> {
+        // the live poller only provides the static information, like the name of the processor,
+        // nothing is scheduled so it only answers the requests
         let mut poller = SystemInfoPoller::default();
         poller.init();
-        let shared_poller = Arc::new(Mutex::new(poller));
-        let poller_clone = shared_poller.clone();
-
         let (tx, rx) = mpsc::channel();
-        thread::spawn(move || loop {
-            match poller_clone.lock() {
-                Ok(mut poller) => {
-                    let ctx = poller.polling_context();
-                    let update = SystemInfoUpdate::from((&ctx, &mut *poller));
-
-                    if let Err(error) = tx.send(update) {
-                        eprintln!("Failed to send system info update: {}", error);
-                        break;
-                    }
+        let live_poller = Poller::new(
+            vec![Box::new(ProviderSource::new(poller))],
+            PollingScheduler::new(config.refresh_interval()),
+            tx.clone(),
+        )
+        .spawn();
+
+        thread::spawn(move || {
+            let mut previous_timestamp: Option<f64> = None;
+            for (timestamp, update) in snapshots {
+                if let Some(previous) = previous_timestamp {
+                    let pause = Duration::from_secs_f64(((timestamp - previous) / speed).max(0.0));
+                    thread::sleep(pause.min(MAX_REPLAY_PAUSE));
                 }
-                Err(error) => eprintln!("Error acquiring polling context lock: {}", error),
-            }
+                previous_timestamp = Some(timestamp);
 
-            thread::sleep(Duration::from_secs(3));
+                if tx.send((0, UserEvent::from(update))).is_err() {
+                    // the user interface has been closed
+                    break;
+                }
+            }
         });
 
-        View {
-            current_tab: MenuState::default(),
+        let terminal = termion_terminal()?;
+        Self::init(config, terminal, true, live_poller, rx, Vec::new(), true)
+    }
+}
+
+impl<B: Backend> View<B> {
+    /// Displays the system information of `provider` as the local system on `terminal`,
+    /// like a `TestBackend` drawing into a buffer, without listening to the keyboard.
+    /// It is driven by `Self::tick()` and the messages passed to `Self::update()`,
+    /// so the user interface can be exercised with the canned snapshots of a `MockProvider`.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// use core::{Config, MockProvider};
+    /// use ratatui::{backend::TestBackend, Terminal};
+    /// use tui::View;
+    ///
+    /// let terminal = Terminal::new(TestBackend::new(140, 40)).unwrap();
+    /// let provider = MockProvider::new(Vec::new());
+    /// let mut view = View::with_backend(Config::default(), provider, terminal).unwrap();
+    /// view.tick();
+    /// println!("{:?}", view.terminal().backend().buffer());
+    /// ```
+    pub fn with_backend<P: SystemInfoProvider + 'static>(
+        config: Config,
+        provider: P,
+        terminal: Terminal<B>,
+    ) -> Result<Self, OceloError> {
+        let source = ProviderSource::new(provider);
+        Self::with_sources(
+            config,
+            terminal,
+            false,
+            "local".to_string(),
+            Box::new(source),
+            None,
+        )
+    }
+
+    /// Displays the system information fetched from `source` on the `terminal`,
+    /// listening to its keyboard if `keyboard` is set, see `Self::with_source`.
+    fn with_sources(
+        config: Config,
+        terminal: Terminal<B>,
+        keyboard: bool,
+        name: String,
+        source: Box<dyn DataSource>,
+        recorder: Option<Recorder>,
+    ) -> Result<Self, OceloError> {
+        let is_local = source.remote_address().is_none();
+        let mut hosts = vec![HostSource {
+            name,
+            local: is_local,
+        }];
+        let mut remote_sources: Vec<Box<dyn DataSource>> = Vec::new();
+        for host in &config.hosts {
+            let source = RemoteSource::from_host(host).with_alert_rules(config.alerts.clone());
+            remote_sources.push(Box::new(source));
+            hosts.push(HostSource {
+                name: host.name.clone(),
+                local: false,
+            });
+        }
+        let mut sources = vec![source];
+        sources.extend(remote_sources);
+        let scheduler = PollingScheduler::new(config.refresh_interval());
+
+        let (tx, rx) = mpsc::channel();
+        let poller = Poller::new(sources, scheduler, tx)
+            .with_recorder(recorder)
+            .with_hooks(AlertHooks::new(config.alert_hooks.clone()))
+            .with_self_usage(config.show_self_usage)
+            // the default columns have no values which are slow to read
+            .with_process_columns(config.process_columns.clone())
+            .spawn();
+
+        Self::init(config, terminal, keyboard, poller, rx, hosts, false)
+    }
+
+    /// Initializing the terminal with the ratatui renderer, listening to its keyboard if `keyboard` is set,
+    /// then mounting the components which display the updates received on `updates`
+    /// from the spawned `poller`.
+    /// The processes can only be signaled if the first of the `hosts` is the local system.
+    fn init(
+        config: Config,
+        mut terminal: Terminal<B>,
+        keyboard: bool,
+        poller: (Sender<PollerCommand>, JoinHandle<()>),
+        updates: Receiver<(usize, UserEvent)>,
+        hosts: Vec<HostSource>,
+        replaying: bool,
+    ) -> Result<Self, OceloError> {
+        terminal.clear().map_err(ui_error)?;
+        terminal.hide_cursor().map_err(ui_error)?;
+
+        let (commands, poller) = poller;
+        let schedule = PollingScheduler::new(config.refresh_interval());
+        let interval = schedule.interval(SystemInfoPollingContext::Overview);
+        let host_generation = Arc::new(AtomicUsize::new(0));
+        let paused = Arc::new(AtomicBool::new(false));
+        let mut listener = EventListenerCfg::default()
+            // lets the components follow their background work, like the progress of a disk usage scan
+            .tick_interval(Duration::from_millis(250))
+            // receive the updates of the background thread as user events
+            .add_port(
+                Box::new(SystemInfoPort::new(
+                    updates,
+                    host_generation.clone(),
+                    paused.clone(),
+                )),
+                Duration::from_millis(100),
+                1,
+            );
+        if keyboard {
+            // 30 fps
+            listener = listener.termion_input_listener(Duration::from_millis(33), 1);
+        }
+        let mut tuirealm = Application::init(listener);
+        let process_controller = hosts
+            .first()
+            .is_some_and(|host| host.local)
+            .then(ProcessController::default);
+
+        let overview = OverView::default()
+            .with_history(History::with_retention(DEFAULT_RETENTION, interval))
+            .with_units(config.units)
+            .with_chart_style(config.chart_style);
+        // invalid colors fall back to the defaults
+        let highlight_color = Color::from_str(&config.theme.highlight).unwrap_or(Color::Yellow);
+        let text_color = Color::from_str(&config.theme.text).unwrap_or(Color::White);
+
+        tuirealm
+            .mount(
+                Components::Menu,
+                Box::new(
+                    Menu::default()
+                        .with_colors(highlight_color, text_color)
+                        .with_hosts(hosts.iter().map(|host| host.name.clone()).collect()),
+                ),
+                // the global key bindings are disabled while the processes, the services or the containers display a dialog,
+                // or the logs are being searched, or the details of a process, the connections or the disk usage are displayed
+                vec![Sub::new(
+                    SubEventClause::Any,
+                    SubClause::and(
+                        SubClause::and(
+                            SubClause::not(SubClause::HasAttrValue(
+                                Components::Processes,
+                                Attribute::Custom(MODAL),
+                                AttrValue::Flag(true),
+                            )),
+                            SubClause::and(
+                                SubClause::not(SubClause::HasAttrValue(
+                                    Components::Services,
+                                    Attribute::Custom(MODAL),
+                                    AttrValue::Flag(true),
+                                )),
+                                SubClause::and(
+                                    SubClause::not(SubClause::HasAttrValue(
+                                        Components::Containers,
+                                        Attribute::Custom(MODAL),
+                                        AttrValue::Flag(true),
+                                    )),
+                                    SubClause::not(SubClause::HasAttrValue(
+                                        Components::Logs,
+                                        Attribute::Custom(MODAL),
+                                        AttrValue::Flag(true),
+                                    )),
+                                ),
+                            ),
+                        ),
+                        SubClause::and(
+                            SubClause::not(SubClause::IsMounted(Components::ProcessDetails)),
+                            SubClause::and(
+                                SubClause::not(SubClause::IsMounted(Components::Connections)),
+                                SubClause::and(
+                                    SubClause::not(SubClause::IsMounted(Components::Limits)),
+                                    SubClause::and(
+                                        SubClause::not(SubClause::IsMounted(Components::Security)),
+                                        SubClause::not(SubClause::IsMounted(
+                                            Components::DirectoryUsage,
+                                        )),
+                                    ),
+                                ),
+                            ),
+                        ),
+                    ),
+                )],
+            )
+            .map_err(ui_error)?;
+        tuirealm
+            .mount(
+                Components::Overvieww,
+                Box::new(overview),
+                vec![Sub::new(
+                    SubEventClause::User(UserEvent::OverView(Box::default())),
+                    SubClause::Always,
+                )],
+            )
+            .map_err(ui_error)?;
+        tuirealm
+            .mount(
+                Components::AlertLog,
+                Box::new(AlertLog::default()),
+                vec![Sub::new(
+                    SubEventClause::User(UserEvent::Alerts(Vec::new())),
+                    SubClause::Always,
+                )],
+            )
+            .map_err(ui_error)?;
+        tuirealm
+            .mount(
+                Components::StatusBar,
+                Box::new(StatusBar::default().with_units(config.units)),
+                vec![
+                    Sub::new(
+                        SubEventClause::User(UserEvent::Error(String::new())),
+                        SubClause::Always,
+                    ),
+                    Sub::new(
+                        SubEventClause::User(UserEvent::SelfUsage(SelfUsage::default())),
+                        SubClause::Always,
+                    ),
+                ],
+            )
+            .map_err(ui_error)?;
+        tuirealm
+            .attr(
+                &Components::StatusBar,
+                Attribute::Custom(POLLING_INTERVAL),
+                AttrValue::Size(interval.as_secs() as u16),
+            )
+            .map_err(ui_error)?;
+        tuirealm.active(&Components::Overvieww).map_err(ui_error)?;
+
+        let default_tab = MenuState::from(config.default_tab);
+        let mut view = View {
+            current_tab: default_tab,
             quit: false,
             // render the screen at least one time
             redraw: true,
             terminal,
             tuirealm,
-            system_info: shared_poller,
-            sysinfo_rx: rx,
-        }
+            commands,
+            poller: Some(poller),
+            schedule,
+            replaying,
+            hosts,
+            current_host: 0,
+            host_generation,
+            paused,
+            process_controller,
+            config,
+        };
+        view.show_host_name()?;
+        view.switch_view(default_tab)?;
+        Ok(view)
     }
-}
 
-impl View {
     pub fn render(&mut self) {
-        assert!(self
-            .terminal
-            .draw(|frame| {
-                let layout = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)])
-                    .split(frame.area());
-                let current_view = Components::from(&self.current_tab);
-                self.tuirealm.view(&Components::Menu, frame, layout[0]);
-                self.tuirealm.view(&current_view, frame, layout[1]);
-            })
-            .is_ok())
+        let result = self.terminal.draw(|frame| {
+            let layout = Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+            let current_view = Components::from(&self.current_tab);
+            self.tuirealm.view(&Components::Menu, frame, layout[0]);
+            self.tuirealm.view(&current_view, frame, layout[1]);
+            if current_view == Components::Processes {
+                self.tuirealm
+                    .view(&Components::ProcessDetails, frame, layout[1]);
+            }
+            if current_view == Components::Overvieww {
+                self.tuirealm.view(&Components::Limits, frame, layout[1]);
+                self.tuirealm.view(&Components::Security, frame, layout[1]);
+            }
+            if current_view == Components::NetworkDetails {
+                self.tuirealm
+                    .view(&Components::Connections, frame, layout[1]);
+            }
+            if current_view == Components::DiskDetails {
+                self.tuirealm
+                    .view(&Components::DirectoryUsage, frame, layout[1]);
+            }
+            self.tuirealm.view(&Components::AlertLog, frame, layout[1]);
+            self.tuirealm.view(&Components::StatusBar, frame, layout[2]);
+        });
+        if let Err(error) = result {
+            self.show_status(format!("Failed to draw the user interface: {}", error));
+        }
     }
 
-    pub fn run(&mut self) {
+    /// Runs the main loop until the user quits, then restores the terminal.
+    pub fn run(&mut self) -> Result<(), OceloError> {
         while !self.quit {
-            // if have update from the backend, receive it and convert it to json,
-            // then update the Overview Component
-            if let Ok(update) = self.sysinfo_rx.try_recv() {
-                self.handle_update(update);
-            }
+            self.tick();
+        }
 
-            match self.tuirealm.tick(PollStrategy::Once) {
-                Ok(messages) if !messages.is_empty() => {
-                    self.redraw = true;
-                    for msg in messages {
-                        let mut message = Some(msg);
-                        while let Some(m) = message {
-                            message = self.update(Some(m));
-                        }
+        self.close()
+    }
+
+    /// Handles the pending events, like the updates of the background thread and the pressed keys,
+    /// then redraws the user interface if they changed it.
+    pub fn tick(&mut self) {
+        match self.tuirealm.tick(PollStrategy::Once) {
+            Ok(messages) if !messages.is_empty() => {
+                self.redraw = true;
+                for msg in messages {
+                    let mut message = Some(msg);
+                    while let Some(m) = message {
+                        message = self.update(Some(m));
                     }
                 }
-                Err(error) => {
-                    eprintln!("Error rendering ui: {}", error);
-                }
-                _ => {}
             }
-
-            if self.redraw {
-                self.render();
-                self.redraw = false;
+            Err(error) => {
+                self.show_status(format!("Failed to process the events: {}", error));
             }
+            _ => {}
+        }
+
+        if self.redraw {
+            self.render();
+            self.redraw = false;
         }
+    }
 
-        self.close();
+    /// Returns the terminal the user interface is drawn on, like to read the buffer of a `TestBackend`.
+    pub fn terminal(&self) -> &Terminal<B> {
+        &self.terminal
     }
 
-    /// Restore terminal to its original state and close the application.
-    pub fn close(&mut self) {
-        self.terminal
-            .raw_mut()
+    /// Restore terminal to its original state and close the application,
+    /// waiting for the background thread to stop polling.
+    pub fn close(&mut self) -> Result<(), OceloError> {
+        // the terminal is restored first, as the thread may be waiting for an unreachable remote agent
+        let restored = self
+            .terminal
             .clear()
-            .expect("Failed to clear screen!");
-        self.terminal
-            .raw_mut()
-            .show_cursor()
-            .expect("Failed to show cursor!");
-    }
-
-    fn handle_update(&mut self, update: SystemInfoUpdate) {
-        match update {
-            SystemInfoUpdate::CpuAndMemory(cpu_update) => match cpu_update.to_json() {
-                Ok(cpu_update_json) => assert!(self
-                    .tuirealm
-                    .attr(
-                        &Components::CpuDetails,
-                        Attribute::Value,
-                        AttrValue::String(cpu_update_json)
-                    )
-                    .is_ok()),
-                Err(error) => eprint!("Failed to create JSON from CpuAndMemory: {}", error),
-            },
-            SystemInfoUpdate::Disk => {}
-            SystemInfoUpdate::Network => {}
-            SystemInfoUpdate::OverView(overview_update) => match overview_update.to_json() {
-                Ok(json) => {
-                    assert!(self
-                        .tuirealm
-                        .attr(
-                            &Components::Overvieww,
-                            Attribute::Custom("_SYSTEM_OVERVIEW"),
-                            AttrValue::String(json),
-                        )
-                        .is_ok());
-                }
-                Err(error) => {
-                    eprint!("Failed to create JSON from SystemOverviewInfo: {}", error)
-                }
-            },
-            SystemInfoUpdate::Process(process_list) => match process_list_to_json(process_list) {
-                Ok(json) => assert!(self
-                    .tuirealm
-                    .attr(
-                        &Components::Processes,
-                        Attribute::Value,
-                        AttrValue::String(json)
-                    )
-                    .is_ok()),
-                Err(error) => eprintln!("Failed to create JSON from ProcessList: {}", error),
-            },
+            .and_then(|()| self.terminal.show_cursor())
+            .map_err(ui_error);
+
+        self.send(PollerCommand::Shutdown);
+        if let Some(poller) = self.poller.take() {
+            let _ = poller.join();
         }
+        restored
+    }
 
+    /// Displays `message` in the status bar, instead of printing it over the user interface.
+    fn show_status(&mut self, message: impl Into<String>) {
+        // the status bar is mounted at initialization, so setting its text can not fail
+        let _ = self.tuirealm.attr(
+            &Components::StatusBar,
+            Attribute::Text,
+            AttrValue::String(message.into()),
+        );
         self.redraw = true;
     }
 
-    fn switch_view(&mut self, tab: MenuState) {
+    /// Writes the exported samples of `chart` into the working directory,
+    /// into a file named after the chart and the current time, like `ocelo-cpu-20240301-123000.csv`.
+    fn export_chart(&mut self, chart: &str, format: ExportFormat, contents: &str) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        // `2024-03-01 12:30:00 UTC` becomes `20240301-123000`
+        let timestamp = format_date_time(now)
+            .trim_end_matches(" UTC")
+            .replace(['-', ':'], "")
+            .replace(' ', "-");
+        let path = PathBuf::from(format!(
+            "ocelo-{}-{}.{}",
+            chart,
+            timestamp,
+            format.extension()
+        ));
+        let status = match fs::write(&path, contents) {
+            Ok(()) => format!("Exported the chart data to {}", path.display()),
+            Err(error) => format!("Failed to export the chart data: {}", error),
+        };
+        self.show_status(status);
+    }
+
+    /// Requests the details of the process `pid` from the background thread,
+    /// they are displayed once they are fetched.
+    fn show_process_details(&mut self, pid: u32) -> Result<(), OceloError> {
+        if self.replaying {
+            self.show_status("The details of the replayed processes were not recorded");
+            return Ok(());
+        }
+        self.send(PollerCommand::RequestProcessDetails(pid));
+        Ok(())
+    }
+
+    /// Displays the fetched `details` of a process over the process list.
+    fn display_process_details(&mut self, details: ProcessInfo) -> Result<(), OceloError> {
+        self.tuirealm
+            .remount(
+                Components::ProcessDetails,
+                Box::new(
+                    ProcessDetails::new(details)
+                        .with_units(self.config.units)
+                        .with_exact_durations(self.config.exact_durations),
+                ),
+                Vec::new(),
+            )
+            .map_err(ui_error)?;
+        self.tuirealm
+            .active(&Components::ProcessDetails)
+            .map_err(ui_error)
+    }
+
+    /// Closes the details of the process and gives the key presses back to the process list.
+    fn close_process_details(&mut self) -> Result<(), OceloError> {
+        if self.tuirealm.mounted(&Components::ProcessDetails) {
+            self.tuirealm
+                .umount(&Components::ProcessDetails)
+                .map_err(ui_error)?;
+        }
+        self.tuirealm
+            .active(&Components::Processes)
+            .map_err(ui_error)
+    }
+
+    /// Displays the connections of the system over the network interfaces,
+    /// polling them beside the interfaces until they are closed.
+    fn show_connections(&mut self) -> Result<(), OceloError> {
+        if self.replaying {
+            self.show_status("The connections of the replayed system were not recorded");
+            return Ok(());
+        }
+        self.tuirealm
+            .remount(
+                Components::Connections,
+                Box::new(Connections::default()),
+                vec![Sub::new(
+                    SubEventClause::User(UserEvent::Connections(Vec::new())),
+                    SubClause::Always,
+                )],
+            )
+            .map_err(ui_error)?;
+        self.tuirealm
+            .active(&Components::Connections)
+            .map_err(ui_error)?;
+        self.set_polling_contexts(&[
+            SystemInfoPollingContext::Network,
+            SystemInfoPollingContext::Connections,
+        ])
+    }
+
+    /// Closes the connections and gives the key presses back to the network interfaces.
+    fn close_connections(&mut self) -> Result<(), OceloError> {
+        if self.tuirealm.mounted(&Components::Connections) {
+            self.tuirealm
+                .umount(&Components::Connections)
+                .map_err(ui_error)?;
+        }
+        self.switch_view(self.current_tab)
+    }
+
+    /// Displays the kernel tunables and the resource limits of the system over the overview,
+    /// polling them beside the overview until they are closed.
+    fn show_limits(&mut self) -> Result<(), OceloError> {
+        if self.replaying {
+            self.show_status("The limits of the replayed system were not recorded");
+            return Ok(());
+        }
+        self.tuirealm
+            .remount(
+                Components::Limits,
+                Box::new(Limits::default().with_units(self.config.units)),
+                vec![Sub::new(
+                    SubEventClause::User(UserEvent::Limits(SystemLimits::default())),
+                    SubClause::Always,
+                )],
+            )
+            .map_err(ui_error)?;
+        self.tuirealm
+            .active(&Components::Limits)
+            .map_err(ui_error)?;
+        self.set_polling_contexts(&[
+            SystemInfoPollingContext::Overview,
+            SystemInfoPollingContext::Limits,
+        ])
+    }
+
+    /// Closes the limits and gives the key presses back to the overview.
+    fn close_limits(&mut self) -> Result<(), OceloError> {
+        if self.tuirealm.mounted(&Components::Limits) {
+            self.tuirealm
+                .umount(&Components::Limits)
+                .map_err(ui_error)?;
+        }
+        self.tuirealm
+            .active(&Components::Overvieww)
+            .map_err(ui_error)?;
+        self.switch_view(self.current_tab)
+    }
+
+    /// Displays the failed logins, the exposed ports and the firewall of the system over the overview,
+    /// polling them beside the overview until they are closed.
+    fn show_security(&mut self) -> Result<(), OceloError> {
+        if self.replaying {
+            self.show_status("The security of the replayed system was not recorded");
+            return Ok(());
+        }
+        self.tuirealm
+            .remount(
+                Components::Security,
+                Box::new(Security::default()),
+                vec![Sub::new(
+                    SubEventClause::User(UserEvent::Security(SecurityOverview::default())),
+                    SubClause::Always,
+                )],
+            )
+            .map_err(ui_error)?;
+        self.tuirealm
+            .active(&Components::Security)
+            .map_err(ui_error)?;
+        self.set_polling_contexts(&[
+            SystemInfoPollingContext::Overview,
+            SystemInfoPollingContext::Security,
+        ])
+    }
+
+    /// Closes the security overview and gives the key presses back to the overview.
+    fn close_security(&mut self) -> Result<(), OceloError> {
+        if self.tuirealm.mounted(&Components::Security) {
+            self.tuirealm
+                .umount(&Components::Security)
+                .map_err(ui_error)?;
+        }
+        self.tuirealm
+            .active(&Components::Overvieww)
+            .map_err(ui_error)?;
+        self.switch_view(self.current_tab)
+    }
+
+    /// Displays the largest directories and files under `mount`, scanning them in the background.
+    /// Only the disks of the local system can be scanned.
+    fn show_disk_usage(&mut self, mount: PathBuf) -> Result<(), OceloError> {
+        if self.process_controller.is_none() {
+            self.show_status("Only the disks of the local system can be scanned");
+            return Ok(());
+        }
+        self.tuirealm
+            .remount(
+                Components::DirectoryUsage,
+                Box::new(DirectoryUsage::new(mount).with_units(self.config.units)),
+                Vec::new(),
+            )
+            .map_err(ui_error)?;
+        self.tuirealm
+            .active(&Components::DirectoryUsage)
+            .map_err(ui_error)
+    }
+
+    /// Closes the disk usage and gives the key presses back to the storage devices.
+    fn close_disk_usage(&mut self) -> Result<(), OceloError> {
+        if self.tuirealm.mounted(&Components::DirectoryUsage) {
+            self.tuirealm
+                .umount(&Components::DirectoryUsage)
+                .map_err(ui_error)?;
+        }
+        self.tuirealm
+            .active(&Components::DiskDetails)
+            .map_err(ui_error)
+    }
+
+    /// Changes the polling interval of the displayed system information with the result of `change`
+    /// and displays the new value in the status bar.
+    fn change_polling_interval(
+        &mut self,
+        change: impl Fn(Duration) -> Duration,
+    ) -> Result<(), OceloError> {
+        for ctx in self.schedule.contexts() {
+            let new_interval = change(self.schedule.interval(ctx));
+            self.schedule.set_interval(ctx, new_interval);
+            // the scheduler clamps the interval the same way in the background thread
+            if !self.replaying {
+                self.send(PollerCommand::SetInterval(ctx, new_interval));
+            }
+        }
+        let interval = self
+            .schedule
+            .contexts()
+            .first()
+            .map_or(DEFAULT_POLLING_INTERVAL, |ctx| self.schedule.interval(*ctx));
+
+        self.tuirealm
+            .attr(
+                &Components::StatusBar,
+                Attribute::Custom(POLLING_INTERVAL),
+                AttrValue::Size(interval.as_secs() as u16),
+            )
+            .map_err(ui_error)
+    }
+
+    /// Displays the system information of the host at `index`, keeping the polling context and interval.
+    /// The components showing the details of the previous host are remounted with empty histories.
+    fn switch_host(&mut self, index: usize) -> Result<(), OceloError> {
+        if index == self.current_host {
+            return Ok(());
+        }
+        let Some(is_local) = self.hosts.get(index).map(|host| host.local) else {
+            return Ok(());
+        };
+
+        // the updates of the previous host still waiting in the channel are dropped from now on
+        let generation = self.host_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.send(PollerCommand::SwitchHost(index, generation));
+        self.process_controller = is_local.then(ProcessController::default);
+        self.current_host = index;
+
+        for component in [
+            Components::CpuDetails,
+            Components::DiskDetails,
+            Components::NetworkDetails,
+            Components::Processes,
+            Components::ProcessDetails,
+            Components::Connections,
+            Components::Limits,
+            Components::Security,
+            Components::DirectoryUsage,
+            Components::Sensors,
+            Components::Users,
+            Components::Services,
+            Components::Containers,
+            Components::Logs,
+        ] {
+            if self.tuirealm.mounted(&component) {
+                self.tuirealm.umount(&component).map_err(ui_error)?;
+            }
+        }
+        // the load history of the previous host is not continued
+        let history = self.new_history(SystemInfoPollingContext::Overview);
+        self.tuirealm
+            .remount(
+                Components::Overvieww,
+                Box::new(
+                    OverView::default()
+                        .with_history(history)
+                        .with_units(self.config.units)
+                        .with_chart_style(self.config.chart_style),
+                ),
+                vec![Sub::new(
+                    SubEventClause::User(UserEvent::OverView(Box::default())),
+                    SubClause::Always,
+                )],
+            )
+            .map_err(ui_error)?;
+        self.tuirealm
+            .attr(
+                &Components::Menu,
+                Attribute::Custom(CURRENT_HOST),
+                AttrValue::Length(index),
+            )
+            .map_err(ui_error)?;
+        self.show_host_name()?;
+        self.switch_view(self.current_tab)
+    }
+
+    /// Displays the name of the current host in the status bar,
+    /// the local system is displayed by its own name instead of the name of its source.
+    fn show_host_name(&mut self) -> Result<(), OceloError> {
+        let local_name = self
+            .process_controller
+            .as_ref()
+            .and_then(|_| core::host_name());
+        let name = local_name.unwrap_or_else(|| {
+            self.hosts
+                .get(self.current_host)
+                // the replayed recordings have no hosts to switch between
+                .map_or_else(|| "replay".to_string(), |host| host.name.clone())
+        });
+        self.tuirealm
+            .attr(
+                &Components::StatusBar,
+                Attribute::Custom(HOST_NAME),
+                AttrValue::String(name),
+            )
+            .map_err(ui_error)
+    }
+
+    /// Stops or continues displaying the updates of the system information,
+    /// polling right away after resuming so the display is not outdated until the next interval.
+    fn toggle_pause(&mut self) -> Result<(), OceloError> {
+        let paused = !self.paused.fetch_xor(true, Ordering::SeqCst);
+        if !paused {
+            self.trigger_poll();
+        }
+        self.tuirealm
+            .attr(
+                &Components::StatusBar,
+                Attribute::Custom(PAUSED),
+                AttrValue::Flag(paused),
+            )
+            .map_err(ui_error)
+    }
+
+    /// Changes the system information polled by the background thread to the `contexts` of the displayed components,
+    /// like the overview and the pane over it, each at its own interval.
+    /// The new contexts are polled right away so they are displayed without waiting for the next interval.
+    /// The status bar displays the interval of the first context.
+    fn set_polling_contexts(
+        &mut self,
+        contexts: &[SystemInfoPollingContext],
+    ) -> Result<(), OceloError> {
+        if self.schedule.contexts() == contexts {
+            return Ok(());
+        }
+        self.schedule.reschedule(contexts);
+        if !self.replaying {
+            self.send(PollerCommand::SetContexts(contexts.to_vec()));
+        }
+        let interval = contexts
+            .first()
+            .map_or(DEFAULT_POLLING_INTERVAL, |ctx| self.schedule.interval(*ctx));
+        self.tuirealm
+            .attr(
+                &Components::StatusBar,
+                Attribute::Custom(POLLING_INTERVAL),
+                AttrValue::Size(interval.as_secs() as u16),
+            )
+            .map_err(ui_error)
+    }
+
+    /// Asks the background thread to poll every scheduled context right away,
+    /// fetching the whole process list as the changes polled meanwhile may not have been displayed.
+    fn trigger_poll(&self) {
+        if !self.replaying {
+            self.send(PollerCommand::RequestSnapshot);
+        }
+    }
+
+    /// Sends the `command` to the background thread without waiting for its result.
+    fn send(&self, command: PollerCommand) {
+        // the thread only stops when the user interface is closed
+        let _ = self.commands.send(command);
+    }
+
+    /// Creates an empty history keeping the samples for `DEFAULT_RETENTION` at the polling interval of `ctx`.
+    fn new_history(&self, ctx: SystemInfoPollingContext) -> History {
+        History::with_retention(DEFAULT_RETENTION, self.schedule.interval(ctx))
+    }
+
+    fn switch_view(&mut self, tab: MenuState) -> Result<(), OceloError> {
         match tab {
             MenuState::CpuMemoryDetails => {
                 if !self.tuirealm.mounted(&Components::CpuDetails) {
-                    let cpu_info = self.system_info.lock().unwrap().get_cpu_info();
+                    let history = self.new_history(SystemInfoPollingContext::CpuAndMemory);
                     self.tuirealm
                         .mount(
                             Components::CpuDetails,
                             Box::new(
                                 CpuMemoryDetails::default()
-                                    .with_core_count(cpu_info.core_count)
-                                    .with_cpu_name(cpu_info.name),
+                                    .with_history(history)
+                                    .with_units(self.config.units)
+                                    .with_chart_style(self.config.chart_style),
+                            ),
+                            vec![
+                                Sub::new(
+                                    SubEventClause::User(UserEvent::CpuAndMemory(
+                                        CpuMemoryUpdate::default(),
+                                    )),
+                                    SubClause::Always,
+                                ),
+                                Sub::new(
+                                    SubEventClause::User(UserEvent::CpuInfo(Box::default())),
+                                    SubClause::Always,
+                                ),
+                            ],
+                        )
+                        .map_err(ui_error)?;
+                    // the name and the layout of the processor are displayed once they are fetched
+                    self.send(PollerCommand::RequestCpuInfo);
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::CpuAndMemory])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::CpuDetails)
+                    .map_err(ui_error)?;
+            }
+            MenuState::DiskDetails => {
+                if !self.tuirealm.mounted(&Components::DiskDetails) {
+                    let history = self.new_history(SystemInfoPollingContext::Disks);
+                    self.tuirealm
+                        .mount(
+                            Components::DiskDetails,
+                            Box::new(
+                                DiskDetails::default()
+                                    .with_history(history)
+                                    .with_units(self.config.units)
+                                    .with_chart_style(self.config.chart_style),
                             ),
-                            vec![],
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Disk(DiskInfo::default())),
+                                SubClause::Always,
+                            )],
                         )
-                        .unwrap();
+                        .map_err(ui_error)?;
                 }
-                self.system_info
-                    .lock()
-                    .unwrap()
-                    .set_polling_context(SystemInfoPollingContext::CpuAndMemory);
-                self.tuirealm.blur().unwrap();
-                self.tuirealm.active(&Components::CpuDetails).unwrap();
+                self.set_polling_contexts(&[SystemInfoPollingContext::Disks])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::DiskDetails)
+                    .map_err(ui_error)?;
+            }
+            MenuState::NetworkDetails => {
+                if !self.tuirealm.mounted(&Components::NetworkDetails) {
+                    let history = self.new_history(SystemInfoPollingContext::Network);
+                    self.tuirealm
+                        .mount(
+                            Components::NetworkDetails,
+                            Box::new(
+                                NetworkDetails::default()
+                                    .with_history(history)
+                                    .with_units(self.config.units)
+                                    .with_bits(self.config.network_bits)
+                                    .with_chart_style(self.config.chart_style),
+                            ),
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Network(Box::default())),
+                                SubClause::Always,
+                            )],
+                        )
+                        .map_err(ui_error)?;
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::Network])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::NetworkDetails)
+                    .map_err(ui_error)?;
+            }
+            MenuState::SensorsDetails => {
+                if !self.tuirealm.mounted(&Components::Sensors) {
+                    let history = self.new_history(SystemInfoPollingContext::Sensors);
+                    self.tuirealm
+                        .mount(
+                            Components::Sensors,
+                            Box::new(Sensors::default().with_history(history)),
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Sensors(SensorsInfo::default())),
+                                SubClause::Always,
+                            )],
+                        )
+                        .map_err(ui_error)?;
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::Sensors])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::Sensors)
+                    .map_err(ui_error)?;
+            }
+            MenuState::UsersDetails => {
+                if !self.tuirealm.mounted(&Components::Users) {
+                    self.tuirealm
+                        .mount(
+                            Components::Users,
+                            Box::new(Users::default()),
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Users(SessionsInfo::default())),
+                                SubClause::Always,
+                            )],
+                        )
+                        .map_err(ui_error)?;
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::Users])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm.active(&Components::Users).map_err(ui_error)?;
+            }
+            MenuState::ServicesDetails => {
+                if !self.tuirealm.mounted(&Components::Services) {
+                    self.tuirealm
+                        .mount(
+                            Components::Services,
+                            Box::new(Services::default().with_units(self.config.units)),
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Services(ServicesInfo::default())),
+                                SubClause::Always,
+                            )],
+                        )
+                        .map_err(ui_error)?;
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::Services])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::Services)
+                    .map_err(ui_error)?;
+            }
+            MenuState::ContainersDetails => {
+                if !self.tuirealm.mounted(&Components::Containers) {
+                    self.tuirealm
+                        .mount(
+                            Components::Containers,
+                            Box::new(Containers::default().with_units(self.config.units)),
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Containers(
+                                    ContainersInfo::default(),
+                                )),
+                                SubClause::Always,
+                            )],
+                        )
+                        .map_err(ui_error)?;
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::Containers])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::Containers)
+                    .map_err(ui_error)?;
+            }
+            MenuState::LogsDetails => {
+                if !self.tuirealm.mounted(&Components::Logs) {
+                    self.tuirealm
+                        .mount(
+                            Components::Logs,
+                            Box::new(Logs::default()),
+                            vec![Sub::new(
+                                SubEventClause::User(UserEvent::Logs(LogsInfo::default())),
+                                SubClause::Always,
+                            )],
+                        )
+                        .map_err(ui_error)?;
+                }
+                self.set_polling_contexts(&[SystemInfoPollingContext::Logs])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm.active(&Components::Logs).map_err(ui_error)?;
             }
-            MenuState::DiskDetails => {}
-            MenuState::NetworkDetails => {}
             MenuState::OverView => {
-                self.system_info
-                    .lock()
-                    .unwrap()
-                    .set_polling_context(SystemInfoPollingContext::Overview);
+                self.set_polling_contexts(&[SystemInfoPollingContext::Overview])?;
             }
             MenuState::ProcessDetails => {
                 if !self.tuirealm.mounted(&Components::Processes) {
-                    //let processes = self.system_info.lock().unwrap().get_process_list();
+                    //let processes = self.system_info.lock()?.get_process_list();
                     self.tuirealm
                         .mount(
                             Components::Processes,
-                            Box::new(Processes::default()),
-                            vec![],
+                            Box::new(
+                                Processes::default()
+                                    .with_columns(self.config.process_columns.clone())
+                                    .with_units(self.config.units)
+                                    .with_exact_durations(self.config.exact_durations),
+                            ),
+                            vec![
+                                Sub::new(
+                                    SubEventClause::User(
+                                        UserEvent::Process(ProcessList::default()),
+                                    ),
+                                    SubClause::Always,
+                                ),
+                                Sub::new(
+                                    SubEventClause::User(UserEvent::ProcessDelta(
+                                        ProcessDelta::default(),
+                                    )),
+                                    SubClause::Always,
+                                ),
+                                Sub::new(
+                                    SubEventClause::User(UserEvent::CpuInfo(Box::default())),
+                                    SubClause::Always,
+                                ),
+                                Sub::new(
+                                    SubEventClause::User(UserEvent::ProcessDetails(Box::default())),
+                                    SubClause::Always,
+                                ),
+                            ],
                         )
-                        .unwrap();
+                        .map_err(ui_error)?;
+                    // the share of a core is computed once the number of cores is fetched
+                    self.send(PollerCommand::RequestCpuInfo);
                 }
-                self.system_info
-                    .lock()
-                    .unwrap()
-                    .set_polling_context(SystemInfoPollingContext::Processes);
-                self.tuirealm.blur().unwrap();
-                self.tuirealm.active(&Components::Processes).unwrap();
+                self.set_polling_contexts(&[SystemInfoPollingContext::Processes])?;
+                self.tuirealm.blur().map_err(ui_error)?;
+                self.tuirealm
+                    .active(&Components::Processes)
+                    .map_err(ui_error)?;
             }
         }
 
+        self.tuirealm
+            .attr(
+                &Components::StatusBar,
+                Attribute::Custom(KEY_HINTS),
+                AttrValue::String(tab.key_hints().to_string()),
+            )
+            .map_err(ui_error)?;
         self.tuirealm
             .attr(
                 &Components::Menu,
                 Attribute::Value,
                 AttrValue::Length(self.current_tab.index()),
             )
-            .unwrap();
+            .map_err(ui_error)
     }
 }
 
-impl Update<Message> for View {
+impl<B: Backend> Update<Message> for View<B> {
     fn update(&mut self, msg: Option<Message>) -> Option<Message> {
         if let Some(message) = msg {
-            match message {
+            let result = match message {
                 Message::ChangeNextMenu => {
                     self.current_tab.next();
-                    self.switch_view(self.current_tab);
+                    self.switch_view(self.current_tab)
                 }
                 Message::ChangePreviousMenu => {
                     self.current_tab.previous();
-                    self.switch_view(self.current_tab);
+                    self.switch_view(self.current_tab)
                 }
                 Message::Tick => {
                     self.redraw = true;
-                    self.switch_view(self.current_tab);
+                    self.switch_view(self.current_tab)
+                }
+                Message::Quit => {
+                    self.quit = true;
+                    Ok(())
+                }
+                Message::Redraw => {
+                    self.redraw = true;
+                    Ok(())
+                }
+                Message::SpeedUpPolling => self.change_polling_interval(|interval| {
+                    interval.saturating_sub(POLLING_INTERVAL_STEP)
+                }),
+                Message::SlowDownPolling => self.change_polling_interval(|interval| {
+                    interval.saturating_add(POLLING_INTERVAL_STEP)
+                }),
+                Message::TerminateProcess(pid) => {
+                    let status = match &mut self.process_controller {
+                        Some(controller) => controller
+                            .terminate(pid)
+                            .err()
+                            .map(|error| format!("Failed to terminate process: {}", error)),
+                        None => Some(
+                            "Only the processes of the local system can be terminated".to_string(),
+                        ),
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
+                }
+                Message::KillProcess(pid) => {
+                    let status = match &mut self.process_controller {
+                        Some(controller) => controller
+                            .kill(pid)
+                            .err()
+                            .map(|error| format!("Failed to kill process: {}", error)),
+                        None => {
+                            Some("Only the processes of the local system can be killed".to_string())
+                        }
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
+                }
+                Message::SendSignal(pid, signal) => {
+                    let status = match &mut self.process_controller {
+                        Some(controller) => controller
+                            .send_signal(pid, signal)
+                            .err()
+                            .map(|error| format!("Failed to send {}: {}", signal, error)),
+                        None => Some(
+                            "Signals can only be sent to the processes of the local system"
+                                .to_string(),
+                        ),
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
+                }
+                Message::SetPriority(pid, nice) => {
+                    let status = match &mut self.process_controller {
+                        Some(controller) => controller
+                            .set_priority(pid, nice)
+                            .err()
+                            .map(|error| format!("Failed to change the priority: {}", error)),
+                        None => Some(
+                            "Only the priority of the local processes can be changed".to_string(),
+                        ),
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
+                }
+                Message::SetAffinity(pid, cores) => {
+                    let status = match &mut self.process_controller {
+                        Some(controller) => controller
+                            .set_affinity(pid, &cores)
+                            .err()
+                            .map(|error| format!("Failed to pin the process: {}", error)),
+                        None => Some("Only the local processes can be pinned to cores".to_string()),
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
                 }
-                Message::Quit => self.quit = true,
+                Message::ControlService(name, action) => {
+                    let status = match &self.process_controller {
+                        Some(_) => core::services::control_service(&name, action)
+                            .err()
+                            .map(|error| format!("Failed to {} the service: {}", action, error)),
+                        None => Some(
+                            "Only the services of the local system can be controlled".to_string(),
+                        ),
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
+                }
+                Message::ControlContainer(id, action) => {
+                    let status = match &self.process_controller {
+                        Some(_) => core::containers::control_container(&id, action)
+                            .err()
+                            .map(|error| format!("Failed to {} the container: {}", action, error)),
+                        None => Some(
+                            "Only the containers of the local system can be controlled".to_string(),
+                        ),
+                    };
+                    if let Some(status) = status {
+                        self.show_status(status);
+                    }
+                    Ok(())
+                }
+                Message::ExportChart(chart, format, contents) => {
+                    self.export_chart(&chart, format, &contents);
+                    Ok(())
+                }
+                Message::TogglePause => self.toggle_pause(),
+                Message::SwitchHost(index) => self.switch_host(index),
+                Message::ShowProcessDetails(pid) => self.show_process_details(pid),
+                Message::DisplayProcessDetails(details) => self.display_process_details(*details),
+                Message::CloseProcessDetails => self.close_process_details(),
+                Message::ChangeProcessColumns(columns) => {
+                    self.send(PollerCommand::SetProcessColumns(columns));
+                    self.trigger_poll();
+                    self.redraw = true;
+                    Ok(())
+                }
+                Message::ShowConnections => self.show_connections(),
+                Message::CloseConnections => self.close_connections(),
+                Message::ShowLimits => self.show_limits(),
+                Message::CloseLimits => self.close_limits(),
+                Message::ShowSecurity => self.show_security(),
+                Message::CloseSecurity => self.close_security(),
+                Message::ShowDiskUsage(mount) => self.show_disk_usage(mount),
+                Message::CloseDiskUsage => self.close_disk_usage(),
+                Message::ToggleAlertLog => {
+                    let visible = self
+                        .tuirealm
+                        .query(&Components::AlertLog, Attribute::Display)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|display| display.unwrap_flag());
+                    self.tuirealm
+                        .attr(
+                            &Components::AlertLog,
+                            Attribute::Display,
+                            AttrValue::Flag(!visible),
+                        )
+                        .map_err(ui_error)
+                }
+            };
+            if let Err(error) = result {
+                self.show_status(error.to_string());
             }
         }
 
         None
     }
 }
+
+/// Converts the errors of the terminal and the application into `OceloError::Ui`.
+fn ui_error(error: impl Display) -> OceloError {
+    OceloError::Ui(error.to_string())
+}
+
+/// Switches the terminal to raw mode and to its alternate screen, which are left when it is dropped.
+fn termion_terminal() -> Result<Terminal<TermionBackend<TermionOutput>>, OceloError> {
+    let output = io::stdout()
+        .into_raw_mode()
+        .and_then(|output| output.into_alternate_screen())
+        .map_err(ui_error)?;
+    Terminal::new(TermionBackend::new(MouseTerminal::from(output))).map_err(ui_error)
+}
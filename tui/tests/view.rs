@@ -0,0 +1,116 @@
+use core::model::{ProcessInfo, ProcessList, SystemOverviewInfo};
+use core::{Config, MockProvider};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::thread;
+use std::time::{Duration, Instant};
+use tui::{Message, View};
+use tuirealm::Update;
+
+/// How long the background thread is waited for to poll the provider and the view to display it.
+const DISPLAY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A canned snapshot of the host `host_name` with the processor used at `usage` percent.
+fn snapshot(host_name: &str, usage: f32) -> SystemOverviewInfo {
+    let mut snapshot = SystemOverviewInfo::default();
+    snapshot.overview.host_name = host_name.to_string();
+    snapshot.overview.kernel_version = "6.1.0".to_string();
+    snapshot.cpu.name = "Mock CPU".to_string();
+    snapshot.cpu.core_count = 4;
+    snapshot.cpu.usage = usage;
+    snapshot.memory.total = 8 << 30;
+    snapshot.memory.used = 2 << 30;
+    snapshot
+}
+
+fn process(pid: u32, name: &str) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Displays the canned snapshots of `provider` on a buffer instead of the terminal, polling them every second.
+fn view(provider: MockProvider) -> View<TestBackend> {
+    let config = Config {
+        refresh_interval: 1,
+        ..Default::default()
+    };
+    let terminal = Terminal::new(TestBackend::new(140, 40)).unwrap();
+    View::with_backend(config, provider, terminal).unwrap()
+}
+
+/// Returns the lines drawn on the buffer of `view`.
+fn screen(view: &View<TestBackend>) -> String {
+    let buffer = view.terminal().backend().buffer();
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|line| line.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Handles the events of `view` until `text` is displayed, failing after `DISPLAY_TIMEOUT`.
+fn tick_until(view: &mut View<TestBackend>, text: &str) {
+    let deadline = Instant::now() + DISPLAY_TIMEOUT;
+    while !screen(view).contains(text) {
+        assert!(
+            Instant::now() < deadline,
+            "'{}' is not displayed on:\n{}",
+            text,
+            screen(view)
+        );
+        view.tick();
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn draws_the_overview_of_the_snapshot() {
+    let mut view = view(MockProvider::new(vec![snapshot("mock-host", 12.0)]));
+
+    tick_until(&mut view, "Hostname: mock-host");
+    tick_until(&mut view, "Mock CPU");
+    view.close().unwrap();
+}
+
+#[test]
+fn displays_the_snapshots_as_they_are_polled() {
+    let mut view = view(MockProvider::new(vec![
+        snapshot("first-host", 12.0),
+        snapshot("second-host", 12.0),
+    ]));
+
+    tick_until(&mut view, "Hostname: first-host");
+    tick_until(&mut view, "Hostname: second-host");
+    view.close().unwrap();
+}
+
+#[test]
+fn switches_to_the_processes_of_the_provider() {
+    let provider = MockProvider::new(vec![snapshot("mock-host", 12.0)]).with_processes(vec![
+        ProcessList::from(vec![process(1, "mock-init"), process(42, "mock-shell")]),
+    ]);
+    let mut view = view(provider);
+    tick_until(&mut view, "Hostname: mock-host");
+
+    // from the overview over the processor and the memory
+    view.update(Some(Message::ChangeNextMenu));
+    view.update(Some(Message::ChangeNextMenu));
+    tick_until(&mut view, "mock-shell");
+    assert!(screen(&view).contains("mock-init"));
+    view.close().unwrap();
+}
+
+#[test]
+fn counts_the_raised_alerts_in_the_menu() {
+    let rule = "cpu.usage > 90".parse().unwrap();
+    let provider =
+        MockProvider::new(vec![snapshot("mock-host", 95.0)]).with_alert_rules(vec![rule]);
+    let mut view = view(provider);
+
+    tick_until(&mut view, "! 1 alerts (a)");
+    view.close().unwrap();
+}